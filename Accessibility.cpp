@@ -0,0 +1,55 @@
+#include "raylib.h"
+#include "Accessibility.hpp"
+#include <cmath>
+
+
+// Built from the Okabe-Ito set; ordered standard, glass, stone, metal and
+// chosen so neighbours also differ in brightness.
+static const unsigned int PRESETS[][4] = {
+    {0xF5F5F5FF, 0x56B4E9FF, 0xE69F00FF, 0x0072B2FF}, // deuteranopia
+    {0xF5F5F5FF, 0x56B4E9FF, 0xF0E442FF, 0x0072B2FF}, // protanopia
+    {0xF5F5F5FF, 0x009E73FF, 0xD55E00FF, 0xCC79A7FF}, // tritanopia
+};
+
+ThemePalette accessiblePalette(const ThemePalette& palette, ColorBlindMode mode) {
+    ThemePalette adjusted = palette;
+    if (mode == ColorBlindMode::Off) {
+        return adjusted;
+    }
+    const unsigned int* preset = PRESETS[(int) mode - 1];
+    for (int i = 0; i < 4; i++) {
+        adjusted.bricks[i] = GetColor(preset[i]);
+    }
+    return adjusted;
+}
+
+// Diagonal "/" stripes clipped to the rectangle
+static void drawStripes(Rectangle rect, float spacing, Color color) {
+    for (float offset = spacing; offset < rect.width + rect.height; offset += spacing) {
+        // the line x + y = offset (in rect-local coordinates)
+        Vector2 start = {rect.x + fminf(offset, rect.width), rect.y + fmaxf(0, offset - rect.width)};
+        Vector2 end = {rect.x + fmaxf(0, offset - rect.height), rect.y + fminf(offset, rect.height)};
+        DrawLineV(start, end, color);
+    }
+}
+
+void drawBrickPattern(Rectangle brick, BrickMaterial material, Color brickColor) {
+    Color ink = ColorBrightness(brickColor, -0.55f);
+    switch (material) {
+    case BrickMaterial::Standard:
+        break;
+    case BrickMaterial::Glass:
+        drawStripes(brick, 6, ink);
+        break;
+    case BrickMaterial::Stone:
+        for (float y = brick.y + 3; y < brick.y + brick.height - 1; y += 4) {
+            for (float x = brick.x + 3; x < brick.x + brick.width - 1; x += 6) {
+                DrawRectangleV({x, y}, {2, 2}, ink);
+            }
+        }
+        break;
+    case BrickMaterial::Metal:
+        DrawRectangleLinesEx({brick.x + 1, brick.y + 1, brick.width - 2, brick.height - 2}, 2, ink);
+        break;
+    }
+}
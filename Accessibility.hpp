@@ -0,0 +1,20 @@
+#pragma once
+#include "raylib.h"
+#include "Brick.hpp"
+#include "Theme.hpp"
+
+
+enum class ColorBlindMode {
+    Off,
+    Deuteranopia,
+    Protanopia,
+    Tritanopia,
+};
+
+// Replaces the theme's brick colors with a preset that stays distinguishable
+// under the given color vision deficiency. Everything else is left alone.
+ThemePalette accessiblePalette(const ThemePalette& palette, ColorBlindMode mode);
+
+// Draws the per-material overlay pattern so brick types can be told apart
+// without relying on color at all.
+void drawBrickPattern(Rectangle brick, BrickMaterial material, Color brickColor);
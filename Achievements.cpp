@@ -0,0 +1,184 @@
+#include "raylib.h"
+#include "Achievements.hpp"
+#include <algorithm>
+#include <cstdlib>
+#include <sstream>
+
+
+std::vector<std::string> achievementFiles() {
+    std::string root = GetApplicationDirectory();
+    std::vector<std::string> files;
+    std::string bundled = root + "assets/achievements.xml";
+    if (FileExists(bundled.c_str())) {
+        files.push_back(bundled);
+    }
+    std::string mods = root + "mods";
+    if (DirectoryExists(mods.c_str())) {
+        FilePathList modDirectories = LoadDirectoryFiles(mods.c_str());
+        std::vector<std::string> found;
+        for (unsigned int i = 0; i < modDirectories.count; i++) {
+            std::string path = std::string(modDirectories.paths[i]) + "/achievements.xml";
+            if (FileExists(path.c_str())) {
+                found.push_back(path);
+            }
+        }
+        UnloadDirectoryFiles(modDirectories);
+        // the same order on every machine
+        std::sort(found.begin(), found.end());
+        files.insert(files.end(), found.begin(), found.end());
+    }
+    return files;
+}
+
+bool parseAchievements(const XmlNode& root, std::vector<Achievement>& list, std::string& problem) {
+    if (root.name != "achievements") {
+        problem = "the root element must be <achievements>";
+        return false;
+    }
+    std::vector<Achievement> parsed;
+    for (const XmlNode* node : root.childrenNamed("achievement")) {
+        Achievement achievement;
+        achievement.id = node->attribute("id");
+        achievement.name = node->attribute("name", achievement.id);
+        achievement.description = node->attribute("description");
+        achievement.event = node->attribute("event");
+        achievement.count = node->attributeInt("count", 1);
+        achievement.minValue = node->attributeInt("min_value", INT_MIN);
+        achievement.maxValue = node->attributeInt("max_value", INT_MAX);
+        achievement.xp = std::max(0, node->attributeInt("xp", achievement.xp));
+        std::string scope = node->attribute("scope", "total");
+        std::string where = "line " + std::to_string(node->line) + ": ";
+        if (achievement.id.empty() || achievement.event.empty()) {
+            problem = where + "an achievement needs an id and an event";
+            return false;
+        }
+        if (achievement.count < 1) {
+            problem = where + "count must be at least 1";
+            return false;
+        }
+        if (scope != "total" && scope != "run") {
+            problem = where + "unknown scope '" + scope + "', expected total or run";
+            return false;
+        }
+        achievement.perRun = scope == "run";
+        auto sameId = [&](const Achievement& other) { return other.id == achievement.id; };
+        if (std::any_of(list.begin(), list.end(), sameId) || std::any_of(parsed.begin(), parsed.end(), sameId)) {
+            problem = where + "there is already an achievement '" + achievement.id + "'";
+            return false;
+        }
+        parsed.push_back(achievement);
+    }
+    list.insert(list.end(), parsed.begin(), parsed.end());
+    return true;
+}
+
+std::vector<Achievement> loadAchievements() {
+    std::vector<Achievement> list;
+    for (const std::string& path : achievementFiles()) {
+        XmlNode root;
+        XmlError error;
+        std::string problem;
+        if (!loadXmlFile(path, root, error)) {
+            TraceLog(LOG_WARNING, "ACHIEVEMENTS: Skipping %s: %s", path.c_str(), error.describe().c_str());
+        } else if (!parseAchievements(root, list, problem)) {
+            TraceLog(LOG_WARNING, "ACHIEVEMENTS: Skipping %s: %s", path.c_str(), problem.c_str());
+        }
+    }
+    return list;
+}
+
+void AchievementTracker::load(const std::vector<Achievement>& definitions, const std::string& saved) {
+    achievements = definitions;
+    counts.assign(achievements.size(), 0);
+    fresh.clear();
+    std::stringstream list(saved);
+    std::string entry;
+    while (std::getline(list, entry, ',')) {
+        size_t colon = entry.find(':');
+        if (colon == std::string::npos) {
+            continue;
+        }
+        std::string id = entry.substr(0, colon);
+        for (size_t i = 0; i < achievements.size(); i++) {
+            if (achievements[i].id == id) {
+                counts[i] = std::clamp(std::atoi(entry.c_str() + colon + 1), 0, achievements[i].count);
+            }
+        }
+    }
+    // run scoped progress never carries over from one session to the next
+    startRun();
+}
+
+std::string AchievementTracker::format() const {
+    std::string saved;
+    for (size_t i = 0; i < achievements.size(); i++) {
+        bool unlocked = counts[i] >= achievements[i].count;
+        if (counts[i] > 0 && (unlocked || !achievements[i].perRun)) {
+            saved += (saved.empty() ? "" : ",") + achievements[i].id + ":" + std::to_string(counts[i]);
+        }
+    }
+    return saved;
+}
+
+void AchievementTracker::onEvent(const GameEvent& event) {
+    record(eventTypeName(event.type), event.value);
+}
+
+void AchievementTracker::record(const std::string& event, int value) {
+    for (size_t i = 0; i < achievements.size(); i++) {
+        const Achievement& achievement = achievements[i];
+        if (counts[i] >= achievement.count || achievement.event != event || value < achievement.minValue || value > achievement.maxValue) {
+            continue;
+        }
+        if (++counts[i] == achievement.count) {
+            fresh.push_back(i);
+        }
+    }
+}
+
+void AchievementTracker::startRun() {
+    for (size_t i = 0; i < achievements.size(); i++) {
+        if (achievements[i].perRun && counts[i] < achievements[i].count) {
+            counts[i] = 0;
+        }
+    }
+}
+
+const std::vector<Achievement>& AchievementTracker::getAchievements() const {
+    return achievements;
+}
+
+int AchievementTracker::progress(const std::string& id) const {
+    for (size_t i = 0; i < achievements.size(); i++) {
+        if (achievements[i].id == id) {
+            return counts[i];
+        }
+    }
+    return 0;
+}
+
+bool AchievementTracker::isUnlocked(const std::string& id) const {
+    for (size_t i = 0; i < achievements.size(); i++) {
+        if (achievements[i].id == id) {
+            return counts[i] >= achievements[i].count;
+        }
+    }
+    return false;
+}
+
+int AchievementTracker::getUnlockedCount() const {
+    int unlocked = 0;
+    for (size_t i = 0; i < achievements.size(); i++) {
+        unlocked += counts[i] >= achievements[i].count ? 1 : 0;
+    }
+    return unlocked;
+}
+
+std::vector<const Achievement*> AchievementTracker::takeUnlocked() {
+    std::vector<const Achievement*> unlocked;
+    for (size_t i : fresh) {
+        unlocked.push_back(&achievements[i]);
+    }
+    fresh.clear();
+    return unlocked;
+}
@@ -0,0 +1,65 @@
+#pragma once
+#include <climits>
+#include <string>
+#include <vector>
+#include "Events.hpp"
+#include "Xml.hpp"
+
+
+// One achievement, as an achievements.xml describes it:
+//   <achievement id="demolition" name="Demolition" description="Break 500 bricks"
+//                event="brick_destroyed" count="500"/>
+// It counts the events named event (eventTypeName()'s names, and
+// "level_cleared" with the lives the level cost as its value) whose value is
+// within min_value to max_value, and is earned at count of them. scope="run"
+// starts the count over every run, for the ones that have to be done in one
+// go; xp is what earning it adds to the profile (see Progression.hpp).
+struct Achievement {
+    std::string id;
+    std::string name;
+    std::string description;
+    std::string event;
+    int count = 1;
+    int minValue = INT_MIN;
+    int maxValue = INT_MAX;
+    bool perRun = false;
+    int xp = 50;
+};
+
+// assets/achievements.xml, then every mods/<mod>/achievements.xml
+std::vector<std::string> achievementFiles();
+// Adds the <achievements> root's entries to list; false with the problem when
+// one can't be counted as written (a repeated id is one).
+bool parseAchievements(const XmlNode& root, std::vector<Achievement>& list, std::string& problem);
+// Every file there is, a broken one skipped with a warning.
+std::vector<Achievement> loadAchievements();
+
+// A profile's progress on each achievement: events go in, and whatever
+// reaches its count is unlocked for good. Replays, the tutorial and cheated
+// runs are kept away from it by the caller, like PlayerStats.
+class AchievementTracker {
+  public:
+    // saved is the profile's "id:count,..." (see format()); ids no
+    // achievement has any more are dropped.
+    void load(const std::vector<Achievement>& definitions, const std::string& saved);
+    // The counts worth keeping: the lifetime ones, and the run ones only
+    // once they're unlocked.
+    std::string format() const;
+
+    void onEvent(const GameEvent& event);
+    void record(const std::string& event, int value);
+    // The run scoped counts back to 0, short of the unlocked ones.
+    void startRun();
+
+    const std::vector<Achievement>& getAchievements() const;
+    int progress(const std::string& id) const;
+    bool isUnlocked(const std::string& id) const;
+    int getUnlockedCount() const;
+    // Unlocked since the last call, oldest first, for the toasts.
+    std::vector<const Achievement*> takeUnlocked();
+
+  private:
+    std::vector<Achievement> achievements;
+    std::vector<int> counts; // one per achievement, capped at its count
+    std::vector<size_t> fresh;
+};
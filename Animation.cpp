@@ -0,0 +1,66 @@
+#include "raylib.h"
+#include "Animation.hpp"
+
+
+void AnimationState::play(const AnimationClip& animationClip) {
+    clip = animationClip;
+    time = 0;
+    playing = true;
+}
+
+void AnimationState::update(float deltaTime) {
+    if (playing) {
+        time += deltaTime;
+    }
+}
+
+int AnimationState::getFrame() {
+    if (clip.frames <= 1 || clip.frameDuration <= 0) {
+        return 0;
+    }
+    int frame = (int) (time / clip.frameDuration);
+    if (clip.loop) {
+        return frame % clip.frames;
+    }
+    return frame < clip.frames ? frame : clip.frames - 1;
+}
+
+bool AnimationState::isFinished() {
+    return !clip.loop && time >= clip.frames * clip.frameDuration;
+}
+
+Rectangle AnimationState::frameRegion(TextureAtlas& atlas) {
+    Rectangle sheet = atlas.region(clip.sheet);
+    float frameWidth = sheet.width / (clip.frames > 0 ? clip.frames : 1);
+    return {sheet.x + frameWidth * getFrame(), sheet.y, frameWidth, sheet.height};
+}
+
+void AnimationLibrary::add(const AnimationClip& clip) {
+    clips[clip.name] = clip;
+}
+
+bool AnimationLibrary::has(const std::string& name) {
+    return clips.count(name) > 0;
+}
+
+AnimationClip AnimationLibrary::get(const std::string& name) {
+    auto it = clips.find(name);
+    return it == clips.end() ? AnimationClip() : it->second;
+}
+
+void AnimationLibrary::loadFromXml(const XmlNode& node) {
+    for (const XmlNode* entry : node.childrenNamed("animation")) {
+        AnimationClip clip;
+        clip.name = entry->attribute("name");
+        clip.sheet = entry->attribute("sheet", clip.name);
+        clip.frames = entry->attributeInt("frames", 1);
+        float fps = entry->attributeFloat("fps", 10);
+        clip.frameDuration = fps > 0 ? 1.0f / fps : 0;
+        clip.loop = entry->attribute("loop", "true") != "false";
+        if (clip.name.empty()) {
+            TraceLog(LOG_WARNING, "ANIMATION: line %d: <animation> without a name", entry->line);
+            continue;
+        }
+        add(clip);
+    }
+}
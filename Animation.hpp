@@ -0,0 +1,46 @@
+#pragma once
+#include "raylib.h"
+#include <map>
+#include <string>
+#include "Atlas.hpp"
+#include "Xml.hpp"
+
+
+// Frame-based animation over a sprite sheet: the sheet is an atlas region
+// with the frames laid out left to right.
+struct AnimationClip {
+    std::string name;
+    std::string sheet;
+    int frames = 1;
+    float frameDuration = 0.1f; // seconds
+    bool loop = true;
+};
+
+// Per-entity playback state, advanced by the game clock so animations pause
+// with the simulation.
+class AnimationState {
+  public:
+    AnimationClip clip;
+    float time = 0;
+    bool playing = false;
+
+    void play(const AnimationClip& animationClip);
+    void update(float deltaTime);
+    int getFrame();
+    bool isFinished();
+    Rectangle frameRegion(TextureAtlas& atlas);
+};
+
+class AnimationLibrary {
+  public:
+    void add(const AnimationClip& clip);
+    bool has(const std::string& name);
+    AnimationClip get(const std::string& name);
+
+    // Reads <animation name="..." sheet="..." frames="4" fps="8" loop="true"/>
+    // children of the given node.
+    void loadFromXml(const XmlNode& node);
+
+  private:
+    std::map<std::string, AnimationClip> clips;
+};
@@ -0,0 +1,134 @@
+#include "raylib.h"
+#include "Args.hpp"
+#include "Sanitize.hpp"
+#include <cstdio>
+#include <cstdlib>
+
+
+// Besides regular flags, the OS hands us a bare level path when a .blvl file is
+// double-clicked and a breakout:// URL when an invite link is opened.
+LaunchOptions parseArgs(int argc, char** argv) {
+    LaunchOptions options;
+
+    for (int i = 1; i < argc; i++) {
+        std::string arg = argv[i];
+
+        if (i == 1 && arg == "inspect") {
+            options.inspect = true;
+            if (i + 1 < argc) {
+                options.inspectPath = argv[++i];
+            }
+        } else if (i == 1 && arg == "validate") {
+            options.validate = true;
+            while (i + 1 < argc) {
+                options.validatePaths.push_back(argv[++i]);
+            }
+        } else if (arg == "--pack-atlas") {
+            options.packAtlas = true;
+        } else if ((arg == "--record-input" || arg == "--play-input") && i + 1 < argc) {
+            (arg == "--record-input" ? options.recordInput : options.playInput) = argv[++i];
+        } else if ((arg == "--record-replay" || arg == "--replay") && i + 1 < argc) {
+            (arg == "--record-replay" ? options.recordReplay : options.replayPath) = argv[++i];
+        } else if (arg == "--golden" || arg == "--update-golden") {
+            options.golden = true;
+            options.updateGolden = arg == "--update-golden";
+        } else if (arg == "--benchmark") {
+            options.benchmark = true;
+        } else if ((arg == "--bench-filter" || arg == "--baseline" || arg == "--save-baseline") && i + 1 < argc) {
+            std::string& value = arg == "--bench-filter" ? options.benchFilter : arg == "--baseline" ? options.baselinePath : options.saveBaselinePath;
+            value = argv[++i];
+        } else if (arg == "--telemetry") {
+            options.telemetry = true;
+        } else if (arg == "--log" && i + 1 < argc) {
+            options.logFilter = argv[++i];
+        } else if (arg == "--seed" && i + 1 < argc) {
+            const char* text = argv[++i];
+            char* end = nullptr;
+            uint64_t seed = strtoull(text, &end, 10);
+            if (*text == '\0' || *end != '\0') {
+                TraceLog(LOG_WARNING, "ARGS: Ignoring invalid seed: %s", text);
+            } else {
+                options.seed = seed;
+                options.hasSeed = true;
+            }
+        } else if (arg == "--tutorial") {
+            options.tutorial = true;
+        } else if (arg == "--weekly") {
+            options.weekly = true;
+        } else if (arg == "--daily") {
+            options.daily = true;
+        } else if (arg == "--windowed") {
+            options.windowed = true;
+        } else if (arg == "--profile" && i + 1 < argc) {
+            options.profile = argv[++i];
+        } else if (arg == "--mutators" && i + 1 < argc) {
+            options.mutators = argv[++i];
+        } else if (arg == "--mode" && i + 1 < argc) {
+            options.mode = argv[++i];
+        } else if (arg == "--connect" && i + 1 < argc) {
+            options.connectHost = argv[++i];
+        } else if (arg == "--help" || arg == "-h") {
+            options.help = true;
+        } else if (arg == "--headless") {
+            options.headless = true;
+        } else if (arg == "--bot" && i + 1 < argc) {
+            options.bot = argv[++i];
+        } else if (arg == "--ticks" && i + 1 < argc) {
+            options.ticks = strtoull(argv[++i], nullptr, 10);
+        } else if (arg == "--level" && i + 1 < argc) {
+            std::string path = argv[++i];
+            options.levelPath = sanitizeLevelPath(path);
+            if (options.levelPath.empty()) {
+                TraceLog(LOG_WARNING, "ARGS: Ignoring invalid level file: %s", path.c_str());
+            }
+        } else if (arg.rfind("breakout://", 0) == 0) {
+            options.levelPath = sanitizeLevelLink(arg);
+            if (options.levelPath.empty()) {
+                TraceLog(LOG_WARNING, "ARGS: Ignoring invalid level link: %s", arg.c_str());
+            }
+        } else if (arg.rfind("-", 0) != 0) {
+            options.levelPath = sanitizeLevelPath(arg);
+            if (options.levelPath.empty()) {
+                TraceLog(LOG_WARNING, "ARGS: Ignoring invalid level file: %s", arg.c_str());
+            }
+        } else {
+            TraceLog(LOG_WARNING, "ARGS: Unknown option: %s", arg.c_str());
+        }
+    }
+
+    return options;
+}
+
+void printUsage() {
+    printf(
+        "Usage: breakout [options] [level.blvl | breakout://...]\n"
+        "       breakout inspect <file>\n"
+        "       breakout validate <level.blvl | pack>...\n"
+        "\n"
+        "  --level <file>            play this level instead of the built-in one\n"
+        "  --seed <n>                seed the run instead of using the clock\n"
+        "  --tutorial                play the tutorial again\n"
+        "  --weekly                  play this week's challenge\n"
+        "  --daily                   play today's challenge\n"
+        "  --windowed                stay windowed even with fullscreen on in the settings\n"
+        "  --profile <name>          play as this profile for this run\n"
+        "  --mutators <a,b,...>      run modifiers, as many as wanted: double_speed,\n"
+        "                            hardcore, invisible_bricks, mirrored_controls,\n"
+        "                            one_life, tiny_paddle, wide_paddle\n"
+        "  --mode <id>               classic, endless, time_attack, survival, coop,\n"
+        "                            versus or practice\n"
+        "  --connect <host>          join an online game (not available yet)\n"
+        "  --replay <file>           watch a replay\n"
+        "  --record-replay <file>    save a replay of this run on exit\n"
+        "  --play-input <file>       drive the paddle from an input recording\n"
+        "  --record-input <file>     save this run's input on exit\n"
+        "  --telemetry               keep a local session log for this run\n"
+        "  --log <filter>            log filter, e.g. info,audio=warning,level=debug\n"
+        "  --headless                play with a bot, print a JSON result and exit\n"
+        "    --bot <easy|normal|hard>, --ticks <n>\n"
+        "  --benchmark               time the hot paths and exit\n"
+        "    --bench-filter <text>, --baseline <file>, --save-baseline <file>\n"
+        "  --golden                  render the golden scenes, compare them with golden/ and exit\n"
+        "  --update-golden           render the golden scenes and store them as the references\n"
+        "  --pack-atlas              pack assets/sprites into assets/atlas.png and exit\n");
+}
@@ -0,0 +1,43 @@
+#pragma once
+#include <cstdint>
+#include <string>
+#include <vector>
+
+
+struct LaunchOptions {
+    std::string levelPath; // empty means the built-in brick layout
+    bool packAtlas = false; // pack assets/sprites into assets/atlas.png and exit
+    std::string recordInput; // write the per-tick input stream here on exit
+    std::string playInput;   // drive the paddle from a recording instead of the devices
+    std::string recordReplay; // write a replay of this run here on exit
+    std::string replayPath;   // watch a replay instead of playing
+    bool headless = false;    // run the simulation with a bot, print a JSON result and exit
+    std::string bot = "normal";
+    uint64_t ticks = 5 * 60 * 60; // headless runs stop here at the latest
+    bool golden = false;          // render the golden scenes, compare and exit, see GoldenImages
+    bool updateGolden = false;    // with golden, store the frames as the new references
+    bool benchmark = false;       // time the hot paths, print a table and exit
+    std::string benchFilter;      // only cases whose name contains this
+    std::string baselinePath;     // compare against these results
+    std::string saveBaselinePath; // write this run's results here
+    bool telemetry = false;       // log this session even with the setting off
+    std::string logFilter;        // overrides the settings' log_filter, see setLogFilter()
+    bool hasSeed = false;
+    uint64_t seed = 0;            // with hasSeed, instead of the clock (or headless runs' 0)
+    bool windowed = false;        // ignore the fullscreen setting for this run
+    std::string profile;          // play as this profile for this run, empty for the settings' one
+    std::string mutators;         // comma separated Mutator ids
+    std::string mode = "classic"; // a GameModeInfo id
+    std::string connectHost;      // there is no online play yet; only reported
+    bool tutorial = false;        // play the tutorial even if the profile finished it
+    bool weekly = false;          // play this week's challenge, see weeklyChallenge()
+    bool daily = false;           // play today's, see dailyChallenge()
+    bool help = false;
+    bool inspect = false;         // `breakout inspect <file>`, see runInspect()
+    std::string inspectPath;
+    bool validate = false;        // `breakout validate <file>...`, see runValidate()
+    std::vector<std::string> validatePaths;
+};
+
+LaunchOptions parseArgs(int argc, char** argv);
+void printUsage();
@@ -0,0 +1,171 @@
+#include "raylib.h"
+#include "Atlas.hpp"
+#include <algorithm>
+#include <fstream>
+#include <sstream>
+#include "Log.hpp"
+
+
+static const int PADDING = 1;
+
+bool TextureAtlas::build(const std::vector<std::string>& imagePaths, int maxSize) {
+    LogSpan span("ATLAS", "build");
+    struct Pending {
+        std::string name;
+        Image image;
+    };
+    std::vector<Pending> pending;
+    for (const std::string& path : imagePaths) {
+        Image loaded = LoadImage(path.c_str());
+        if (!IsImageReady(loaded)) {
+            TraceLog(LOG_WARNING, "ATLAS: Could not load %s", path.c_str());
+            continue;
+        }
+        ImageFormat(&loaded, PIXELFORMAT_UNCOMPRESSED_R8G8B8A8);
+        pending.push_back({GetFileNameWithoutExt(path.c_str()), loaded});
+    }
+    if (pending.empty()) {
+        return false;
+    }
+
+    // Shelf packing: tallest first, left to right, new shelf when a row is full
+    std::sort(pending.begin(), pending.end(), [](const Pending& a, const Pending& b) {
+        return a.image.height > b.image.height;
+    });
+
+    std::map<std::string, Rectangle> packed;
+    int x = 0;
+    int y = 0;
+    int shelfHeight = 0;
+    int usedWidth = 0;
+    int usedHeight = 0;
+    for (const Pending& entry : pending) {
+        int width = entry.image.width + PADDING;
+        int height = entry.image.height + PADDING;
+        if (x + width > maxSize) {
+            x = 0;
+            y += shelfHeight;
+            shelfHeight = 0;
+        }
+        if (width > maxSize || y + height > maxSize) {
+            TraceLog(LOG_WARNING, "ATLAS: %s does not fit into a %dx%d atlas", entry.name.c_str(), maxSize, maxSize);
+            continue;
+        }
+        packed[entry.name] = {(float) x, (float) y, (float) entry.image.width, (float) entry.image.height};
+        x += width;
+        shelfHeight = std::max(shelfHeight, height);
+        usedWidth = std::max(usedWidth, x);
+        usedHeight = std::max(usedHeight, y + shelfHeight);
+    }
+
+    unload();
+    image = GenImageColor(usedWidth, usedHeight, BLANK);
+    for (const Pending& entry : pending) {
+        auto it = packed.find(entry.name);
+        if (it != packed.end()) {
+            Rectangle source = {0, 0, (float) entry.image.width, (float) entry.image.height};
+            ImageDraw(&image, entry.image, source, it->second, WHITE);
+        }
+        UnloadImage(entry.image);
+    }
+    regions = packed;
+    texture = LoadTextureFromImage(image);
+    TraceLog(LOG_INFO, "ATLAS: Packed %d sprites into %dx%d", (int) regions.size(), usedWidth, usedHeight);
+    return IsTextureReady(texture);
+}
+
+bool TextureAtlas::buildFromDirectory(const std::string& directory, int maxSize) {
+    if (!DirectoryExists(directory.c_str())) {
+        return false;
+    }
+    FilePathList files = LoadDirectoryFilesEx(directory.c_str(), ".png", true);
+    std::vector<std::string> paths;
+    for (unsigned int i = 0; i < files.count; i++) {
+        paths.push_back(files.paths[i]);
+    }
+    UnloadDirectoryFiles(files);
+    return build(paths, maxSize);
+}
+
+bool TextureAtlas::save(const std::string& imagePath, const std::string& manifestPath) {
+    if (!IsImageReady(image)) {
+        return false;
+    }
+    std::ofstream manifest(manifestPath);
+    if (!manifest || !ExportImage(image, imagePath.c_str())) {
+        TraceLog(LOG_WARNING, "ATLAS: Could not write %s", imagePath.c_str());
+        return false;
+    }
+    for (auto& entry : regions) {
+        const Rectangle& rect = entry.second;
+        manifest << entry.first << " " << rect.x << " " << rect.y << " " << rect.width << " " << rect.height << "\n";
+    }
+    return true;
+}
+
+bool TextureAtlas::load(const std::string& imagePath, const std::string& manifestPath) {
+    std::ifstream manifest(manifestPath);
+    if (!manifest || !FileExists(imagePath.c_str())) {
+        return false;
+    }
+    std::map<std::string, Rectangle> loaded;
+    std::string line;
+    int lineNumber = 0;
+    while (std::getline(manifest, line)) {
+        lineNumber++;
+        if (line.empty() || line[0] == '#') {
+            continue;
+        }
+        std::istringstream fields(line);
+        std::string name;
+        Rectangle rect;
+        if (!(fields >> name >> rect.x >> rect.y >> rect.width >> rect.height)) {
+            TraceLog(LOG_WARNING, "ATLAS: %s:%d: malformed region", manifestPath.c_str(), lineNumber);
+            continue;
+        }
+        loaded[name] = rect;
+    }
+
+    unload();
+    image = LoadImage(imagePath.c_str());
+    texture = LoadTextureFromImage(image);
+    regions = loaded;
+    return IsTextureReady(texture);
+}
+
+void TextureAtlas::unload() {
+    if (IsTextureReady(texture)) {
+        UnloadTexture(texture);
+    }
+    if (IsImageReady(image)) {
+        UnloadImage(image);
+    }
+    texture = {};
+    image = {};
+    regions.clear();
+}
+
+bool TextureAtlas::isReady() {
+    return IsTextureReady(texture);
+}
+
+bool TextureAtlas::has(const std::string& name) {
+    return regions.count(name) > 0;
+}
+
+Rectangle TextureAtlas::region(const std::string& name) {
+    auto it = regions.find(name);
+    return it == regions.end() ? Rectangle{0, 0, 0, 0} : it->second;
+}
+
+Texture2D TextureAtlas::getTexture() {
+    return texture;
+}
+
+bool loadGameAtlas(TextureAtlas& atlas) {
+    std::string assets = std::string(GetApplicationDirectory()) + "assets/";
+    if (atlas.load(assets + "atlas.png", assets + "atlas.manifest")) {
+        return true;
+    }
+    return atlas.buildFromDirectory(assets + "sprites");
+}
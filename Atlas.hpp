@@ -0,0 +1,36 @@
+#pragma once
+#include "raylib.h"
+#include <map>
+#include <string>
+#include <vector>
+
+
+// Packs individual sprite images into one shared texture so everything drawn
+// through the SpriteBatch can share a single texture bind.
+//
+// The manifest next to the atlas image lists one region per line:
+//   name x y width height
+class TextureAtlas {
+  public:
+    // Packs every image in imagePaths, named by file name without extension.
+    bool build(const std::vector<std::string>& imagePaths, int maxSize = 2048);
+    bool buildFromDirectory(const std::string& directory, int maxSize = 2048);
+    // Writes the packed image and its manifest for shipping a prebuilt atlas.
+    bool save(const std::string& imagePath, const std::string& manifestPath);
+    bool load(const std::string& imagePath, const std::string& manifestPath);
+    void unload();
+
+    bool isReady();
+    bool has(const std::string& name);
+    Rectangle region(const std::string& name);
+    Texture2D getTexture();
+
+  private:
+    Image image = {};
+    Texture2D texture = {};
+    std::map<std::string, Rectangle> regions;
+};
+
+// Loads the prebuilt atlas from assets/ if there is one, otherwise packs
+// assets/sprites/ at load time.
+bool loadGameAtlas(TextureAtlas& atlas);
@@ -0,0 +1,344 @@
+#ifndef BREAKOUT_NO_AUDIO
+#include "raylib.h"
+#include "Audio.hpp"
+#include <cmath>
+#include <fstream>
+#include <sstream>
+#include "Log.hpp"
+
+
+AudioBus parseAudioBus(const std::string& name) {
+    if (name == "music") {
+        return AudioBus::Music;
+    }
+    if (name == "ui") {
+        return AudioBus::Ui;
+    }
+    if (name == "master") {
+        return AudioBus::Master;
+    }
+    return AudioBus::Sfx;
+}
+
+std::string soundManifestPath() {
+    return std::string(GetApplicationDirectory()) + "assets/sounds/sounds.manifest";
+}
+
+bool AudioEngine::init() {
+    InitAudioDevice();
+    ready = IsAudioDeviceReady();
+    if (!ready) {
+        TraceLog(LOG_WARNING, "AUDIO: No audio device, running silent");
+        return false;
+    }
+    mixer.init();
+    return true;
+}
+
+void AudioEngine::unload() {
+    unloadSounds();
+    mixer.unload();
+    if (ready) {
+        CloseAudioDevice();
+        ready = false;
+    }
+}
+
+void AudioEngine::unloadSounds() {
+    for (auto& entry : sounds) {
+        SoundAsset& asset = entry.second;
+        for (size_t i = 1; i < asset.voices.size(); i++) {
+            UnloadSoundAlias(asset.voices[i].sound);
+        }
+        UnloadSound(asset.source);
+    }
+    sounds.clear();
+}
+
+bool AudioEngine::isReady() {
+    return ready;
+}
+
+void AudioEngine::apply(const Settings& settings) {
+    setVolume(AudioBus::Master, settings.masterVolume);
+    setVolume(AudioBus::Music, settings.musicVolume);
+    setVolume(AudioBus::Sfx, settings.sfxVolume);
+    setVolume(AudioBus::Ui, settings.uiVolume);
+    muteOnFocusLoss = settings.muteOnFocusLoss;
+    backgroundAudio = settings.backgroundAudio;
+    lowLatency = settings.lowLatencySfx;
+    // raylib stops the main loop while minimized unless told to keep running
+    if (backgroundAudio) {
+        SetWindowState(FLAG_WINDOW_ALWAYS_RUN);
+    } else {
+        ClearWindowState(FLAG_WINDOW_ALWAYS_RUN);
+    }
+    if (!muteOnFocusLoss && !IsWindowMinimized()) {
+        setSuspended(false);
+    }
+}
+
+void AudioEngine::onWindowEvent(WindowEvent event) {
+    switch (event) {
+    case WindowEvent::FocusLost:
+        if (muteOnFocusLoss) {
+            setSuspended(true);
+        }
+        break;
+    case WindowEvent::Minimized:
+        if (muteOnFocusLoss || !backgroundAudio) {
+            setSuspended(true);
+        }
+        break;
+    case WindowEvent::FocusGained:
+    case WindowEvent::Restored:
+        if (IsWindowFocused() && !IsWindowMinimized()) {
+            setSuspended(false);
+        }
+        break;
+    }
+}
+
+void AudioEngine::setSuspended(bool value) {
+    if (value == suspended) {
+        return;
+    }
+    suspended = value;
+    refreshVolumes(AudioBus::Master);
+}
+
+bool AudioEngine::isSuspended() {
+    return suspended;
+}
+
+bool AudioEngine::loadManifest(const std::string& path) {
+    LogSpan span("AUDIO", "manifest");
+    std::ifstream file(path);
+    if (!file) {
+        TraceLog(LOG_INFO, "AUDIO: No sound manifest at %s", path.c_str());
+        return false;
+    }
+    std::string directory = GetDirectoryPath(path.c_str());
+    std::string line;
+    bool complete = true;
+    while (std::getline(file, line)) {
+        if (line.empty() || line[0] == '#') {
+            continue;
+        }
+        std::istringstream fields(line);
+        if (line.compare(0, 4, "cue ") == 0) {
+            std::string keyword, name, variant;
+            SoundCue cue;
+            fields >> keyword >> name >> cue.minPitch >> cue.maxPitch >> cue.minVolume >> cue.maxVolume;
+            while (fields >> variant) {
+                cue.variants.push_back(variant);
+            }
+            addCue(name, cue);
+            continue;
+        }
+        std::string name, bus, filename;
+        int voices = 4;
+        if (!(fields >> name >> bus >> filename)) {
+            continue;
+        }
+        fields >> voices;
+        complete = loadSound(name, directory + "/" + filename, parseAudioBus(bus), voices) && complete;
+    }
+    return complete;
+}
+
+bool AudioEngine::reloadManifest(const std::string& path) {
+    stopAll();
+    unloadSounds();
+    mixer.clearSamples();
+    cues.clear();
+    return loadManifest(path);
+}
+
+bool AudioEngine::loadSound(const std::string& name, const std::string& path, AudioBus bus, int voices) {
+    if (!ready) {
+        return false;
+    }
+    if (!FileExists(path.c_str())) {
+        TraceLog(LOG_WARNING, "AUDIO: Missing sound %s (%s)", name.c_str(), path.c_str());
+        return false;
+    }
+    Sound source = LoadSound(path.c_str());
+    if (!IsSoundReady(source)) {
+        TraceLog(LOG_WARNING, "AUDIO: Could not load %s", path.c_str());
+        return false;
+    }
+
+    SoundAsset asset;
+    asset.bus = bus;
+    asset.source = source;
+    asset.voices.push_back({source});
+    for (int i = 1; i < voices; i++) {
+        asset.voices.push_back({LoadSoundAlias(source)});
+    }
+    if (bus == AudioBus::Sfx && mixer.isReady()) {
+        Wave wave = LoadWave(path.c_str());
+        asset.mixerSample = mixer.addSample(wave);
+        UnloadWave(wave);
+    }
+    sounds[name] = asset;
+    return true;
+}
+
+bool AudioEngine::has(const std::string& name) {
+    return sounds.count(name) > 0;
+}
+
+AudioEngine::Voice* AudioEngine::oldestVoice() {
+    Voice* oldest = nullptr;
+    for (auto& entry : sounds) {
+        for (Voice& voice : entry.second.voices) {
+            if (IsSoundPlaying(voice.sound) && (!oldest || voice.started < oldest->started)) {
+                oldest = &voice;
+            }
+        }
+    }
+    return oldest;
+}
+
+int AudioEngine::getActiveVoices() {
+    int active = mixer.getActiveVoices();
+    for (auto& entry : sounds) {
+        for (Voice& voice : entry.second.voices) {
+            active += IsSoundPlaying(voice.sound) ? 1 : 0;
+        }
+    }
+    return active;
+}
+
+bool AudioEngine::play(const std::string& name, float volume, float pan, float pitch, double time) {
+    auto found = sounds.find(name);
+    if (!ready || found == sounds.end()) {
+        return false;
+    }
+    SoundAsset& asset = found->second;
+    float gain = busGain(asset.bus);
+    if (gain <= 0) {
+        return false;
+    }
+    if (lowLatency && asset.mixerSample >= 0) {
+        // the mixer applies the bus gain itself so volume changes are live
+        mixer.schedule(asset.mixerSample, time, volume, pan, pitch);
+        return true;
+    }
+    if (getActiveVoices() >= MAX_VOICES) {
+        if (Voice* oldest = oldestVoice()) {
+            StopSound(oldest->sound);
+        }
+    }
+
+    // round-robin through this sound's aliases, reusing the oldest one
+    Voice& voice = asset.voices[asset.next];
+    asset.next = (asset.next + 1) % asset.voices.size();
+    voice.started = GetTime();
+    voice.volume = volume;
+    SetSoundVolume(voice.sound, volume * gain);
+    SetSoundPan(voice.sound, pan);
+    SetSoundPitch(voice.sound, pitch);
+    PlaySound(voice.sound);
+    return true;
+}
+
+void AudioEngine::setListener(Vector2 position, float width, float distance) {
+    listener = position;
+    fieldWidth = width;
+    range = distance;
+}
+
+bool AudioEngine::playAt(const std::string& name, Vector2 position, float volume, float pitch, double time) {
+    // keep a little of both channels at the edges, hard panning is tiring
+    float pan = fieldWidth > 0 ? 0.15f + 0.7f * fminf(fmaxf(position.x / fieldWidth, 0), 1) : 0.5f;
+    float attenuation = 1;
+    if (range > 0) {
+        float distance = hypotf(position.x - listener.x, position.y - listener.y);
+        attenuation = 1 - 0.6f * fminf(distance / range, 1);
+    }
+    return play(name, volume * attenuation, pan, pitch, time);
+}
+
+void AudioEngine::addCue(const std::string& name, const SoundCue& cue) {
+    cues[name] = cue;
+}
+
+static float randomRange(float min, float max) {
+    return min + (max - min) * (GetRandomValue(0, 10000) / 10000.0f);
+}
+
+bool AudioEngine::playCue(const std::string& name, Vector2 position, int comboStep, double time) {
+    float comboPitch = powf(2, (comboStep < 0 ? 0 : (comboStep > 7 ? 7 : comboStep)) / 12.0f);
+    auto found = cues.find(name);
+    if (found == cues.end()) {
+        return playAt(name, position, 1, comboPitch, time);
+    }
+    SoundCue& cue = found->second;
+    // only pick among variants that actually loaded
+    std::vector<const std::string*> loaded;
+    for (const std::string& variant : cue.variants) {
+        if (has(variant)) {
+            loaded.push_back(&variant);
+        }
+    }
+    if (loaded.empty()) {
+        return false;
+    }
+    const std::string& variant = *loaded[GetRandomValue(0, loaded.size() - 1)];
+    float pitch = randomRange(cue.minPitch, cue.maxPitch) * comboPitch;
+    return playAt(variant, position, randomRange(cue.minVolume, cue.maxVolume), pitch, time);
+}
+
+void AudioEngine::stopAll() {
+    mixer.stopAll();
+    for (auto& entry : sounds) {
+        for (Voice& voice : entry.second.voices) {
+            StopSound(voice.sound);
+        }
+    }
+}
+
+void AudioEngine::setVolume(AudioBus bus, float volume) {
+    volumes[(int) bus] = volume < 0 ? 0 : (volume > 1 ? 1 : volume);
+    refreshVolumes(bus);
+}
+
+float AudioEngine::getVolume(AudioBus bus) {
+    return volumes[(int) bus];
+}
+
+void AudioEngine::setMuted(AudioBus bus, bool mute) {
+    muted[(int) bus] = mute;
+    refreshVolumes(bus);
+}
+
+bool AudioEngine::isMuted(AudioBus bus) {
+    return muted[(int) bus];
+}
+
+float AudioEngine::busGain(AudioBus bus) {
+    int master = (int) AudioBus::Master;
+    if (suspended || muted[master] || muted[(int) bus]) {
+        return 0;
+    }
+    return bus == AudioBus::Master ? volumes[master] : volumes[master] * volumes[(int) bus];
+}
+
+// Applies a volume change to sounds that are already playing
+void AudioEngine::refreshVolumes(AudioBus bus) {
+    mixer.setGain(busGain(AudioBus::Sfx));
+    for (auto& entry : sounds) {
+        SoundAsset& asset = entry.second;
+        if (bus != AudioBus::Master && asset.bus != bus) {
+            continue;
+        }
+        float gain = busGain(asset.bus);
+        for (Voice& voice : asset.voices) {
+            SetSoundVolume(voice.sound, voice.volume * gain);
+        }
+    }
+}
+
+#endif
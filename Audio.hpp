@@ -0,0 +1,117 @@
+#pragma once
+#include "raylib.h"
+#include <map>
+#include <string>
+#include <vector>
+#include "Lifecycle.hpp"
+#include "SfxMixer.hpp"
+#include "Settings.hpp"
+
+
+enum class AudioBus {
+    Master,
+    Music,
+    Sfx,
+    Ui,
+};
+
+AudioBus parseAudioBus(const std::string& name);
+
+// A pool of interchangeable samples for one kind of event. Each trigger picks
+// a random variant with a random pitch and volume inside the ranges, so a
+// rally of identical hits doesn't sound like a machine gun.
+struct SoundCue {
+    std::vector<std::string> variants;
+    float minPitch = 1;
+    float maxPitch = 1;
+    float minVolume = 1;
+    float maxVolume = 1;
+};
+
+// Sound playback over raylib's audio device. Every sound belongs to a bus;
+// a bus's effective gain is its own volume times the master volume (zero if
+// either is muted). Each sound gets a few aliases so it can overlap itself,
+// and the total number of voices is capped: past MAX_VOICES the oldest voice
+// is cut, so a burst of 200 brick hits stays a handful of sounds instead of
+// clipping.
+class AudioEngine {
+  public:
+    static constexpr int MAX_VOICES = 32;
+
+    bool init();
+    void unload();
+    bool isReady();
+    // Bus volumes and the focus/minimize behavior from the settings.
+    void apply(const Settings& settings);
+    void onWindowEvent(WindowEvent event);
+    // Silences everything without touching the player's volumes or mutes.
+    void setSuspended(bool suspended);
+    bool isSuspended();
+
+    // Lines of "name bus file [voices]", files relative to the manifest, and
+    // "cue name min-pitch max-pitch min-volume max-volume sound..." pools.
+    bool loadManifest(const std::string& path);
+    // Drops every sound and cue and loads the manifest again.
+    bool reloadManifest(const std::string& path);
+    bool loadSound(const std::string& name, const std::string& path, AudioBus bus, int voices = 4);
+    bool has(const std::string& name);
+
+    // pan 0 = left, 0.5 = center, 1 = right. time is when the sound should
+    // start (GetTime()-based, e.g. GameEvent::time); effects honor it through
+    // the low-latency mixer, negative means now.
+    bool play(const std::string& name, float volume = 1, float pan = 0.5f, float pitch = 1, double time = -1);
+    // Pans by the x position across the field and gets quieter with distance
+    // from the listener (the paddle).
+    bool playAt(const std::string& name, Vector2 position, float volume = 1, float pitch = 1, double time = -1);
+    void setListener(Vector2 position, float fieldWidth, float range);
+
+    void addCue(const std::string& name, const SoundCue& cue);
+    // Plays a random variant of the cue at a position; every combo step
+    // raises the pitch a semitone (capped at a fifth). Falls back to a plain
+    // sound of the same name when no cue is defined.
+    bool playCue(const std::string& name, Vector2 position, int comboStep = 0, double time = -1);
+    void stopAll();
+
+    void setVolume(AudioBus bus, float volume);
+    float getVolume(AudioBus bus);
+    void setMuted(AudioBus bus, bool muted);
+    bool isMuted(AudioBus bus);
+    float busGain(AudioBus bus);
+
+    int getActiveVoices();
+
+  private:
+    struct Voice {
+        Sound sound;
+        double started = -1;
+        float volume = 1;
+    };
+
+    struct SoundAsset {
+        AudioBus bus;
+        Sound source;
+        std::vector<Voice> voices; // voices[0] plays the source itself
+        int next = 0;
+        int mixerSample = -1;
+    };
+
+    std::map<std::string, SoundAsset> sounds;
+    std::map<std::string, SoundCue> cues;
+    float volumes[4] = {1, 1, 1, 1};
+    bool muted[4] = {false, false, false, false};
+    bool ready = false;
+    bool suspended = false;
+    bool muteOnFocusLoss = false;
+    bool backgroundAudio = true;
+    bool lowLatency = true;
+    SfxMixer mixer;
+    Vector2 listener = {0, 0};
+    float fieldWidth = 0;
+    float range = 0;
+
+    void unloadSounds();
+    Voice* oldestVoice();
+    void refreshVolumes(AudioBus bus);
+};
+
+std::string soundManifestPath();
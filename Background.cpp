@@ -0,0 +1,51 @@
+#include "raylib.h"
+#include "Background.hpp"
+#include <cmath>
+
+
+void Background::load(const std::string& directory, const std::vector<BackgroundLayer>& layers) {
+    unload();
+    for (const BackgroundLayer& layer : layers) {
+        std::string path = directory + "/" + layer.image;
+        Texture2D texture = LoadTexture(path.c_str());
+        if (!IsTextureReady(texture)) {
+            TraceLog(LOG_WARNING, "BACKGROUND: Could not load %s", path.c_str());
+            continue;
+        }
+        SetTextureWrap(texture, TEXTURE_WRAP_REPEAT);
+        loaded.push_back({layer, texture, {0, 0}});
+    }
+}
+
+void Background::unload() {
+    for (LoadedLayer& entry : loaded) {
+        UnloadTexture(entry.texture);
+    }
+    loaded.clear();
+}
+
+void Background::setReducedMotion(bool enabled) {
+    reducedMotion = enabled;
+}
+
+void Background::update(float deltaTime, Vector2 focusPoint) {
+    if (reducedMotion) {
+        return;
+    }
+    focus = focusPoint;
+    for (LoadedLayer& entry : loaded) {
+        entry.scroll.x = fmodf(entry.scroll.x + entry.layer.scrollSpeed.x * deltaTime, (float) entry.texture.width);
+        entry.scroll.y = fmodf(entry.scroll.y + entry.layer.scrollSpeed.y * deltaTime, (float) entry.texture.height);
+    }
+}
+
+void Background::draw(int screenWidth, int screenHeight) {
+    Vector2 center = {screenWidth / 2.0f, screenHeight / 2.0f};
+    for (LoadedLayer& entry : loaded) {
+        float parallax = reducedMotion ? 0 : entry.layer.ballParallax;
+        Vector2 lean = {(focus.x - center.x) * parallax, (focus.y - center.y) * parallax};
+        // with repeat wrapping, offsetting the source rectangle scrolls the tiling
+        Rectangle source = {-entry.scroll.x - lean.x, -entry.scroll.y - lean.y, (float) screenWidth, (float) screenHeight};
+        DrawTexturePro(entry.texture, source, {0, 0, (float) screenWidth, (float) screenHeight}, {0, 0}, 0, entry.layer.tint);
+    }
+}
@@ -0,0 +1,36 @@
+#pragma once
+#include "raylib.h"
+#include <string>
+#include <vector>
+
+
+struct BackgroundLayer {
+    std::string image; // path relative to the theme directory
+    Vector2 scrollSpeed = {0, 0}; // pixels per second
+    float ballParallax = 0;       // how far the layer leans towards the ball, 0 = fixed
+    Color tint = WHITE;
+};
+
+// Draws a theme's background layers back to front. Each layer tiles its
+// image, scrolls at its own speed and can shift slightly with the ball to
+// fake depth.
+class Background {
+  public:
+    void load(const std::string& directory, const std::vector<BackgroundLayer>& layers);
+    void unload();
+    // Freezes scrolling and ball parallax.
+    void setReducedMotion(bool enabled);
+    void update(float deltaTime, Vector2 focus);
+    void draw(int screenWidth, int screenHeight);
+
+  private:
+    struct LoadedLayer {
+        BackgroundLayer layer;
+        Texture2D texture;
+        Vector2 scroll;
+    };
+
+    std::vector<LoadedLayer> loaded;
+    Vector2 focus = {0, 0};
+    bool reducedMotion = false;
+};
@@ -0,0 +1,32 @@
+#include "raylib.h"
+#include "Ball.hpp"
+#include <cmath>
+
+
+Ball::Ball(Vector2 position, int width, int height) : Entity(position, width, height) {
+}
+
+void Ball::addForce(Vector2 amount) {
+    force.x += amount.x;
+    force.y += amount.y;
+}
+
+void Ball::advance(float timeScale) {
+    if (force.x != 0 || force.y != 0) {
+        float speed = sqrtf(velocity.x * velocity.x + velocity.y * velocity.y);
+        Vector2 turned = {velocity.x + force.x * timeScale, velocity.y + force.y * timeScale};
+        float length = sqrtf(turned.x * turned.x + turned.y * turned.y);
+        if (speed > 0 && length > 0) {
+            turned = {turned.x * speed / length, turned.y * speed / length};
+            if (fabsf(turned.y) < speed * MIN_VERTICAL) {
+                float vertical = (velocity.y < 0 ? -1 : 1) * speed * MIN_VERTICAL;
+                turned = {(turned.x < 0 ? -1 : 1) * sqrtf(speed * speed - vertical * vertical), vertical};
+            }
+            velocity = turned;
+        }
+        force = {0, 0};
+    }
+    position.x += velocity.x * timeScale;
+    position.y += velocity.y * timeScale;
+    updateRectangle();
+}
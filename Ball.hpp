@@ -0,0 +1,41 @@
+#pragma once
+#include "raylib.h"
+#include <cstdint>
+#include "Entity.hpp"
+
+
+enum class BallState {
+    Free,
+    Serving, // a new ball, waiting on the paddle to be served
+    Caught,  // held by the catch power-up where it landed, until launched
+};
+
+// A ball in play. There can be several at once (multi-ball, see PowerUp.hpp);
+// the id stays with a ball for as long as it lives, so anything following one
+// (its trail) can tell them apart.
+class Ball : public Entity {
+  public:
+    // the least share of its speed that stays vertical
+    static constexpr float MIN_VERTICAL = 0.35f;
+
+    uint32_t id = 0;
+    BallState state = BallState::Free;
+    // while on the paddle: from the paddle's center to the ball's
+    float paddleOffset = 0;
+    // fireball: breaks through bricks instead of bouncing off them
+    bool piercing = false;
+    // the paddle it's on or last left, 1 for co-op's second; its points go
+    // to that player
+    int owner = 0;
+    // Forces pushed on it this tick (magnet, ...), in pixels per tick per
+    // tick; advance() uses them up.
+    Vector2 force = {0, 0};
+
+    Ball(Vector2 position, int width, int height);
+
+    bool isFree() const { return state == BallState::Free; }
+    // One tick's move, time-scaled (see GameState::timeScale). Forces turn
+    // the ball but never change its speed, and never so far it runs flat.
+    void advance(float timeScale);
+    void addForce(Vector2 amount);
+};
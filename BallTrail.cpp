@@ -0,0 +1,105 @@
+#include "raylib.h"
+#include "BallTrail.hpp"
+#include <cmath>
+
+
+// Speed (pixels per update) at which the whole history is shown
+static const float FULL_TRAIL_SPEED = 8.0f;
+
+BallTrail::BallTrail(int capacity) {
+    history.resize(capacity);
+    head = 0;
+    count = 0;
+    speed = 0;
+}
+
+void BallTrail::record(Vector2 center, Vector2 velocity) {
+    history[head] = center;
+    head = (head + 1) % history.size();
+    if (count < (int) history.size()) {
+        count++;
+    }
+    speed = sqrtf(velocity.x * velocity.x + velocity.y * velocity.y);
+}
+
+void BallTrail::clear() {
+    count = 0;
+}
+
+// age 0 is the most recent point
+Vector2 BallTrail::pointAt(int age) {
+    int index = (head - 1 - age + (int) history.size()) % history.size();
+    return history[index];
+}
+
+void BallTrail::draw(float size, Color color, TrailStyle style) {
+    float speedFactor = fminf(speed / FULL_TRAIL_SPEED, 1.0f);
+    int visible = (int) (count * (0.25f + 0.75f * speedFactor));
+    if (visible < 2) {
+        return;
+    }
+    float intensity = 0.3f + 0.5f * speedFactor;
+
+    if (style == TrailStyle::Fireball) {
+        BeginBlendMode(BLEND_ADDITIVE);
+        size *= 1.5f;
+        intensity = 1.0f;
+    }
+
+    // oldest first so the head of the trail is drawn on top
+    for (int age = visible - 1; age >= 1; age--) {
+        float t = 1.0f - (float) age / visible;
+        float segmentSize = size * (0.3f + 0.7f * t);
+        Color segmentColor = color;
+        if (style == TrailStyle::Fireball) {
+            segmentColor = t > 0.6f ? YELLOW : (t > 0.3f ? ORANGE : RED);
+        }
+        segmentColor.a = (unsigned char) (segmentColor.a * t * intensity);
+
+        Vector2 point = pointAt(age);
+        if (style == TrailStyle::Ribbon) {
+            DrawLineEx(pointAt(age - 1), point, segmentSize * 0.6f, segmentColor);
+            continue;
+        }
+        if (style == TrailStyle::Sparkle) {
+            // every third point, alternately offset to either side of the path
+            if (age % 3 != 0) {
+                continue;
+            }
+            float offset = (age % 2 == 0 ? 0.5f : -0.5f) * size;
+            point = {point.x + offset, point.y - offset};
+            segmentSize *= 0.5f;
+        }
+        DrawRectangleV({point.x - segmentSize / 2, point.y - segmentSize / 2}, {segmentSize, segmentSize}, segmentColor);
+    }
+
+    if (style == TrailStyle::Fireball) {
+        EndBlendMode();
+    }
+}
+
+void BallTrails::record(std::vector<Ball>& balls) {
+    for (auto it = trails.begin(); it != trails.end();) {
+        bool inPlay = false;
+        for (const Ball& ball : balls) {
+            inPlay = inPlay || ball.id == it->first;
+        }
+        it = inPlay ? std::next(it) : trails.erase(it);
+    }
+    for (Ball& ball : balls) {
+        trails[ball.id].record(ball.getCenter(), ball.velocity);
+    }
+}
+
+void BallTrails::clear() {
+    trails.clear();
+}
+
+void BallTrails::draw(std::vector<Ball>& balls, Color color, TrailStyle style) {
+    for (Ball& ball : balls) {
+        auto found = trails.find(ball.id);
+        if (found != trails.end()) {
+            found->second.draw(ball.rectangle.width, ball.piercing ? ORANGE : color, ball.piercing ? TrailStyle::Fireball : style);
+        }
+    }
+}
@@ -0,0 +1,44 @@
+#pragma once
+#include "raylib.h"
+#include <map>
+#include <vector>
+#include "Ball.hpp"
+
+
+enum class TrailStyle {
+    Normal,
+    Fireball,
+    Ribbon,  // one continuous band (unlockable, see Cosmetics)
+    Sparkle, // scattered glints (unlockable)
+};
+
+// Ring buffer of a ball's recent centers. The faster the ball moves the more
+// of the history is drawn and the brighter the trail gets.
+class BallTrail {
+  public:
+    BallTrail(int capacity = 24);
+
+    void record(Vector2 center, Vector2 velocity);
+    void clear();
+    void draw(float size, Color color, TrailStyle style);
+
+  private:
+    std::vector<Vector2> history;
+    int head;
+    int count;
+    float speed;
+
+    Vector2 pointAt(int age);
+};
+
+// One trail per ball in play, by id; a ball's trail starts when it shows up
+// and goes when it does. A piercing ball always trails fire.
+class BallTrails {
+  public:
+    void record(std::vector<Ball>& balls);
+    void clear();
+    void draw(std::vector<Ball>& balls, Color color, TrailStyle style);
+
+  private:
+    std::map<uint32_t, BallTrail> trails;
+};
@@ -0,0 +1,7 @@
+#include "raylib.h"
+#include "Barrier.hpp"
+
+
+Rectangle Barrier::getRectangle(int fieldWidth, int fieldHeight) const {
+    return {0, fieldHeight - GAP - HEIGHT, (float) fieldWidth, HEIGHT};
+}
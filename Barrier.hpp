@@ -0,0 +1,15 @@
+#pragma once
+#include "raylib.h"
+
+
+// The shield power-up: a bar of energy just above the bottom edge that sends
+// the first ball to reach it back up, then goes. Not a brick and not a wall,
+// so it has its own shape here instead of living in either list.
+struct Barrier {
+    static constexpr float HEIGHT = 6;
+    static constexpr float GAP = 12; // from the bottom edge to the barrier's bottom
+
+    bool up = false;
+
+    Rectangle getRectangle(int fieldWidth, int fieldHeight) const;
+};
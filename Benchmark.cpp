@@ -0,0 +1,235 @@
+#include "raylib.h"
+#include "Benchmark.hpp"
+#include <algorithm>
+#include <chrono>
+#include <cstdio>
+#include <cstdlib>
+#include <fstream>
+#include <map>
+#include "Bot.hpp"
+#include "Events.hpp"
+#include "FramePacer.hpp"
+#include "InputRecord.hpp"
+#include "Level.hpp"
+#include "Simulation.hpp"
+#include "SpriteBatch.hpp"
+
+
+static const int SAMPLES = 15;
+static const double SAMPLE_SECONDS = 0.005;
+
+// Results feed in here so the optimizer can't drop the work.
+static volatile uint64_t sink = 0;
+
+template <typename Body>
+static BenchmarkResult measure(const std::string& name, Body body) {
+    using Clock = std::chrono::steady_clock;
+    auto time = [&body](long long iterations) {
+        Clock::time_point start = Clock::now();
+        for (long long i = 0; i < iterations; i++) {
+            body();
+        }
+        return std::chrono::duration<double>(Clock::now() - start).count();
+    };
+
+    // doubling until a sample is long enough for the clock, which also warms up
+    long long batch = 1;
+    while (time(batch) < SAMPLE_SECONDS && batch < (1LL << 30)) {
+        batch *= 2;
+    }
+    std::vector<double> samples;
+    for (int i = 0; i < SAMPLES; i++) {
+        samples.push_back(time(batch) * 1e9 / batch);
+    }
+    std::nth_element(samples.begin(), samples.begin() + SAMPLES / 2, samples.end());
+    return {name, samples[SAMPLES / 2], batch * SAMPLES};
+}
+
+// A dense grid of small bricks in the top part of the field.
+static std::vector<Brick> brickGrid(int count) {
+    std::vector<Brick> bricks;
+    const int columns = 50;
+    for (int i = 0; i < count; i++) {
+        bricks.push_back({15 + 25 * (i % columns), 40 + 10 * (i / columns), 24, 8});
+    }
+    return bricks;
+}
+
+static std::string levelSource(const std::vector<Brick>& bricks) {
+    std::string source = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<level name=\"Benchmark\">\n";
+    for (const Brick& brick : bricks) {
+        source += TextFormat("  <brick x=\"%d\" y=\"%d\" width=\"%d\" height=\"%d\" material=\"glass\"/>\n",
+            (int) brick.rectangle.x, (int) brick.rectangle.y, (int) brick.rectangle.width, (int) brick.rectangle.height);
+    }
+    return source + "</level>\n";
+}
+
+// A minute of the hard bot playing, the input a mouse player produces: a
+// new steering target nearly every tick.
+static std::vector<InputFrame> recordedMinute() {
+    std::vector<Brick> bricks;
+    createBricks(bricks);
+    Simulation simulation;
+    simulation.reset(bricks, 0);
+    Bot bot(BotSkill::Hard, 0);
+    std::vector<InputFrame> frames;
+    for (int i = 0; i < 60 * FramePacer::TICK_RATE; i++) {
+        frames.push_back(bot.think(simulation.state, simulation.rules));
+        simulation.step(frames.back(), nullptr);
+    }
+    return frames;
+}
+
+std::vector<BenchmarkResult> runBenchmarks(const std::string& filter) {
+    std::vector<BenchmarkResult> results;
+    auto selected = [&filter](const std::string& name) {
+        return filter.empty() || name.find(filter) != std::string::npos;
+    };
+
+    // one iteration is one tick: the bot's input, the step and dispatching its events
+    for (int count : {80, 500, 2000}) {
+        std::string name = "simulation/tick bricks=" + std::to_string(count);
+        if (!selected(name)) {
+            continue;
+        }
+        std::vector<Brick> bricks;
+        if (count == 80) {
+            createBricks(bricks);
+        } else {
+            bricks = brickGrid(count);
+        }
+        Simulation simulation;
+        simulation.reset(bricks, 0);
+        Bot bot(BotSkill::Hard, 0);
+        EventBus events;
+        results.push_back(measure(name, [&]() {
+            if (simulation.state.lives <= 0 || simulation.state.bricksLeft <= 0) {
+                simulation.reset(bricks, 0);
+            }
+            simulation.step(bot.think(simulation.state, simulation.rules), &events);
+            events.dispatch();
+        }));
+    }
+    if (selected("simulation/checksum bricks=2000")) {
+        Simulation simulation;
+        simulation.reset(brickGrid(2000), 0);
+        results.push_back(measure("simulation/checksum bricks=2000", [&]() { sink = sink + simulation.checksum(); }));
+    }
+
+    for (int count : {80, 2000}) {
+        std::string name = "level/parse bricks=" + std::to_string(count);
+        if (!selected(name)) {
+            continue;
+        }
+        std::string source = levelSource(brickGrid(count));
+        results.push_back(measure(name, [&]() {
+            Level level;
+            std::string error;
+            parseLevel(source, "benchmark.blvl", level, error);
+            sink = sink + level.bricks.size();
+        }));
+    }
+
+    if (selected("input/record minute") || selected("input/play minute")) {
+        std::vector<InputFrame> frames = recordedMinute();
+        InputRecorder recorder;
+        std::vector<uint8_t> data;
+        auto record = [&]() {
+            recorder.start(FramePacer::TICK_RATE);
+            for (size_t tick = 0; tick < frames.size(); tick++) {
+                recorder.record(tick, frames[tick]);
+            }
+            data = recorder.finish(frames.size());
+        };
+        if (selected("input/record minute")) {
+            results.push_back(measure("input/record minute", [&]() {
+                record();
+                sink = sink + data.size();
+            }));
+        }
+        if (selected("input/play minute")) {
+            record();
+            InputPlayback playback;
+            std::string error;
+            results.push_back(measure("input/play minute", [&]() {
+                playback.parse(data, "benchmark", error);
+                for (size_t tick = 0; tick < frames.size(); tick++) {
+                    sink = sink + playback.frameAt(tick).held;
+                }
+            }));
+        }
+    }
+
+    // sorting only; submitting needs a GL context
+    if (selected("sprites/prepare sprites=2000")) {
+        SpriteBatch batch;
+        Texture2D textures[4] = {};
+        for (int i = 0; i < 4; i++) {
+            textures[i] = {(unsigned int) (i + 1), 256, 256, 1, 7};
+        }
+        results.push_back(measure("sprites/prepare sprites=2000", [&]() {
+            batch.begin();
+            for (int i = 0; i < 2000; i++) {
+                float x = (float) (i % 50) * 25;
+                float y = (float) (i / 50) * 10;
+                batch.add(textures[(i * 7) % 4], {0, 0, 16, 16}, {x, y, 24, 8}, WHITE, i % 3);
+            }
+            batch.prepare();
+        }));
+    }
+    return results;
+}
+
+static std::map<std::string, double> loadBaseline(const std::string& path) {
+    std::map<std::string, double> baseline;
+    std::ifstream file(path);
+    std::string line;
+    while (std::getline(file, line)) {
+        // "name<TAB>nanoseconds"; names have spaces in them
+        size_t tab = line.rfind('\t');
+        if (tab != std::string::npos) {
+            baseline[line.substr(0, tab)] = atof(line.c_str() + tab + 1);
+        }
+    }
+    return baseline;
+}
+
+int runBenchmarkCli(const LaunchOptions& options) {
+    std::map<std::string, double> baseline;
+    if (!options.baselinePath.empty()) {
+        baseline = loadBaseline(options.baselinePath);
+        if (baseline.empty()) {
+            TraceLog(LOG_WARNING, "BENCH: No results in baseline %s", options.baselinePath.c_str());
+        }
+    }
+
+    std::vector<BenchmarkResult> results = runBenchmarks(options.benchFilter);
+    int regressions = 0;
+    for (const BenchmarkResult& result : results) {
+        printf("%-32s %12.1f ns", result.name.c_str(), result.nanoseconds);
+        auto previous = baseline.find(result.name);
+        if (previous != baseline.end() && previous->second > 0) {
+            double change = result.nanoseconds / previous->second - 1;
+            bool regressed = change > BENCHMARK_TOLERANCE;
+            regressions += regressed ? 1 : 0;
+            printf("  %+6.1f%%%s", change * 100, regressed ? "  REGRESSED" : "");
+        }
+        printf("\n");
+    }
+
+    if (!options.saveBaselinePath.empty()) {
+        std::ofstream file(options.saveBaselinePath);
+        for (const BenchmarkResult& result : results) {
+            file << result.name << '\t' << result.nanoseconds << '\n';
+        }
+        if (!file) {
+            TraceLog(LOG_WARNING, "BENCH: Could not write %s", options.saveBaselinePath.c_str());
+            return 2;
+        }
+    }
+    if (regressions > 0) {
+        TraceLog(LOG_WARNING, "BENCH: %d case(s) more than %d%% slower than %s", regressions, (int) (BENCHMARK_TOLERANCE * 100), options.baselinePath.c_str());
+        return 1;
+    }
+    return 0;
+}
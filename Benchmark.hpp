@@ -0,0 +1,23 @@
+#pragma once
+#include <string>
+#include <vector>
+#include "Args.hpp"
+
+
+struct BenchmarkResult {
+    std::string name;
+    double nanoseconds; // per iteration, median of the samples
+    long long iterations;
+};
+
+// Times the hot paths that don't need a GPU: simulation ticks over growing
+// brick fields, level parsing, replay/input serialization and sprite batch
+// sorting. Each case is calibrated to a few milliseconds per sample and
+// reports the median, so one slow sample doesn't move the number.
+std::vector<BenchmarkResult> runBenchmarks(const std::string& filter);
+
+// `breakout --benchmark [--bench-filter TEXT] [--baseline FILE] [--save-baseline FILE]`:
+// prints a table, and with a baseline fails (exit code 1) when any case got
+// more than BENCHMARK_TOLERANCE slower, which is what the release check runs.
+const double BENCHMARK_TOLERANCE = 0.15;
+int runBenchmarkCli(const LaunchOptions& options);
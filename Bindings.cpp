@@ -0,0 +1,436 @@
+#include "raylib.h"
+#include "Bindings.hpp"
+#include <cctype>
+#include <cmath>
+#include <cstdlib>
+
+
+struct NamedCode {
+    int code;
+    const char* name;
+};
+
+// Letters and digits are named after their character; everything else that
+// someone might reasonably bind is listed here.
+static const NamedCode KEY_NAMES[] = {
+    {KEY_SPACE, "Space"}, {KEY_ENTER, "Enter"}, {KEY_TAB, "Tab"}, {KEY_BACKSPACE, "Backspace"},
+    {KEY_ESCAPE, "Escape"}, {KEY_INSERT, "Insert"}, {KEY_DELETE, "Delete"},
+    {KEY_LEFT, "Left"}, {KEY_RIGHT, "Right"}, {KEY_UP, "Up"}, {KEY_DOWN, "Down"},
+    {KEY_PAGE_UP, "Page up"}, {KEY_PAGE_DOWN, "Page down"}, {KEY_HOME, "Home"}, {KEY_END, "End"},
+    {KEY_CAPS_LOCK, "Caps lock"}, {KEY_PAUSE, "Pause"},
+    {KEY_LEFT_SHIFT, "Left shift"}, {KEY_RIGHT_SHIFT, "Right shift"},
+    {KEY_LEFT_CONTROL, "Left control"}, {KEY_RIGHT_CONTROL, "Right control"},
+    {KEY_LEFT_ALT, "Left alt"}, {KEY_RIGHT_ALT, "Right alt"},
+    {KEY_COMMA, "Comma"}, {KEY_PERIOD, "Period"}, {KEY_SLASH, "Slash"}, {KEY_SEMICOLON, "Semicolon"},
+    {KEY_APOSTROPHE, "Apostrophe"}, {KEY_MINUS, "Minus"}, {KEY_EQUAL, "Equal"}, {KEY_GRAVE, "Grave"},
+    {KEY_LEFT_BRACKET, "Left bracket"}, {KEY_RIGHT_BRACKET, "Right bracket"}, {KEY_BACKSLASH, "Backslash"},
+    {KEY_F1, "F1"}, {KEY_F2, "F2"}, {KEY_F3, "F3"}, {KEY_F4, "F4"}, {KEY_F5, "F5"}, {KEY_F6, "F6"},
+    {KEY_F7, "F7"}, {KEY_F8, "F8"}, {KEY_F9, "F9"}, {KEY_F10, "F10"}, {KEY_F11, "F11"}, {KEY_F12, "F12"},
+    {KEY_KP_0, "Keypad 0"}, {KEY_KP_1, "Keypad 1"}, {KEY_KP_2, "Keypad 2"}, {KEY_KP_3, "Keypad 3"},
+    {KEY_KP_4, "Keypad 4"}, {KEY_KP_5, "Keypad 5"}, {KEY_KP_6, "Keypad 6"}, {KEY_KP_7, "Keypad 7"},
+    {KEY_KP_8, "Keypad 8"}, {KEY_KP_9, "Keypad 9"}, {KEY_KP_ENTER, "Keypad enter"},
+};
+
+static const NamedCode MOUSE_NAMES[] = {
+    {MOUSE_BUTTON_LEFT, "Left"}, {MOUSE_BUTTON_RIGHT, "Right"}, {MOUSE_BUTTON_MIDDLE, "Middle"},
+    {MOUSE_BUTTON_SIDE, "Side"}, {MOUSE_BUTTON_EXTRA, "Extra"},
+    {MOUSE_BUTTON_FORWARD, "Forward"}, {MOUSE_BUTTON_BACK, "Back"},
+};
+
+static const NamedCode PAD_NAMES[] = {
+    {GAMEPAD_BUTTON_RIGHT_FACE_DOWN, "A"}, {GAMEPAD_BUTTON_RIGHT_FACE_RIGHT, "B"},
+    {GAMEPAD_BUTTON_RIGHT_FACE_LEFT, "X"}, {GAMEPAD_BUTTON_RIGHT_FACE_UP, "Y"},
+    {GAMEPAD_BUTTON_LEFT_FACE_UP, "Dpad up"}, {GAMEPAD_BUTTON_LEFT_FACE_DOWN, "Dpad down"},
+    {GAMEPAD_BUTTON_LEFT_FACE_LEFT, "Dpad left"}, {GAMEPAD_BUTTON_LEFT_FACE_RIGHT, "Dpad right"},
+    {GAMEPAD_BUTTON_LEFT_TRIGGER_1, "LB"}, {GAMEPAD_BUTTON_LEFT_TRIGGER_2, "LT"},
+    {GAMEPAD_BUTTON_RIGHT_TRIGGER_1, "RB"}, {GAMEPAD_BUTTON_RIGHT_TRIGGER_2, "RT"},
+    {GAMEPAD_BUTTON_MIDDLE_LEFT, "Back"}, {GAMEPAD_BUTTON_MIDDLE, "Guide"}, {GAMEPAD_BUTTON_MIDDLE_RIGHT, "Start"},
+    {GAMEPAD_BUTTON_LEFT_THUMB, "L3"}, {GAMEPAD_BUTTON_RIGHT_THUMB, "R3"},
+};
+
+static const char* ACTION_NAMES[] = {"Move left", "Move right", "Launch ball", "Activate power", "Pause", "Debug overlay", "Restart level"};
+static const char* ACTION_KEYS[] = {"bind_move_left", "bind_move_right", "bind_launch", "bind_power", "bind_pause", "bind_debug", "bind_restart"};
+
+bool InputBinding::operator==(const InputBinding& other) const {
+    return kind == other.kind && (kind == InputKind::None || code == other.code);
+}
+
+bool InputBinding::operator!=(const InputBinding& other) const {
+    return !(*this == other);
+}
+
+const char* actionName(Action action) {
+    return ACTION_NAMES[(int) action];
+}
+
+const char* actionKey(Action action) {
+    return ACTION_KEYS[(int) action];
+}
+
+template <size_t N>
+static const char* lookupName(const NamedCode (&names)[N], int code) {
+    for (const NamedCode& entry : names) {
+        if (entry.code == code) {
+            return entry.name;
+        }
+    }
+    return nullptr;
+}
+
+static std::string keyName(int code) {
+    if ((code >= KEY_A && code <= KEY_Z) || (code >= KEY_ZERO && code <= KEY_NINE)) {
+        return std::string(1, (char) code);
+    }
+    const char* name = lookupName(KEY_NAMES, code);
+    return name ? name : "";
+}
+
+std::string describeInput(InputBinding input) {
+    switch (input.kind) {
+    case InputKind::None:
+        return "-";
+    case InputKind::Key: {
+        std::string name = keyName(input.code);
+        return name.empty() ? TextFormat("Key %d", input.code) : name;
+    }
+    case InputKind::MouseButton: {
+        const char* name = lookupName(MOUSE_NAMES, input.code);
+        return name ? std::string("Mouse ") + (char) tolower(name[0]) + (name + 1) : TextFormat("Mouse %d", input.code);
+    }
+    case InputKind::GamepadButton: {
+        const char* name = lookupName(PAD_NAMES, input.code);
+        return name ? std::string("Pad ") + name : TextFormat("Pad %d", input.code);
+    }
+    }
+    return "";
+}
+
+// "Left shift" -> "LEFT_SHIFT"
+static std::string persistedName(const std::string& name) {
+    std::string result = name;
+    for (char& c : result) {
+        c = c == ' ' ? '_' : (char) toupper(c);
+    }
+    return result;
+}
+
+std::string formatInput(InputBinding input) {
+    switch (input.kind) {
+    case InputKind::None:
+        return "none";
+    case InputKind::Key: {
+        std::string name = keyName(input.code);
+        return "key:" + (name.empty() ? std::to_string(input.code) : persistedName(name));
+    }
+    case InputKind::MouseButton: {
+        const char* name = lookupName(MOUSE_NAMES, input.code);
+        return "mouse:" + (name ? persistedName(name) : std::to_string(input.code));
+    }
+    case InputKind::GamepadButton: {
+        const char* name = lookupName(PAD_NAMES, input.code);
+        return "pad:" + (name ? persistedName(name) : std::to_string(input.code));
+    }
+    }
+    return "none";
+}
+
+template <size_t N>
+static bool parseCode(const NamedCode (&names)[N], const std::string& text, int& code) {
+    for (const NamedCode& entry : names) {
+        if (persistedName(entry.name) == text) {
+            code = entry.code;
+            return true;
+        }
+    }
+    char* end = nullptr;
+    long value = strtol(text.c_str(), &end, 10);
+    if (text.empty() || *end != '\0') {
+        return false;
+    }
+    code = (int) value;
+    return true;
+}
+
+bool parseInput(const std::string& text, InputBinding& input) {
+    if (text == "none") {
+        input = InputBinding();
+        return true;
+    }
+    size_t separator = text.find(':');
+    if (separator == std::string::npos) {
+        return false;
+    }
+    std::string kind = text.substr(0, separator);
+    std::string name = persistedName(text.substr(separator + 1));
+    if (kind == "key") {
+        if (name.size() == 1 && isalnum((unsigned char) name[0])) {
+            input = {InputKind::Key, name[0]};
+            return true;
+        }
+        input.kind = InputKind::Key;
+        return parseCode(KEY_NAMES, name, input.code);
+    }
+    if (kind == "mouse") {
+        input.kind = InputKind::MouseButton;
+        return parseCode(MOUSE_NAMES, name, input.code);
+    }
+    if (kind == "pad") {
+        input.kind = InputKind::GamepadButton;
+        return parseCode(PAD_NAMES, name, input.code);
+    }
+    return false;
+}
+
+std::vector<std::string> layoutNames() {
+    return {"standard", "left hand", "right hand", "custom"};
+}
+
+static InputBinding key(int code) {
+    return {InputKind::Key, code};
+}
+
+static InputBinding pad(int code) {
+    return {InputKind::GamepadButton, code};
+}
+
+bool applyLayout(InputBindings& bindings, const std::string& name) {
+    struct Row {
+        Action action;
+        InputBinding primary, secondary, gamepad;
+    };
+    std::vector<Row> rows;
+    if (name == "left hand") {
+        rows = {
+            {Action::MoveLeft, key(KEY_A), key(KEY_Z), pad(GAMEPAD_BUTTON_LEFT_FACE_LEFT)},
+            {Action::MoveRight, key(KEY_D), key(KEY_C), pad(GAMEPAD_BUTTON_LEFT_FACE_RIGHT)},
+            {Action::Launch, key(KEY_SPACE), key(KEY_W), pad(GAMEPAD_BUTTON_LEFT_TRIGGER_1)},
+            {Action::ActivatePower, key(KEY_S), key(KEY_LEFT_SHIFT), pad(GAMEPAD_BUTTON_LEFT_TRIGGER_2)},
+            {Action::Pause, key(KEY_Q), key(KEY_TAB), pad(GAMEPAD_BUTTON_MIDDLE_LEFT)},
+            {Action::Debug, key(KEY_F3), {}, {}},
+            {Action::Restart, key(KEY_R), {}, {}},
+        };
+    } else if (name == "right hand") {
+        rows = {
+            {Action::MoveLeft, key(KEY_LEFT), key(KEY_KP_4), pad(GAMEPAD_BUTTON_RIGHT_FACE_LEFT)},
+            {Action::MoveRight, key(KEY_RIGHT), key(KEY_KP_6), pad(GAMEPAD_BUTTON_RIGHT_FACE_RIGHT)},
+            {Action::Launch, key(KEY_UP), key(KEY_KP_0), pad(GAMEPAD_BUTTON_RIGHT_FACE_DOWN)},
+            {Action::ActivatePower, key(KEY_DOWN), key(KEY_RIGHT_SHIFT), pad(GAMEPAD_BUTTON_RIGHT_FACE_UP)},
+            {Action::Pause, key(KEY_RIGHT_CONTROL), key(KEY_KP_ENTER), pad(GAMEPAD_BUTTON_MIDDLE_RIGHT)},
+            {Action::Debug, key(KEY_F3), {}, {}},
+            {Action::Restart, key(KEY_END), {}, {}},
+        };
+    } else if (name == "standard") {
+        bindings.resetDefaults();
+        return true;
+    } else {
+        return false;
+    }
+    for (const Row& row : rows) {
+        bindings.clear(row.action, 0);
+        bindings.clear(row.action, 1);
+        bindings.clear(row.action, InputBindings::GAMEPAD_SLOT);
+    }
+    for (const Row& row : rows) {
+        bindings.bind(row.action, 0, row.primary);
+        bindings.bind(row.action, 1, row.secondary);
+        bindings.bind(row.action, InputBindings::GAMEPAD_SLOT, row.gamepad);
+    }
+    return true;
+}
+
+InputBindings::InputBindings() {
+    resetDefaults();
+}
+
+void InputBindings::resetDefaults() {
+    for (auto& slots : bindings) {
+        for (InputBinding& input : slots) {
+            input = InputBinding();
+        }
+    }
+    bindings[(int) Action::MoveLeft][0] = {InputKind::Key, KEY_LEFT};
+    bindings[(int) Action::MoveLeft][1] = {InputKind::Key, KEY_A};
+    bindings[(int) Action::MoveRight][0] = {InputKind::Key, KEY_RIGHT};
+    bindings[(int) Action::MoveRight][1] = {InputKind::Key, KEY_D};
+    bindings[(int) Action::Launch][0] = {InputKind::Key, KEY_SPACE};
+    bindings[(int) Action::Launch][1] = {InputKind::MouseButton, MOUSE_BUTTON_LEFT};
+    bindings[(int) Action::ActivatePower][0] = {InputKind::Key, KEY_E};
+    bindings[(int) Action::ActivatePower][1] = {InputKind::MouseButton, MOUSE_BUTTON_RIGHT};
+    bindings[(int) Action::Pause][0] = {InputKind::Key, KEY_P};
+    bindings[(int) Action::Pause][1] = {InputKind::Key, KEY_PAUSE};
+    bindings[(int) Action::Debug][0] = {InputKind::Key, KEY_F3};
+    bindings[(int) Action::Restart][0] = {InputKind::Key, KEY_R};
+
+    bindings[(int) Action::MoveLeft][GAMEPAD_SLOT] = {InputKind::GamepadButton, GAMEPAD_BUTTON_LEFT_FACE_LEFT};
+    bindings[(int) Action::MoveRight][GAMEPAD_SLOT] = {InputKind::GamepadButton, GAMEPAD_BUTTON_LEFT_FACE_RIGHT};
+    bindings[(int) Action::Launch][GAMEPAD_SLOT] = {InputKind::GamepadButton, GAMEPAD_BUTTON_RIGHT_FACE_DOWN};
+    bindings[(int) Action::ActivatePower][GAMEPAD_SLOT] = {InputKind::GamepadButton, GAMEPAD_BUTTON_RIGHT_FACE_LEFT};
+    bindings[(int) Action::Pause][GAMEPAD_SLOT] = {InputKind::GamepadButton, GAMEPAD_BUTTON_MIDDLE_RIGHT};
+}
+
+InputBinding InputBindings::get(Action action, int slot) const {
+    return bindings[(int) action][slot];
+}
+
+void InputBindings::clear(Action action, int slot) {
+    bindings[(int) action][slot] = InputBinding();
+}
+
+int InputBindings::bind(Action action, int slot, InputBinding input) {
+    InputBinding previous = bindings[(int) action][slot];
+    bindings[(int) action][slot] = input;
+    if (input.kind == InputKind::None) {
+        return -1;
+    }
+    for (int other = 0; other < ACTION_COUNT; other++) {
+        for (int otherSlot = 0; otherSlot < SLOTS; otherSlot++) {
+            if (other == (int) action && otherSlot == slot) {
+                continue;
+            }
+            if (bindings[other][otherSlot] == input) {
+                bindings[other][otherSlot] = other == (int) action ? InputBinding() : previous;
+                return other == (int) action ? -1 : other;
+            }
+        }
+    }
+    return -1;
+}
+
+std::vector<Action> InputBindings::conflicts(Action action) const {
+    std::vector<Action> result;
+    for (int other = 0; other < ACTION_COUNT; other++) {
+        if (other == (int) action) {
+            continue;
+        }
+        bool shared = false;
+        for (const InputBinding& mine : bindings[(int) action]) {
+            for (const InputBinding& theirs : bindings[other]) {
+                shared = shared || (mine.kind != InputKind::None && mine == theirs);
+            }
+        }
+        if (shared) {
+            result.push_back((Action) other);
+        }
+    }
+    return result;
+}
+
+static bool inputDown(InputBinding input, int gamepad, bool keyboard) {
+    switch (input.kind) {
+    case InputKind::None:
+        return false;
+    case InputKind::Key:
+        return keyboard && IsKeyDown(input.code);
+    case InputKind::MouseButton:
+        return keyboard && IsMouseButtonDown(input.code);
+    case InputKind::GamepadButton:
+        return gamepad >= 0 && IsGamepadButtonDown(gamepad, input.code);
+    }
+    return false;
+}
+
+static bool inputPressed(InputBinding input, int gamepad, bool keyboard) {
+    switch (input.kind) {
+    case InputKind::None:
+        return false;
+    case InputKind::Key:
+        return keyboard && IsKeyPressed(input.code);
+    case InputKind::MouseButton:
+        return keyboard && IsMouseButtonPressed(input.code);
+    case InputKind::GamepadButton:
+        return gamepad >= 0 && IsGamepadButtonPressed(gamepad, input.code);
+    }
+    return false;
+}
+
+int InputBindings::activeGamepad() const {
+    if (gamepad == NO_GAMEPAD) {
+        return -1;
+    }
+    if (gamepad >= 0) {
+        return IsGamepadAvailable(gamepad) ? gamepad : -1;
+    }
+    for (int i = 0; i < 4; i++) {
+        if (IsGamepadAvailable(i)) {
+            return i;
+        }
+    }
+    return -1;
+}
+
+bool InputBindings::isDown(Action action) const {
+    int pad = activeGamepad();
+    for (const InputBinding& input : bindings[(int) action]) {
+        if (inputDown(input, pad, keyboard)) {
+            return true;
+        }
+    }
+    return false;
+}
+
+bool InputBindings::isPressed(Action action) const {
+    int pad = activeGamepad();
+    for (const InputBinding& input : bindings[(int) action]) {
+        if (inputPressed(input, pad, keyboard)) {
+            return true;
+        }
+    }
+    return false;
+}
+
+float InputBindings::analogMove(float deadzone) const {
+    int pad = activeGamepad();
+    if (pad < 0) {
+        return 0;
+    }
+    float x = GetGamepadAxisMovement(pad, GAMEPAD_AXIS_LEFT_X);
+    if (fabsf(x) <= deadzone) {
+        return 0;
+    }
+    // rescale so the stick starts at 0 right at the deadzone edge instead of jumping
+    float magnitude = fminf((fabsf(x) - deadzone) / (1 - deadzone), 1);
+    return x < 0 ? -magnitude : magnitude;
+}
+
+// raylib reports triggers from -1 (released) to 1 (fully pulled)
+float InputBindings::fineControl() const {
+    int pad = activeGamepad();
+    if (pad < 0 || GetGamepadAxisCount(pad) <= GAMEPAD_AXIS_RIGHT_TRIGGER) {
+        return 0;
+    }
+    float left = GetGamepadAxisMovement(pad, GAMEPAD_AXIS_LEFT_TRIGGER);
+    float right = GetGamepadAxisMovement(pad, GAMEPAD_AXIS_RIGHT_TRIGGER);
+    return fminf(fmaxf((fmaxf(left, right) + 1) / 2, 0), 1);
+}
+
+std::string InputBindings::format(Action action) const {
+    std::string text;
+    for (int slot = 0; slot < SLOTS; slot++) {
+        text += (slot > 0 ? ", " : "") + formatInput(bindings[(int) action][slot]);
+    }
+    return text;
+}
+
+// Unreadable entries keep whatever was there before (the default), so a typo
+// in the profile costs one binding rather than the whole action.
+void InputBindings::parse(Action action, const std::string& text) {
+    size_t start = 0;
+    for (int slot = 0; slot < SLOTS && start <= text.size(); slot++) {
+        size_t end = text.find(',', start);
+        std::string entry = text.substr(start, end == std::string::npos ? std::string::npos : end - start);
+        size_t first = entry.find_first_not_of(' ');
+        size_t last = entry.find_last_not_of(' ');
+        entry = first == std::string::npos ? "" : entry.substr(first, last - first + 1);
+
+        InputBinding input;
+        if (parseInput(entry, input)) {
+            bindings[(int) action][slot] = input;
+        } else if (!entry.empty()) {
+            TraceLog(LOG_WARNING, "BINDINGS: Ignoring unknown input '%s' for %s", entry.c_str(), actionKey(action));
+        }
+        if (end == std::string::npos) {
+            break;
+        }
+        start = end + 1;
+    }
+}
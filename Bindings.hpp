@@ -0,0 +1,95 @@
+#pragma once
+#include "raylib.h"
+#include <string>
+#include <vector>
+
+
+// Everything gameplay reacts to goes through an action, never a raw key, so
+// rebinding in the controls menu reaches every mode at once.
+enum class Action {
+    MoveLeft,
+    MoveRight,
+    Launch,
+    ActivatePower,
+    Pause,
+    Debug,
+    Restart, // the level over from the start, without the pause menu
+};
+
+const int ACTION_COUNT = 7;
+
+enum class InputKind {
+    None,
+    Key,
+    MouseButton,
+    GamepadButton,
+};
+
+struct InputBinding {
+    InputKind kind = InputKind::None;
+    int code = 0;
+
+    bool operator==(const InputBinding& other) const;
+    bool operator!=(const InputBinding& other) const;
+};
+
+const char* actionName(Action action);
+// The profile key the action's bindings are saved under, e.g. "bind_move_left".
+const char* actionKey(Action action);
+
+// "Left", "Space", "Mouse right", "Pad A"...
+std::string describeInput(InputBinding input);
+// Persisted form, "key:LEFT", "mouse:RIGHT" or "pad:A"; unnamed keys fall back
+// to the raylib key code ("key:161").
+std::string formatInput(InputBinding input);
+bool parseInput(const std::string& text, InputBinding& input);
+
+// Each action has a primary and a secondary keyboard/mouse input plus one
+// controller button. An input can only belong to one action at a time;
+// conflicts() lists the ones that break that rule (hand-edited profiles),
+// bind() never creates them.
+//
+// Controllers go through raylib's gamepad layer (GLFW's SDL-style mappings on
+// every desktop platform), using Xbox button names for what is really a
+// positional layout: "Pad A" is the bottom face button on any pad.
+class InputBindings {
+  public:
+    static const int SLOTS = 3;
+    static const int GAMEPAD_SLOT = 2;
+    static const int ANY_GAMEPAD = -1;
+    static const int NO_GAMEPAD = -2;
+    // which controller to read; local multiplayer narrows this per player
+    int gamepad = ANY_GAMEPAD;
+    bool keyboard = true; // keyboard and mouse bindings count
+
+    InputBindings();
+    void resetDefaults();
+
+    InputBinding get(Action action, int slot) const;
+    void clear(Action action, int slot);
+    // If another action already uses input, it gets this slot's previous
+    // binding in exchange; returns that action, or -1 if nothing was swapped.
+    int bind(Action action, int slot, InputBinding input);
+    std::vector<Action> conflicts(Action action) const;
+
+    bool isDown(Action action) const;
+    bool isPressed(Action action) const;
+    // Left stick x in -1..1, rescaled past the deadzone; 0 without a controller.
+    float analogMove(float deadzone) const;
+    // How far the further-pulled trigger is held, 0..1.
+    float fineControl() const;
+    int activeGamepad() const;
+
+    // "key:LEFT, key:A, pad:DPAD_LEFT", the value stored in the profile
+    std::string format(Action action) const;
+    void parse(Action action, const std::string& text);
+
+  private:
+    InputBinding bindings[ACTION_COUNT][SLOTS];
+};
+
+// Named binding presets offered in the profile menu: "standard", "left hand"
+// (everything around WASD and the left of the pad), "right hand" (arrows,
+// keypad and the face buttons) and "custom", which leaves the bindings alone.
+std::vector<std::string> layoutNames();
+bool applyLayout(InputBindings& bindings, const std::string& name);
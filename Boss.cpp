@@ -0,0 +1,125 @@
+#include "raylib.h"
+#include "Boss.hpp"
+#include <algorithm>
+#include <cmath>
+#include <sstream>
+
+
+Rectangle Debris::getRectangle() const {
+    return {position.x - SIZE / 2, position.y - SIZE / 2, SIZE, SIZE};
+}
+
+Rectangle Boss::segmentRect(const BossSegment& segment) const {
+    return {position.x + segment.offset.x, position.y + segment.offset.y, segment.offset.width, segment.offset.height};
+}
+
+Rectangle Boss::bounds() const {
+    if (segments.empty()) {
+        return {position.x, position.y, 0, 0};
+    }
+    Rectangle first = segmentRect(segments[0]);
+    float left = first.x, top = first.y, right = first.x + first.width, bottom = first.y + first.height;
+    for (const BossSegment& segment : segments) {
+        Rectangle rect = segmentRect(segment);
+        left = fminf(left, rect.x);
+        top = fminf(top, rect.y);
+        right = fmaxf(right, rect.x + rect.width);
+        bottom = fmaxf(bottom, rect.y + rect.height);
+    }
+    return {left, top, right - left, bottom - top};
+}
+
+int Boss::weakPoint() const {
+    int found = -1;
+    for (size_t i = 0; i < segments.size(); i++) {
+        const BossSegment& segment = segments[i];
+        if (segment.order > 0 && segment.hitPoints > 0 && (found < 0 || segment.order < segments[found].order)) {
+            found = (int) i;
+        }
+    }
+    return found;
+}
+
+int Boss::phase() const {
+    if (!alive()) {
+        return 0;
+    }
+    int broken = 0;
+    for (const BossSegment& segment : segments) {
+        broken += segment.order > 0 && segment.hitPoints <= 0 ? 1 : 0;
+    }
+    return broken + 1;
+}
+
+void Boss::move(float seconds) {
+    if (!path.moves()) {
+        return;
+    }
+    pathTime = fmodf(pathTime + seconds, path.period);
+    Vector2 offset = path.offsetAt(pathTime);
+    position = {origin.x + offset.x, origin.y + offset.y};
+}
+
+BossAttack parseBossAttack(const std::string& name, bool& known) {
+    known = true;
+    if (name == "rain") return BossAttack::Rain;
+    if (name == "aimed") return BossAttack::Aimed;
+    if (name == "spread") return BossAttack::Spread;
+    known = false;
+    return BossAttack::Rain;
+}
+
+bool parseBoss(const XmlNode& node, Boss& result, std::string& problem) {
+    result = Boss();
+    Boss boss;
+    boss.name = node.attribute("name", "Boss");
+    boss.music = node.attribute("music");
+    boss.origin = {node.attributeFloat("x", 640), node.attributeFloat("y", 140)};
+    boss.position = boss.origin;
+    boss.attackPeriod = node.attributeFloat("attack_period", boss.attackPeriod);
+    if (boss.attackPeriod <= 0) {
+        problem = "attack_period must be more than 0";
+        return false;
+    }
+    boss.attackTimer = boss.attackPeriod;
+    if (!parseBrickPath(node, boss.path, problem)) {
+        return false;
+    }
+
+    std::stringstream list(node.attribute("attacks", "rain"));
+    std::string entry;
+    while (std::getline(list, entry, ',')) {
+        bool known;
+        BossAttack attack = parseBossAttack(entry, known);
+        if (!known) {
+            problem = "unknown attack '" + entry + "', expected rain, aimed or spread";
+            return false;
+        }
+        boss.attacks.push_back(attack);
+    }
+
+    std::vector<int> orders;
+    for (const XmlNode* child : node.childrenNamed("segment")) {
+        BossSegment segment;
+        segment.offset = {child->attributeFloat("x", 0), child->attributeFloat("y", 0), child->attributeFloat("width", 48), child->attributeFloat("height", 24)};
+        segment.order = std::max(0, child->attributeInt("weak", 0));
+        if (segment.order > 0) {
+            segment.hitPoints = std::max(1, child->attributeInt("hp", 1));
+            segment.maxHitPoints = segment.hitPoints;
+            if (std::find(orders.begin(), orders.end(), segment.order) != orders.end()) {
+                problem = "two weak points are both weak=\"" + std::to_string(segment.order) + "\"";
+                return false;
+            }
+            orders.push_back(segment.order);
+            boss.maxHealth += segment.hitPoints;
+        }
+        boss.segments.push_back(segment);
+    }
+    if (boss.maxHealth == 0) {
+        problem = "a boss needs at least one <segment weak=\"1\">";
+        return false;
+    }
+    boss.health = boss.maxHealth;
+    result = boss;
+    return true;
+}
@@ -0,0 +1,80 @@
+#pragma once
+#include "raylib.h"
+#include <string>
+#include <vector>
+#include "BrickPath.hpp"
+#include "Xml.hpp"
+
+
+enum class BossAttack {
+    Rain,   // a handful of debris dropped at random across the field
+    Aimed,  // one piece thrown at where the paddle is
+    Spread, // a fan of debris from the boss's middle
+};
+
+// A chunk the boss throws. It hurts the paddle it lands on (see
+// Simulation::step) and falls off the bottom otherwise.
+struct Debris {
+    static constexpr float SIZE = 14;
+    static constexpr float SPEED = 3; // pixels per tick
+
+    Vector2 position; // center
+    Vector2 velocity;
+
+    Rectangle getRectangle() const;
+};
+
+// A piece of the boss's body. Armor bounces the ball like metal; weak points
+// take hits one at a time in their order, and a broken one is armor from
+// then on.
+struct BossSegment {
+    Rectangle offset; // from the boss's position
+    int order = 0;    // 0 for armor, weak points 1, 2, 3...
+    int hitPoints = 0;
+    int maxHitPoints = 0;
+};
+
+// A level's boss: one body of segments that moves as one along a BrickPath
+// and every attackPeriod seconds throws the next of its attacks. It is
+// beaten when its last weak point breaks, and counts as one of the level's
+// bricksLeft until then. Plain values, so it snapshots with GameState.
+struct Boss {
+    std::string name;
+    std::string music; // track id played instead of the level's, empty for none
+    Vector2 origin = {0, 0};
+    Vector2 position = {0, 0};
+    BrickPath path;
+    float pathTime = 0;
+    std::vector<BossSegment> segments;
+    std::vector<BossAttack> attacks;
+    float attackPeriod = 3; // seconds
+    float attackTimer = 3;  // until the next
+    int nextAttack = 0;
+    int maxHealth = 0;      // every weak point's hit points; 0 when the level has no boss
+    int health = 0;
+
+    bool present() const { return maxHealth > 0; }
+    bool alive() const { return health > 0; }
+    Rectangle segmentRect(const BossSegment& segment) const;
+    // around every segment, where it is now
+    Rectangle bounds() const;
+    // The index of the weak point that takes hits now, -1 once beaten.
+    int weakPoint() const;
+    // 1 while on the first weak point, 2 on the second..., 0 with no boss
+    // or once beaten; for musicIntensity().
+    int phase() const;
+    // seconds of its path, already scaled by the state's timeScale
+    void move(float seconds);
+};
+
+//   <boss name="Warden" x="640" y="140" attacks="rain,aimed,spread" attack_period="3"
+//         music="boss" path="sweep" range="300" period="6">
+//     <segment x="-80" y="0" width="60" height="40"/>
+//     <segment x="-20" y="10" width="40" height="20" weak="1" hp="3"/>
+//     <segment x="20" y="0" width="60" height="40" weak="2" hp="4"/>
+//   </boss>
+// Segment x and y are from the boss's x and y to the segment's top left. The
+// path attributes are the same as a brick's (see BrickPath); hp defaults to
+// 1. False with the problem when the boss can't be fought as written.
+bool parseBoss(const XmlNode& node, Boss& boss, std::string& problem);
+BossAttack parseBossAttack(const std::string& name, bool& known);
@@ -0,0 +1,143 @@
+#include "raylib.h"
+#include "Bot.hpp"
+#include <algorithm>
+#include <cmath>
+
+
+struct BotTuning {
+    const char* name;
+    int reactionTicks; // how often it looks at the ball
+    float maxError;    // how far off the landing point it may aim
+    int serveTicks;    // how long it holds the ball before serving
+};
+
+// how far ahead a landing is followed through gravity wells, ten seconds
+static const int MAX_PREDICT_TICKS = 600;
+
+static const BotTuning TUNING[] = {
+    {"easy", 8, 30, 60},
+    {"normal", 5, 20, 30},
+    {"hard", 0, 0, 10},
+};
+
+bool parseBotSkill(const std::string& name, BotSkill& skill) {
+    for (int i = 0; i < 3; i++) {
+        if (name == TUNING[i].name) {
+            skill = (BotSkill) i;
+            return true;
+        }
+    }
+    return false;
+}
+
+const char* botSkillName(BotSkill skill) {
+    return TUNING[(int) skill].name;
+}
+
+Bot::Bot(BotSkill botSkill, uint64_t seed) : skill(botSkill), random(seed) {
+}
+
+// With several balls in play, the one to save first: the falling one nearest
+// the paddle, or the lowest when none are falling.
+static Ball& watchedBall(GameState& state) {
+    Ball* watched = &state.balls[0];
+    for (Ball& ball : state.balls) {
+        bool falling = ball.velocity.y > 0;
+        bool watchedFalling = watched->velocity.y > 0;
+        if ((falling && !watchedFalling) || (falling == watchedFalling && ball.position.y > watched->position.y)) {
+            watched = &ball;
+        }
+    }
+    return *watched;
+}
+
+InputFrame Bot::think(GameState& state, const SimulationRules& rules) {
+    const BotTuning& tuning = TUNING[(int) skill];
+    InputFrame input;
+    float paddleX = state.player.getCenter().x;
+
+    if (state.ballHeld) {
+        ballFalling = false;
+        target = paddleX;
+        if (++serveTicks >= tuning.serveTicks) {
+            input.setPressed(Action::Launch, true);
+            serveTicks = 0;
+        }
+        return input;
+    }
+    serveTicks = 0;
+    if (state.effects.has(PowerUpType::Laser) && state.laserCooldown == 0) {
+        input.setPressed(Action::ActivatePower, true);
+    }
+
+    // a new miss is rolled every time the ball starts coming down
+    Ball& ball = watchedBall(state);
+    bool falling = ball.velocity.y > 0;
+    if (falling && !ballFalling) {
+        aimError = (random.unit() * 2 - 1) * tuning.maxError;
+    }
+    ballFalling = falling;
+
+    if (reactionTicks > 0) {
+        reactionTicks--;
+    } else {
+        reactionTicks = tuning.reactionTicks;
+        float landing = skill == BotSkill::Easy ? ball.getCenter().x : predictLanding(ball, state, rules);
+        target = landing + aimError;
+    }
+
+    // under reversed (or mirrored) controls it knows to push the other way
+    bool reversed = controlsReversed(state, rules);
+    if (skill == BotSkill::Hard) {
+        input.setSteer(target);
+        return reversed ? input.mirrored(rules.width) : input;
+    }
+    // the paddle coasts to a stop (v^2 / 2a, braking at 0.2 per tick), so
+    // let go before getting there
+    float gap = target - paddleX;
+    float speed = state.player.velocity.x;
+    float coast = speed * speed / 0.4f;
+    if (gap < -coast - 5) {
+        input.setHeld(Action::MoveLeft, true);
+    } else if (gap > coast + 5) {
+        input.setHeld(Action::MoveRight, true);
+    }
+    return reversed ? input.mirrored(rules.width) : input;
+}
+
+float Bot::predictLanding(Ball& ball, GameState& state, const SimulationRules& rules) {
+    Vector2 center = ball.getCenter();
+    Vector2 velocity = ball.velocity;
+    if (velocity.y <= 0) {
+        // going up: stay under it until it turns
+        return center.x;
+    }
+    float paddleY = state.player.getRectangle().y;
+    float width = (float) rules.width;
+    bool wells = std::any_of(state.bricks.begin(), state.bricks.end(), [](const Brick& brick) { return brick.well.active(); });
+    if (wells) {
+        // the path bends, so follow it a tick at a time the way the
+        // simulation moves it, off the walls but not the bricks
+        Ball ghost = ball;
+        ghost.force = {0, 0};
+        for (int tick = 0; tick < MAX_PREDICT_TICKS; tick++) {
+            ghost.addForce(wellForce(state.bricks, ghost.getCenter()));
+            ghost.advance(state.timeScale);
+            Vector2 at = ghost.getCenter();
+            if (at.y >= paddleY) {
+                return at.x;
+            }
+            if ((at.x < 0 && ghost.velocity.x < 0) || (at.x > width && ghost.velocity.x > 0)) {
+                ghost.velocity.x = -ghost.velocity.x;
+            }
+            if (at.y < 0 && ghost.velocity.y < 0) {
+                ghost.velocity.y = -ghost.velocity.y;
+            }
+        }
+        return ghost.getCenter().x;
+    }
+    float x = center.x + velocity.x * (paddleY - center.y) / velocity.y;
+    // fold the straight line back into the field, once per wall bounce
+    x = fmodf(fabsf(x), 2 * width);
+    return x > width ? 2 * width - x : x;
+}
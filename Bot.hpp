@@ -0,0 +1,39 @@
+#pragma once
+#include <cstdint>
+#include <string>
+#include "InputRecord.hpp"
+#include "Random.hpp"
+#include "Simulation.hpp"
+
+
+enum class BotSkill {
+    Easy,   // follows the ball late and loosely with the keys
+    Normal, // reads the bounce off the walls, still with the keys
+    Hard,   // reads the bounce and steers straight there, like a mouse
+};
+
+bool parseBotSkill(const std::string& name, BotSkill& skill);
+const char* botSkillName(BotSkill skill);
+
+// Plays by producing InputFrames, so it goes through exactly the input path a
+// player does. Its mistakes come from its own Random rather than the
+// simulation's, so a bot run is as repeatable as a replay and the bot can't
+// change what the game rolls.
+class Bot {
+  public:
+    Bot(BotSkill skill, uint64_t seed);
+
+    // The input for the tick the state is about to run.
+    InputFrame think(GameState& state, const SimulationRules& rules);
+
+  private:
+    BotSkill skill;
+    Random random;
+    int reactionTicks = 0; // until the next look at the ball
+    int serveTicks = 0;    // spent holding the ball
+    float target = 0;      // paddle center to head for
+    float aimError = 0;
+    bool ballFalling = false;
+
+    float predictLanding(Ball& ball, GameState& state, const SimulationRules& rules);
+};
@@ -0,0 +1,76 @@
+#include "raylib.h"
+#include "Brick.hpp"
+#include "FramePacer.hpp"
+#include <cmath>
+#include <cstring>
+
+
+Brick::Brick(int x, int y, int width, int height, BrickMaterial brickMaterial, int hits) : Entity(x, y, width, height) {
+    material = brickMaterial;
+    hitPoints = hits;
+    maxHitPoints = hits;
+    origin = position;
+    velocity = {0, 0};
+}
+
+int Brick::damage() const {
+    return maxHitPoints - hitPoints;
+}
+
+bool Brick::isDestructible() const {
+    return material != BrickMaterial::Metal;
+}
+
+void Brick::move(float seconds) {
+    pathTime = fmodf(pathTime + seconds, path.period);
+    Vector2 offset = path.offsetAt(pathTime);
+    Vector2 next = path.offsetAt(pathTime + FramePacer::TICK_SECONDS);
+    position = {origin.x + offset.x, origin.y + offset.y};
+    velocity = {next.x - offset.x, next.y - offset.y};
+    updateRectangle();
+}
+
+void Brick::shift(Vector2 by) {
+    origin.x += by.x;
+    origin.y += by.y;
+    position.x += by.x;
+    position.y += by.y;
+    updateRectangle();
+}
+
+int destructibleCount(const std::vector<Brick>& bricks) {
+    int count = 0;
+    for (const Brick& brick : bricks) {
+        count += brick.isDestructible() ? 1 : 0;
+    }
+    return count;
+}
+
+Vector2 wellForce(const std::vector<Brick>& bricks, Vector2 at) {
+    Vector2 force = {0, 0};
+    for (const Brick& brick : bricks) {
+        // the last brick of a level stays in the list once broken
+        if (!brick.well.active() || brick.hitPoints <= 0 || brick.phased) {
+            continue;
+        }
+        const Rectangle& rect = brick.rectangle;
+        Vector2 center = {rect.x + rect.width / 2, rect.y + rect.height / 2};
+        float dx = center.x - at.x;
+        float dy = center.y - at.y;
+        float distance = sqrtf(dx * dx + dy * dy);
+        if (distance >= brick.well.radius || distance < 1) {
+            continue;
+        }
+        float pull = brick.well.strength * (1 - distance / brick.well.radius);
+        force.x += dx / distance * pull;
+        force.y += dy / distance * pull;
+    }
+    return force;
+}
+
+BrickMaterial parseBrickMaterial(const char* name) {
+    if (strcmp(name, "glass") == 0) return BrickMaterial::Glass;
+    if (strcmp(name, "stone") == 0) return BrickMaterial::Stone;
+    if (strcmp(name, "metal") == 0) return BrickMaterial::Metal;
+    return BrickMaterial::Standard;
+}
@@ -0,0 +1,75 @@
+#pragma once
+#include "raylib.h"
+#include <string>
+#include <vector>
+#include "Animation.hpp"
+#include "BrickPath.hpp"
+#include "Entity.hpp"
+
+
+enum class BrickMaterial {
+    Standard,
+    Glass,
+    Stone,
+    Metal,
+};
+
+BrickMaterial parseBrickMaterial(const char* name);
+
+// Pulls a free ball towards its brick's center from within radius, or pushes
+// it away when strength is negative: hardest at the middle, fading to
+// nothing at the edge. strength is in pixels per tick per tick, like
+// Ball::force, so it only ever bends the ball's path.
+struct GravityWell {
+    float strength = 0;
+    float radius = 160;
+
+    bool active() const { return strength != 0 && radius > 0; }
+};
+
+class Brick: public Entity {
+  public:
+    static constexpr int MAX_HIT_POINTS = 4;
+    // how see-through a phased brick is drawn
+    static constexpr float PHASED_ALPHA = 0.25f;
+
+    BrickMaterial material;
+    AnimationState animation;
+    int hitPoints;    // hits left before it breaks
+    int maxHitPoints; // what it started with
+    int coins = 0;    // dropped when it breaks
+    int timeBonus = 0; // seconds dropped when it breaks, in time attack
+    // A moving brick goes round its path from origin, where the level put
+    // it; its velocity is how far it goes in a tick at full speed.
+    BrickPath path;
+    Vector2 origin;
+    float pathTime = 0;
+    GravityWell well;
+    // Switch bricks phase the bricks of a group in and out each time they're
+    // hit. A phased brick is still there to be cleared, but balls, lasers and
+    // wells go through it until it's switched back.
+    std::string group;
+    std::string switchGroup; // the group hitting it toggles, if any
+    bool phased = false;
+    bool hidden = false; // invisible_bricks: it's there, but not drawn until it's hit
+
+
+    Brick(int x, int y, int width, int height, BrickMaterial material = BrickMaterial::Standard, int hitPoints = 1);
+
+    // Hits it has taken, which is how cracked it looks.
+    int damage() const;
+    // seconds of its path, already scaled by the state's timeScale
+    void move(float seconds);
+    // somewhere else for good, its path going with it
+    void shift(Vector2 by);
+
+    // Whether anything can break it: metal bricks are walls in the middle of
+    // the field, which a fireball bounces off too, and a level is cleared
+    // without them.
+    bool isDestructible() const;
+};
+
+// The ones a level needs broken to be cleared.
+int destructibleCount(const std::vector<Brick>& bricks);
+// Every well still standing, added up, on a ball centered at.
+Vector2 wellForce(const std::vector<Brick>& bricks, Vector2 at);
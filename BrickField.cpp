@@ -0,0 +1,207 @@
+#include "raylib.h"
+#include "rlgl.h"
+#include "raymath.h"
+#include "BrickField.hpp"
+
+
+static const char* VERTEX_SHADER = R"(#version 330
+in vec3 vertexPosition;
+in vec4 instanceRect;
+in vec4 instanceUv;
+in vec4 instanceColor;
+uniform mat4 mvp;
+out vec2 fragTexCoord;
+out vec4 fragColor;
+out float textured;
+void main() {
+    vec2 corner = vertexPosition.xy;
+    textured = instanceUv.z < 0.0 ? 0.0 : 1.0;
+    fragTexCoord = instanceUv.xy + corner * instanceUv.zw;
+    fragColor = instanceColor;
+    gl_Position = mvp * vec4(instanceRect.xy + corner * instanceRect.zw, 0.0, 1.0);
+}
+)";
+
+static const char* FRAGMENT_SHADER = R"(#version 330
+in vec2 fragTexCoord;
+in vec4 fragColor;
+in float textured;
+uniform sampler2D texture0;
+out vec4 finalColor;
+void main() {
+    finalColor = textured > 0.5 ? texture(texture0, fragTexCoord) * fragColor : fragColor;
+}
+)";
+
+static const char* MATERIAL_SPRITES[] = {"brick", "brick_glass", "brick_stone", "brick_metal"};
+
+bool BrickField::init() {
+    shader = LoadShaderFromMemory(VERTEX_SHADER, FRAGMENT_SHADER);
+    if (!IsShaderReady(shader)) {
+        TraceLog(LOG_WARNING, "BRICKS: Instancing shader failed to compile, using the sprite batch");
+        return false;
+    }
+    mvpLocation = GetShaderLocation(shader, "mvp");
+    textureLocation = GetShaderLocation(shader, "texture0");
+
+    // unit quad as two triangles, scaled and offset per instance
+    static const float quad[] = {0, 0, 1, 0, 1, 1, 0, 0, 1, 1, 0, 1};
+    vao = rlLoadVertexArray();
+    rlEnableVertexArray(vao);
+    quadBuffer = rlLoadVertexBuffer(quad, sizeof(quad), false);
+    int position = GetShaderLocationAttrib(shader, "vertexPosition");
+    rlSetVertexAttribute(position, 2, RL_FLOAT, false, 0, 0);
+    rlEnableVertexAttribute(position);
+    rlDisableVertexArray();
+
+    allocate(INSTANCING_THRESHOLD * 2);
+    ready = true;
+    return true;
+}
+
+void BrickField::allocate(int instanceCount) {
+    rlEnableVertexArray(vao);
+    if (instanceBuffer) {
+        rlUnloadVertexBuffer(instanceBuffer);
+    }
+    capacity = instanceCount;
+    instanceBuffer = rlLoadVertexBuffer(nullptr, capacity * sizeof(Instance), true);
+
+    const char* attributes[] = {"instanceRect", "instanceUv", "instanceColor"};
+    for (int i = 0; i < 3; i++) {
+        int location = GetShaderLocationAttrib(shader, attributes[i]);
+        if (location < 0) {
+            continue;
+        }
+        rlSetVertexAttribute(location, 4, RL_FLOAT, false, sizeof(Instance), (const void*) (i * 4 * sizeof(float)));
+        rlSetVertexAttributeDivisor(location, 1);
+        rlEnableVertexAttribute(location);
+    }
+    rlDisableVertexArray();
+}
+
+void BrickField::unload() {
+    if (!ready) {
+        return;
+    }
+    rlUnloadVertexBuffer(instanceBuffer);
+    rlUnloadVertexBuffer(quadBuffer);
+    rlUnloadVertexArray(vao);
+    UnloadShader(shader);
+    instanceBuffer = 0;
+    ready = false;
+}
+
+bool BrickField::isReady() {
+    return ready;
+}
+
+void BrickField::markDirty() {
+    dirty = true;
+}
+
+// Cheap CPU-side check so animated bricks still upload only on frame changes
+bool BrickField::changed(std::vector<Brick>& bricks) {
+    if (dirty || (int) bricks.size() != count) {
+        return true;
+    }
+    for (int i = 0; i < count; i++) {
+        int frame = bricks[i].animation.playing ? bricks[i].animation.getFrame() : -1;
+        // a moving brick is somewhere else every frame
+        if (frame != frames[i] || bricks[i].phased != phased[i] || bricks[i].hidden != hidden[i] || bricks[i].path.moves()) {
+            return true;
+        }
+    }
+    return false;
+}
+
+void BrickField::rebuild(TextureAtlas& atlas, const Theme& theme, const ThemePalette& palette, std::vector<Brick>& bricks) {
+    count = bricks.size();
+    instances.resize(count);
+    frames.resize(count);
+    phased.resize(count);
+    hidden.resize(count);
+    Texture2D texture = atlas.getTexture();
+    float textureWidth = texture.width > 0 ? texture.width : 1;
+    float textureHeight = texture.height > 0 ? texture.height : 1;
+
+    for (int i = 0; i < count; i++) {
+        Brick& brick = bricks[i];
+        Instance& instance = instances[i];
+        Rectangle rect = brick.getRectangle();
+        int material = (int) brick.material;
+        Color color = palette.bricks[material];
+        if (brick.phased) {
+            color = Fade(color, Brick::PHASED_ALPHA);
+        }
+        phased[i] = brick.phased;
+        if (brick.hidden) {
+            color.a = 0;
+        }
+        hidden[i] = brick.hidden;
+
+        Rectangle region = {0, 0, -1, -1};
+        AnimationState& animation = brick.animation;
+        frames[i] = animation.playing ? animation.getFrame() : -1;
+        if (atlas.isReady()) {
+            if (animation.playing && atlas.has(animation.clip.sheet)) {
+                region = animation.frameRegion(atlas);
+            } else if (atlas.has(theme.sprite(MATERIAL_SPRITES[material]))) {
+                region = atlas.region(theme.sprite(MATERIAL_SPRITES[material]));
+            }
+        }
+
+        instance.rect[0] = rect.x;
+        instance.rect[1] = rect.y;
+        instance.rect[2] = rect.width;
+        instance.rect[3] = rect.height;
+        if (region.width < 0) {
+            instance.uv[0] = instance.uv[1] = 0;
+            instance.uv[2] = instance.uv[3] = -1;
+        } else {
+            instance.uv[0] = region.x / textureWidth;
+            instance.uv[1] = region.y / textureHeight;
+            instance.uv[2] = region.width / textureWidth;
+            instance.uv[3] = region.height / textureHeight;
+        }
+        instance.color[0] = color.r / 255.0f;
+        instance.color[1] = color.g / 255.0f;
+        instance.color[2] = color.b / 255.0f;
+        instance.color[3] = color.a / 255.0f;
+    }
+
+    if (count > capacity) {
+        allocate(count * 2);
+    }
+    if (count > 0) {
+        rlUpdateVertexBuffer(instanceBuffer, instances.data(), count * sizeof(Instance), 0);
+    }
+    dirty = false;
+}
+
+void BrickField::draw(TextureAtlas& atlas, const Theme& theme, const ThemePalette& palette, std::vector<Brick>& bricks) {
+    if (!ready) {
+        return;
+    }
+    if (changed(bricks)) {
+        rebuild(atlas, theme, palette, bricks);
+    }
+    if (count == 0) {
+        return;
+    }
+    // flush whatever the immediate-mode batch holds so draw order is kept
+    rlDrawRenderBatchActive();
+
+    Matrix mvp = MatrixMultiply(rlGetMatrixModelview(), rlGetMatrixProjection());
+    int slot = 0;
+    rlEnableShader(shader.id);
+    rlSetUniformMatrix(mvpLocation, mvp);
+    rlActiveTextureSlot(0);
+    rlEnableTexture(atlas.isReady() ? atlas.getTexture().id : rlGetTextureIdDefault());
+    rlSetUniform(textureLocation, &slot, RL_SHADER_UNIFORM_INT, 1);
+    rlEnableVertexArray(vao);
+    rlDrawVertexArrayInstanced(0, 6, count);
+    rlDisableVertexArray();
+    rlDisableTexture();
+    rlDisableShader();
+}
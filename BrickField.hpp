@@ -0,0 +1,53 @@
+#pragma once
+#include "raylib.h"
+#include <vector>
+#include "Atlas.hpp"
+#include "Brick.hpp"
+#include "Theme.hpp"
+
+
+// Draws a large brick field (endless mode can have thousands) with one
+// instanced call. Each brick's rectangle, atlas UVs and color live in a GPU
+// instance buffer that is only rewritten when markDirty() was called or a
+// brick's animation frame changed; otherwise drawing costs one draw call and
+// no uploads. Needs GLSL 330, so isReady() is false on older GL and callers
+// fall back to the sprite batch.
+class BrickField {
+  public:
+    // Below this many bricks the sprite batch is just as fast.
+    static constexpr int INSTANCING_THRESHOLD = 500;
+
+    bool init();
+    void unload();
+    bool isReady();
+    void markDirty();
+
+    // Must be called inside the world's Mode2D so the camera applies.
+    void draw(TextureAtlas& atlas, const Theme& theme, const ThemePalette& palette, std::vector<Brick>& bricks);
+
+  private:
+    struct Instance {
+        float rect[4];
+        float uv[4]; // negative width = untextured
+        float color[4];
+    };
+
+    Shader shader = {};
+    unsigned int vao = 0;
+    unsigned int quadBuffer = 0;
+    unsigned int instanceBuffer = 0;
+    int capacity = 0;
+    int count = 0;
+    int mvpLocation = -1;
+    int textureLocation = -1;
+    bool ready = false;
+    bool dirty = true;
+    std::vector<Instance> instances;
+    std::vector<int> frames; // animation frame each instance was built with
+    std::vector<bool> phased; // and whether it was phased out
+    std::vector<bool> hidden; // or not to be seen at all
+
+    bool changed(std::vector<Brick>& bricks);
+    void rebuild(TextureAtlas& atlas, const Theme& theme, const ThemePalette& palette, std::vector<Brick>& bricks);
+    void allocate(int instanceCount);
+};
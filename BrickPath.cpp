@@ -0,0 +1,79 @@
+#include "raylib.h"
+#include "BrickPath.hpp"
+#include <cmath>
+
+
+bool BrickPath::moves() const {
+    return kind != BrickPathKind::None;
+}
+
+Vector2 BrickPath::offsetAt(float seconds) const {
+    float loop = seconds / period + phase;
+    loop -= floorf(loop);
+    float angle = 2 * PI * loop;
+    switch (kind) {
+    case BrickPathKind::None:
+        break;
+    case BrickPathKind::Sweep:
+        return {range * (1 - cosf(angle)) / 2, 0};
+    case BrickPathKind::Circle:
+        return {radius * cosf(angle) - radius, radius * sinf(angle)};
+    case BrickPathKind::Waypoints: {
+        // by distance along the loop, so short legs don't go slower
+        float total = 0;
+        for (size_t i = 0; i < points.size(); i++) {
+            Vector2 a = points[i];
+            Vector2 b = points[(i + 1) % points.size()];
+            total += hypotf(b.x - a.x, b.y - a.y);
+        }
+        float along = loop * total;
+        for (size_t i = 0; i < points.size(); i++) {
+            Vector2 a = points[i];
+            Vector2 b = points[(i + 1) % points.size()];
+            float length = hypotf(b.x - a.x, b.y - a.y);
+            if (along <= length && length > 0) {
+                return {a.x + (b.x - a.x) * along / length, a.y + (b.y - a.y) * along / length};
+            }
+            along -= length;
+        }
+        return points.empty() ? Vector2{0, 0} : points.front();
+    }
+    }
+    return {0, 0};
+}
+
+bool parseBrickPath(const XmlNode& node, BrickPath& path, std::string& problem) {
+    path = BrickPath();
+    std::string kind = node.attribute("path", "none");
+    if (kind == "none") {
+        return true;
+    }
+    BrickPath parsed;
+    parsed.period = node.attributeFloat("period", parsed.period);
+    parsed.phase = node.attributeFloat("phase", parsed.phase);
+    if (parsed.period <= 0) {
+        problem = "path period must be more than 0";
+        return false;
+    }
+    if (kind == "sweep") {
+        parsed.kind = BrickPathKind::Sweep;
+        parsed.range = node.attributeFloat("range", parsed.range);
+    } else if (kind == "circle") {
+        parsed.kind = BrickPathKind::Circle;
+        parsed.radius = node.attributeFloat("radius", parsed.radius);
+    } else if (kind == "waypoints") {
+        parsed.kind = BrickPathKind::Waypoints;
+        for (const XmlNode* point : node.childrenNamed("point")) {
+            parsed.points.push_back({point->attributeFloat("x", 0), point->attributeFloat("y", 0)});
+        }
+        if (parsed.points.size() < 2) {
+            problem = "a waypoints path needs at least two <point>s";
+            return false;
+        }
+    } else {
+        problem = "unknown path '" + kind + "'";
+        return false;
+    }
+    path = parsed;
+    return true;
+}
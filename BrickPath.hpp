@@ -0,0 +1,38 @@
+#pragma once
+#include "raylib.h"
+#include <string>
+#include <vector>
+#include "Xml.hpp"
+
+
+enum class BrickPathKind {
+    None,
+    Sweep,     // right by range and back, easing at either end
+    Circle,    // round a circle of radius, its rightmost point where the brick starts
+    Waypoints, // straight lines through points and back to the first, at an even speed
+};
+
+// How a moving brick moves: an offset from where the level put it, as a
+// function of time, looping every period seconds. A function rather than
+// a speed that gets integrated, so a brick is where its path says it is at
+// any time and never drifts off it.
+struct BrickPath {
+    BrickPathKind kind = BrickPathKind::None;
+    float period = 4; // seconds
+    float phase = 0;  // 0 to 1, how far round the loop it starts
+    float range = 100;
+    float radius = 40;
+    std::vector<Vector2> points; // offsets; the first is where it starts
+
+    bool moves() const;
+    Vector2 offsetAt(float seconds) const;
+};
+
+//   <brick x="200" y="120" path="sweep" range="300" period="5"/>
+//   <brick x="640" y="160" path="circle" radius="60" period="3" phase="0.5"/>
+//   <brick x="400" y="100" path="waypoints" period="6">
+//     <point x="0" y="0"/> <point x="120" y="0"/> <point x="60" y="80"/>
+//   </brick>
+// The path attributes of a <brick> or <row>; false with the problem when
+// something is off, leaving path not moving.
+bool parseBrickPath(const XmlNode& node, BrickPath& path, std::string& problem);
@@ -0,0 +1,130 @@
+#include "raylib.h"
+#include "Capture.hpp"
+#include "Gif.hpp"
+#include "Sanitize.hpp"
+#include <cstring>
+#include <ctime>
+#include <filesystem>
+
+
+static std::string capturesDirectory() {
+    std::string directory = std::string(GetApplicationDirectory()) + "captures/";
+    std::error_code error;
+    std::filesystem::create_directories(directory, error);
+    return directory;
+}
+
+std::string captureFilename(const std::string& label, const std::string& extension) {
+    char stamp[32];
+    time_t now = time(nullptr);
+    strftime(stamp, sizeof(stamp), "%Y%m%d-%H%M%S", localtime(&now));
+    return capturesDirectory() + sanitizeFilename("breakout-" + label + "-" + stamp + extension);
+}
+
+void Capture::init(int clipSeconds) {
+    scaled = LoadRenderTexture(CLIP_WIDTH, CLIP_HEIGHT);
+    SetTextureFilter(scaled.texture, TEXTURE_FILTER_BILINEAR);
+    setClipLength(clipSeconds);
+}
+
+void Capture::unload() {
+    if (worker.joinable()) {
+        worker.join();
+    }
+    UnloadRenderTexture(scaled);
+    frames.clear();
+}
+
+void Capture::setClipLength(int seconds) {
+    int count = (seconds > 0 ? seconds : 1) * CLIP_FPS;
+    if (count == (int) frames.size()) {
+        return;
+    }
+    frames.assign(count, std::vector<unsigned char>());
+    head = 0;
+    filled = 0;
+}
+
+void Capture::requestScreenshot(const std::string& label) {
+    pendingScreenshot = label.empty() ? "screenshot" : label;
+}
+
+void Capture::requestClip(const std::string& label) {
+    pendingClip = label.empty() ? "clip" : label;
+}
+
+bool Capture::isEncoding() {
+    return encoding;
+}
+
+void Capture::record(RenderTexture2D canvas, float deltaTime) {
+    if (!pendingScreenshot.empty()) {
+        saveScreenshot(canvas, pendingScreenshot);
+        pendingScreenshot.clear();
+    }
+    timer += deltaTime;
+    if (timer >= 1.0f / CLIP_FPS && IsRenderTextureReady(scaled)) {
+        timer = 0;
+        grabFrame(canvas);
+    }
+    if (!pendingClip.empty()) {
+        saveClip(pendingClip);
+        pendingClip.clear();
+    }
+}
+
+void Capture::grabFrame(RenderTexture2D canvas) {
+    BeginTextureMode(scaled);
+    Rectangle source = {0, 0, (float) canvas.texture.width, (float) -canvas.texture.height};
+    DrawTexturePro(canvas.texture, source, {0, 0, CLIP_WIDTH, CLIP_HEIGHT}, {0, 0}, 0, WHITE);
+    EndTextureMode();
+
+    Image image = LoadImageFromTexture(scaled.texture);
+    ImageFlipVertical(&image);
+    ImageFormat(&image, PIXELFORMAT_UNCOMPRESSED_R8G8B8A8);
+    std::vector<unsigned char>& frame = frames[head];
+    frame.resize(CLIP_WIDTH * CLIP_HEIGHT * 4);
+    memcpy(frame.data(), image.data, frame.size());
+    UnloadImage(image);
+
+    head = (head + 1) % frames.size();
+    filled = filled < (int) frames.size() ? filled + 1 : filled;
+}
+
+void Capture::saveScreenshot(RenderTexture2D canvas, const std::string& label) {
+    Image image = LoadImageFromTexture(canvas.texture);
+    ImageFlipVertical(&image);
+    std::string path = captureFilename(label, ".png");
+    if (ExportImage(image, path.c_str())) {
+        TraceLog(LOG_INFO, "CAPTURE: Saved %s", path.c_str());
+    }
+    UnloadImage(image);
+}
+
+void Capture::saveClip(const std::string& label) {
+    if (encoding) {
+        TraceLog(LOG_WARNING, "CAPTURE: Still encoding the previous clip");
+        return;
+    }
+    if (filled == 0) {
+        return;
+    }
+    if (worker.joinable()) {
+        worker.join();
+    }
+    // oldest frame first
+    std::vector<std::vector<unsigned char>> clip;
+    int start = filled < (int) frames.size() ? 0 : head;
+    for (int i = 0; i < filled; i++) {
+        clip.push_back(frames[(start + i) % frames.size()]);
+    }
+    std::string path = captureFilename(label, ".gif");
+
+    encoding = true;
+    worker = std::thread([this, clip = std::move(clip), path]() {
+        if (writeGif(path, clip, CLIP_WIDTH, CLIP_HEIGHT, 100 / CLIP_FPS)) {
+            TraceLog(LOG_INFO, "CAPTURE: Saved %s", path.c_str());
+        }
+        encoding = false;
+    });
+}
@@ -0,0 +1,49 @@
+#pragma once
+#include "raylib.h"
+#include <atomic>
+#include <string>
+#include <thread>
+#include <vector>
+
+
+// Screenshots and "save the last N seconds" clips. While playing, a
+// downscaled copy of the canvas is kept in a ring buffer at CLIP_FPS; saving a
+// clip copies the buffer and encodes it to a GIF on a worker thread so the
+// game doesn't hitch. Files go to captures/ with names run through
+// sanitizeFilename().
+class Capture {
+  public:
+    static constexpr int CLIP_WIDTH = 320;
+    static constexpr int CLIP_HEIGHT = 180;
+    static constexpr int CLIP_FPS = 15;
+
+    void init(int clipSeconds);
+    void unload();
+    void setClipLength(int seconds);
+
+    // label ends up in the file name (usually the level name)
+    void requestScreenshot(const std::string& label);
+    void requestClip(const std::string& label);
+
+    // Grabs a clip frame when due and handles pending requests; must run
+    // outside of any texture mode, after the canvas is final.
+    void record(RenderTexture2D canvas, float deltaTime);
+    bool isEncoding();
+
+  private:
+    RenderTexture2D scaled = {};
+    std::vector<std::vector<unsigned char>> frames;
+    int head = 0;
+    int filled = 0;
+    float timer = 0;
+    std::string pendingScreenshot;
+    std::string pendingClip;
+    std::thread worker;
+    std::atomic<bool> encoding{false};
+
+    void grabFrame(RenderTexture2D canvas);
+    void saveScreenshot(RenderTexture2D canvas, const std::string& label);
+    void saveClip(const std::string& label);
+};
+
+std::string captureFilename(const std::string& label, const std::string& extension);
@@ -0,0 +1,241 @@
+#include "raylib.h"
+#include "Challenge.hpp"
+#include "Mutators.hpp"
+#include "Random.hpp"
+#include <algorithm>
+#include <cstdlib>
+#include <cstring>
+#include <sstream>
+
+
+// One of these a week, in turn.
+struct Rotation {
+    const char* name;
+    const char* mutators;
+    int levels;
+};
+static const Rotation ROTATIONS[] = {
+    {"Fast hands", "double_speed", 3},
+    {"Last ball", "one_life", 2},
+    {"Needle", "tiny_paddle", 3},
+    {"Big and fast", "double_speed,wide_paddle", 3},
+    {"Sudden death", "one_life,tiny_paddle", 2},
+};
+static const int ROTATION_COUNT = sizeof(ROTATIONS) / sizeof(ROTATIONS[0]);
+static const time_t WEEK = 7 * 24 * 60 * 60;
+
+std::string isoWeek(time_t when) {
+    std::tm local = *std::localtime(&when);
+    char week[16];
+    strftime(week, sizeof(week), "%G-W%V", &local);
+    return week;
+}
+
+static uint64_t hashText(const std::string& text) {
+    // FNV-1a, like the level hash
+    uint64_t hash = 1469598103934665603ull;
+    for (unsigned char c : text) {
+        hash = (hash ^ c) * 1099511628211ull;
+    }
+    return hash;
+}
+
+// Every level in levels/ (not the tutorial), sorted so the shuffle only
+// depends on the seed; the built-in layout when there are none.
+static std::vector<std::string> candidateLevels() {
+    std::vector<std::string> found;
+    std::string directory = std::string(GetApplicationDirectory()) + "levels";
+    if (DirectoryExists(directory.c_str())) {
+        FilePathList files = LoadDirectoryFilesEx(directory.c_str(), ".blvl", false);
+        for (unsigned int i = 0; i < files.count; i++) {
+            found.push_back(files.paths[i]);
+        }
+        UnloadDirectoryFiles(files);
+    }
+    std::sort(found.begin(), found.end());
+    if (found.empty()) {
+        found.push_back("");
+    }
+    return found;
+}
+
+Challenge weeklyChallenge(time_t when) {
+    Challenge challenge;
+    challenge.week = isoWeek(when);
+    challenge.id = "weekly-" + challenge.week;
+    challenge.seed = hashText(challenge.id);
+
+    int year = std::atoi(challenge.week.c_str());
+    int number = std::atoi(challenge.week.c_str() + challenge.week.find('W') + 1);
+    const Rotation& rotation = ROTATIONS[(year * 53 + number) % ROTATION_COUNT];
+    challenge.name = rotation.name;
+    std::string error;
+    parseMutators(rotation.mutators, challenge.mutators, error);
+
+    std::vector<std::string> levels = candidateLevels();
+    Random random(challenge.seed);
+    for (int i = (int) levels.size() - 1; i > 0; i--) {
+        std::swap(levels[i], levels[random.range(0, i)]);
+    }
+    levels.resize(std::min<size_t>(levels.size(), rotation.levels));
+    challenge.levels = levels;
+    return challenge;
+}
+
+void finishChallenge(ChallengeStreak& streak, const Challenge& challenge, time_t now) {
+    if (streak.lastWeek == challenge.week) {
+        return;
+    }
+    streak.current = currentStreak(streak, now) + 1;
+    streak.best = std::max(streak.best, streak.current);
+    streak.lastWeek = challenge.week;
+}
+
+int currentStreak(const ChallengeStreak& streak, time_t now) {
+    if (streak.lastWeek == isoWeek(now) || streak.lastWeek == isoWeek(now - WEEK)) {
+        return streak.current;
+    }
+    return 0;
+}
+
+std::string isoDate(time_t when) {
+    std::tm local = *std::localtime(&when);
+    char date[16];
+    strftime(date, sizeof(date), "%Y-%m-%d", &local);
+    return date;
+}
+
+static const char* DAILY_PREFIX = "daily:";
+
+DailyChallenge dailyChallenge(time_t when) {
+    DailyChallenge daily;
+    daily.date = isoDate(when);
+    daily.id = "daily-" + daily.date;
+    daily.levelPath = DAILY_PREFIX + daily.date;
+    daily.seed = hashText(daily.id);
+
+    // one or two, shuffled out of every mutator; the paddle ones would only
+    // undo each other, and hardcore is the player's to take on
+    std::vector<std::string> ids;
+    for (const Mutator& mutator : allMutators()) {
+        if (std::string(mutator.id) != "hardcore") {
+            ids.push_back(mutator.id);
+        }
+    }
+    Random random(daily.seed + 1);
+    for (int i = (int) ids.size() - 1; i > 0; i--) {
+        std::swap(ids[i], ids[random.range(0, i)]);
+    }
+    ids.resize(std::min<size_t>(ids.size(), random.range(1, 2)));
+    if (std::find(ids.begin(), ids.end(), "tiny_paddle") != ids.end()) {
+        ids.erase(std::remove(ids.begin(), ids.end(), "wide_paddle"), ids.end());
+    }
+    std::string list;
+    for (const std::string& id : ids) {
+        list += (list.empty() ? "" : ",") + id;
+    }
+    std::string error;
+    parseMutators(list, daily.mutators, error);
+    return daily;
+}
+
+// the built-in layout's grid: 20 columns of 48 by 10 from x 50, rows 15 apart
+static const int LAYOUT_COLUMNS = 20;
+static const int LAYOUT_LEFT = 50;
+static const int LAYOUT_TOP = 50;
+static const int LAYOUT_STEP_X = 50;
+static const int LAYOUT_STEP_Y = 15;
+static const int LAYOUT_FEWEST_BRICKS = 20;
+
+std::vector<Brick> generateLayout(uint64_t seed) {
+    Random random(seed);
+    std::vector<Brick> bricks;
+    int rows = random.range(4, 8);
+    for (int row = 0; row < rows; row++) {
+        float fill = 0.5f + 0.4f * random.unit();
+        int toughest = std::min(1 + row / 2, 3);
+        int y = LAYOUT_TOP + row * LAYOUT_STEP_Y;
+        for (int column = 0; column < LAYOUT_COLUMNS / 2; column++) {
+            if (random.unit() >= fill) {
+                continue;
+            }
+            float roll = random.unit();
+            // no metal on the top row, so nothing gets walled off up there
+            BrickMaterial material = roll < 0.05f && row > 0 ? BrickMaterial::Metal
+                : roll < 0.2f                                ? BrickMaterial::Glass
+                : roll < 0.4f                                ? BrickMaterial::Stone
+                                                             : BrickMaterial::Standard;
+            int hitPoints = material == BrickMaterial::Standard || material == BrickMaterial::Stone ? random.range(1, toughest) : 1;
+            int coins = material != BrickMaterial::Metal && random.unit() < 0.05f ? 1 : 0;
+            for (int side : {column, LAYOUT_COLUMNS - 1 - column}) {
+                Brick brick(LAYOUT_LEFT + side * LAYOUT_STEP_X, y, 48, 10, material, hitPoints);
+                brick.coins = coins;
+                bricks.push_back(brick);
+            }
+        }
+    }
+    // a sparse roll still gets a full row along the top
+    if (destructibleCount(bricks) < LAYOUT_FEWEST_BRICKS) {
+        for (Brick& brick : bricks) {
+            brick.shift({0, (float) LAYOUT_STEP_Y});
+        }
+        for (int column = 0; column < LAYOUT_COLUMNS; column++) {
+            bricks.push_back({LAYOUT_LEFT + column * LAYOUT_STEP_X, LAYOUT_TOP, 48, 10});
+        }
+    }
+    return bricks;
+}
+
+bool loadDailyLevel(const std::string& path, Level& level) {
+    if (path.rfind(DAILY_PREFIX, 0) != 0 || path.size() == strlen(DAILY_PREFIX)) {
+        return false;
+    }
+    std::string date = path.substr(strlen(DAILY_PREFIX));
+    level = Level();
+    level.path = path;
+    level.name = "Daily " + date;
+    level.bricks = generateLayout(hashText("daily-" + date));
+    return true;
+}
+
+int dailyBest(const std::string& record, const std::string& date) {
+    std::stringstream list(record);
+    std::string entry;
+    while (std::getline(list, entry, ',')) {
+        size_t colon = entry.find(':');
+        if (colon != std::string::npos && entry.compare(0, colon, date) == 0) {
+            return std::atoi(entry.c_str() + colon + 1);
+        }
+    }
+    return 0;
+}
+
+bool recordDailyScore(std::string& record, const std::string& date, int score) {
+    std::vector<std::string> days;
+    std::stringstream list(record);
+    std::string entry;
+    while (std::getline(list, entry, ',')) {
+        size_t colon = entry.find(':');
+        if (colon == std::string::npos) {
+            continue;
+        }
+        if (entry.compare(0, colon, date) == 0) {
+            if (score <= std::atoi(entry.c_str() + colon + 1)) {
+                return false;
+            }
+            continue;
+        }
+        days.push_back(entry);
+    }
+    days.push_back(date + ":" + std::to_string(score));
+    // dates sort as text, so the oldest go first
+    std::sort(days.begin(), days.end());
+    if ((int) days.size() > DAILY_HISTORY) {
+        days.erase(days.begin(), days.end() - DAILY_HISTORY);
+    }
+    record.clear();
+    for (const std::string& day : days) {
+        record += (record.empty() ? "" : ",") + day;
+    }
+    return true;
+}
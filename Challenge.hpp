@@ -0,0 +1,62 @@
+#pragma once
+#include <cstdint>
+#include <ctime>
+#include <string>
+#include <vector>
+#include "Level.hpp"
+
+
+// This week's challenge (ISO weeks, Monday to Sunday in local time): a seed,
+// a set of mutators and a gauntlet of levels played back to back, score and
+// lives carried over, all worked out from the week alone. Everyone with the
+// same levels/ folder gets the same challenge.
+struct Challenge {
+    std::string week; // "2026-W42"
+    std::string id;   // "weekly-2026-W42": its leaderboard, splits and streak
+    std::string name; // this rotation's
+    uint64_t seed = 0;
+    std::string mutators;            // canonical, see Mutators.hpp
+    std::vector<std::string> levels; // in play order; empty for the built-in layout
+};
+
+std::string isoWeek(time_t when);
+Challenge weeklyChallenge(time_t when);
+
+// A streak is how many weeks in a row ended with the challenge finished.
+struct ChallengeStreak {
+    std::string lastWeek; // the last finished one
+    int current = 0;
+    int best = 0;
+};
+
+// Counts challenge as finished; nothing new when its week already was.
+void finishChallenge(ChallengeStreak& streak, const Challenge& challenge, time_t now);
+// The streak as it stands now: a week missed since the last finish ends it.
+int currentStreak(const ChallengeStreak& streak, time_t now);
+
+// Today's challenge (local calendar days): one level generated from the
+// date and a mutator or two picked by it. Nothing comes from levels/, so
+// everyone gets the same layout whatever they have installed.
+struct DailyChallenge {
+    std::string date;      // "2026-10-14"
+    std::string id;        // "daily-2026-10-14": its leaderboard and record
+    std::string levelPath; // "daily:2026-10-14", which loadDailyLevel() builds
+    uint64_t seed = 0;
+    std::string mutators;  // canonical, see Mutators.hpp
+};
+
+std::string isoDate(time_t when);
+DailyChallenge dailyChallenge(time_t when);
+// A layout from seed alone, mirrored down the middle so neither side is
+// the easy one.
+std::vector<Brick> generateLayout(uint64_t seed);
+// The level behind a daily's levelPath, for play and for its replays;
+// false when path isn't one.
+bool loadDailyLevel(const std::string& path, Level& level);
+
+// The profile's best score for each day played, "2026-10-13:1520,2026-10-14:980",
+// the last DAILY_HISTORY days of them.
+const int DAILY_HISTORY = 30;
+int dailyBest(const std::string& record, const std::string& date);
+// Keeps score as the day's best if it beats what's there; true when it did.
+bool recordDailyScore(std::string& record, const std::string& date, int score);
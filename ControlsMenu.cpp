@@ -0,0 +1,125 @@
+#include "raylib.h"
+#include "ControlsMenu.hpp"
+#include "Localization.hpp"
+#include "Ui.hpp"
+
+
+void ControlsMenu::toggle() {
+    isOpen = !isOpen;
+    setCapturing(false);
+    message.clear();
+}
+
+// Escape normally closes the window; while waiting for an input it has to
+// mean "never mind" instead.
+void ControlsMenu::setCapturing(bool enabled) {
+    capturing = enabled;
+    SetExitKey(enabled ? KEY_NULL : KEY_ESCAPE);
+}
+
+static bool reserved(int key) {
+    return key == KEY_F1 || key == KEY_F2 || key == KEY_ESCAPE;
+}
+
+bool ControlsMenu::update(InputBindings& bindings) {
+    if (!isOpen) {
+        return false;
+    }
+    Action action = (Action) selected;
+
+    if (capturing) {
+        InputBinding input;
+        int key = GetKeyPressed();
+        if (key == KEY_ESCAPE) {
+            setCapturing(false);
+            message.clear();
+            return false;
+        }
+        if (key != KEY_NULL && reserved(key)) {
+            message = TextFormat(tr("%s is reserved for the menus").c_str(), describeInput({InputKind::Key, key}).c_str());
+            return false;
+        }
+        // the controller column only takes pad buttons, the others only keys and mouse
+        if (slot == InputBindings::GAMEPAD_SLOT) {
+            int button = GetGamepadButtonPressed();
+            if (button != GAMEPAD_BUTTON_UNKNOWN && bindings.activeGamepad() >= 0) {
+                input = {InputKind::GamepadButton, button};
+            }
+        } else if (key != KEY_NULL) {
+            input = {InputKind::Key, key};
+        }
+        for (int button = MOUSE_BUTTON_LEFT; button <= MOUSE_BUTTON_BACK && input.kind == InputKind::None && slot != InputBindings::GAMEPAD_SLOT; button++) {
+            if (IsMouseButtonPressed(button)) {
+                input = {InputKind::MouseButton, button};
+            }
+        }
+        if (input.kind == InputKind::None) {
+            return false;
+        }
+        setCapturing(false);
+        int swapped = bindings.bind(action, slot, input);
+        message = swapped < 0 ? "" : TextFormat(tr("%s was moved from %s").c_str(), describeInput(input).c_str(), tr(actionName((Action) swapped)).c_str());
+        return true;
+    }
+
+    if (IsKeyPressed(KEY_DOWN)) {
+        selected = (selected + 1) % ACTION_COUNT;
+        message.clear();
+    } else if (IsKeyPressed(KEY_UP)) {
+        selected = (selected - 1 + ACTION_COUNT) % ACTION_COUNT;
+        message.clear();
+    }
+    if (IsKeyPressed(KEY_RIGHT)) {
+        slot = (slot + 1) % InputBindings::SLOTS;
+    } else if (IsKeyPressed(KEY_LEFT)) {
+        slot = (slot - 1 + InputBindings::SLOTS) % InputBindings::SLOTS;
+    }
+    if (IsKeyPressed(KEY_ENTER)) {
+        // the keypress that opened the capture is only seen next frame
+        setCapturing(true);
+        message.clear();
+    } else if (IsKeyPressed(KEY_BACKSPACE) || IsKeyPressed(KEY_DELETE)) {
+        bindings.clear(action, slot);
+        return true;
+    }
+    return false;
+}
+
+void ControlsMenu::draw(TextRenderer& text, const InputBindings& bindings, Rectangle bounds, float scale) {
+    if (!isOpen) {
+        return;
+    }
+    float rowHeight = 28 * scale;
+    int fontSize = 20 * scale;
+    float columnWidth = 160 * scale;
+    Vector2 size = {740 * scale, 130 * scale + rowHeight * ACTION_COUNT};
+    Vector2 corner = anchorPoint(Anchor::Center, bounds, size);
+    float margin = 20 * scale;
+
+    DrawRectangle(0, 0, GetScreenWidth(), GetScreenHeight(), Fade(BLACK, 0.6f));
+    DrawRectangleRec({corner.x, corner.y, size.x, size.y}, Fade(DARKGRAY, 0.95f));
+    text.draw(tr("Controls"), {corner.x + margin, corner.y + 15 * scale}, 30 * scale, RAYWHITE);
+    static const char* COLUMNS[] = {"Primary", "Secondary", "Controller"};
+    for (int column = 0; column < InputBindings::SLOTS; column++) {
+        float x = corner.x + size.x - margin - (InputBindings::SLOTS - column) * columnWidth;
+        text.draw(tr(COLUMNS[column]), {x, corner.y + 60 * scale}, 16 * scale, GRAY);
+    }
+
+    for (int i = 0; i < ACTION_COUNT; i++) {
+        Action action = (Action) i;
+        int rowY = corner.y + 90 * scale + i * rowHeight;
+        // a hand-edited profile can still bind one key twice; show it
+        bool conflicting = !bindings.conflicts(action).empty();
+        Color color = conflicting ? RED : (i == selected ? YELLOW : LIGHTGRAY);
+        text.draw(tr(actionName(action)), {corner.x + margin, (float) rowY}, fontSize, color);
+        for (int column = 0; column < InputBindings::SLOTS; column++) {
+            bool active = i == selected && column == slot;
+            std::string value = active && capturing ? tr(column == InputBindings::GAMEPAD_SLOT ? "Press a button..." : "Press a key...") : describeInput(bindings.get(action, column));
+            float x = corner.x + size.x - margin - (InputBindings::SLOTS - column) * columnWidth;
+            text.draw(value, {x, (float) rowY}, fontSize, active ? YELLOW : color);
+        }
+    }
+
+    std::string footer = message.empty() ? tr("Enter: rebind   Backspace: clear   F2: close") : message;
+    text.draw(footer, {corner.x + margin, corner.y + size.y - 30 * scale}, 16 * scale, LIGHTGRAY);
+}
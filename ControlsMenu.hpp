@@ -0,0 +1,30 @@
+#pragma once
+#include "raylib.h"
+#include <string>
+#include "Bindings.hpp"
+#include "Text.hpp"
+
+
+// Overlay for rebinding actions. Up/down picks the action, left/right the
+// column (primary, secondary, controller), enter waits for the next input,
+// backspace clears the slot. Escape cancels a pending capture instead of
+// quitting. Menu keys themselves (F1, F2, arrows, enter) are fixed so a bad
+// binding can never lock anyone out of this menu.
+class ControlsMenu {
+  public:
+    bool isOpen = false;
+
+    void toggle();
+    // Returns true if a binding was changed this frame.
+    bool update(InputBindings& bindings);
+    // bounds is the UI layout's safe area, in window pixels.
+    void draw(TextRenderer& text, const InputBindings& bindings, Rectangle bounds, float scale);
+
+  private:
+    int selected = 0;
+    int slot = 0;
+    bool capturing = false;
+    std::string message;
+
+    void setCapturing(bool enabled);
+};
@@ -0,0 +1,206 @@
+#include "raylib.h"
+#include "Cosmetics.hpp"
+#include "Theme.hpp"
+#include <algorithm>
+#include <sstream>
+
+
+static std::vector<Cosmetic> COSMETICS = {
+    {"classic", CosmeticSlot::Skin, "Classic", "", nullptr},
+    {"ember", CosmeticSlot::Skin, "Ember", "Clear a level", [](const RunRecord& run) { return run.cleared; }, 0, ORANGE, {200, 50, 30, 255}},
+    {"frost", CosmeticSlot::Skin, "Frost", "Break 10 bricks in one combo", [](const RunRecord& run) { return run.bestCombo >= 10; }, 0, SKYBLUE, {90, 150, 230, 255}},
+    {"gold", CosmeticSlot::Skin, "Gold", "Clear 3 different levels", [](const RunRecord& run) { return run.levelsCleared >= 3; }, 0, GOLD, {230, 170, 40, 255}},
+    {"amethyst", CosmeticSlot::Skin, "Amethyst", "Reach player level 5", [](const RunRecord& run) { return run.playerLevel >= 5; }, 0, {190, 120, 255, 255}, {120, 60, 200, 255}},
+    {"flat", CosmeticSlot::Paddle, "Flat", "", nullptr},
+    {"rounded", CosmeticSlot::Paddle, "Rounded", "5 perfect catches in one run", [](const RunRecord& run) { return run.perfectCatches >= 5; }},
+    {"capsule", CosmeticSlot::Paddle, "Capsule", "Clear a level without losing a life", [](const RunRecord& run) { return run.cleared && run.livesLost == 0; }},
+    {"standard", CosmeticSlot::Trail, "Standard", "", nullptr},
+    {"ribbon", CosmeticSlot::Trail, "Ribbon", "Score 50 in one run", [](const RunRecord& run) { return run.score >= 50; }},
+    {"sparkle", CosmeticSlot::Trail, "Sparkle", "Clear 5 different levels", [](const RunRecord& run) { return run.levelsCleared >= 5; }},
+};
+
+const std::vector<Cosmetic>& allCosmetics() {
+    return COSMETICS;
+}
+
+std::vector<std::string> cosmeticCatalogFiles() {
+    std::string root = GetApplicationDirectory();
+    std::vector<std::string> files;
+    std::string bundled = root + "assets/cosmetics.xml";
+    if (FileExists(bundled.c_str())) {
+        files.push_back(bundled);
+    }
+    std::string mods = root + "mods";
+    if (DirectoryExists(mods.c_str())) {
+        FilePathList modDirectories = LoadDirectoryFiles(mods.c_str());
+        std::vector<std::string> found;
+        for (unsigned int i = 0; i < modDirectories.count; i++) {
+            std::string path = std::string(modDirectories.paths[i]) + "/cosmetics.xml";
+            if (FileExists(path.c_str())) {
+                found.push_back(path);
+            }
+        }
+        UnloadDirectoryFiles(modDirectories);
+        // the same shop on every machine
+        std::sort(found.begin(), found.end());
+        files.insert(files.end(), found.begin(), found.end());
+    }
+    return files;
+}
+
+bool parseCosmeticCatalog(const XmlNode& root, std::vector<Cosmetic>& list, std::string& problem) {
+    if (root.name != "cosmetics") {
+        problem = "the root element must be <cosmetics>";
+        return false;
+    }
+    std::vector<Cosmetic> parsed;
+    for (const XmlNode* node : root.childrenNamed("skin")) {
+        Cosmetic skin = {node->attribute("id"), CosmeticSlot::Skin, node->attribute("name", node->attribute("id")), "", nullptr};
+        skin.price = node->attributeInt("price", 0);
+        skin.ball = parseColor(node->attribute("ball"), BLANK);
+        skin.paddle = parseColor(node->attribute("paddle"), BLANK);
+        std::string where = "line " + std::to_string(node->line) + ": ";
+        if (skin.id.empty()) {
+            problem = where + "a skin needs an id";
+            return false;
+        }
+        if (skin.price < 1) {
+            problem = where + "price must be at least 1";
+            return false;
+        }
+        if (skin.ball.a == 0 || skin.paddle.a == 0) {
+            problem = where + "a skin needs ball and paddle colors, like #rrggbb";
+            return false;
+        }
+        auto sameId = [&](const Cosmetic& other) { return other.id == skin.id; };
+        if (std::any_of(list.begin(), list.end(), sameId) || std::any_of(parsed.begin(), parsed.end(), sameId)) {
+            problem = where + "there is already a cosmetic '" + skin.id + "'";
+            return false;
+        }
+        parsed.push_back(skin);
+    }
+    list.insert(list.end(), parsed.begin(), parsed.end());
+    return true;
+}
+
+void loadCosmeticCatalog() {
+    for (const std::string& path : cosmeticCatalogFiles()) {
+        XmlNode root;
+        XmlError error;
+        std::string problem;
+        if (!loadXmlFile(path, root, error)) {
+            TraceLog(LOG_WARNING, "COSMETICS: Skipping %s: %s", path.c_str(), error.describe().c_str());
+        } else if (!parseCosmeticCatalog(root, COSMETICS, problem)) {
+            TraceLog(LOG_WARNING, "COSMETICS: Skipping %s: %s", path.c_str(), problem.c_str());
+        }
+    }
+}
+
+static const Cosmetic* findCosmetic(const std::string& id, CosmeticSlot slot) {
+    for (const Cosmetic& cosmetic : COSMETICS) {
+        if (cosmetic.slot == slot && id == cosmetic.id) {
+            return &cosmetic;
+        }
+    }
+    return nullptr;
+}
+
+bool isUnlocked(const std::string& unlocked, const std::string& id) {
+    for (const Cosmetic& cosmetic : COSMETICS) {
+        if (id == cosmetic.id && !cosmetic.earned && cosmetic.price == 0) {
+            return true;
+        }
+    }
+    std::istringstream list(unlocked);
+    std::string entry;
+    while (std::getline(list, entry, ',')) {
+        if (entry == id) {
+            return true;
+        }
+    }
+    return false;
+}
+
+std::vector<std::string> unlockedCosmetics(const std::string& unlocked, CosmeticSlot slot) {
+    std::vector<std::string> ids;
+    for (const Cosmetic& cosmetic : COSMETICS) {
+        if (cosmetic.slot == slot && isUnlocked(unlocked, cosmetic.id)) {
+            ids.push_back(cosmetic.id);
+        }
+    }
+    return ids;
+}
+
+std::vector<const Cosmetic*> earnUnlocks(const RunRecord& run, std::string& unlocked) {
+    std::vector<const Cosmetic*> earned;
+    for (const Cosmetic& cosmetic : COSMETICS) {
+        if (cosmetic.earned && !isUnlocked(unlocked, cosmetic.id) && cosmetic.earned(run)) {
+            unlocked += (unlocked.empty() ? "" : ",") + cosmetic.id;
+            earned.push_back(&cosmetic);
+        }
+    }
+    return earned;
+}
+
+bool buyCosmetic(const std::string& id, int& coins, std::string& unlocked) {
+    for (const Cosmetic& cosmetic : COSMETICS) {
+        if (cosmetic.id == id && cosmetic.price > 0 && coins >= cosmetic.price && !isUnlocked(unlocked, id)) {
+            coins -= cosmetic.price;
+            unlocked += (unlocked.empty() ? "" : ",") + id;
+            return true;
+        }
+    }
+    return false;
+}
+
+std::string CosmeticLoadout::format() const {
+    return skin + "," + paddle + "," + trail;
+}
+
+void CosmeticLoadout::parse(const std::string& text) {
+    *this = CosmeticLoadout();
+    std::istringstream list(text);
+    std::string* slots[] = {&skin, &paddle, &trail};
+    std::string entry;
+    for (int i = 0; i < 3 && std::getline(list, entry, ','); i++) {
+        if (findCosmetic(entry, (CosmeticSlot) i)) {
+            *slots[i] = entry;
+        }
+    }
+}
+
+void CosmeticLoadout::restrictTo(const std::string& unlocked) {
+    CosmeticLoadout defaults;
+    std::string* slots[] = {&skin, &paddle, &trail};
+    const std::string* fallbacks[] = {&defaults.skin, &defaults.paddle, &defaults.trail};
+    for (int i = 0; i < 3; i++) {
+        if (!findCosmetic(*slots[i], (CosmeticSlot) i) || !isUnlocked(unlocked, *slots[i])) {
+            *slots[i] = *fallbacks[i];
+        }
+    }
+}
+
+// classic keeps whatever the theme says
+Color CosmeticLoadout::ballColor(Color themed) const {
+    const Cosmetic* found = findCosmetic(skin, CosmeticSlot::Skin);
+    return found && found->ball.a > 0 ? found->ball : themed;
+}
+
+Color CosmeticLoadout::paddleColor(Color themed) const {
+    const Cosmetic* found = findCosmetic(skin, CosmeticSlot::Skin);
+    return found && found->paddle.a > 0 ? found->paddle : themed;
+}
+
+float CosmeticLoadout::paddleRoundness() const {
+    if (paddle == "rounded") {
+        return 0.5f;
+    }
+    return paddle == "capsule" ? 1.0f : 0;
+}
+
+TrailStyle CosmeticLoadout::trailStyle() const {
+    if (trail == "ribbon") {
+        return TrailStyle::Ribbon;
+    }
+    return trail == "sparkle" ? TrailStyle::Sparkle : TrailStyle::Normal;
+}
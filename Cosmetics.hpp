@@ -0,0 +1,81 @@
+#pragma once
+#include "raylib.h"
+#include <string>
+#include <vector>
+#include "BallTrail.hpp"
+#include "Xml.hpp"
+
+
+enum class CosmeticSlot {
+    Skin,   // ball and paddle colors
+    Paddle, // paddle shape; the collision box never changes
+    Trail,  // ball trail
+};
+
+// What a finished run did, for unlocks to check against.
+struct RunRecord {
+    int score = 0;
+    bool cleared = false;
+    int livesLost = 0;
+    int bestCombo = 0;
+    int perfectCatches = 0;
+    int levelsCleared = 0; // different levels the profile has cleared, this one included
+    int playerLevel = 1;   // with this run's XP
+};
+
+// Earned by a run, bought in the shop for its price or, with neither, had
+// by everyone.
+struct Cosmetic {
+    std::string id;
+    CosmeticSlot slot;
+    std::string name;
+    std::string requirement;               // shown while locked; empty for the ones that aren't earned
+    bool (*earned)(const RunRecord& run);
+    int price = 0;                         // coins, 0 for the ones the shop doesn't sell
+    Color ball = BLANK;                    // a skin's; blank keeps the theme's
+    Color paddle = BLANK;
+};
+
+// The built in ones, then the catalog's skins.
+const std::vector<Cosmetic>& allCosmetics();
+
+// The shop's catalog, assets/cosmetics.xml then every mods/<mod>/cosmetics.xml:
+//   <cosmetics>
+//     <skin id="neon" name="Neon" price="25" ball="#7dff6a" paddle="#1fa83a"/>
+//   </cosmetics>
+std::vector<std::string> cosmeticCatalogFiles();
+// Adds the <cosmetics> root's skins to list; false with the problem when one
+// can't be sold as written (an id list already has is one).
+bool parseCosmeticCatalog(const XmlNode& root, std::vector<Cosmetic>& list, std::string& problem);
+// Into allCosmetics(), once at startup; a broken file is skipped with a warning.
+void loadCosmeticCatalog();
+
+// unlocked is the profile's comma separated list of earned ids.
+bool isUnlocked(const std::string& unlocked, const std::string& id);
+// The ids of slot the profile may pick from, defaults first.
+std::vector<std::string> unlockedCosmetics(const std::string& unlocked, CosmeticSlot slot);
+// Adds whatever run earns that unlocked doesn't have yet; returns the new ones.
+std::vector<const Cosmetic*> earnUnlocks(const RunRecord& run, std::string& unlocked);
+// Takes id's price off coins and adds it to unlocked; false, with neither
+// touched, when the shop doesn't sell it, it's owned already or coins are short.
+bool buyCosmetic(const std::string& id, int& coins, std::string& unlocked);
+
+// What a player has on. Stored per profile, and in replays as
+// "skin,paddle,trail" so playback looks like the run did.
+struct CosmeticLoadout {
+    std::string skin = "classic";
+    std::string paddle = "flat";
+    std::string trail = "standard";
+
+    std::string format() const;
+    // Unknown ids fall back to the defaults; empty text is all defaults.
+    void parse(const std::string& text);
+    // Puts anything unlocked doesn't have (a hand-edited profile) back to the default.
+    void restrictTo(const std::string& unlocked);
+
+    Color ballColor(Color themed) const;
+    Color paddleColor(Color themed) const;
+    // 0 for the flat paddle, which is the theme's sprite
+    float paddleRoundness() const;
+    TrailStyle trailStyle() const;
+};
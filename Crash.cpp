@@ -0,0 +1,179 @@
+#include "raylib.h"
+#include "Crash.hpp"
+#include <csignal>
+#include <cstdio>
+#include <cstdlib>
+#include <ctime>
+#include <exception>
+#include <filesystem>
+#include "FramePacer.hpp"
+#include "Log.hpp"
+#include "Sanitize.hpp"
+
+#if defined(__has_include)
+#if __has_include(<execinfo.h>)
+#include <execinfo.h>
+#define HAVE_BACKTRACE 1
+#endif
+#endif
+
+#if defined(_WIN32)
+// windows.h collides with raylib's names, so only what the dialog needs
+extern "C" {
+__declspec(dllimport) int __stdcall MessageBoxA(void* window, const char* text, const char* caption, unsigned int type);
+__declspec(dllimport) void* __stdcall ShellExecuteA(void* window, const char* operation, const char* file, const char* parameters, const char* directory, int show);
+}
+#endif
+
+
+static CrashHandler* installed = nullptr;
+static volatile sig_atomic_t crashing = 0;
+
+static std::string shellQuote(const std::string& text) {
+    std::string quoted = "'";
+    for (char c : text) {
+        quoted += c == '\'' ? std::string("'\\''") : std::string(1, c);
+    }
+    return quoted + "'";
+}
+
+static void showCrashDialog(const std::string& folder) {
+    std::string message = "BreakOut ran into a problem and has to close.\n\nA report was saved to\n" + folder + "\n\nOpen the folder?";
+    fprintf(stderr, "%s\n", message.c_str());
+#if defined(_WIN32)
+    const unsigned int MB_YESNO = 0x4, MB_ICONERROR = 0x10;
+    if (MessageBoxA(nullptr, message.c_str(), "BreakOut", MB_YESNO | MB_ICONERROR) == 6) { // IDYES
+        ShellExecuteA(nullptr, "open", folder.c_str(), nullptr, nullptr, 1);
+    }
+#elif defined(__APPLE__)
+    std::string script;
+    for (char c : message) {
+        script += (c == '"' || c == '\\') ? std::string("\\") + c : std::string(1, c);
+    }
+    script = "button returned of (display dialog \"" + script + "\" buttons {\"Close\", \"Open folder\"} default button 2 with icon stop)";
+    if (system(("osascript -e " + shellQuote(script) + " 2>/dev/null | grep -q Open").c_str()) == 0) {
+        if (system(("open " + shellQuote(folder)).c_str()) != 0) {
+            fprintf(stderr, "Could not open %s\n", folder.c_str());
+        }
+    }
+#else
+    if (system(("zenity --question --title=BreakOut --ok-label='Open folder' --cancel-label=Close --text=" + shellQuote(message) + " 2>/dev/null").c_str()) == 0) {
+        if (system(("xdg-open " + shellQuote(folder) + " >/dev/null 2>&1").c_str()) != 0) {
+            fprintf(stderr, "Could not open %s\n", folder.c_str());
+        }
+    }
+#endif
+}
+
+static void onSignal(int signal) {
+    if (!crashing && installed) {
+        crashing = 1;
+        const char* name = signal == SIGSEGV ? "segmentation fault" : signal == SIGABRT ? "abort" : signal == SIGFPE ? "arithmetic error" : signal == SIGILL ? "illegal instruction" : "fatal signal";
+        showCrashDialog(installed->writeBundle(std::string(name) + " (signal " + std::to_string(signal) + ")"));
+    }
+    // let the default action finish the job, core dump and exit code included
+    std::signal(signal, SIG_DFL);
+    std::raise(signal);
+}
+
+static void onTerminate() {
+    if (!crashing && installed) {
+        crashing = 1;
+        std::string reason = "uncaught exception";
+        try {
+            if (std::exception_ptr exception = std::current_exception()) {
+                std::rethrow_exception(exception);
+            }
+        } catch (const std::exception& exception) {
+            reason += ": " + std::string(exception.what());
+        } catch (...) {
+        }
+        showCrashDialog(installed->writeBundle(reason));
+    }
+    std::signal(SIGABRT, SIG_DFL);
+    std::abort();
+}
+
+void CrashHandler::install() {
+    installed = this;
+    logLines.assign(LOG_LINES, "");
+    tail.assign(TAIL_TICKS, {0, {}});
+    addLogSink([this](const LogRecord& record) { addLogLine(formatLogRecord(record)); });
+    for (int signal : {SIGSEGV, SIGABRT, SIGFPE, SIGILL}) {
+        std::signal(signal, onSignal);
+    }
+#ifdef SIGBUS
+    std::signal(SIGBUS, onSignal);
+#endif
+    std::set_terminate(onTerminate);
+}
+
+void CrashHandler::setStateSummary(std::function<std::string()> summary) {
+    stateSummary = summary;
+}
+
+void CrashHandler::recordInput(uint64_t tick, const InputFrame& frame) {
+    if (tail.empty()) {
+        return;
+    }
+    tail[tailNext] = {tick, frame};
+    tailNext = (tailNext + 1) % tail.size();
+    tailCount = tailCount < tail.size() ? tailCount + 1 : tailCount;
+}
+
+void CrashHandler::addLogLine(const std::string& line) {
+    if (logLines.empty()) {
+        return;
+    }
+    logLines[logNext] = line;
+    logNext = (logNext + 1) % logLines.size();
+}
+
+std::string CrashHandler::writeBundle(const std::string& reason) {
+    char stamp[32];
+    time_t now = time(nullptr);
+    strftime(stamp, sizeof(stamp), "%Y%m%d-%H%M%S", localtime(&now));
+    std::string folder = std::string(GetApplicationDirectory()) + "crashes/" + sanitizeFilename(std::string("crash-") + stamp) + "/";
+    std::error_code error;
+    std::filesystem::create_directories(folder, error);
+
+    if (FILE* report = fopen((folder + "report.txt").c_str(), "w")) {
+        fprintf(report, "BreakOut crashed: %s\n", reason.c_str());
+        fprintf(report, "Time: %s\n\n", stamp);
+        fprintf(report, "Backtrace:\n");
+#ifdef HAVE_BACKTRACE
+        void* frames[64];
+        int count = backtrace(frames, 64);
+        fflush(report);
+        backtrace_symbols_fd(frames, count, fileno(report));
+#else
+        fprintf(report, "  (not available on this platform)\n");
+#endif
+        fprintf(report, "\nGame state:\n%s\n", stateSummary ? stateSummary().c_str() : "  (none)");
+        fclose(report);
+    }
+
+    if (FILE* log = fopen((folder + "log.txt").c_str(), "w")) {
+        for (size_t i = 0; i < logLines.size(); i++) {
+            const std::string& line = logLines[(logNext + i) % logLines.size()];
+            if (!line.empty()) {
+                fprintf(log, "%s\n", line.c_str());
+            }
+        }
+        fclose(log);
+    }
+
+    if (tailCount > 0) {
+        InputRecorder recorder;
+        recorder.start(FramePacer::TICK_RATE);
+        size_t oldest = (tailNext + tail.size() - tailCount) % tail.size();
+        uint64_t lastTick = 0;
+        for (size_t i = 0; i < tailCount; i++) {
+            const TailFrame& entry = tail[(oldest + i) % tail.size()];
+            recorder.record(entry.tick, entry.frame);
+            lastTick = entry.tick;
+        }
+        recorder.save(folder + "input-tail.bkin", lastTick + 1);
+    }
+    return folder;
+}
@@ -0,0 +1,46 @@
+#pragma once
+#include <cstdint>
+#include <functional>
+#include <string>
+#include <vector>
+#include "InputRecord.hpp"
+
+
+// Turns a crash (segfault, abort, uncaught exception...) into a bundle in
+// crashes/<sanitized name>/ instead of the window just vanishing:
+//
+//   report.txt       what happened, a backtrace where the platform has one,
+//                    and the game state summary
+//   log.txt          the last LOG_LINES lines of the log
+//   input-tail.bkin  the last TAIL_TICKS ticks of input, as an input recording
+//
+// then tells the player in a native dialog and offers to open the folder.
+// Only one handler can be installed; it keeps the log lines as a log sink,
+// so installLogging() has to have run for log.txt to have anything in it.
+class CrashHandler {
+  public:
+    static const int LOG_LINES = 200;
+    static const int TAIL_TICKS = 10 * 60;
+
+    void install();
+    // Called at crash time, so it must not rely on anything that may be the cause.
+    void setStateSummary(std::function<std::string()> summary);
+    void recordInput(uint64_t tick, const InputFrame& frame);
+
+    // Keeps a line for log.txt; the TraceLog callback feeds this.
+    void addLogLine(const std::string& line);
+    // Writes the bundle and returns its folder; the signal handlers call this.
+    std::string writeBundle(const std::string& reason);
+
+  private:
+    struct TailFrame {
+        uint64_t tick;
+        InputFrame frame;
+    };
+    std::vector<std::string> logLines; // ring, logNext is the oldest
+    size_t logNext = 0;
+    std::vector<TailFrame> tail;       // ring, tailNext is the oldest
+    size_t tailNext = 0;
+    size_t tailCount = 0;
+    std::function<std::string()> stateSummary;
+};
@@ -0,0 +1,76 @@
+#ifdef BREAKOUT_DEV_TOOLS
+#include "raylib.h"
+#include "DevTools.hpp"
+#include <string>
+
+
+void DevTools::update(float canvasScale, int canvasWidth, int canvasHeight) {
+    center = {canvasWidth / 2.0f, canvasHeight / 2.0f};
+    if (IsKeyPressed(KEY_F5)) {
+        cheats.invincibleBall = !cheats.invincibleBall;
+        TraceLog(LOG_INFO, "DEV: Invincible ball %s", cheats.invincibleBall ? "on" : "off");
+    }
+    if (IsKeyPressed(KEY_F7)) {
+        cheats.infiniteLives = !cheats.infiniteLives;
+        TraceLog(LOG_INFO, "DEV: Infinite lives %s", cheats.infiniteLives ? "on" : "off");
+    }
+    if (IsKeyPressed(KEY_F8)) {
+        freeCamera = !freeCamera;
+        pan = {0, 0};
+        zoom = 1;
+    }
+    if (IsKeyPressed(KEY_PERIOD)) {
+        stepRequested = true;
+    }
+    if (!freeCamera) {
+        return;
+    }
+    if (IsMouseButtonDown(MOUSE_BUTTON_RIGHT) && canvasScale > 0) {
+        Vector2 delta = GetMouseDelta();
+        pan.x += delta.x / canvasScale;
+        pan.y += delta.y / canvasScale;
+    }
+    float wheel = GetMouseWheelMove();
+    if (wheel != 0) {
+        zoom *= wheel > 0 ? 1.1f : 1 / 1.1f;
+        zoom = zoom < 0.25f ? 0.25f : (zoom > 8 ? 8 : zoom);
+    }
+}
+
+void DevTools::apply(Simulation& simulation, bool replaying) {
+    simulation.cheats = replaying ? SimulationCheats() : cheats;
+}
+
+bool DevTools::takeStep() {
+    bool requested = stepRequested;
+    stepRequested = false;
+    return requested;
+}
+
+Camera2D DevTools::camera(Camera2D base) {
+    if (!freeCamera) {
+        return base;
+    }
+    base.target = center;
+    base.offset = {base.offset.x + center.x + pan.x, base.offset.y + center.y + pan.y};
+    base.zoom = zoom;
+    return base;
+}
+
+void DevTools::draw(Rectangle bounds, float scale) {
+    std::string active;
+    if (cheats.invincibleBall) {
+        active += "  invincible ball";
+    }
+    if (cheats.infiniteLives) {
+        active += "  infinite lives";
+    }
+    if (freeCamera) {
+        active += "  free camera";
+    }
+    if (!active.empty()) {
+        DrawText(("DEV:" + active).c_str(), bounds.x, bounds.y, 10 * scale, RED);
+    }
+}
+
+#endif
@@ -0,0 +1,52 @@
+#pragma once
+#include "raylib.h"
+#include "Simulation.hpp"
+
+
+// Cheats for testing content, only in BREAKOUT_DEV_TOOLS builds:
+//
+//   F5  invincible ball (the bottom edge bounces it)
+//   F7  infinite lives
+//   F8  free camera: drag with the right mouse button to pan, wheel to zoom;
+//       F8 again snaps back
+//   .   while paused, run exactly one tick
+//
+// A run that had a cheat on for even one tick is marked GameState::cheated
+// and won't be written as a replay (or, later, show up on a leaderboard).
+// Replays always play back without them. Other builds get an empty shell.
+#ifndef BREAKOUT_DEV_TOOLS
+
+class DevTools {
+  public:
+    void update(float, int, int) {}
+    void apply(Simulation&, bool) {}
+    bool takeStep() { return false; }
+    Camera2D camera(Camera2D base) { return base; }
+    void draw(Rectangle, float) {}
+};
+
+#else
+
+class DevTools {
+  public:
+    // Reads the keys; canvasScale turns mouse movement into canvas pixels.
+    void update(float canvasScale, int canvasWidth, int canvasHeight);
+    // Hands the toggles to the simulation; replaying turns them all off.
+    void apply(Simulation& simulation, bool replaying);
+    // Whether a single paused tick was asked for since the last call.
+    bool takeStep();
+    // The world camera: base (screen shake) as is, or moved by the free camera.
+    Camera2D camera(Camera2D base);
+    // Names the cheats that are on, so a screenshot can't pass for real play.
+    void draw(Rectangle bounds, float scale);
+
+  private:
+    SimulationCheats cheats;
+    bool freeCamera = false;
+    bool stepRequested = false;
+    Vector2 pan = {0, 0};
+    float zoom = 1;
+    Vector2 center = {0, 0};
+};
+
+#endif
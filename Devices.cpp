@@ -0,0 +1,218 @@
+#include "raylib.h"
+#include "Devices.hpp"
+#include "Localization.hpp"
+#include "Ui.hpp"
+#include <string>
+
+
+bool InputDevice::operator==(const InputDevice& other) const {
+    return type == other.type && (type != DeviceType::Gamepad || gamepad == other.gamepad);
+}
+
+// Closing with a player still unplugged gives up on them, otherwise the
+// screen would just come straight back.
+void GamepadWatcher::subscribe(Handler handler) {
+    handlers.push_back(handler);
+}
+
+void GamepadWatcher::update() {
+    for (int i = 0; i < MAX_GAMEPADS; i++) {
+        bool available = IsGamepadAvailable(i);
+        if (available == connected[i]) {
+            continue;
+        }
+        connected[i] = available;
+        if (available) {
+            names[i] = GetGamepadName(i);
+        }
+        TraceLog(LOG_INFO, "DEVICES: Controller %d (%s) %s", i, names[i].c_str(), available ? "connected" : "disconnected");
+        for (Handler& handler : handlers) {
+            handler(available ? GamepadEvent::Connected : GamepadEvent::Disconnected, i);
+        }
+    }
+}
+
+const std::string& GamepadWatcher::getName(int gamepad) {
+    return names[gamepad];
+}
+
+void DeviceAssignment::toggle() {
+    isOpen = !isOpen;
+    if (isOpen) {
+        return;
+    }
+    for (PlayerSlot& slot : slots) {
+        if (slot.disconnected) {
+            slot = PlayerSlot();
+        }
+    }
+}
+
+void DeviceAssignment::reset() {
+    for (PlayerSlot& slot : slots) {
+        slot = PlayerSlot();
+    }
+}
+
+bool DeviceAssignment::isAssigned(const InputDevice& device) {
+    for (PlayerSlot& slot : slots) {
+        if (slot.device == device) {
+            return true;
+        }
+    }
+    return false;
+}
+
+// A held slot is filled before a new one is opened, so whoever picks up a
+// spare controller after an unplug carries on as that player.
+void DeviceAssignment::join(const InputDevice& device) {
+    if (isAssigned(device)) {
+        return;
+    }
+    for (PlayerSlot& slot : slots) {
+        if (slot.disconnected) {
+            TraceLog(LOG_INFO, "DEVICES: %s took over a disconnected player", device.type == DeviceType::Keyboard ? "Keyboard" : device.name.c_str());
+            slot = {device, false};
+            return;
+        }
+    }
+    for (PlayerSlot& slot : slots) {
+        if (slot.device.type == DeviceType::None) {
+            slot = {device, false};
+            return;
+        }
+    }
+}
+
+void DeviceAssignment::leave(const InputDevice& device) {
+    for (PlayerSlot& slot : slots) {
+        if (slot.device == device) {
+            slot = PlayerSlot();
+        }
+    }
+}
+
+static bool joinKey(int key) {
+    // function keys open menus and take screenshots; they never mean "join"
+    return key != KEY_NULL && key != KEY_ESCAPE && !(key >= KEY_F1 && key <= KEY_F12);
+}
+
+void DeviceAssignment::onGamepadEvent(GamepadEvent event, int gamepad, const std::string& name) {
+    for (PlayerSlot& slot : slots) {
+        if (slot.device.type != DeviceType::Gamepad) {
+            continue;
+        }
+        if (event == GamepadEvent::Disconnected && slot.device.gamepad == gamepad && !slot.disconnected) {
+            slot.disconnected = true;
+            return;
+        }
+        // the controller that left coming back, on whichever index it got
+        if (event == GamepadEvent::Connected && slot.disconnected && (slot.device.name == name || slot.device.gamepad == gamepad)) {
+            slot.device.gamepad = gamepad;
+            slot.disconnected = false;
+            return;
+        }
+    }
+}
+
+void DeviceAssignment::update() {
+    if (!isOpen) {
+        return;
+    }
+
+    InputDevice keyboard = {DeviceType::Keyboard, -1, ""};
+    for (int key = GetKeyPressed(); key != KEY_NULL; key = GetKeyPressed()) {
+        if (key == KEY_BACKSPACE) {
+            leave(keyboard);
+        } else if (joinKey(key)) {
+            join(keyboard);
+        }
+    }
+    for (int pad = 0; pad < 4; pad++) {
+        if (!IsGamepadAvailable(pad)) {
+            continue;
+        }
+        InputDevice device = {DeviceType::Gamepad, pad, GetGamepadName(pad)};
+        for (int button = GAMEPAD_BUTTON_LEFT_FACE_UP; button <= GAMEPAD_BUTTON_RIGHT_THUMB; button++) {
+            if (!IsGamepadButtonPressed(pad, button)) {
+                continue;
+            }
+            if (button == GAMEPAD_BUTTON_RIGHT_FACE_RIGHT) {
+                leave(device);
+            } else {
+                join(device);
+            }
+        }
+    }
+}
+
+bool DeviceAssignment::hasDisconnected() {
+    for (PlayerSlot& slot : slots) {
+        if (slot.disconnected) {
+            return true;
+        }
+    }
+    return false;
+}
+
+int DeviceAssignment::getJoinedCount() {
+    int count = 0;
+    for (PlayerSlot& slot : slots) {
+        count += slot.device.type != DeviceType::None;
+    }
+    return count;
+}
+
+const PlayerSlot& DeviceAssignment::getSlot(int index) {
+    return slots[index];
+}
+
+InputBindings DeviceAssignment::bindingsFor(int slot, const InputBindings& base) {
+    InputBindings bindings = base;
+    if (getJoinedCount() == 0 && slot == 0) {
+        return bindings;
+    }
+    const InputDevice& device = slots[slot].device;
+    bindings.keyboard = device.type == DeviceType::Keyboard;
+    bindings.gamepad = device.type == DeviceType::Gamepad && !slots[slot].disconnected ? device.gamepad : InputBindings::NO_GAMEPAD;
+    return bindings;
+}
+
+void DeviceAssignment::draw(TextRenderer& text, Rectangle bounds, float scale) {
+    if (!isOpen) {
+        return;
+    }
+    float margin = 20 * scale;
+    float cardWidth = 200 * scale;
+    float cardHeight = 120 * scale;
+    Vector2 size = {margin + MAX_PLAYERS * (cardWidth + margin), 110 * scale + cardHeight};
+    Vector2 corner = anchorPoint(Anchor::Center, bounds, size);
+    int fontSize = 20 * scale;
+
+    DrawRectangle(0, 0, GetScreenWidth(), GetScreenHeight(), Fade(BLACK, 0.6f));
+    DrawRectangleRec({corner.x, corner.y, size.x, size.y}, Fade(DARKGRAY, 0.95f));
+    text.draw(tr("Players"), {corner.x + margin, corner.y + 15 * scale}, 30 * scale, RAYWHITE);
+
+    for (int i = 0; i < MAX_PLAYERS; i++) {
+        const PlayerSlot& slot = slots[i];
+        Rectangle card = {corner.x + margin + i * (cardWidth + margin), corner.y + 60 * scale, cardWidth, cardHeight};
+        Color color = slot.disconnected ? RED : (slot.device.type == DeviceType::None ? GRAY : YELLOW);
+        DrawRectangleLinesEx(card, 2 * scale, color);
+        text.draw(TextFormat(tr("Player %d").c_str(), i + 1), {card.x + 10 * scale, card.y + 10 * scale}, fontSize, color);
+
+        std::string device = tr("Press a button");
+        if (slot.disconnected) {
+            device = tr("Reconnect or press a button");
+        } else if (slot.device.type == DeviceType::Keyboard) {
+            device = tr("Keyboard");
+        } else if (slot.device.type == DeviceType::Gamepad) {
+            device = slot.device.name;
+        }
+        // controller names can be long; let them run to the card edge and no further
+        BeginScissorMode(card.x, card.y, card.width, card.height);
+        text.draw(device, {card.x + 10 * scale, card.y + 50 * scale}, 16 * scale, LIGHTGRAY);
+        EndScissorMode();
+    }
+
+    text.draw(tr("Backspace / Pad B: leave   F4: done"), {corner.x + margin, corner.y + size.y - 30 * scale}, 16 * scale, LIGHTGRAY);
+}
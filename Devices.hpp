@@ -0,0 +1,84 @@
+#pragma once
+#include "raylib.h"
+#include <functional>
+#include <string>
+#include <vector>
+#include "Bindings.hpp"
+#include "Text.hpp"
+
+
+enum class DeviceType {
+    None,
+    Keyboard, // keyboard and mouse; raylib sees every keyboard as one
+    Gamepad,
+};
+
+struct InputDevice {
+    DeviceType type = DeviceType::None;
+    int gamepad = -1;
+    std::string name; // controllers can come back on another index; the name finds them
+
+    bool operator==(const InputDevice& other) const;
+};
+
+struct PlayerSlot {
+    InputDevice device;
+    bool disconnected = false; // joined, but the controller went away
+};
+
+enum class GamepadEvent {
+    Connected,
+    Disconnected,
+};
+
+// Edge-triggered controller plug/unplug events, like WindowLifecycle does for
+// the window, so nobody has to restart the game after a cable pull.
+class GamepadWatcher {
+  public:
+    static const int MAX_GAMEPADS = 4;
+    using Handler = std::function<void(GamepadEvent, int gamepad)>;
+
+    void subscribe(Handler handler);
+    // Call at the start of every frame.
+    void update();
+    // The name the controller had while connected, so a disconnect can still say what left.
+    const std::string& getName(int gamepad);
+
+  private:
+    std::vector<Handler> handlers;
+    bool connected[MAX_GAMEPADS] = {};
+    std::string names[MAX_GAMEPADS];
+};
+
+// Local multiplayer seats. The join screen hands out slots in the order
+// devices press a button ("press a button to join"). When a joined controller
+// is unplugged its slot is held: plugging it back in restores it, and any
+// free device pressing a button while the screen is up takes it over; the
+// same controller showing up on a different index is recognised by name. Until
+// anyone joins, player one reads every device like single player always has.
+class DeviceAssignment {
+  public:
+    static const int MAX_PLAYERS = 4;
+    bool isOpen = false;
+
+    void toggle();
+    // Takes joins; only does anything while open.
+    void update();
+    void onGamepadEvent(GamepadEvent event, int gamepad, const std::string& name);
+    bool hasDisconnected();
+    int getJoinedCount();
+    const PlayerSlot& getSlot(int index);
+    void reset();
+
+    // The profile's bindings narrowed down to the slot's device.
+    InputBindings bindingsFor(int slot, const InputBindings& base);
+
+    void draw(TextRenderer& text, Rectangle bounds, float scale);
+
+  private:
+    PlayerSlot slots[MAX_PLAYERS];
+
+    bool isAssigned(const InputDevice& device);
+    void join(const InputDevice& device);
+    void leave(const InputDevice& device);
+};
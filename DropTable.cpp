@@ -0,0 +1,86 @@
+#include "raylib.h"
+#include "DropTable.hpp"
+#include "Simulation.hpp"
+#include <algorithm>
+
+
+DropTable DropTable::standard() {
+    DropTable table;
+    for (const PowerUp& powerUp : allPowerUps()) {
+        table.entries.push_back({powerUp.type, powerUp.rarity});
+    }
+    return table;
+}
+
+std::vector<std::string> DropTable::apply(const XmlNode& node) {
+    std::vector<std::string> problems;
+    chance = std::clamp(node.attributeFloat("chance", chance), 0.0f, 1.0f);
+    if (node.hasAttribute("repeats")) {
+        repeats = node.attribute("repeats") == "true";
+    }
+    for (const XmlNode* drop : node.childrenNamed("drop")) {
+        std::string id = drop->attribute("power");
+        const PowerUp* powerUp = findPowerUp(id);
+        if (!powerUp) {
+            problems.push_back(TextFormat("%d: unknown power-up '%s'", drop->line, id.c_str()));
+            continue;
+        }
+        auto found = std::find_if(entries.begin(), entries.end(), [&](const DropEntry& entry) { return entry.type == powerUp->type; });
+        if (found == entries.end()) {
+            entries.push_back({powerUp->type, powerUp->rarity});
+            found = entries.end() - 1;
+        }
+        if (drop->hasAttribute("rarity") && !parsePowerUpRarity(drop->attribute("rarity"), found->rarity)) {
+            problems.push_back(TextFormat("%d: unknown rarity '%s'", drop->line, drop->attribute("rarity").c_str()));
+        }
+        found->weight = std::max(0.0f, drop->attributeFloat("weight", found->weight));
+    }
+    return problems;
+}
+
+float DropTable::weightOf(const DropEntry& entry) const {
+    return powerUpRarityWeight(entry.rarity) * entry.weight;
+}
+
+bool DropTable::pick(float roll, int previous, const std::function<bool(PowerUpType)>& allowed, PowerUpType& picked) const {
+    auto candidate = [&](const DropEntry& entry) {
+        return weightOf(entry) > 0 && (repeats || (int) entry.type != previous) && (!allowed || allowed(entry.type));
+    };
+    float total = 0;
+    for (const DropEntry& entry : entries) {
+        total += candidate(entry) ? weightOf(entry) : 0;
+    }
+    if (total <= 0) {
+        return false;
+    }
+    float target = roll * total;
+    for (const DropEntry& entry : entries) {
+        if (!candidate(entry)) {
+            continue;
+        }
+        picked = entry.type;
+        target -= weightOf(entry);
+        if (target < 0) {
+            break;
+        }
+    }
+    // rounding leaves the last candidate picked
+    return true;
+}
+
+bool DropTable::roll(GameState& state, Vector2 position, Pickup& drop) const {
+    if (state.random.unit() >= chance) {
+        return false;
+    }
+    PowerUpType type;
+    auto allowed = [&](PowerUpType candidate) {
+        const PowerUp& powerUp = powerUpInfo(candidate);
+        return !powerUp.canDrop || powerUp.canDrop(state);
+    };
+    if (!pick(state.random.unit(), state.lastDrop, allowed, type)) {
+        return false;
+    }
+    state.lastDrop = (int) type;
+    drop = Pickup::powerUpAt(type, position);
+    return true;
+}
@@ -0,0 +1,48 @@
+#pragma once
+#include "raylib.h"
+#include <functional>
+#include <string>
+#include <vector>
+#include "Pickup.hpp"
+#include "PowerUp.hpp"
+#include "Xml.hpp"
+
+struct GameState;
+
+
+struct DropEntry {
+    PowerUpType type;
+    PowerUpRarity rarity;
+    float weight = 1; // on top of the rarity's; 0 never drops
+};
+
+// Which power-up a broken brick drops, if any. Every power-up starts at its
+// rarity from PowerUp.hpp; a level can change any of that with a <drops>
+// element, and only levels can, so the level file still holds everything a
+// replay needs to roll the same drops.
+class DropTable {
+  public:
+    float chance = 0.08f; // that a broken brick drops anything at all
+    bool repeats = false; // whether the same power-up may drop twice in a row
+    std::vector<DropEntry> entries;
+
+    // Every power-up at its own rarity.
+    static DropTable standard();
+
+    //   <drops chance="0.12" repeats="true">
+    //     <drop power="laser" rarity="rare" weight="2"/>
+    //     <drop power="shrink" weight="0"/>
+    //   </drops>
+    // Over what the table has already: attributes left out stay as they
+    // were. Returns what it had to skip, for the level loader to warn about.
+    std::vector<std::string> apply(const XmlNode& node);
+
+    // An entry's share of the picks: its rarity's weight times its own.
+    float weightOf(const DropEntry& entry) const;
+    // Picks by roll, in [0, 1), among the entries allowed says can drop,
+    // leaving out previous (a PowerUpType, or -1) unless repeats is set.
+    // False when nothing is left to pick.
+    bool pick(float roll, int previous, const std::function<bool(PowerUpType)>& allowed, PowerUpType& picked) const;
+    // Rolls the state's generator for a drop from a brick broken at position.
+    bool roll(GameState& state, Vector2 position, Pickup& drop) const;
+};
@@ -0,0 +1,95 @@
+#include "EffectManager.hpp"
+#include "FramePacer.hpp"
+#include <algorithm>
+
+
+static const PowerUpType OPPOSITES[][2] = {
+    {PowerUpType::Grow, PowerUpType::Shrink},
+    {PowerUpType::SlowMotion, PowerUpType::SpeedUp},
+};
+
+bool EffectManager::opposite(PowerUpType type, PowerUpType& other) {
+    for (const auto& pair : OPPOSITES) {
+        if (pair[0] == type || pair[1] == type) {
+            other = pair[0] == type ? pair[1] : pair[0];
+            return true;
+        }
+    }
+    return false;
+}
+
+bool EffectManager::start(const PowerUp& powerUp) {
+    PowerUpType other;
+    if (opposite(powerUp.type, other) && has(other)) {
+        auto running = std::find_if(effects.begin(), effects.end(), [&](const ActiveEffect& effect) { return effect.type == other; });
+        if (--running->stacks == 0) {
+            effects.erase(running);
+        }
+        return false;
+    }
+    int full = powerUp.seconds * FramePacer::TICK_RATE;
+    auto running = std::find_if(effects.begin(), effects.end(), [&](const ActiveEffect& effect) { return effect.type == powerUp.type; });
+    if (running == effects.end()) {
+        effects.push_back({powerUp.type, full, full, 1});
+        return true;
+    }
+    switch (powerUp.stacking) {
+    case PowerUpStacking::Refresh:
+        running->ticks = full;
+        break;
+    case PowerUpStacking::Stack:
+        running->stacks = std::min(running->stacks + 1, MAX_STACKS);
+        // and adds the time, like Extend
+        [[fallthrough]];
+    case PowerUpStacking::Extend:
+        running->ticks = std::min(running->ticks + full, 2 * full);
+        break;
+    }
+    return true;
+}
+
+void EffectManager::stop(PowerUpType type) {
+    effects.erase(std::remove_if(effects.begin(), effects.end(), [&](const ActiveEffect& effect) { return effect.type == type; }), effects.end());
+}
+
+void EffectManager::clear() {
+    effects.clear();
+}
+
+void EffectManager::tick(std::vector<PowerUpType>& expired) {
+    for (size_t i = 0; i < effects.size();) {
+        if (--effects[i].ticks <= 0) {
+            expired.push_back(effects[i].type);
+            effects.erase(effects.begin() + i);
+        } else {
+            i++;
+        }
+    }
+}
+
+const ActiveEffect* EffectManager::find(PowerUpType type) const {
+    for (const ActiveEffect& effect : effects) {
+        if (effect.type == type) {
+            return &effect;
+        }
+    }
+    return nullptr;
+}
+
+bool EffectManager::has(PowerUpType type) const {
+    return find(type) != nullptr;
+}
+
+int EffectManager::ticksLeft(PowerUpType type) const {
+    const ActiveEffect* effect = find(type);
+    return effect ? effect->ticks : 0;
+}
+
+int EffectManager::stacks(PowerUpType type) const {
+    const ActiveEffect* effect = find(type);
+    return effect ? effect->stacks : 0;
+}
+
+const std::vector<ActiveEffect>& EffectManager::active() const {
+    return effects;
+}
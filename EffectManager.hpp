@@ -0,0 +1,47 @@
+#pragma once
+#include <vector>
+#include "PowerUp.hpp"
+
+
+// A timed power-up or debuff in play.
+struct ActiveEffect {
+    PowerUpType type;
+    int ticks;     // left
+    int fullTicks; // what one catch lasts, for the HUD's ring
+    int stacks;    // catches on top of each other (PowerUpStacking::Stack)
+};
+
+// Every timed effect in play, helpful and harmful alike, and the rules for
+// catching one while others run. The timers all live here so they count
+// down, stack and cancel the same way; effects keep the order they were
+// caught in, which is the order they run out in on the same tick and the
+// order the HUD shows them. Plain values, so it snapshots with the rest of
+// GameState.
+class EffectManager {
+  public:
+    // how high PowerUpStacking::Stack goes
+    static constexpr int MAX_STACKS = 2;
+
+    // Grow and shrink, slow motion and speed-up: catching one of a pair
+    // while the other runs takes a stack off the other instead of starting.
+    static bool opposite(PowerUpType type, PowerUpType& other);
+
+    // A catch of a timed power-up, by its stacking rule. False when it only
+    // cancelled its opposite.
+    bool start(const PowerUp& powerUp);
+    void stop(PowerUpType type);
+    void clear();
+    // Counts everything down a tick; what ran out is added to expired.
+    void tick(std::vector<PowerUpType>& expired);
+
+    bool has(PowerUpType type) const;
+    int ticksLeft(PowerUpType type) const; // 0 when not running
+    int stacks(PowerUpType type) const;    // 0 when not running
+    // In the order they were caught.
+    const std::vector<ActiveEffect>& active() const;
+
+  private:
+    std::vector<ActiveEffect> effects;
+
+    const ActiveEffect* find(PowerUpType type) const;
+};
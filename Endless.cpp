@@ -0,0 +1,46 @@
+#include "raylib.h"
+#include "Endless.hpp"
+#include <algorithm>
+#include <cmath>
+
+
+// how much of a row is bricks at first, and at most
+static const float FIRST_FILL = 0.6f;
+static const float MOST_FILL = 0.9f;
+// rows for another hit point on the toughest bricks
+static const int ROWS_PER_HIT_POINT = 12;
+
+float EndlessRules::shiftSeconds(int rowsAdded) {
+    return fmaxf(FIRST_SHIFT_SECONDS - rowsAdded * SPEEDUP_SECONDS, LAST_SHIFT_SECONDS);
+}
+
+std::vector<Brick> generateRow(int rowsAdded, Random& random) {
+    std::vector<Brick> row;
+    float fill = fminf(FIRST_FILL + rowsAdded * 0.01f, MOST_FILL);
+    int toughest = std::min(1 + rowsAdded / ROWS_PER_HIT_POINT, Brick::MAX_HIT_POINTS);
+    for (int column = 0; column < EndlessRules::COLUMNS; column++) {
+        if (random.unit() >= fill) {
+            continue;
+        }
+        float roll = random.unit();
+        BrickMaterial material = roll < 0.15f ? BrickMaterial::Glass : roll < 0.3f ? BrickMaterial::Stone : BrickMaterial::Standard;
+        int hitPoints = random.range(1, toughest);
+        row.push_back({EndlessRules::COLUMN_SPACING * (column + 1), EndlessRules::ROW_TOP, EndlessRules::BRICK_WIDTH,
+            EndlessRules::BRICK_HEIGHT, material, hitPoints});
+    }
+    return row;
+}
+
+void shiftField(std::vector<Brick>& bricks, float step) {
+    for (Brick& brick : bricks) {
+        brick.shift({0, step});
+    }
+}
+
+float fieldBottom(const std::vector<Brick>& bricks) {
+    float bottom = 0;
+    for (const Brick& brick : bricks) {
+        bottom = fmaxf(bottom, brick.rectangle.y + brick.rectangle.height);
+    }
+    return bottom;
+}
@@ -0,0 +1,32 @@
+#pragma once
+#include <vector>
+#include "Brick.hpp"
+#include "Random.hpp"
+
+
+// Endless mode (GameMode::Endless): every so often the whole field moves a
+// row down and a new row comes in at the top, sooner the more rows have
+// come; the run is over when a brick reaches the paddle's line.
+struct EndlessRules {
+    static constexpr float FIRST_SHIFT_SECONDS = 12;
+    static constexpr float LAST_SHIFT_SECONDS = 5; // as fast as it gets
+    static constexpr float SPEEDUP_SECONDS = 0.15f; // shaved off per row
+    static constexpr int ROW_STEP = 15;   // pixels a shift moves the field
+    static constexpr int ROW_TOP = 50;    // where a new row comes in
+    static constexpr int COLUMNS = 20;
+    static constexpr int COLUMN_SPACING = 50;
+    static constexpr int BRICK_WIDTH = 48;
+    static constexpr int BRICK_HEIGHT = 10;
+
+    // The wait until the next shift, after rowsAdded rows.
+    static float shiftSeconds(int rowsAdded);
+};
+
+// The next row, the rowsAdded + 1th, from the simulation's Random: gaps,
+// glass and stone here and there, and tougher bricks as it goes on. Never
+// metal, which would pile up with no way to clear it.
+std::vector<Brick> generateRow(int rowsAdded, Random& random);
+// Moves every brick down by the step, moving bricks' paths with them.
+void shiftField(std::vector<Brick>& bricks, float step);
+// The lowest brick's bottom edge, 0 with none.
+float fieldBottom(const std::vector<Brick>& bricks);
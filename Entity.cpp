@@ -39,6 +39,10 @@ Rectangle Entity::getRectangle() {
     return rectangle;
 }
 
+Vector2 Entity::getCenter() {
+    return {rectangle.x + rectangle.width / 2, rectangle.y + rectangle.height / 2};
+}
+
 Rectangle* Entity::getRectanglePtr() {
     return &rectangle;
 }
@@ -16,6 +16,7 @@ class Entity {
     Vector2 getPosition();
     Vector2 getVelocity();
     Rectangle getRectangle();
+    Vector2 getCenter(); // center of the drawn/colliding rectangle
     Rectangle* getRectanglePtr();
     void setColor(Color);
     bool checkCollision(Rectangle);
@@ -0,0 +1,69 @@
+#include "raylib.h"
+#include "Events.hpp"
+
+
+const char* eventTypeName(EventType type) {
+    switch (type) {
+    case EventType::BrickHit: return "brick_hit";
+    case EventType::BrickDestroyed: return "brick_destroyed";
+    case EventType::WallBounce: return "wall_bounce";
+    case EventType::PaddleBounce: return "paddle_bounce";
+    case EventType::BallLost: return "ball_lost";
+    case EventType::ExtraBallLost: return "extra_ball_lost";
+    case EventType::ScoreAwarded: return "score_awarded";
+    case EventType::ComboIncreased: return "combo_increased";
+    case EventType::ComboDecayed: return "combo_decayed";
+    case EventType::PerfectCatch: return "perfect_catch";
+    case EventType::PowerUpSpawned: return "power_up_spawned";
+    case EventType::PowerUpCollected: return "power_up_collected";
+    case EventType::CoinCollected: return "coin_collected";
+    case EventType::BricksSwitched: return "bricks_switched";
+    case EventType::BossHit: return "boss_hit";
+    case EventType::BossDefeated: return "boss_defeated";
+    case EventType::BossAttacked: return "boss_attacked";
+    case EventType::PaddleDamaged: return "paddle_damaged";
+    case EventType::FieldShifted: return "field_shifted";
+    case EventType::FieldOverran: return "field_overran";
+    case EventType::TimeCollected: return "time_collected";
+    case EventType::TimeUp: return "time_up";
+    case EventType::LaunchesSpent: return "launches_spent";
+    case EventType::DifficultyRaised: return "difficulty_raised";
+    case EventType::FieldRefilled: return "field_refilled";
+    case EventType::GarbageReceived: return "garbage_received";
+    case EventType::LaserFired: return "laser_fired";
+    case EventType::BallCaught: return "ball_caught";
+    case EventType::BarrierHit: return "barrier_hit";
+    case EventType::MenuOpened: return "menu_opened";
+    case EventType::MenuClosed: return "menu_closed";
+    case EventType::SettingChanged: return "setting_changed";
+    }
+    return "unknown";
+}
+
+void EventBus::subscribe(Handler handler) {
+    handlers.push_back(handler);
+}
+
+void EventBus::publish(const GameEvent& event) {
+    queue.push_back(event);
+    queue.back().time = time;
+}
+
+void EventBus::publish(EventType type, Vector2 position, Vector2 velocity, int value) {
+    queue.push_back({type, position, velocity, value, time});
+}
+
+void EventBus::setTime(double now) {
+    time = now;
+}
+
+void EventBus::dispatch() {
+    // handlers may publish follow-up events, which go out on the next dispatch
+    std::vector<GameEvent> pending;
+    pending.swap(queue);
+    for (const GameEvent& event : pending) {
+        for (Handler& handler : handlers) {
+            handler(event);
+        }
+    }
+}
@@ -0,0 +1,71 @@
+#pragma once
+#include "raylib.h"
+#include <functional>
+#include <vector>
+
+
+enum class EventType {
+    BrickHit,       // one that takes more hits; value = hits it has left, -1 for metal
+    BrickDestroyed, // value = combo including this brick
+    WallBounce,
+    PaddleBounce,   // value = where on the paddle, -100 (left end) to 100 (right end)
+    BallLost,       // the last ball in play, which costs a life
+    ExtraBallLost,  // one of several; value = balls still in play
+    ScoreAwarded,   // value = points
+    ComboIncreased, // value = bricks broken since the ball last touched the paddle
+    ComboDecayed,   // too long without a break; value = bricks the combo is down to
+    PerfectCatch,   // ball caught on the middle of the paddle
+    PowerUpSpawned, // value = PowerUpType
+    PowerUpCollected, // value = PowerUpType
+    CoinCollected,  // value = coins caught this run
+    BricksSwitched, // a switch brick was hit; value = bricks of its group now solid
+    BossHit,        // value = health left, -1 when the ball hit armor
+    BossDefeated,   // value = the health it started with
+    BossAttacked,   // value = BossAttack
+    PaddleDamaged,  // debris landed on it
+    FieldShifted,   // endless mode; value = rows added so far
+    FieldOverran,   // endless and versus: the bricks reached the paddle and the run is over
+    TimeCollected,  // time attack; value = seconds added to the clock
+    TimeUp,         // time attack: the clock ran out and the run is over
+    DifficultyRaised, // survival; value = the stage it's at now
+    FieldRefilled,  // survival: the level was cleared and is back; value = the stage
+    GarbageReceived, // versus: the rival sent a junk row; value = its bricks
+    LaunchesSpent,  // puzzle: the last serve's ball was lost and the run is over
+    LaserFired,
+    BallCaught,     // stuck to the paddle by the catch power-up
+    BarrierHit,     // the shield sent a ball back and is gone
+    MenuOpened,
+    MenuClosed,
+    SettingChanged,
+};
+
+// snake_case, for logs and machine-readable output
+const char* eventTypeName(EventType type);
+
+struct GameEvent {
+    EventType type;
+    Vector2 position;
+    Vector2 velocity;
+    int value;
+    double time; // simulation time of the tick that raised it, GetTime()-based
+};
+
+// Gameplay publishes events while it updates; subscribers (effects, audio,
+// scoring...) receive them in publish order when dispatch() is called once per
+// frame, so no system reacts in the middle of the collision pass.
+class EventBus {
+  public:
+    using Handler = std::function<void(const GameEvent&)>;
+
+    void subscribe(Handler handler);
+    void publish(const GameEvent& event);
+    void publish(EventType type, Vector2 position, Vector2 velocity = {0, 0}, int value = 0);
+    void dispatch();
+    // Stamped onto everything published until the next call.
+    void setTime(double time);
+
+  private:
+    std::vector<Handler> handlers;
+    std::vector<GameEvent> queue;
+    double time = -1;
+};
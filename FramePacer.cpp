@@ -0,0 +1,105 @@
+#include "raylib.h"
+#include "FramePacer.hpp"
+#include <cmath>
+
+
+static const float MAX_FRAME_TIME = 0.25f;
+
+void FramePacer::apply(const Settings& settings) {
+    vsync = (VsyncMode) settings.vsyncMode;
+    fpsCap = settings.fpsCap;
+    setVsync(vsync != VsyncMode::Off);
+    missedFrames = 0;
+    // with vsync on the display paces us and the cap only guards against
+    // displays faster than it
+    SetTargetFPS(fpsCap);
+}
+
+void FramePacer::setVsync(bool enabled) {
+    if (enabled == vsyncActive) {
+        return;
+    }
+    if (enabled) {
+        SetWindowState(FLAG_VSYNC_HINT);
+    } else {
+        ClearWindowState(FLAG_VSYNC_HINT);
+    }
+    vsyncActive = enabled;
+}
+
+// Drops vsync after a run of late frames and brings it back once frames are
+// comfortably within the refresh interval again.
+void FramePacer::updateAdaptiveVsync(float refreshInterval) {
+    if (vsync != VsyncMode::Adaptive) {
+        return;
+    }
+    if (vsyncActive && frameTime > refreshInterval * 1.5f) {
+        if (++missedFrames >= 5) {
+            setVsync(false);
+            missedFrames = 0;
+        }
+    } else if (!vsyncActive && smoothed < refreshInterval * 0.9f) {
+        setVsync(true);
+        missedFrames = 0;
+    } else {
+        missedFrames = 0;
+    }
+}
+
+void FramePacer::beginFrame() {
+    frameTime = fminf(GetFrameTime(), MAX_FRAME_TIME);
+    history[historyHead] = frameTime;
+    historyHead = (historyHead + 1) % HISTORY;
+
+    int refreshRate = GetMonitorRefreshRate(GetCurrentMonitor());
+    float refreshInterval = refreshRate > 0 ? 1.0f / refreshRate : TICK_SECONDS;
+    updateAdaptiveVsync(refreshInterval);
+
+    smoothed += (frameTime - smoothed) * 0.1f;
+
+    // Timer jitter around the refresh interval would otherwise make us run 0
+    // or 2 ticks on alternating frames; snap to it when we're close.
+    float delta = frameTime;
+    if (vsyncActive && fabsf(delta - refreshInterval) < refreshInterval * 0.05f) {
+        delta = refreshInterval;
+    }
+    accumulator += delta;
+}
+
+int FramePacer::consumeTicks() {
+    int ticks = 0;
+    while (accumulator >= TICK_SECONDS) {
+        accumulator -= TICK_SECONDS;
+        ticks++;
+    }
+    return ticks;
+}
+
+void FramePacer::resetAccumulator() {
+    accumulator = 0;
+}
+
+float FramePacer::getFrameTime() {
+    return frameTime;
+}
+
+float FramePacer::getSmoothedFrameTime() {
+    return smoothed;
+}
+
+void FramePacer::drawGraph(int x, int y, int width, int height) {
+    // the graph's full height is two ticks worth of frame time
+    float scale = height / (TICK_SECONDS * 2);
+    float barWidth = (float) width / HISTORY;
+
+    DrawRectangle(x, y, width, height, Fade(BLACK, 0.6f));
+    for (int i = 0; i < HISTORY; i++) {
+        float sample = history[(historyHead + i) % HISTORY];
+        float barHeight = fminf(sample * scale, (float) height);
+        Color color = sample > TICK_SECONDS * 1.5f ? RED : (sample > TICK_SECONDS * 1.05f ? YELLOW : GREEN);
+        DrawRectangleV({x + i * barWidth, y + height - barHeight}, {barWidth, barHeight}, color);
+    }
+    int tickLine = y + height - (int) (TICK_SECONDS * scale);
+    DrawLine(x, tickLine, x + width, tickLine, Fade(RAYWHITE, 0.5f));
+    DrawText(TextFormat("%.2f ms (avg %.2f ms)", frameTime * 1000, smoothed * 1000), x + 4, y + 4, 10, RAYWHITE);
+}
@@ -0,0 +1,46 @@
+#pragma once
+#include "raylib.h"
+#include "Settings.hpp"
+
+
+enum class VsyncMode {
+    Off,
+    On,
+    Adaptive, // vsync while we keep up with the display, tear instead of stalling when we don't
+};
+
+// The simulation always advances in fixed ticks; rendering runs at whatever
+// rate vsync / the FPS cap allow. FramePacer measures frames, smooths the
+// delta and tells the loop how many ticks to run.
+class FramePacer {
+  public:
+    static constexpr int TICK_RATE = 60;
+    static constexpr float TICK_SECONDS = 1.0f / TICK_RATE;
+
+    void apply(const Settings& settings);
+    void beginFrame();
+    // Ticks the simulation should advance this frame.
+    int consumeTicks();
+    // Drops banked time, e.g. while paused, so resuming doesn't fast-forward.
+    void resetAccumulator();
+
+    float getFrameTime();
+    float getSmoothedFrameTime();
+    void drawGraph(int x, int y, int width, int height);
+
+  private:
+    static constexpr int HISTORY = 180;
+
+    VsyncMode vsync = VsyncMode::On;
+    bool vsyncActive = false;
+    int missedFrames = 0;
+    int fpsCap = 0;
+    float frameTime = 0;
+    float smoothed = TICK_SECONDS;
+    double accumulator = 0;
+    float history[HISTORY] = {};
+    int historyHead = 0;
+
+    void setVsync(bool enabled);
+    void updateAdaptiveVsync(float refreshInterval);
+};
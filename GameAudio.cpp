@@ -0,0 +1,175 @@
+#ifndef BREAKOUT_NO_AUDIO
+#include "raylib.h"
+#include "GameAudio.hpp"
+#include "PowerUp.hpp"
+
+
+void GameAudio::init(const Settings& settings) {
+    engine.init();
+    apply(settings);
+    engine.loadManifest(soundManifestPath());
+}
+
+void GameAudio::apply(const Settings& settings) {
+    engine.apply(settings);
+    backgroundAudio = settings.backgroundAudio;
+}
+
+void GameAudio::onEvent(const GameEvent& event) {
+    switch (event.type) {
+    case EventType::BrickDestroyed:
+        engine.playCue("brick", event.position, event.value - 1, event.time);
+        break;
+    case EventType::BrickHit:
+        if (event.value < 0) {
+            // metal rings like a wall, only higher
+            engine.playAt("wall", event.position, 0.9f, 1.5f, event.time);
+        } else {
+            // the same brick sound, duller while it holds
+            engine.playAt("brick_1", event.position, 0.7f, 0.8f, event.time);
+        }
+        break;
+    case EventType::BossHit:
+        if (event.value < 0) {
+            engine.playAt("wall", event.position, 0.9f, 1.2f, event.time);
+        } else {
+            engine.playAt("brick_3", event.position, 1, 0.6f, event.time);
+        }
+        break;
+    case EventType::BossDefeated:
+        engine.playCue("brick", event.position, 8, event.time);
+        music.duck(1.5f);
+        break;
+    case EventType::BossAttacked:
+        engine.playAt("laser", event.position, 0.7f, 0.5f, event.time);
+        break;
+    case EventType::PaddleDamaged:
+        engine.playAt("ball_lost", event.position, 0.6f, 1.5f, event.time);
+        break;
+    case EventType::FieldShifted:
+        engine.playAt("wall", event.position, 0.7f, 0.5f, event.time);
+        break;
+    case EventType::FieldOverran:
+        engine.playAt("ball_lost", event.position, 1, 0.7f, event.time);
+        music.duck(1.2f);
+        break;
+    case EventType::ComboDecayed:
+        engine.playAt("wall", event.position, 0.4f, 0.7f, event.time);
+        break;
+    case EventType::TimeCollected:
+        engine.playAt("power_up", event.position, 0.7f, 1.4f, event.time);
+        break;
+    case EventType::TimeUp:
+    case EventType::LaunchesSpent:
+        engine.playAt("ball_lost", event.position, 1, 0.8f, event.time);
+        music.duck(1.2f);
+        break;
+    case EventType::DifficultyRaised:
+        engine.playAt("power_up", event.position, 0.8f, 0.7f, event.time);
+        break;
+    case EventType::FieldRefilled:
+        engine.playAt("barrier", event.position, 0.8f, 0.6f, event.time);
+        break;
+    case EventType::GarbageReceived:
+        engine.playAt("brick_3", event.position, 0.8f, 0.5f, event.time);
+        break;
+    case EventType::BricksSwitched:
+        // the shield's hum, rising as a group comes in and falling as it goes
+        engine.playAt("barrier", event.position, 0.8f, event.value > 0 ? 1.3f : 0.8f, event.time);
+        break;
+    case EventType::WallBounce:
+        engine.playCue("wall", event.position, 0, event.time);
+        break;
+    case EventType::PaddleBounce:
+        engine.playCue("paddle", event.position, 0, event.time);
+        break;
+    case EventType::BallCaught:
+        engine.playAt("paddle", event.position, 0.7f, 0.8f, event.time);
+        break;
+    case EventType::BallLost:
+        engine.playAt("ball_lost", event.position, 1, 1, event.time);
+        music.duck(1.2f);
+        break;
+    case EventType::ExtraBallLost:
+        engine.playAt("ball_lost", event.position, 0.5f, 1.3f, event.time);
+        break;
+    case EventType::PowerUpCollected:
+        // a debuff is the same chime, low and sour
+        engine.playAt("power_up", event.position, 1, powerUpInfo((PowerUpType) event.value).harmful ? 0.6f : 1, event.time);
+        break;
+    case EventType::CoinCollected:
+        // the power-up chime, small and bright
+        engine.playAt("power_up", event.position, 0.5f, 1.8f, event.time);
+        break;
+    case EventType::BarrierHit:
+        engine.playAt("barrier", event.position, 1, 1, event.time);
+        break;
+    case EventType::LaserFired:
+        engine.playCue("laser", event.position, 0, event.time);
+        break;
+    case EventType::MenuOpened:
+        inMenu = true;
+        music.play("menu");
+        break;
+    case EventType::MenuClosed:
+        inMenu = false;
+        music.play(gameplayTrack);
+        break;
+    case EventType::SettingChanged:
+        engine.play("ui_select");
+        break;
+    default:
+        break;
+    }
+}
+
+void GameAudio::onWindowEvent(WindowEvent event) {
+    engine.onWindowEvent(event);
+    // without background audio the loop stops while minimized, so stop the
+    // music cleanly instead of letting the stream stutter
+    if (!backgroundAudio && event == WindowEvent::Minimized) {
+        music.pause();
+    } else if (event == WindowEvent::Restored) {
+        music.resume();
+    }
+}
+
+void GameAudio::setGameplayTrack(const std::string& id) {
+    bool first = gameplayTrack.empty();
+    gameplayTrack = id;
+    if (!inMenu) {
+        music.play(gameplayTrack);
+    }
+    if (first) {
+        // opening the menu shouldn't stall on loading its track
+        music.preload("menu");
+    }
+}
+
+void GameAudio::reloadSounds() {
+    engine.reloadManifest(soundManifestPath());
+}
+
+void GameAudio::setListener(Vector2 position, float fieldWidth, float range) {
+    engine.setListener(position, fieldWidth, range);
+}
+
+void GameAudio::setIntensity(float intensity) {
+    music.setIntensity(intensity);
+}
+
+void GameAudio::setTimeScale(float timeScale) {
+    // half as far as the game slows, which is as low as it still sounds like music
+    music.setPitch(1 - (1 - timeScale) / 2);
+}
+
+void GameAudio::update(float deltaTime) {
+    music.update(deltaTime, engine.busGain(AudioBus::Music));
+}
+
+void GameAudio::unload() {
+    music.unload();
+    engine.unload();
+}
+
+#endif
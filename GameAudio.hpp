@@ -0,0 +1,62 @@
+#pragma once
+#include "raylib.h"
+#include <string>
+#include "Events.hpp"
+#include "Lifecycle.hpp"
+#include "Settings.hpp"
+
+// Everything the game hears goes through here: gameplay and menus only
+// publish events, GameAudio decides what they sound like. Building with
+// BREAKOUT_NO_AUDIO (headless runs, servers) swaps in an empty version and
+// compiles the audio backend out entirely, so nothing links against
+// raylib's audio module.
+#ifdef BREAKOUT_NO_AUDIO
+
+class GameAudio {
+  public:
+    void init(const Settings&) {}
+    void apply(const Settings&) {}
+    void onEvent(const GameEvent&) {}
+    void onWindowEvent(WindowEvent) {}
+    void setGameplayTrack(const std::string&) {}
+    void reloadSounds() {}
+    void setListener(Vector2, float, float) {}
+    void setIntensity(float) {}
+    void setTimeScale(float) {}
+    void update(float) {}
+    void unload() {}
+};
+
+#else
+
+#include "Audio.hpp"
+#include "MusicDirector.hpp"
+
+class GameAudio {
+  public:
+    void init(const Settings& settings);
+    void apply(const Settings& settings);
+    void onEvent(const GameEvent& event);
+    void onWindowEvent(WindowEvent event);
+
+    // What plays during gameplay (the level's track or the theme's); menus
+    // switch to "menu" and come back to it.
+    void setGameplayTrack(const std::string& id);
+    // Loads the sound manifest and its files again (asset hot-reload).
+    void reloadSounds();
+    void setListener(Vector2 position, float fieldWidth, float range);
+    void setIntensity(float intensity);
+    // GameState::timeScale; slow motion drags the music down with it.
+    void setTimeScale(float timeScale);
+    void update(float deltaTime);
+    void unload();
+
+  private:
+    AudioEngine engine;
+    MusicDirector music;
+    std::string gameplayTrack;
+    bool inMenu = false;
+    bool backgroundAudio = true;
+};
+
+#endif
@@ -0,0 +1,119 @@
+#include "raylib.h"
+#include "GameFlow.hpp"
+#include <algorithm>
+
+
+const char* gameScreenName(GameScreen screen) {
+    static const char* const NAMES[] = {
+        "boot", "main_menu", "level_select", "playing", "paused", "game_over", "editor", "replay", "netplay",
+    };
+    return NAMES[(int) screen];
+}
+
+const char* overlayName(Overlay overlay) {
+    static const char* const NAMES[] = {"settings", "controls", "devices", "leaderboard", "stats", "shop", "levels", "place_ball"};
+    return NAMES[(int) overlay];
+}
+
+void GameFlow::onEnter(GameScreen target, Hook hook) {
+    enterHooks[target].push_back(hook);
+}
+
+void GameFlow::onExit(GameScreen target, Hook hook) {
+    exitHooks[target].push_back(hook);
+}
+
+void GameFlow::onOverlay(Overlay overlay, Hook showHook, Hook hideHook) {
+    overlayHooks[overlay] = {showHook, hideHook};
+}
+
+GameScreen GameFlow::getScreen() const {
+    return screen;
+}
+
+GameScreen GameFlow::getPrevious() const {
+    return previous;
+}
+
+bool GameFlow::is(GameScreen target) const {
+    return screen == target;
+}
+
+void GameFlow::change(GameScreen target) {
+    if (target == screen) {
+        return;
+    }
+    TraceLog(LOG_DEBUG, "FLOW: %s -> %s", gameScreenName(screen), gameScreenName(target));
+    // copied, a hook may register more
+    std::vector<Hook> exits = exitHooks[screen];
+    for (Hook& hook : exits) {
+        hook();
+    }
+    previous = screen;
+    screen = target;
+    std::vector<Hook> enters = enterHooks[screen];
+    for (Hook& hook : enters) {
+        hook();
+    }
+}
+
+void GameFlow::show(Overlay overlay) {
+    auto hooks = overlayHooks.find(overlay);
+    if (hooks != overlayHooks.end() && hooks->second.first) {
+        hooks->second.first();
+    }
+}
+
+void GameFlow::hide(Overlay overlay) {
+    auto hooks = overlayHooks.find(overlay);
+    if (hooks != overlayHooks.end() && hooks->second.second) {
+        hooks->second.second();
+    }
+}
+
+void GameFlow::push(Overlay overlay) {
+    if (!overlays.empty() && overlays.back() == overlay) {
+        return;
+    }
+    if (!overlays.empty()) {
+        hide(overlays.back());
+    }
+    overlays.erase(std::remove(overlays.begin(), overlays.end(), overlay), overlays.end());
+    overlays.push_back(overlay);
+    show(overlay);
+}
+
+void GameFlow::close(Overlay overlay) {
+    auto found = std::find(overlays.begin(), overlays.end(), overlay);
+    if (found == overlays.end()) {
+        return;
+    }
+    bool top = found + 1 == overlays.end();
+    overlays.erase(found);
+    if (top) {
+        hide(overlay);
+        if (!overlays.empty()) {
+            show(overlays.back());
+        }
+    }
+}
+
+void GameFlow::toggle(Overlay overlay) {
+    if (!overlays.empty() && overlays.back() == overlay) {
+        close(overlay);
+    } else {
+        push(overlay);
+    }
+}
+
+bool GameFlow::isOpen(Overlay overlay) const {
+    return std::find(overlays.begin(), overlays.end(), overlay) != overlays.end();
+}
+
+bool GameFlow::hasOverlay() const {
+    return !overlays.empty();
+}
+
+int GameFlow::getOverlayCount() const {
+    return (int) overlays.size();
+}
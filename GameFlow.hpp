@@ -0,0 +1,77 @@
+#pragma once
+#include <functional>
+#include <map>
+#include <vector>
+
+
+// The top-level screens. Exactly one is current; the ones marked "no screen
+// yet" are here so that building them means adding hooks, not more flags.
+enum class GameScreen {
+    Boot,
+    MainMenu,    // no screen yet
+    LevelSelect, // no screen yet; the level select is Overlay::Levels for now
+    Playing,
+    Paused,      // from Playing or Replay, which it goes back to
+    GameOver,    // out of lives or the level cleared; the run is over
+    Editor,      // no screen yet
+    Replay,
+    Netplay,     // no screen yet
+};
+
+// Menus that go over whatever screen is current and hold the game while up.
+enum class Overlay {
+    Settings,
+    Controls,
+    Devices,
+    Leaderboard,
+    Stats,
+    Shop,
+    Levels,    // the level select, over Paused or GameOver rather than a screen of its own
+    PlaceBall, // practice's ball placement, over Paused
+};
+
+// snake_case, for logs and crash reports
+const char* gameScreenName(GameScreen screen);
+const char* overlayName(Overlay overlay);
+
+// Screen changes run the old screen's exit hooks, then the new one's enter
+// hooks. Overlays stack: opening one covers the one below, closing it brings
+// that back. Only the top overlay is shown (its show hook runs when it gets
+// to the top, its hide hook when it is covered or closed), so only it takes
+// input.
+class GameFlow {
+  public:
+    using Hook = std::function<void()>;
+
+    void onEnter(GameScreen screen, Hook hook);
+    void onExit(GameScreen screen, Hook hook);
+    void onOverlay(Overlay overlay, Hook show, Hook hide);
+
+    GameScreen getScreen() const;
+    // The screen before the current one (what Paused goes back to).
+    GameScreen getPrevious() const;
+    bool is(GameScreen screen) const;
+    // Nothing happens when screen is already current.
+    void change(GameScreen screen);
+
+    // Puts overlay on top, moving it up if it was further down.
+    void push(Overlay overlay);
+    // Closes overlay wherever it is in the stack.
+    void close(Overlay overlay);
+    // The menu keys: close overlay if it is on top, otherwise push it.
+    void toggle(Overlay overlay);
+    bool isOpen(Overlay overlay) const;
+    bool hasOverlay() const;
+    int getOverlayCount() const;
+
+  private:
+    GameScreen screen = GameScreen::Boot;
+    GameScreen previous = GameScreen::Boot;
+    std::map<GameScreen, std::vector<Hook>> enterHooks;
+    std::map<GameScreen, std::vector<Hook>> exitHooks;
+    std::map<Overlay, std::pair<Hook, Hook>> overlayHooks;
+    std::vector<Overlay> overlays; // bottom first
+
+    void show(Overlay overlay);
+    void hide(Overlay overlay);
+};
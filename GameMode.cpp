@@ -0,0 +1,29 @@
+#include "GameMode.hpp"
+
+
+const std::vector<GameModeInfo>& allGameModes() {
+    static const std::vector<GameModeInfo> MODES = {
+        {GameMode::Classic, "classic", "Classic", "Clear the level"},
+        {GameMode::Endless, "endless", "Endless", "New rows keep pushing the field down; hold them off the paddle"},
+        {GameMode::TimeAttack, "time_attack", "Time Attack", "Break as many bricks as you can before the clock runs out"},
+        {GameMode::Survival, "survival", "Survival", "Last as long as you can as the ball speeds up and the paddle shrinks"},
+        {GameMode::Coop, "coop", "Co-op", "Two players, a paddle each and one pool of lives"},
+        {GameMode::Versus, "versus", "Versus", "A field each; every few bricks you break lands as junk on your rival's"},
+        {GameMode::Practice, "practice", "Practice", "Put the ball anywhere, aim it and try again; nothing is kept"},
+    };
+    return MODES;
+}
+
+const GameModeInfo& gameModeInfo(GameMode mode) {
+    return allGameModes()[(int) mode];
+}
+
+bool parseGameMode(const std::string& id, GameMode& mode) {
+    for (const GameModeInfo& info : allGameModes()) {
+        if (id == info.id) {
+            mode = info.mode;
+            return true;
+        }
+    }
+    return false;
+}
@@ -0,0 +1,29 @@
+#pragma once
+#include <string>
+#include <vector>
+
+
+// What a run is played for. Unlike a mutator, which only tweaks the rules, a
+// mode changes how a run goes and ends, so the simulation asks for it
+// (SimulationRules::mode) and every mode keeps leaderboards of its own.
+enum class GameMode {
+    Classic, // clear the level
+    Endless, // rows keep coming from the top until they reach the paddle (see Endless.hpp)
+    TimeAttack, // as many bricks as the clock allows (see TimeAttack.hpp)
+    Survival, // the level comes back when cleared, harder each time (see Survival.hpp)
+    Coop,     // classic with a second paddle on the right half, lives shared
+    Versus,   // a field each, side by side, trading junk rows (see Versus.hpp)
+    Practice, // lives never run out and the ball can be placed by hand (see Sandbox.hpp)
+};
+
+struct GameModeInfo {
+    GameMode mode;
+    const char* id; // --mode, replays and leaderboard files
+    const char* name;
+    const char* description;
+};
+
+const std::vector<GameModeInfo>& allGameModes();
+const GameModeInfo& gameModeInfo(GameMode mode);
+// False on an id this build doesn't have, leaving mode alone.
+bool parseGameMode(const std::string& id, GameMode& mode);
@@ -0,0 +1,134 @@
+#include "raylib.h"
+#include "Gif.hpp"
+#include <fstream>
+#include <unordered_map>
+
+
+namespace {
+
+// Packs variable-width LZW codes LSB first and splits them into sub-blocks
+struct CodeWriter {
+    std::vector<unsigned char> bytes;
+    unsigned int buffer = 0;
+    int bits = 0;
+
+    void write(int code, int size) {
+        buffer |= code << bits;
+        bits += size;
+        while (bits >= 8) {
+            bytes.push_back(buffer & 0xff);
+            buffer >>= 8;
+            bits -= 8;
+        }
+    }
+
+    void flush() {
+        if (bits > 0) {
+            bytes.push_back(buffer & 0xff);
+        }
+        buffer = 0;
+        bits = 0;
+    }
+};
+
+void writeShort(std::ofstream& file, int value) {
+    file.put(value & 0xff);
+    file.put((value >> 8) & 0xff);
+}
+
+unsigned char paletteIndex(const unsigned char* pixel) {
+    return (pixel[0] * 6 / 256) * 42 + (pixel[1] * 7 / 256) * 6 + pixel[2] * 6 / 256;
+}
+
+void writeImageData(std::ofstream& file, const std::vector<unsigned char>& indices) {
+    const int minCodeSize = 8;
+    const int clearCode = 1 << minCodeSize;
+    CodeWriter writer;
+    std::unordered_map<int, int> table;
+    int codeSize = minCodeSize + 1;
+    int maxCode = clearCode + 1;
+
+    writer.write(clearCode, codeSize);
+    int current = indices[0];
+    for (size_t i = 1; i < indices.size(); i++) {
+        int key = current << 8 | indices[i];
+        auto found = table.find(key);
+        if (found != table.end()) {
+            current = found->second;
+            continue;
+        }
+        writer.write(current, codeSize);
+        table[key] = ++maxCode;
+        if (maxCode >= (1 << codeSize)) {
+            codeSize++;
+        }
+        if (maxCode == 4095) {
+            writer.write(clearCode, codeSize);
+            table.clear();
+            codeSize = minCodeSize + 1;
+            maxCode = clearCode + 1;
+        }
+        current = indices[i];
+    }
+    writer.write(current, codeSize);
+    writer.write(clearCode, codeSize);
+    writer.write(clearCode + 1, minCodeSize + 1);
+    writer.flush();
+
+    file.put(minCodeSize);
+    for (size_t start = 0; start < writer.bytes.size(); start += 255) {
+        size_t length = writer.bytes.size() - start < 255 ? writer.bytes.size() - start : 255;
+        file.put((char) length);
+        file.write((const char*) writer.bytes.data() + start, length);
+    }
+    file.put(0);
+}
+
+}
+
+bool writeGif(const std::string& path, const std::vector<std::vector<unsigned char>>& frames, int width, int height, int delay) {
+    if (frames.empty()) {
+        return false;
+    }
+    std::ofstream file(path, std::ios::binary);
+    if (!file) {
+        TraceLog(LOG_WARNING, "GIF: Could not write %s", path.c_str());
+        return false;
+    }
+
+    file.write("GIF89a", 6);
+    writeShort(file, width);
+    writeShort(file, height);
+    file.put((char) 0xf7); // global color table, 256 entries
+    file.put(0);
+    file.put(0);
+    for (int i = 0; i < 256; i++) {
+        bool used = i < 252;
+        file.put(used ? (char) ((i / 42) * 255 / 5) : 0);
+        file.put(used ? (char) ((i / 6 % 7) * 255 / 6) : 0);
+        file.put(used ? (char) ((i % 6) * 255 / 5) : 0);
+    }
+    // loop forever
+    file.write("\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00", 19);
+
+    std::vector<unsigned char> indices(width * height);
+    for (const std::vector<unsigned char>& frame : frames) {
+        for (int i = 0; i < width * height; i++) {
+            indices[i] = paletteIndex(&frame[i * 4]);
+        }
+        file.write("\x21\xf9\x04\x04", 4);
+        writeShort(file, delay);
+        file.put(0);
+        file.put(0);
+
+        file.put(0x2c);
+        writeShort(file, 0);
+        writeShort(file, 0);
+        writeShort(file, width);
+        writeShort(file, height);
+        file.put(0);
+        writeImageData(file, indices);
+    }
+    file.put(0x3b);
+    return (bool) file;
+}
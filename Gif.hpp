@@ -0,0 +1,10 @@
+#pragma once
+#include <string>
+#include <vector>
+
+
+// Minimal animated GIF writer for gameplay clips. Frames are tightly packed
+// RGBA8 pixels of the given size; colors are mapped to a fixed 6x7x6 palette,
+// which is plenty for short, downscaled captures and needs no per-clip
+// quantization pass. delay is in hundredths of a second.
+bool writeGif(const std::string& path, const std::vector<std::vector<unsigned char>>& frames, int width, int height, int delay);
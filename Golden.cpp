@@ -0,0 +1,83 @@
+#include "raylib.h"
+#include "Golden.hpp"
+#include <algorithm>
+#include <cstdio>
+#include <cstdlib>
+#include <filesystem>
+
+
+GoldenImages::GoldenImages(bool updateReferences)
+    : directory(std::string(GetApplicationDirectory()) + "golden/"), update(updateReferences) {
+    std::error_code error;
+    std::filesystem::create_directories(directory + (update ? "" : "failed/"), error);
+}
+
+bool GoldenImages::check(const std::string& scene, const Image& screen) {
+    checked++;
+    Image frame = ImageCopy(screen);
+    ImageFormat(&frame, PIXELFORMAT_UNCOMPRESSED_R8G8B8A8);
+    std::string referencePath = directory + scene + ".png";
+    if (update) {
+        bool saved = ExportImage(frame, referencePath.c_str());
+        printf("%-24s %s\n", scene.c_str(), saved ? "updated" : "could not be written");
+        if (!saved) {
+            failures.push_back(scene);
+        }
+        UnloadImage(frame);
+        return saved;
+    }
+
+    Image reference = FileExists(referencePath.c_str()) ? LoadImage(referencePath.c_str()) : Image{};
+    if (!IsImageReady(reference) || reference.width != frame.width || reference.height != frame.height) {
+        printf("%-24s FAILED: %s\n", scene.c_str(), IsImageReady(reference) ?
+            TextFormat("reference is %dx%d, frame is %dx%d", reference.width, reference.height, frame.width, frame.height) :
+            "no reference, run with --update-golden");
+        UnloadImage(reference);
+        ExportImage(frame, (directory + "failed/" + scene + ".png").c_str());
+        UnloadImage(frame);
+        failures.push_back(scene);
+        return false;
+    }
+    ImageFormat(&reference, PIXELFORMAT_UNCOMPRESSED_R8G8B8A8);
+
+    const Color* expected = (const Color*) reference.data;
+    const Color* actual = (const Color*) frame.data;
+    Image diff = GenImageColor(frame.width, frame.height, BLACK);
+    Color* marked = (Color*) diff.data;
+    int total = frame.width * frame.height;
+    int differing = 0;
+    for (int i = 0; i < total; i++) {
+        int worst = abs(expected[i].r - actual[i].r);
+        worst = std::max(worst, abs(expected[i].g - actual[i].g));
+        worst = std::max(worst, abs(expected[i].b - actual[i].b));
+        worst = std::max(worst, abs(expected[i].a - actual[i].a));
+        if (worst > CHANNEL_TOLERANCE) {
+            differing++;
+            marked[i] = RED;
+        } else {
+            // a faint copy of the frame so the red has something to sit on
+            marked[i] = {(unsigned char) (actual[i].r / 4), (unsigned char) (actual[i].g / 4), (unsigned char) (actual[i].b / 4), 255};
+        }
+    }
+    float fraction = total > 0 ? (float) differing / total : 0;
+    bool matches = fraction <= PIXEL_TOLERANCE;
+    printf("%-24s %s (%.3f%% of pixels differ)\n", scene.c_str(), matches ? "ok" : "FAILED", fraction * 100);
+    if (!matches) {
+        ExportImage(frame, (directory + "failed/" + scene + ".png").c_str());
+        ExportImage(diff, (directory + "failed/" + scene + ".diff.png").c_str());
+        failures.push_back(scene);
+    }
+    UnloadImage(diff);
+    UnloadImage(reference);
+    UnloadImage(frame);
+    return matches;
+}
+
+int GoldenImages::finish() {
+    if (failures.empty()) {
+        printf("%d scenes %s\n", checked, update ? "updated" : "match");
+        return 0;
+    }
+    printf("%d of %d scenes failed, see %s\n", (int) failures.size(), checked, (directory + (update ? "" : "failed/")).c_str());
+    return 1;
+}
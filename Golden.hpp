@@ -0,0 +1,34 @@
+#pragma once
+#include "raylib.h"
+#include <string>
+#include <vector>
+
+
+// `--golden` renders a fixed list of scenes and compares each frame against
+// golden/<scene>.png next to the executable, so a change to batching or the
+// render graph that moves a pixel shows up before a player sees it.
+// `--update-golden` writes the frames as the new references instead; check
+// those in after looking at them.
+//
+// Drivers round blending and filtering a little differently, so a frame
+// still matches when at most PIXEL_TOLERANCE of its pixels are off by more
+// than CHANNEL_TOLERANCE in any channel. A failed scene leaves
+// golden/failed/<scene>.png and <scene>.diff.png (differing pixels in red).
+class GoldenImages {
+  public:
+    static constexpr int CHANNEL_TOLERANCE = 8;
+    static constexpr float PIXEL_TOLERANCE = 0.002f;
+
+    explicit GoldenImages(bool update);
+    // Compares (or with update, stores) one frame; true when it matches.
+    // The caller keeps owning screen.
+    bool check(const std::string& scene, const Image& screen);
+    // Prints the summary; returns the exit code, 1 when any scene failed.
+    int finish();
+
+  private:
+    std::string directory;
+    bool update;
+    int checked = 0;
+    std::vector<std::string> failures;
+};
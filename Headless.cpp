@@ -0,0 +1,161 @@
+#include "raylib.h"
+#include "Headless.hpp"
+#include <cstdio>
+#include <map>
+#include "Bot.hpp"
+#include "Challenge.hpp"
+#include "Events.hpp"
+#include "FramePacer.hpp"
+#include "Level.hpp"
+#include "Log.hpp"
+#include "Mutators.hpp"
+#include "Replay.hpp"
+#include "Sanitize.hpp"
+#include "Settings.hpp"
+#include "Simulation.hpp"
+#include "Versus.hpp"
+
+
+int runHeadless(const LaunchOptions& options) {
+    // stdout carries the JSON result only
+    installLogging(stderr);
+    std::string filterError;
+    if (!options.logFilter.empty() && !setLogFilter(options.logFilter, filterError)) {
+        TraceLog(LOG_WARNING, "HEADLESS: Bad --log filter: %s", filterError.c_str());
+        return 2;
+    }
+    BotSkill skill;
+    if (!parseBotSkill(options.bot, skill)) {
+        TraceLog(LOG_WARNING, "HEADLESS: Unknown bot '%s', expected easy, normal or hard", options.bot.c_str());
+        return 2;
+    }
+    std::string mutators;
+    std::string mutatorError;
+    if (!parseMutators(options.mutators, mutators, mutatorError)) {
+        TraceLog(LOG_WARNING, "HEADLESS: Bad --mutators: %s", mutatorError.c_str());
+        return 2;
+    }
+    GameMode mode = GameMode::Classic;
+    if (!parseGameMode(options.mode, mode)) {
+        TraceLog(LOG_WARNING, "HEADLESS: Unknown --mode '%s'", options.mode.c_str());
+        return 2;
+    }
+
+    std::vector<Brick> bricks;
+    DropTable drops = DropTable::standard();
+    Boss boss;
+    int launches = 0;
+    LevelPhysics physics;
+    std::string levelName = "classic";
+    if (!options.levelPath.empty()) {
+        Level level;
+        std::string error;
+        if (!loadDailyLevel(options.levelPath, level) && !loadLevel(options.levelPath, level, error)) {
+            TraceLog(LOG_WARNING, "LEVEL: %s", error.c_str());
+            return 2;
+        }
+        bricks = level.bricks;
+        drops = level.drops;
+        boss = level.boss;
+        launches = level.launches;
+        physics = level.physics;
+        levelName = level.name;
+    } else {
+        createBricks(bricks);
+    }
+    int brickTotal = destructibleCount(bricks) + (boss.present() ? 1 : 0);
+
+    // the defaults, not whatever this machine's settings say, so every machine agrees
+    const uint64_t seed = options.hasSeed ? options.seed : 0;
+    Simulation simulation;
+    simulation.rules.inputBufferTicks = Settings().inputBufferMs * FramePacer::TICK_RATE / 1000;
+    applyMutators(mutators, simulation.rules);
+    simulation.rules.mode = (int) mode;
+    simulation.dropTable = drops;
+    simulation.boss = boss;
+    simulation.launchBudget = launches;
+    simulation.physics = physics;
+    simulation.reset(bricks, seed);
+    GameState& game = simulation.state;
+    Bot bot(skill, seed);
+    // versus is the bot against itself, one a seed along so they don't mirror
+    bool versus = mode == GameMode::Versus;
+    Simulation rival;
+    Bot rivalBot(skill, seed + 1);
+    VersusMatch match;
+    if (versus) {
+        rival.rules = simulation.rules;
+        rival.dropTable = drops;
+        rival.boss = boss;
+        rival.launchBudget = launches;
+        rival.physics = physics;
+        rival.reset(bricks, seed);
+        match.start(simulation, rival);
+    }
+
+    EventBus events;
+    std::map<EventType, int> counts;
+    events.subscribe([&counts](const GameEvent& event) { counts[event.type]++; });
+
+    ReplayRecorder replayRecorder;
+    if (!options.recordReplay.empty() && versus) {
+        TraceLog(LOG_WARNING, "HEADLESS: A replay has the one field, not recording versus");
+    } else if (!options.recordReplay.empty()) {
+        ReplayHeader header;
+        header.tickRate = FramePacer::TICK_RATE;
+        header.seed = seed;
+        header.levelHash = levelHash(bricks);
+        header.levelPath = options.levelPath;
+        header.rules = simulation.rules;
+        header.mutators = mutators;
+        header.mode = options.mode;
+        replayRecorder.start(header);
+    }
+
+    while (game.tick < options.ticks && (versus ? match.result(simulation, rival) == VersusResult::Playing : game.lives > 0 && game.bricksLeft > 0)) {
+        InputFrame input = bot.think(game, simulation.rules);
+        replayRecorder.record(input, simulation);
+        simulation.step(input, &events);
+        if (versus) {
+            rival.step(rivalBot.think(rival.state, rival.rules), nullptr);
+            match.exchange(simulation, rival, &events, nullptr);
+        }
+        events.dispatch();
+    }
+    if (replayRecorder.isRecording()) {
+        replayRecorder.save(options.recordReplay, simulation);
+    }
+
+    printf("{\"level\": %s, \"bot\": \"%s\", \"mode\": \"%s\", \"mutators\": %s, \"seed\": %llu, \"ticks\": %llu, \"time\": %.3f, ",
+        jsonString(levelName).c_str(), botSkillName(skill), options.mode.c_str(), jsonString(mutators).c_str(), (unsigned long long) seed, (unsigned long long) game.tick,
+        (double) game.tick / FramePacer::TICK_RATE);
+    printf("\"score\": %d, \"lives\": %d, \"bricks_total\": %d, \"bricks_left\": %d, \"cleared\": %s, \"checksum\": \"%016llx\", ",
+        game.score, game.lives > 0 ? game.lives : 0, brickTotal, game.bricksLeft, game.bricksLeft <= 0 ? "true" : "false",
+        (unsigned long long) simulation.checksum());
+    if (mode == GameMode::Endless) {
+        printf("\"rows_added\": %d, ", game.rowsAdded);
+    } else if (mode == GameMode::TimeAttack) {
+        printf("\"time_left\": %.3f, \"time_collected\": %d, ", (double) game.timeTicks / FramePacer::TICK_RATE, game.timeCollected);
+    } else if (mode == GameMode::Coop) {
+        // the bot only ever plays player one
+        printf("\"player_scores\": [%d, %d], ", game.playerScores[0], game.playerScores[1]);
+    } else if (mode == GameMode::Survival) {
+        printf("\"survived\": %.3f, \"stage\": %d, ", (double) game.survivedTicks / FramePacer::TICK_RATE, game.survivalStage);
+    } else if (versus) {
+        // the rest is player one's, the bot that played the first field
+        VersusResult result = match.result(simulation, rival);
+        const char* winner = result == VersusResult::FirstWins ? "p1" : result == VersusResult::SecondWins ? "p2" : result == VersusResult::Draw ? "draw" : "none";
+        printf("\"winner\": \"%s\", \"rows_sent\": [%d, %d], \"rival_score\": %d, ", winner, match.rowsSent(0), match.rowsSent(1), rival.state.score);
+    }
+    if (launches > 0) {
+        printf("\"launches_left\": %d, \"spare_bonus\": %d, ", game.launchesLeft, game.spareBonus);
+    }
+    printf("\"events\": {");
+    const char* separator = "";
+    for (const auto& count : counts) {
+        printf("%s\"%s\": %d", separator, eventTypeName(count.first), count.second);
+        separator = ", ";
+    }
+    printf("}}\n");
+    return 0;
+}
@@ -0,0 +1,11 @@
+#pragma once
+#include "Args.hpp"
+
+
+// `breakout --headless [--level X] [--bot easy|normal|hard] [--ticks N] [--seed N]`:
+// plays the level with a bot, no window or audio, and prints the result as one
+// JSON object on stdout (logs go to stderr). Nothing is random apart from the
+// seed (0 unless given), so the same arguments always print the same result, which is
+// what CI's determinism checks, the level tuner and the tournament runner rely
+// on. Returns the process exit code.
+int runHeadless(const LaunchOptions& options);
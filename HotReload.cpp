@@ -0,0 +1,61 @@
+#ifdef BREAKOUT_HOT_RELOAD
+#include "raylib.h"
+#include "HotReload.hpp"
+
+
+void AssetWatcher::watchDirectory(const std::string& directory, const char* extensions, AssetKind kind) {
+    Watch watch = {directory, extensions, kind, {}};
+    // what is there now is the baseline, not a change
+    watch.files = scan(watch);
+    watches.push_back(watch);
+}
+
+std::map<std::string, long> AssetWatcher::scan(const Watch& watch) {
+    std::map<std::string, long> files;
+    if (!DirectoryExists(watch.directory.c_str())) {
+        return files;
+    }
+    FilePathList paths = LoadDirectoryFilesEx(watch.directory.c_str(), watch.extensions.c_str(), true);
+    for (unsigned int i = 0; i < paths.count; i++) {
+        files[paths.paths[i]] = GetFileModTime(paths.paths[i]);
+    }
+    UnloadDirectoryFiles(paths);
+    return files;
+}
+
+AssetChanges AssetWatcher::poll(float deltaTime) {
+    AssetChanges changes;
+    untilPoll -= deltaTime;
+    if (untilPoll > 0) {
+        return changes;
+    }
+    untilPoll = POLL_SECONDS;
+    for (Watch& watch : watches) {
+        std::map<std::string, long> files = scan(watch);
+        if (files == watch.files) {
+            continue;
+        }
+        watch.files = files;
+        switch (watch.kind) {
+        case AssetKind::Texture:
+            changes.textures = true;
+            break;
+        case AssetKind::Sound:
+            changes.sounds = true;
+            break;
+        case AssetKind::Theme:
+            changes.themes = true;
+            break;
+        case AssetKind::Shader:
+            changes.shaders = true;
+            break;
+        case AssetKind::Level:
+            changes.levels = true;
+            break;
+        }
+        TraceLog(LOG_INFO, "HOTRELOAD: Change under %s", watch.directory.c_str());
+    }
+    return changes;
+}
+
+#endif
@@ -0,0 +1,69 @@
+#pragma once
+#include <map>
+#include <string>
+#include <vector>
+
+
+enum class AssetKind {
+    Texture,
+    Sound,
+    Theme,
+    Shader,
+    Level,
+};
+
+// What changed on disk since the last poll, by kind of asset.
+struct AssetChanges {
+    bool textures = false;
+    bool sounds = false;
+    bool themes = false;
+    bool shaders = false;
+    bool levels = false;
+
+    bool any() const { return textures || sounds || themes || shaders || levels; }
+};
+
+// Development aid: watches asset folders and reports edited, added or
+// removed files so the game can reload them without a restart. It polls
+// modification times every POLL_SECONDS rather than using OS notifications,
+// which is plenty for someone saving a file in an editor.
+//
+// Only built with BREAKOUT_HOT_RELOAD; otherwise the watcher is an empty
+// shell whose poll() never reports anything, so release builds don't sit
+// there walking directories.
+#ifndef BREAKOUT_HOT_RELOAD
+
+class AssetWatcher {
+  public:
+    static constexpr float POLL_SECONDS = 0.5f;
+
+    void watchDirectory(const std::string&, const char*, AssetKind) {}
+    AssetChanges poll(float) { return {}; }
+};
+
+#else
+
+class AssetWatcher {
+  public:
+    static constexpr float POLL_SECONDS = 0.5f;
+
+    // Every file under directory (recursively) matching extensions, in
+    // raylib's filter form (".png;.jpg"). The directory doesn't have to exist yet.
+    void watchDirectory(const std::string& directory, const char* extensions, AssetKind kind);
+    AssetChanges poll(float deltaTime);
+
+  private:
+    struct Watch {
+        std::string directory;
+        std::string extensions;
+        AssetKind kind;
+        std::map<std::string, long> files; // path -> modification time at the last poll
+    };
+
+    std::vector<Watch> watches;
+    float untilPoll = 0;
+
+    static std::map<std::string, long> scan(const Watch& watch);
+};
+
+#endif
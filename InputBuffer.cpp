@@ -0,0 +1,35 @@
+#include "InputBuffer.hpp"
+
+
+void InputBuffer::setWindow(int ticks) {
+    window = ticks < 0 ? 0 : ticks;
+}
+
+void InputBuffer::update(const InputFrame& input) {
+    for (int i = 0; i < ACTION_COUNT; i++) {
+        if (input.wasPressed((Action) i)) {
+            // +1 so a window of 0 still covers the tick of the press itself
+            remaining[i] = window + 1;
+        } else if (remaining[i] > 0) {
+            remaining[i]--;
+        }
+    }
+}
+
+bool InputBuffer::consume(Action action) {
+    if (remaining[(int) action] <= 0) {
+        return false;
+    }
+    remaining[(int) action] = 0;
+    return true;
+}
+
+bool InputBuffer::isBuffered(Action action) const {
+    return remaining[(int) action] > 0;
+}
+
+void InputBuffer::clear() {
+    for (int& ticks : remaining) {
+        ticks = 0;
+    }
+}
@@ -0,0 +1,23 @@
+#pragma once
+#include "Bindings.hpp"
+#include "InputRecord.hpp"
+
+
+// Keeps launch/power presses alive for a few ticks, so pressing just before
+// the ball lands on the paddle (or a frame early during hit-stop) still
+// counts. It runs inside the simulation on the recorded InputFrames, so
+// replays buffer exactly like the original run did.
+class InputBuffer {
+  public:
+    void setWindow(int ticks);
+    // Call at the start of every tick with that tick's input.
+    void update(const InputFrame& input);
+    // True, once, if the action was pressed within the window.
+    bool consume(Action action);
+    bool isBuffered(Action action) const;
+    void clear();
+
+  private:
+    int window = 0;
+    int remaining[ACTION_COUNT] = {};
+};
@@ -0,0 +1,287 @@
+#include "raylib.h"
+#include "InputRecord.hpp"
+#include <cmath>
+#include <cstring>
+#include <fstream>
+#include <iterator>
+
+
+static const char MAGIC[4] = {'B', 'K', 'I', 'N'};
+static const uint8_t VERSION = 1;
+static const uint8_t FLAG_STEERING = 0x01;
+static const uint8_t FLAG_END = 0x80;
+
+void InputFrame::setHeld(Action action, bool down) {
+    uint8_t bit = 1 << (int) action;
+    held = down ? held | bit : held & ~bit;
+}
+
+void InputFrame::setPressed(Action action, bool down) {
+    uint8_t bit = 1 << (int) action;
+    pressed = down ? pressed | bit : pressed & ~bit;
+}
+
+void InputFrame::setStick(float value) {
+    stick = (int16_t) lroundf(fminf(fmaxf(value, -1), 1) * 32767);
+}
+
+void InputFrame::setFine(float value) {
+    fine = (uint8_t) lroundf(fminf(fmaxf(value, 0), 1) * 255);
+}
+
+void InputFrame::setSteer(float x) {
+    steering = true;
+    steerTarget = (int16_t) lroundf(fminf(fmaxf(x * 4, -32767), 32767));
+}
+
+bool InputFrame::isHeld(Action action) const {
+    return held & (1 << (int) action);
+}
+
+bool InputFrame::wasPressed(Action action) const {
+    return pressed & (1 << (int) action);
+}
+
+float InputFrame::getStick() const {
+    return stick / 32767.0f;
+}
+
+float InputFrame::getFine() const {
+    return fine / 255.0f;
+}
+
+float InputFrame::getSteerTarget() const {
+    return steerTarget / 4.0f;
+}
+
+InputFrame InputFrame::mirrored(float width) const {
+    InputFrame frame = *this;
+    frame.setHeld(Action::MoveLeft, isHeld(Action::MoveRight));
+    frame.setHeld(Action::MoveRight, isHeld(Action::MoveLeft));
+    frame.setPressed(Action::MoveLeft, wasPressed(Action::MoveRight));
+    frame.setPressed(Action::MoveRight, wasPressed(Action::MoveLeft));
+    frame.stick = (int16_t) -stick;
+    if (steering) {
+        frame.setSteer(width - getSteerTarget());
+    }
+    return frame;
+}
+
+bool InputFrame::operator==(const InputFrame& other) const {
+    return held == other.held && pressed == other.pressed && stick == other.stick && fine == other.fine
+        && steering == other.steering && (!steering || steerTarget == other.steerTarget);
+}
+
+bool InputFrame::operator!=(const InputFrame& other) const {
+    return !(*this == other);
+}
+
+static void writeVarint(std::vector<uint8_t>& data, uint64_t value) {
+    while (value >= 0x80) {
+        data.push_back((uint8_t) (value | 0x80));
+        value >>= 7;
+    }
+    data.push_back((uint8_t) value);
+}
+
+static void writeInt16(std::vector<uint8_t>& data, int16_t value) {
+    data.push_back((uint8_t) (value & 0xff));
+    data.push_back((uint8_t) ((uint16_t) value >> 8));
+}
+
+void InputRecorder::start(uint32_t tickRate) {
+    data.assign(MAGIC, MAGIC + 4);
+    data.push_back(VERSION);
+    for (int i = 0; i < 4; i++) {
+        data.push_back((uint8_t) (tickRate >> (8 * i)));
+    }
+    last = InputFrame();
+    lastTick = 0;
+    recording = true;
+    first = true;
+}
+
+void InputRecorder::record(uint64_t tick, const InputFrame& frame) {
+    // a press always gets its own record, even if the previous tick had one too
+    if (!recording || (!first && frame == last && frame.pressed == 0)) {
+        return;
+    }
+    writeVarint(data, tick - lastTick);
+    data.push_back(frame.steering ? FLAG_STEERING : 0);
+    data.push_back(frame.held);
+    data.push_back(frame.pressed);
+    writeInt16(data, frame.stick);
+    data.push_back(frame.fine);
+    if (frame.steering) {
+        writeInt16(data, frame.steerTarget);
+    }
+    // presses never repeat into the next tick, so compare against the frame without them
+    last = frame;
+    last.pressed = 0;
+    lastTick = tick;
+    first = false;
+}
+
+bool InputRecorder::isRecording() {
+    return recording;
+}
+
+const std::vector<uint8_t>& InputRecorder::finish(uint64_t endTick) {
+    if (recording) {
+        recording = false;
+        writeVarint(data, endTick - lastTick);
+        data.push_back(FLAG_END);
+    }
+    return data;
+}
+
+bool InputRecorder::save(const std::string& path, uint64_t endTick) {
+    if (!recording) {
+        return false;
+    }
+    finish(endTick);
+
+    std::ofstream file(path, std::ios::binary);
+    if (!file) {
+        TraceLog(LOG_WARNING, "INPUT: Could not write %s", path.c_str());
+        return false;
+    }
+    file.write((const char*) data.data(), data.size());
+    TraceLog(LOG_INFO, "INPUT: Recorded %llu ticks to %s (%d bytes)", (unsigned long long) endTick, path.c_str(), (int) data.size());
+    return true;
+}
+
+// Walks the byte stream; every read fails cleanly at the end of the data.
+struct Reader {
+    const std::vector<uint8_t>& data;
+    size_t position = 0;
+
+    bool byte(uint8_t& value) {
+        if (position >= data.size()) {
+            return false;
+        }
+        value = data[position++];
+        return true;
+    }
+
+    bool int16(int16_t& value) {
+        uint8_t low, high;
+        if (!byte(low) || !byte(high)) {
+            return false;
+        }
+        value = (int16_t) (low | (high << 8));
+        return true;
+    }
+
+    bool varint(uint64_t& value) {
+        value = 0;
+        for (int shift = 0; shift < 64; shift += 7) {
+            uint8_t part;
+            if (!byte(part)) {
+                return false;
+            }
+            value |= (uint64_t) (part & 0x7f) << shift;
+            if (!(part & 0x80)) {
+                return true;
+            }
+        }
+        return false;
+    }
+};
+
+bool InputPlayback::load(const std::string& path, std::string& error) {
+    records.clear();
+    playing = false;
+    std::ifstream file(path, std::ios::binary);
+    if (!file) {
+        error = "could not open " + path;
+        return false;
+    }
+    std::vector<uint8_t> data((std::istreambuf_iterator<char>(file)), std::istreambuf_iterator<char>());
+    return parse(data, path, error);
+}
+
+bool InputPlayback::parse(const std::vector<uint8_t>& data, const std::string& name, std::string& error) {
+    records.clear();
+    playing = false;
+    if (data.size() < 9 || memcmp(data.data(), MAGIC, 4) != 0) {
+        error = name + " is not an input recording";
+        return false;
+    }
+    if (data[4] != VERSION) {
+        error = name + " has unsupported version " + std::to_string(data[4]);
+        return false;
+    }
+    tickRate = data[5] | (data[6] << 8) | (data[7] << 16) | ((uint32_t) data[8] << 24);
+
+    Reader reader{data, 9};
+    uint64_t tick = 0;
+    while (true) {
+        uint64_t delta;
+        uint8_t flags;
+        if (!reader.varint(delta) || !reader.byte(flags)) {
+            error = name + " is truncated";
+            return false;
+        }
+        tick += delta;
+        if (flags & FLAG_END) {
+            endTick = tick;
+            break;
+        }
+        Record record = {tick, {}};
+        InputFrame& frame = record.frame;
+        frame.steering = flags & FLAG_STEERING;
+        if (!reader.byte(frame.held) || !reader.byte(frame.pressed) || !reader.int16(frame.stick) || !reader.byte(frame.fine)
+            || (frame.steering && !reader.int16(frame.steerTarget))) {
+            error = name + " is truncated";
+            return false;
+        }
+        records.push_back(record);
+    }
+
+    next = 0;
+    current = InputFrame();
+    playing = true;
+    return true;
+}
+
+bool InputPlayback::isPlaying() {
+    return playing;
+}
+
+void InputPlayback::stop() {
+    playing = false;
+}
+
+void InputPlayback::rewind() {
+    next = 0;
+    current = InputFrame();
+    playing = true;
+}
+
+uint32_t InputPlayback::getTickRate() {
+    return tickRate;
+}
+
+uint64_t InputPlayback::getEndTick() {
+    return endTick;
+}
+
+InputFrame InputPlayback::frameAt(uint64_t tick) {
+    bool changed = false;
+    while (next < records.size() && records[next].tick <= tick) {
+        current = records[next].frame;
+        changed = records[next].tick == tick;
+        next++;
+    }
+    InputFrame frame = current;
+    // presses belong to the tick they were recorded on only
+    if (!changed) {
+        frame.pressed = 0;
+    }
+    return frame;
+}
+
+bool InputPlayback::isFinished(uint64_t tick) {
+    return !playing || tick >= endTick;
+}
@@ -0,0 +1,86 @@
+#pragma once
+#include <cstdint>
+#include <string>
+#include <vector>
+#include "Bindings.hpp"
+
+
+// Everything one simulation tick needs from the player, already resolved
+// from whatever devices produced it. Analog values are stored quantized, and
+// live play goes through the same quantization, so a recorded run replays
+// bit for bit.
+struct InputFrame {
+    uint8_t held = 0;    // one bit per Action
+    uint8_t pressed = 0; // edges; only the first tick of a frame sees them
+    int16_t stick = 0;   // analog paddle velocity, -32767..32767
+    uint8_t fine = 0;    // trigger fine control, 0..255
+    bool steering = false;
+    int16_t steerTarget = 0; // paddle center the mouse/touch wants, quarter units
+
+    void setHeld(Action action, bool down);
+    void setPressed(Action action, bool down);
+    void setStick(float value);
+    void setFine(float value);
+    void setSteer(float x);
+
+    bool isHeld(Action action) const;
+    bool wasPressed(Action action) const;
+    float getStick() const;
+    float getFine() const;
+    float getSteerTarget() const;
+    // Left for right: the move actions swapped, the stick turned around and
+    // the steer target reflected across a field this wide (reversed controls).
+    InputFrame mirrored(float width) const;
+
+    bool operator==(const InputFrame& other) const;
+    bool operator!=(const InputFrame& other) const;
+};
+
+// Input files ("BKIN") hold one record per tick where the input changed: a
+// varint tick delta followed by the frame, so idle stretches cost nothing.
+// A final record marks the tick the recording ended on.
+class InputRecorder {
+  public:
+    void start(uint32_t tickRate);
+    void record(uint64_t tick, const InputFrame& frame);
+    bool isRecording();
+    // Stops and returns the finished stream, for embedding in another file.
+    const std::vector<uint8_t>& finish(uint64_t endTick);
+    // Writes everything recorded to path and stops.
+    bool save(const std::string& path, uint64_t endTick);
+
+  private:
+    std::vector<uint8_t> data;
+    InputFrame last;
+    uint64_t lastTick = 0;
+    bool recording = false;
+    bool first = true;
+};
+
+class InputPlayback {
+  public:
+    bool load(const std::string& path, std::string& error);
+    // A stream already in memory; name is only used in errors.
+    bool parse(const std::vector<uint8_t>& data, const std::string& name, std::string& error);
+    bool isPlaying();
+    void stop();
+    // Back to the start, so ticks can be asked for from 0 again (seeking).
+    void rewind();
+    uint32_t getTickRate();
+    uint64_t getEndTick();
+    // Ticks must be asked for in increasing order, as the simulation runs.
+    InputFrame frameAt(uint64_t tick);
+    bool isFinished(uint64_t tick);
+
+  private:
+    struct Record {
+        uint64_t tick;
+        InputFrame frame;
+    };
+    std::vector<Record> records;
+    uint64_t endTick = 0;
+    uint32_t tickRate = 0;
+    size_t next = 0;
+    InputFrame current;
+    bool playing = false;
+};
@@ -0,0 +1,194 @@
+#include "raylib.h"
+#include "Inspect.hpp"
+#include <cstdio>
+#include <cstdlib>
+#include <cstring>
+#include <fstream>
+#include "Challenge.hpp"
+#include "InputRecord.hpp"
+#include "Level.hpp"
+#include "Log.hpp"
+#include "Replay.hpp"
+#include "Sanitize.hpp"
+#include "Simulation.hpp"
+
+
+static std::string hex(uint64_t value) {
+    return TextFormat("\"%016llx\"", (unsigned long long) value);
+}
+
+static int printError(const std::string& path, const char* type, const std::string& error) {
+    printf("{\"file\": %s, \"type\": \"%s\", \"error\": %s}\n", jsonString(path).c_str(), type, jsonString(error).c_str());
+    return 2;
+}
+
+static int inspectReplay(const std::string& path) {
+    ReplayPlayer replay;
+    std::string error;
+    if (!replay.load(path, error)) {
+        return printError(path, "replay", error);
+    }
+    const ReplayHeader& header = replay.getHeader();
+
+    std::vector<Brick> bricks;
+    DropTable drops = DropTable::standard();
+    Boss boss;
+    LevelPhysics physics;
+    bool levelFound = true;
+    if (header.levelPath.empty()) {
+        createBricks(bricks);
+    } else {
+        Level level;
+        levelFound = loadDailyLevel(header.levelPath, level) || loadLevel(header.levelPath, level, error);
+        bricks = level.bricks;
+        drops = level.drops;
+        boss = level.boss;
+        physics = level.physics;
+    }
+    bool levelMatches = levelFound && levelHash(bricks) == header.levelHash;
+
+    printf("{\"file\": %s, \"type\": \"replay\", \"format\": %d, \"simulation_version\": %u, \"tick_rate\": %u, \"seed\": %llu, ",
+        jsonString(path).c_str(), replay.getFormatVersion(), header.simulationVersion, header.tickRate, (unsigned long long) header.seed);
+    printf("\"level\": %s, \"level_hash\": %s, \"level_found\": %s, \"level_matches\": %s, \"mode\": %s, \"mutators\": %s, \"cosmetics\": %s, ",
+        jsonString(header.levelPath.empty() ? "built-in" : header.levelPath).c_str(), hex(header.levelHash).c_str(),
+        levelFound ? "true" : "false", levelMatches ? "true" : "false", jsonString(header.mode).c_str(), jsonString(header.mutators).c_str(),
+        jsonString(header.cosmetics).c_str());
+    const SimulationRules& rules = header.rules;
+    printf("\"rules\": {\"width\": %d, \"height\": %d, \"input_mode\": %d, \"auto_launch\": %s, \"scan_speed\": %.3f, \"input_buffer_ticks\": %d}, ",
+        rules.width, rules.height, rules.inputMode, rules.autoLaunch ? "true" : "false", rules.scanSpeed, rules.inputBufferTicks);
+    printf("\"ticks\": %llu, \"checkpoints\": %d, ", (unsigned long long) replay.getEndTick(), (int) replay.getCheckpointCount());
+    if (replay.hasResult()) {
+        const ReplayResult& claimed = replay.getResult();
+        printf("\"claimed\": {\"score\": %d, \"lives\": %d, \"checksum\": %s}, ", claimed.score, claimed.lives, hex(claimed.checksum).c_str());
+    } else {
+        printf("\"claimed\": null, ");
+    }
+
+    // a different layout can't reproduce anything, so don't pretend to check it
+    if (!levelMatches) {
+        printf("\"replayed\": null, \"valid\": false}\n");
+        return 1;
+    }
+    Simulation simulation;
+    simulation.rules = header.rules;
+    simulation.dropTable = drops;
+    simulation.boss = boss;
+    simulation.physics = physics;
+    simulation.reset(bricks, header.seed);
+    while (!replay.isFinished(simulation)) {
+        replay.step(simulation, nullptr);
+    }
+    const GameState& game = simulation.state;
+    uint64_t checksum = simulation.checksum();
+    bool valid = !replay.hasDiverged();
+    if (replay.hasResult()) {
+        const ReplayResult& claimed = replay.getResult();
+        valid = valid && claimed.score == game.score && claimed.lives == game.lives && claimed.checksum == checksum;
+    }
+    printf("\"replayed\": {\"score\": %d, \"lives\": %d, \"checksum\": %s, \"diverged_at\": %s}, \"valid\": %s}\n",
+        game.score, game.lives, hex(checksum).c_str(),
+        replay.hasDiverged() ? TextFormat("%llu", (unsigned long long) replay.getDivergedTick()) : "null", valid ? "true" : "false");
+    return valid ? 0 : 1;
+}
+
+static int inspectInput(const std::string& path) {
+    InputPlayback playback;
+    std::string error;
+    if (!playback.load(path, error)) {
+        return printError(path, "input", error);
+    }
+    double seconds = playback.getTickRate() > 0 ? (double) playback.getEndTick() / playback.getTickRate() : 0;
+    printf("{\"file\": %s, \"type\": \"input\", \"tick_rate\": %u, \"ticks\": %llu, \"seconds\": %.3f}\n",
+        jsonString(path).c_str(), playback.getTickRate(), (unsigned long long) playback.getEndTick(), seconds);
+    return 0;
+}
+
+static int inspectLevel(const std::string& path) {
+    Level level;
+    std::string error;
+    if (!loadLevel(path, level, error)) {
+        return printError(path, "level", error);
+    }
+    printf("{\"file\": %s, \"type\": \"level\", \"name\": %s, \"theme\": %s, \"music\": %s, \"bricks\": %d, \"hash\": %s, \"problems\": [",
+        jsonString(path).c_str(), jsonString(level.name).c_str(), jsonString(level.theme).c_str(), jsonString(level.music).c_str(),
+        (int) level.bricks.size(), hex(levelHash(level.bricks)).c_str());
+    const char* separator = "";
+    for (const std::string& problem : validateLevel(level)) {
+        printf("%s%s", separator, jsonString(problem).c_str());
+        separator = ", ";
+    }
+    printf("]}\n");
+    return 0;
+}
+
+static std::string trim(const std::string& text) {
+    size_t start = text.find_first_not_of(" \t\r");
+    if (start == std::string::npos) {
+        return "";
+    }
+    return text.substr(start, text.find_last_not_of(" \t\r") - start + 1);
+}
+
+// true/false and numbers keep their type, everything else is a string
+static std::string jsonValue(const std::string& value) {
+    if (value == "true" || value == "false") {
+        return value;
+    }
+    // strtod alone would also take "inf" and "0x1f", which JSON doesn't
+    char* end = nullptr;
+    strtod(value.c_str(), &end);
+    if (!value.empty() && *end == '\0' && value.find_first_not_of("+-.0123456789eE") == std::string::npos) {
+        return value;
+    }
+    return jsonString(value);
+}
+
+static int inspectConfig(const std::string& path) {
+    std::ifstream file(path);
+    if (!file) {
+        return printError(path, "config", "could not open " + path);
+    }
+    printf("{\"file\": %s, \"type\": \"config\", \"values\": {", jsonString(path).c_str());
+    const char* separator = "";
+    std::string line;
+    while (std::getline(file, line)) {
+        line = trim(line);
+        size_t equals = line.find('=');
+        if (line.empty() || line[0] == '#' || equals == std::string::npos) {
+            continue;
+        }
+        printf("%s%s: %s", separator, jsonString(trim(line.substr(0, equals))).c_str(), jsonValue(trim(line.substr(equals + 1))).c_str());
+        separator = ", ";
+    }
+    printf("}}\n");
+    return 0;
+}
+
+int runInspect(const LaunchOptions& options) {
+    // stdout carries the JSON only
+    installLogging(stderr);
+    const std::string& path = options.inspectPath;
+    if (path.empty()) {
+        fprintf(stderr, "Usage: breakout inspect <file>\n");
+        return 2;
+    }
+    char magic[4] = {};
+    std::ifstream file(path, std::ios::binary);
+    if (!file) {
+        return printError(path, "unknown", "could not open " + path);
+    }
+    file.read(magic, 4);
+    if (memcmp(magic, "BKRP", 4) == 0) {
+        return inspectReplay(path);
+    }
+    if (memcmp(magic, "BKIN", 4) == 0) {
+        return inspectInput(path);
+    }
+    if (IsFileExtension(path.c_str(), ".blvl")) {
+        return inspectLevel(path);
+    }
+    if (IsFileExtension(path.c_str(), ".cfg")) {
+        return inspectConfig(path);
+    }
+    return printError(path, "unknown", "not a replay, input recording, level or config file");
+}
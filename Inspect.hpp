@@ -0,0 +1,17 @@
+#pragma once
+#include "Args.hpp"
+
+
+// `breakout inspect <file>`: prints what a file holds as one JSON object on
+// stdout, for support tickets and for checking a file before trusting it.
+//
+//   .bkrp   the replay header and result, then the run simulated again
+//           without a window to check every checkpoint and the claimed score
+//   .bkin   an input recording's tick rate and length
+//   .blvl   a level's name, bricks, hash and validation problems
+//   .cfg    settings and profiles, key = value turned into JSON
+//
+// Files are told apart by their first bytes where they have a magic. Returns
+// 0 when the file checks out, 1 when it doesn't (a replay that diverges or
+// claims another score) and 2 when it can't be read at all.
+int runInspect(const LaunchOptions& options);
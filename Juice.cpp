@@ -0,0 +1,115 @@
+#include "raylib.h"
+#include "Juice.hpp"
+#include <cmath>
+
+
+static const float MAX_HIT_STOP = 0.25f;
+
+void Juice::apply(const Settings& settings) {
+    enabled = settings.juice;
+    intensity = settings.juiceIntensity;
+    reducedMotion = settings.reducedMotion;
+    if (reducedMotion) {
+        shakes.clear();
+    }
+    if (!enabled) {
+        reset();
+    }
+}
+
+void Juice::onEvent(const GameEvent& event) {
+    switch (event.type) {
+    case EventType::BrickDestroyed:
+        shake({2, 30, 10});
+        break;
+    case EventType::BrickHit:
+        shake({1, 30, 10});
+        break;
+    case EventType::PaddleBounce:
+        shake({1, 20, 14});
+        break;
+    case EventType::BallLost:
+        shake({10, 18, 4});
+        hitStop(0.15f);
+        break;
+    case EventType::ExtraBallLost:
+        shake({3, 20, 8});
+        break;
+    case EventType::BossHit:
+        shake({event.value < 0 ? 1.0f : 3.0f, 30, 10});
+        break;
+    case EventType::BossDefeated:
+        shake({12, 18, 3});
+        hitStop(0.2f);
+        break;
+    case EventType::PaddleDamaged:
+        shake({5, 24, 6});
+        break;
+    case EventType::FieldShifted:
+        shake({2, 12, 8});
+        break;
+    case EventType::DifficultyRaised:
+        shake({3, 18, 6});
+        break;
+    case EventType::GarbageReceived:
+        shake({4, 16, 8});
+        break;
+    default:
+        break;
+    }
+}
+
+void Juice::shake(ShakeParams params) {
+    if (!enabled || intensity <= 0 || reducedMotion) {
+        return;
+    }
+    params.amplitude *= intensity;
+    shakes.push_back({params, 0, GetRandomValue(0, 628) / 100.0f});
+}
+
+void Juice::hitStop(float seconds) {
+    if (!enabled || intensity <= 0) {
+        return;
+    }
+    freezeTimer = fmaxf(freezeTimer, fminf(seconds * intensity, MAX_HIT_STOP));
+}
+
+// Takes real (unscaled) frame time so hit-stop itself doesn't freeze the shake.
+void Juice::update(float deltaTime) {
+    if (freezeTimer > 0) {
+        freezeTimer -= deltaTime;
+    }
+
+    offset = {0, 0};
+    for (size_t i = 0; i < shakes.size();) {
+        Shake& current = shakes[i];
+        current.time += deltaTime;
+        float amplitude = current.params.amplitude * expf(-current.params.decay * current.time);
+        if (amplitude < 0.1f) {
+            shakes[i] = shakes.back();
+            shakes.pop_back();
+            continue;
+        }
+        float angle = 2 * PI * current.params.frequency * current.time + current.phase;
+        offset.x += amplitude * sinf(angle);
+        offset.y += amplitude * sinf(angle * 1.3f + 1.7f);
+        i++;
+    }
+}
+
+bool Juice::isFrozen() {
+    return freezeTimer > 0;
+}
+
+Camera2D Juice::getCamera() {
+    Camera2D camera = {};
+    camera.offset = offset;
+    camera.zoom = 1;
+    return camera;
+}
+
+void Juice::reset() {
+    shakes.clear();
+    freezeTimer = 0;
+    offset = {0, 0};
+}
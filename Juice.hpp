@@ -0,0 +1,43 @@
+#pragma once
+#include "raylib.h"
+#include <vector>
+#include "Events.hpp"
+#include "Settings.hpp"
+
+
+struct ShakeParams {
+    float amplitude; // pixels
+    float frequency; // oscillations per second
+    float decay;     // exponential falloff per second
+};
+
+// Screen shake and hit-stop. Effects are triggered from gameplay events and
+// scaled by the intensity setting; with juice turned off nothing is applied.
+// Reduced motion drops the shake but keeps the (motionless) hit-stop.
+class Juice {
+  public:
+    void apply(const Settings& settings);
+    void onEvent(const GameEvent& event);
+
+    void shake(ShakeParams params);
+    void hitStop(float seconds);
+
+    void update(float deltaTime);
+    bool isFrozen();
+    Camera2D getCamera();
+    void reset();
+
+  private:
+    struct Shake {
+        ShakeParams params;
+        float time;
+        float phase;
+    };
+
+    std::vector<Shake> shakes;
+    float freezeTimer = 0;
+    float intensity = 1;
+    bool enabled = true;
+    bool reducedMotion = false;
+    Vector2 offset = {0, 0};
+};
@@ -0,0 +1,204 @@
+#include "raylib.h"
+#include "LayeredMusic.hpp"
+#include "Xml.hpp"
+#include <cmath>
+
+
+std::string musicTrackPath(const std::string& id) {
+    if (id.empty()) {
+        return "";
+    }
+    std::string root = GetApplicationDirectory();
+    std::string bundled = root + "music/" + id + "/track.xml";
+    if (FileExists(bundled.c_str())) {
+        return bundled;
+    }
+    std::string found;
+    std::string mods = root + "mods";
+    if (DirectoryExists(mods.c_str())) {
+        FilePathList modDirectories = LoadDirectoryFiles(mods.c_str());
+        for (unsigned int i = 0; i < modDirectories.count && found.empty(); i++) {
+            std::string path = std::string(modDirectories.paths[i]) + "/music/" + id + "/track.xml";
+            if (FileExists(path.c_str())) {
+                found = path;
+            }
+        }
+        UnloadDirectoryFiles(modDirectories);
+    }
+    return found;
+}
+
+float musicIntensity(int combo, int bricksLeft, int bricksTotal, int bossPhase) {
+    float comboPart = fminf(combo / 8.0f, 1.0f);
+    float clearedPart = bricksTotal > 0 ? 1 - (float) bricksLeft / bricksTotal : 0;
+    float bossPart = fminf(bossPhase / 3.0f, 1.0f);
+    return fminf(fmaxf(fmaxf(comboPart, clearedPart * 0.8f), bossPart), 1.0f);
+}
+
+// the player itself needs raylib's audio module
+#ifndef BREAKOUT_NO_AUDIO
+
+bool LayeredMusic::load(const std::string& trackPath) {
+    unload();
+    XmlNode root;
+    XmlError error;
+    if (!FileExists(trackPath.c_str())) {
+        return false;
+    }
+    if (!loadXmlFile(trackPath, root, error) || root.name != "track") {
+        TraceLog(LOG_WARNING, "MUSIC: Skipping %s: %s", trackPath.c_str(),
+            error.message.empty() ? "root element must be <track>" : error.describe().c_str());
+        return false;
+    }
+    std::string directory = GetDirectoryPath(trackPath.c_str());
+    name = root.attribute("name", GetFileName(directory.c_str()));
+    bpm = root.attributeFloat("bpm", 120);
+    beatsPerBar = root.attributeInt("beats-per-bar", 4);
+
+    for (const XmlNode* node : root.childrenNamed("stem")) {
+        MusicStem stem;
+        stem.name = node->attribute("name");
+        stem.file = directory + "/" + node->attribute("file");
+        stem.threshold = node->attributeFloat("intensity", 0);
+        stem.stream = LoadMusicStream(stem.file.c_str());
+        if (!IsMusicReady(stem.stream)) {
+            TraceLog(LOG_WARNING, "MUSIC: Could not load stem %s of %s", stem.file.c_str(), name.c_str());
+            continue;
+        }
+        stem.stream.looping = true;
+        // a track loaded mid slow motion comes in at the same speed
+        SetMusicPitch(stem.stream, pitch);
+        stems.push_back(stem);
+    }
+    if (stems.empty()) {
+        TraceLog(LOG_WARNING, "MUSIC: Track %s has no playable stems", trackPath.c_str());
+        return false;
+    }
+    applyTargets();
+    for (MusicStem& stem : stems) {
+        stem.volume = stem.target; // start in the right state instead of fading in
+    }
+    return true;
+}
+
+void LayeredMusic::unload() {
+    for (MusicStem& stem : stems) {
+        UnloadMusicStream(stem.stream);
+    }
+    stems.clear();
+    playing = false;
+    pending = false;
+}
+
+bool LayeredMusic::isLoaded() {
+    return !stems.empty();
+}
+
+void LayeredMusic::play() {
+    for (MusicStem& stem : stems) {
+        PlayMusicStream(stem.stream);
+    }
+    playing = true;
+    lastPosition = 0;
+}
+
+void LayeredMusic::stop() {
+    for (MusicStem& stem : stems) {
+        StopMusicStream(stem.stream);
+    }
+    playing = false;
+}
+
+void LayeredMusic::pause() {
+    for (MusicStem& stem : stems) {
+        PauseMusicStream(stem.stream);
+    }
+    playing = false;
+}
+
+void LayeredMusic::resume() {
+    for (MusicStem& stem : stems) {
+        ResumeMusicStream(stem.stream);
+    }
+    playing = true;
+}
+
+void LayeredMusic::seek(float position) {
+    for (MusicStem& stem : stems) {
+        SeekMusicStream(stem.stream, position);
+    }
+    lastPosition = position;
+}
+
+void LayeredMusic::setIntensity(float value) {
+    if (value != intensity) {
+        intensity = value;
+        pending = true;
+    }
+}
+
+void LayeredMusic::setPitch(float value) {
+    if (value == pitch) {
+        return;
+    }
+    pitch = value;
+    for (MusicStem& stem : stems) {
+        SetMusicPitch(stem.stream, pitch);
+    }
+}
+
+float LayeredMusic::barLength() {
+    return 60.0f / (bpm > 0 ? bpm : 120) * beatsPerBar;
+}
+
+void LayeredMusic::applyTargets() {
+    for (MusicStem& stem : stems) {
+        stem.target = intensity >= stem.threshold ? 1 : 0;
+    }
+    pending = false;
+}
+
+void LayeredMusic::update(float deltaTime, float busGain) {
+    if (!playing || stems.empty()) {
+        return;
+    }
+    for (MusicStem& stem : stems) {
+        UpdateMusicStream(stem.stream);
+    }
+
+    float position = GetMusicTimePlayed(stems[0].stream);
+    if (pending) {
+        float bar = barLength();
+        // crossed a bar line since last frame (or the track looped)
+        if (floorf(position / bar) != floorf(lastPosition / bar) || position < lastPosition) {
+            applyTargets();
+        }
+    }
+    // streams drift apart slightly over long sessions; pull them back on loop
+    if (position < lastPosition) {
+        for (size_t i = 1; i < stems.size(); i++) {
+            SeekMusicStream(stems[i].stream, position);
+        }
+    }
+    lastPosition = position;
+
+    float fadeStep = deltaTime / (60.0f / (bpm > 0 ? bpm : 120));
+    for (MusicStem& stem : stems) {
+        if (stem.volume < stem.target) {
+            stem.volume = fminf(stem.volume + fadeStep, stem.target);
+        } else if (stem.volume > stem.target) {
+            stem.volume = fmaxf(stem.volume - fadeStep, stem.target);
+        }
+        SetMusicVolume(stem.stream, stem.volume * busGain);
+    }
+}
+
+std::string LayeredMusic::getName() {
+    return name;
+}
+
+float LayeredMusic::getPosition() {
+    return stems.empty() ? 0 : GetMusicTimePlayed(stems[0].stream);
+}
+
+#endif
@@ -0,0 +1,68 @@
+#pragma once
+#include "raylib.h"
+#include <string>
+#include <vector>
+
+
+// One stem of a layered track. A stem is heard once the game's intensity
+// reaches its threshold; the base stem has threshold 0 and always plays.
+struct MusicStem {
+    std::string name;
+    std::string file;
+    float threshold = 0;
+    Music stream = {};
+    float volume = 0;
+    float target = 0;
+};
+
+// music/<id>/track.xml:
+//   <track name="Neon Drive" bpm="120" beats-per-bar="4">
+//     <stem name="base" file="base.ogg" intensity="0"/>
+//     <stem name="percussion" file="percussion.ogg" intensity="0.35"/>
+//     <stem name="lead" file="lead.ogg" intensity="0.7"/>
+//   </track>
+// All stems are equally long and play in lockstep. Layer changes wait for
+// the next bar line and then fade over one beat, so layers come in on the
+// music instead of mid-phrase.
+class LayeredMusic {
+  public:
+    bool load(const std::string& trackPath);
+    void unload();
+    bool isLoaded();
+
+    void play();
+    void stop();
+    void pause();
+    void resume();
+    void seek(float position);
+
+    // 0 = calm, 1 = everything playing; see musicIntensity()
+    void setIntensity(float intensity);
+    void setPitch(float pitch);
+    void update(float deltaTime, float busGain);
+
+    std::string getName();
+    float getPosition();
+
+  private:
+    std::string name;
+    std::vector<MusicStem> stems;
+    float bpm = 120;
+    int beatsPerBar = 4;
+    float intensity = 0;
+    bool pending = false; // a layer change is waiting for the next bar
+    float lastPosition = 0;
+    bool playing = false;
+    float pitch = 1;
+
+    float barLength();
+    void applyTargets();
+};
+
+// Maps the state of play onto a music intensity: long combos, few bricks
+// left and later boss phases all push it up.
+float musicIntensity(int combo, int bricksLeft, int bricksTotal, int bossPhase = 0);
+
+// Finds music/<id>/track.xml, or mods/<mod>/music/<id>/track.xml for tracks
+// shipped by mods. Returns an empty string if no such track exists.
+std::string musicTrackPath(const std::string& id);
@@ -0,0 +1,217 @@
+#include "raylib.h"
+#include "Leaderboard.hpp"
+#include "FramePacer.hpp"
+#include "Sanitize.hpp"
+#include <algorithm>
+#include <cstdio>
+#include <cstdlib>
+#include <ctime>
+#include <filesystem>
+#include <fstream>
+#include <sstream>
+
+
+static const char* const HEADER = "# breakout leaderboard 1";
+
+static std::string applicationDirectory(const std::string& name) {
+    std::string directory = std::string(GetApplicationDirectory()) + name + "/";
+    std::error_code error;
+    std::filesystem::create_directories(directory, error);
+    return directory;
+}
+
+LeaderboardKey globalLeaderboardKey() {
+    LeaderboardKey key;
+    key.mode.clear();
+    key.global = true;
+    return key;
+}
+
+std::string leaderboardPath(const LeaderboardKey& key) {
+    if (key.global) {
+        return applicationDirectory("leaderboards") + "global.txt";
+    }
+    std::string name = TextFormat("%016llx-%s", (unsigned long long) key.levelHash, key.mode.c_str());
+    if (!key.mutators.empty()) {
+        name += "-" + key.mutators;
+    }
+    return applicationDirectory("leaderboards") + sanitizeFilename(name + ".txt");
+}
+
+std::string leaderboardReplayPath(const std::string& levelName) {
+    char stamp[32];
+    time_t now = time(nullptr);
+    strftime(stamp, sizeof(stamp), "%Y%m%d-%H%M%S", localtime(&now));
+    return applicationDirectory("replays") + sanitizeFilename(levelName + "-" + stamp + ".bkrp");
+}
+
+std::string formatRunTime(uint64_t ticks) {
+    uint64_t hundredths = ticks * 100 / FramePacer::TICK_RATE;
+    return TextFormat("%d:%02d.%02d", (int) (hundredths / 6000), (int) (hundredths / 100 % 60), (int) (hundredths % 100));
+}
+
+// Better runs first; what is still tied goes to whoever got there first,
+// since the ranking sorts stably.
+static bool ranksAbove(const LeaderboardEntry& a, const LeaderboardEntry& b) {
+    if (a.score != b.score) {
+        return a.score > b.score;
+    }
+    if (a.cleared != b.cleared) {
+        return a.cleared;
+    }
+    return a.ticks < b.ticks;
+}
+
+// One run per line, tab separated; names can't hold a tab, so none are
+// escaped. Boards from before the name and the columns after it have the
+// profile as the name.
+//   score  ticks  cleared  date  profile  replay  name  level  mode  mutators
+bool Leaderboard::load(const LeaderboardKey& boardKey) {
+    key = boardKey;
+    runs.clear();
+    sortedScores.clear();
+    std::ifstream file(leaderboardPath(key));
+    if (!file) {
+        return true;
+    }
+    std::string line;
+    if (!std::getline(file, line) || line != HEADER) {
+        TraceLog(LOG_WARNING, "LEADERBOARD: %s is not a leaderboard, starting a new one", leaderboardPath(key).c_str());
+        return false;
+    }
+    while (std::getline(file, line)) {
+        std::istringstream fields(line);
+        std::string score, ticks, cleared;
+        LeaderboardEntry entry;
+        if (!std::getline(fields, score, '\t') || !std::getline(fields, ticks, '\t') || !std::getline(fields, cleared, '\t')) {
+            continue;
+        }
+        std::getline(fields, entry.date, '\t');
+        std::getline(fields, entry.profile, '\t');
+        std::getline(fields, entry.replay, '\t');
+        std::getline(fields, entry.name, '\t');
+        std::getline(fields, entry.level, '\t');
+        std::getline(fields, entry.mode, '\t');
+        std::getline(fields, entry.mutators, '\t');
+        if (entry.name.empty()) {
+            entry.name = entry.profile;
+        }
+        entry.score = atoi(score.c_str());
+        entry.ticks = strtoull(ticks.c_str(), nullptr, 10);
+        entry.cleared = cleared == "1";
+        runs.push_back(entry);
+        sortedScores.insert(std::upper_bound(sortedScores.begin(), sortedScores.end(), entry.score), entry.score);
+    }
+    return true;
+}
+
+bool Leaderboard::save() {
+    std::string path = leaderboardPath(key);
+    std::ofstream file(path);
+    if (!file) {
+        TraceLog(LOG_WARNING, "LEADERBOARD: Could not write %s", path.c_str());
+        return false;
+    }
+    file << HEADER << "\n";
+    for (const LeaderboardEntry& entry : runs) {
+        file << entry.score << '\t' << entry.ticks << '\t' << (entry.cleared ? 1 : 0) << '\t'
+             << entry.date << '\t' << entry.profile << '\t' << entry.replay << '\t' << entry.name << '\t' << entry.level << '\t'
+             << entry.mode << '\t' << entry.mutators << "\n";
+    }
+    return true;
+}
+
+// Indices into runs, best first.
+std::vector<int> Leaderboard::ranking() const {
+    std::vector<int> order(runs.size());
+    for (size_t i = 0; i < runs.size(); i++) {
+        order[i] = (int) i;
+    }
+    std::stable_sort(order.begin(), order.end(), [this](int a, int b) { return ranksAbove(runs[a], runs[b]); });
+    return order;
+}
+
+bool Leaderboard::qualifies(const LeaderboardEntry& entry) const {
+    std::vector<int> order = ranking();
+    return (int) order.size() < TOP_ENTRIES || ranksAbove(entry, runs[order[TOP_ENTRIES - 1]]);
+}
+
+int Leaderboard::submit(const LeaderboardEntry& entry) {
+    runs.push_back(entry);
+    if (runs.back().date.empty()) {
+        char date[16];
+        time_t now = time(nullptr);
+        strftime(date, sizeof(date), "%Y-%m-%d", localtime(&now));
+        runs.back().date = date;
+    }
+    LeaderboardEntry& added = runs.back();
+    if (added.name.empty()) {
+        added.name = added.profile;
+    }
+    // the file is tab separated
+    std::replace(added.name.begin(), added.name.end(), '\t', ' ');
+    std::replace(added.level.begin(), added.level.end(), '\t', ' ');
+    sortedScores.insert(std::upper_bound(sortedScores.begin(), sortedScores.end(), entry.score), entry.score);
+
+    std::vector<int> order = ranking();
+    if ((int) runs.size() > MAX_RUNS) {
+        // the oldest run below the top list; its replay goes with it
+        int forgotten = *std::min_element(order.begin() + TOP_ENTRIES, order.end());
+        if (!runs[forgotten].replay.empty()) {
+            remove(runs[forgotten].replay.c_str());
+        }
+        sortedScores.erase(std::lower_bound(sortedScores.begin(), sortedScores.end(), runs[forgotten].score));
+        runs.erase(runs.begin() + forgotten);
+        order = ranking();
+    }
+    int last = (int) runs.size() - 1;
+    for (int place = 0; place < TOP_ENTRIES && place < (int) order.size(); place++) {
+        if (order[place] == last) {
+            return place;
+        }
+    }
+    return -1;
+}
+
+void Leaderboard::nameLatest(const std::string& name) {
+    if (runs.empty()) {
+        return;
+    }
+    runs.back().name = name;
+    std::replace(runs.back().name.begin(), runs.back().name.end(), '\t', ' ');
+}
+
+const LeaderboardKey& Leaderboard::getKey() const {
+    return key;
+}
+
+std::vector<LeaderboardEntry> Leaderboard::top() const {
+    std::vector<LeaderboardEntry> entries;
+    for (int index : ranking()) {
+        if ((int) entries.size() == TOP_ENTRIES) {
+            break;
+        }
+        entries.push_back(runs[index]);
+    }
+    return entries;
+}
+
+int Leaderboard::getRunCount() const {
+    return (int) runs.size();
+}
+
+int Leaderboard::percentile(float fraction) const {
+    if (sortedScores.empty()) {
+        return 0;
+    }
+    fraction = fraction < 0 ? 0 : (fraction > 1 ? 1 : fraction);
+    return sortedScores[(size_t) (fraction * (sortedScores.size() - 1) + 0.5f)];
+}
+
+float Leaderboard::shareBelow(int score) const {
+    if (sortedScores.empty()) {
+        return 0;
+    }
+    size_t below = std::lower_bound(sortedScores.begin(), sortedScores.end(), score) - sortedScores.begin();
+    return (float) below / sortedScores.size();
+}
@@ -0,0 +1,77 @@
+#pragma once
+#include <cstdint>
+#include <string>
+#include <vector>
+
+
+// Which board a run belongs on. Levels are told apart by their layout hash,
+// so a renamed or moved file keeps its scores and an edited one starts over.
+// The global board has every run of every level, mode and mutator on it.
+struct LeaderboardKey {
+    uint64_t levelHash = 0;
+    std::string mode = "classic";
+    std::string mutators; // comma separated, empty without
+    bool global = false;
+};
+
+LeaderboardKey globalLeaderboardKey();
+
+struct LeaderboardEntry {
+    int score = 0;
+    uint64_t ticks = 0;  // how long the run took, in simulation ticks
+    bool cleared = false;
+    std::string profile;
+    std::string date;    // YYYY-MM-DD
+    std::string replay;  // path of the run's replay, empty when none was kept
+    std::string name;    // typed in when the run made a top list, the profile's until then
+    std::string level;   // the level's name, the challenge's for a challenge
+    std::string mode;
+    std::string mutators;
+};
+
+// leaderboards/<hash>-<mode>[-<mutators>].txt next to the executable, or
+// leaderboards/global.txt.
+std::string leaderboardPath(const LeaderboardKey& key);
+// Where a run that makes a board keeps its replay.
+std::string leaderboardReplayPath(const std::string& levelName);
+// "1:23.45"
+std::string formatRunTime(uint64_t ticks);
+
+// Every finished run on one board, for the top list as well as context
+// ("better than 80% of your runs"). Runs rank by score, then a cleared run
+// over one that wasn't, then by time; a tie after that goes to whoever got
+// there first. The scores are also kept sorted, so the median and
+// percentiles are a lookup.
+// Past MAX_RUNS the oldest run that isn't in the top list is forgotten.
+class Leaderboard {
+  public:
+    static const int TOP_ENTRIES = 10;
+    static const int MAX_RUNS = 1000;
+
+    // A board without a file yet is empty, not an error.
+    bool load(const LeaderboardKey& key);
+    bool save();
+
+    // Whether the run would make the top list, i.e. is worth keeping a replay of.
+    bool qualifies(const LeaderboardEntry& entry) const;
+    // Adds a run, dated today unless it says otherwise; returns its place in
+    // the top list (0 is first) or -1.
+    int submit(const LeaderboardEntry& entry);
+    // Puts name on the run submitted last, once it's been typed in.
+    void nameLatest(const std::string& name);
+
+    const LeaderboardKey& getKey() const;
+    std::vector<LeaderboardEntry> top() const;
+    int getRunCount() const;
+    // The score at fraction (0..1) of the way up the runs; 0.5 is the median.
+    int percentile(float fraction) const;
+    // How many of the runs scored less, 0..1.
+    float shareBelow(int score) const;
+
+  private:
+    LeaderboardKey key;
+    std::vector<LeaderboardEntry> runs; // in the order they were played
+    std::vector<int> sortedScores;      // ascending
+
+    std::vector<int> ranking() const;
+};
@@ -0,0 +1,189 @@
+#include "raylib.h"
+#include "LeaderboardScreen.hpp"
+#include "Localization.hpp"
+#include "Mutators.hpp"
+#include "Ui.hpp"
+#include <cmath>
+
+
+void LeaderboardScreen::toggle() {
+    isOpen = !isOpen;
+    // closed halfway through, the run keeps the name it started with
+    setEnteringName(false);
+}
+
+// Escape normally closes the window; while a name is typed in it has to
+// mean "keep the one it had" instead.
+void LeaderboardScreen::setEnteringName(bool enabled) {
+    enteringName = enabled;
+    SetExitKey(enabled ? KEY_NULL : KEY_ESCAPE);
+}
+
+void LeaderboardScreen::showRun(const LeaderboardEntry& entry, int place, int globalPlace) {
+    isOpen = true;
+    hasRun = true;
+    run = entry;
+    runPlace = place;
+    runGlobalPlace = globalPlace;
+    notes.clear();
+    breakdown.clear();
+    // where the run landed, the level's board when it made both
+    showingGlobal = place < 0 && globalPlace >= 0;
+    name = entry.name;
+    setEnteringName(place >= 0 || globalPlace >= 0);
+}
+
+void LeaderboardScreen::addNote(const std::string& note) {
+    notes.push_back(note);
+}
+
+void LeaderboardScreen::setBreakdown(const std::vector<std::pair<std::string, std::string>>& figures) {
+    breakdown = figures;
+}
+
+bool LeaderboardScreen::isEnteringName() const {
+    return enteringName;
+}
+
+static std::string mutatorNames(const std::string& mutators) {
+    std::string names;
+    for (const Mutator* mutator : mutatorList(mutators)) {
+        names += (names.empty() ? "" : ", ") + tr(mutator->name);
+    }
+    return names;
+}
+
+bool LeaderboardScreen::update(std::string& typed) {
+    if (!isOpen) {
+        return false;
+    }
+    if (!enteringName) {
+        if (IsKeyPressed(KEY_LEFT) || IsKeyPressed(KEY_RIGHT)) {
+            showingGlobal = !showingGlobal;
+        }
+        return false;
+    }
+    // only what every font has, so the board reads the same in any language
+    for (int codepoint = GetCharPressed(); codepoint != 0; codepoint = GetCharPressed()) {
+        if (codepoint >= 32 && codepoint < 127 && (int) name.size() < MAX_NAME_LENGTH) {
+            name += (char) codepoint;
+        }
+    }
+    if ((IsKeyPressed(KEY_BACKSPACE) || IsKeyPressedRepeat(KEY_BACKSPACE)) && !name.empty()) {
+        name.pop_back();
+    }
+    if (IsKeyPressed(KEY_ESCAPE)) {
+        name = run.name;
+    } else if (!IsKeyPressed(KEY_ENTER) && !IsKeyPressed(KEY_KP_ENTER)) {
+        return false;
+    }
+    // a name of nothing but spaces is no name
+    if (name.find_first_not_of(' ') == std::string::npos) {
+        name = run.name;
+    }
+    setEnteringName(false);
+    run.name = name;
+    typed = name;
+    return true;
+}
+
+void LeaderboardScreen::draw(TextRenderer& text, const Leaderboard& board, const Leaderboard& global, const std::string& levelName, Rectangle bounds, float scale) {
+    if (!isOpen) {
+        return;
+    }
+    const Leaderboard& shown = showingGlobal ? global : board;
+    int place = showingGlobal ? runGlobalPlace : runPlace;
+    std::vector<LeaderboardEntry> entries = shown.top();
+    float rowHeight = 26 * scale;
+    int fontSize = 20 * scale;
+    float breakdownHeight = breakdown.empty() ? 0 : 55 * scale;
+    Vector2 size = {740 * scale, 170 * scale + breakdownHeight + rowHeight * (Leaderboard::TOP_ENTRIES + notes.size())};
+    Vector2 corner = anchorPoint(Anchor::Center, bounds, size);
+    float margin = 20 * scale;
+
+    DrawRectangle(0, 0, GetScreenWidth(), GetScreenHeight(), Fade(BLACK, 0.6f));
+    DrawRectangleRec({corner.x, corner.y, size.x, size.y}, Fade(DARKGRAY, 0.95f));
+    text.draw(tr(showingGlobal ? "All levels" : "Leaderboard"), {corner.x + margin, corner.y + 15 * scale}, 30 * scale, RAYWHITE);
+    std::string subtitle = tr("Every level, mode and mutator");
+    if (!showingGlobal) {
+        const LeaderboardKey& key = board.getKey();
+        std::string mutators = mutatorNames(key.mutators);
+        subtitle = levelName + ", " + tr(key.mode) + (mutators.empty() ? "" : " (" + mutators + ")");
+    }
+    text.draw(subtitle, {corner.x + margin + 200 * scale, corner.y + 25 * scale}, 16 * scale, GRAY);
+    std::string other = tr(showingGlobal ? "Left/right: this level" : "Left/right: all levels");
+    Vector2 otherSize = text.measure(other, 16 * scale);
+    text.draw(other, {corner.x + size.x - margin - otherSize.x, corner.y + 25 * scale}, 16 * scale, GRAY);
+
+    // the global board swaps the time and replay for where the run was played
+    static const char* COLUMNS[] = {"#", "Score", "Time", "Player", "Date", "Replay"};
+    static const float COLUMN_X[] = {0, 50, 160, 290, 480, 620};
+    static const char* GLOBAL_COLUMNS[] = {"#", "Score", "Player", "Level", "Mutators", "Date"};
+    static const float GLOBAL_COLUMN_X[] = {0, 50, 150, 310, 470, 610};
+    const char* const* columns = showingGlobal ? GLOBAL_COLUMNS : COLUMNS;
+    const float* columnX = showingGlobal ? GLOBAL_COLUMN_X : COLUMN_X;
+    for (int column = 0; column < 6; column++) {
+        text.draw(tr(columns[column]), {corner.x + margin + columnX[column] * scale, corner.y + 60 * scale}, 16 * scale, GRAY);
+    }
+    if (entries.empty()) {
+        text.draw(tr("No finished runs yet"), {corner.x + margin, corner.y + 90 * scale}, fontSize, LIGHTGRAY);
+    }
+    for (size_t i = 0; i < entries.size(); i++) {
+        const LeaderboardEntry& entry = entries[i];
+        float rowY = corner.y + 90 * scale + i * rowHeight;
+        bool isRun = hasRun && (int) i == place;
+        Color color = isRun ? YELLOW : LIGHTGRAY;
+        // the run's row shows the name as it's typed, with a blinking caret
+        std::string player = isRun && enteringName ? name + (fmod(GetTime(), 1) < 0.5 ? "_" : " ") : entry.name;
+        std::string time = formatRunTime(entry.ticks) + (entry.cleared ? "" : " *");
+        std::string mutators = mutatorNames(entry.mutators);
+        if (mutators.size() > 18) {
+            mutators = tr(mutatorList(entry.mutators).front()->name) + TextFormat(" +%d", (int) mutatorList(entry.mutators).size() - 1);
+        }
+        std::string values[6];
+        if (showingGlobal) {
+            values[0] = std::to_string(i + 1);
+            values[1] = std::to_string(entry.score);
+            values[2] = player;
+            values[3] = entry.level;
+            values[4] = mutators;
+            values[5] = entry.date;
+        } else {
+            values[0] = std::to_string(i + 1);
+            values[1] = std::to_string(entry.score);
+            values[2] = time;
+            values[3] = player;
+            values[4] = entry.date;
+            values[5] = entry.replay.empty() ? "" : tr("saved");
+        }
+        for (int column = 0; column < 6; column++) {
+            text.draw(values[column], {corner.x + margin + columnX[column] * scale, rowY}, fontSize, color);
+        }
+    }
+
+    // the breakdown's figures side by side, each under its label
+    float breakdownY = corner.y + 100 * scale + rowHeight * Leaderboard::TOP_ENTRIES;
+    for (size_t i = 0; i < breakdown.size(); i++) {
+        float x = corner.x + margin + i * (size.x - 2 * margin) / breakdown.size();
+        text.draw(breakdown[i].first, {x, breakdownY}, 16 * scale, GRAY);
+        text.draw(breakdown[i].second, {x, breakdownY + 20 * scale}, fontSize, RAYWHITE);
+    }
+
+    float footerY = corner.y + size.y - 65 * scale - rowHeight * notes.size();
+    if (enteringName) {
+        text.draw(tr("A top run! Type a name, enter to keep it"), {corner.x + margin, footerY}, 16 * scale, YELLOW);
+    } else if (shown.getRunCount() > 0) {
+        std::string context = TextFormat(trn("%d run", "%d runs", shown.getRunCount()).c_str(), shown.getRunCount())
+            + std::string("   ") + TextFormat(tr("median %d   top 10%% from %d   * not cleared").c_str(), shown.percentile(0.5f), shown.percentile(0.9f));
+        text.draw(context, {corner.x + margin, footerY}, 16 * scale, GRAY);
+    }
+    if (hasRun) {
+        std::string placeText = place >= 0 ? TextFormat("#%d", place + 1) : TextFormat(tr("not in the top %d").c_str(), Leaderboard::TOP_ENTRIES);
+        std::string summary = TextFormat(tr("This run: %d in %s, %s, better than %d%% of runs").c_str(), run.score, formatRunTime(run.ticks).c_str(),
+            placeText.c_str(), (int) (shown.shareBelow(run.score) * 100));
+        text.draw(summary, {corner.x + margin, footerY + 25 * scale}, fontSize, YELLOW);
+    }
+    for (size_t i = 0; i < notes.size(); i++) {
+        text.draw(notes[i], {corner.x + margin, footerY + 25 * scale + (i + 1) * rowHeight}, fontSize, GOLD);
+    }
+}
@@ -0,0 +1,48 @@
+#pragma once
+#include "raylib.h"
+#include <string>
+#include <vector>
+#include "Leaderboard.hpp"
+#include "Text.hpp"
+
+
+// The current level's board, on F11 or by itself when a run ends; left and
+// right switch to the global board and back. After a run, its row is
+// highlighted and the footer says where it landed among all the runs so far,
+// not just the top ten. A run that made either top list has its name typed
+// in first, the last name used to start with.
+class LeaderboardScreen {
+  public:
+    static const int MAX_NAME_LENGTH = 16;
+
+    bool isOpen = false;
+
+    void toggle();
+    // Opens the board on a run that just went onto it; place and globalPlace
+    // are what Leaderboard::submit() returned for the two boards.
+    void showRun(const LeaderboardEntry& entry, int place, int globalPlace);
+    // A line under the run's summary ("Unlocked ..."), until the next run.
+    void addNote(const std::string& note);
+    // The level's breakdown, each a label and its figure, in a row under the
+    // board until the next run.
+    void setBreakdown(const std::vector<std::pair<std::string, std::string>>& figures);
+    // True on the frame a typed in name is kept (enter, or escape for the
+    // one it started with), with it in name.
+    bool update(std::string& name);
+    bool isEnteringName() const;
+    // bounds is the UI layout's safe area, in window pixels.
+    void draw(TextRenderer& text, const Leaderboard& board, const Leaderboard& global, const std::string& levelName, Rectangle bounds, float scale);
+
+  private:
+    bool hasRun = false;
+    LeaderboardEntry run;
+    int runPlace = -1;
+    int runGlobalPlace = -1;
+    std::vector<std::string> notes;
+    std::vector<std::pair<std::string, std::string>> breakdown;
+    bool showingGlobal = false;
+    bool enteringName = false;
+    std::string name;
+
+    void setEnteringName(bool enabled);
+};
@@ -0,0 +1,390 @@
+#include "raylib.h"
+#include "Level.hpp"
+#include "FramePacer.hpp"
+#include "LayeredMusic.hpp"
+#include "Log.hpp"
+#include "Xml.hpp"
+#include <algorithm>
+#include <cmath>
+#include <cstdlib>
+#include <fstream>
+#include <sstream>
+
+
+// a well's pull when the level doesn't give one; about what the magnet
+// power-up pulls with from across the field
+static const float WELL_PULL = 0.06f;
+
+// levels/classic.blvl's bricks, for when no level is given; kept here so the
+// headless tools play it without the levels/ directory next to them
+static const char* BUILT_IN_LEVEL = R"(<level name="Classic">
+  <grid x="50" y="50" spacing="50" row_spacing="15" width="48" height="10">
+    ####################
+    ####################
+    ####################
+    ####################
+  </grid>
+</level>)";
+
+// A .blvl file is XML:
+//
+//   <level name="Classic" theme="neon" music="classic" par="120">
+//     <row x="50" y="50" count="20" spacing="50" width="48" height="10"/>
+//     <brick x="640" y="200" width="48" height="10" material="glass" animation="shimmer"/>
+//     <row x="50" y="80" count="20" hp="3"/>
+//     <animation name="shimmer" sheet="brick_shimmer" frames="4" fps="8"/>
+//     <prompt text="Press {launch} to serve" until="launch"/>
+//     <drops chance="0.12"><drop power="fireball" rarity="common"/></drops>
+//   </level>
+// hp is how many hits a brick takes, 1 to Brick::MAX_HIT_POINTS (1 when left
+// out). material="metal" bricks never break and the level is cleared without
+// them, so they take no hp. coins is how many a brick drops when it breaks
+// (0 when left out), time how many seconds of clock it drops in time
+// attack. A brick or row can move along a path (see BrickPath).
+// well="attract" or "repel" makes it a gravity well that bends the ball's
+// path within well_radius pixels, by pull at its center (see GravityWell).
+// switch="gates" makes a brick phase every brick with group="gates" in or
+// out when hit; phased="true" starts a brick phased out. A <boss> (see
+// Boss.hpp) has to be beaten as well as the bricks broken; a level can be
+// just a boss. launches="3" makes it a puzzle: cleared with three serves
+// or not at all, and scored by the serves left (see Simulation::launchBudget).
+// See DropTable for <drops>.
+// <stars score="40,80" seconds="90,60" lives_lost="1,0"/> gives what two
+// and then three stars take, any of them left out for no limit (see
+// StarThresholds); stars_required="6" keeps the level locked in the level
+// select until the profile has six stars.
+// A <grid> lays bricks out as text, one line a row and one character a
+// brick, '.' for a gap and '#' for the grid's own material and hp:
+//   <grid x="50" y="50" spacing="50" row_spacing="15" width="48" height="10">
+//     <key char="s" material="stone" hp="2"/>
+//     ##ss##
+//     #....#
+//   </grid>
+// row_spacing is from one row's top to the next's.
+// <physics ball_speed="1.2" paddle_width="0.8"/> speeds the ball up and
+// narrows the paddle for the level (see LevelPhysics), and background="sky.png"
+// draws that image, from the level's directory, instead of the theme's layers.
+// Problems are reported with the line and column they are at.
+bool loadLevel(const std::string& path, Level& level, std::string& error) {
+    LogSpan span("LEVEL", "load");
+    std::ifstream file(path, std::ios::binary);
+    if (!file) {
+        error = path + ": cannot open file";
+        return false;
+    }
+    std::stringstream buffer;
+    buffer << file.rdbuf();
+    return parseLevel(buffer.str(), path, level, error);
+}
+
+// "two,three", the two star threshold then the three star one
+static bool parseStarPair(const XmlNode& node, const char* name, int pair[2], std::string& problem) {
+    std::string text = node.attribute(name);
+    if (text.empty()) {
+        return true;
+    }
+    char* end = nullptr;
+    long two = strtol(text.c_str(), &end, 10);
+    long three = *end == ',' ? strtol(end + 1, &end, 10) : 0;
+    if (*end != '\0' || text.find(',') == std::string::npos) {
+        problem = std::string(name) + " must be two numbers, for two stars and three, like \"40,80\"";
+        return false;
+    }
+    pair[0] = (int) two;
+    pair[1] = (int) three;
+    return true;
+}
+
+// A <grid> character and the brick it stands for.
+struct GridKey {
+    char character;
+    BrickMaterial material;
+    int hitPoints;
+};
+
+// The <grid>'s bricks, each character's from its <key> (or the grid's own
+// material and hp for '#'), spacing apart in a row and rowSpacing between rows.
+static bool parseGrid(const XmlNode& node, GridKey own, int spacing, int rowSpacing, std::vector<Brick>& bricks, std::string& problem) {
+    int x = node.attributeInt("x", 0);
+    int y = node.attributeInt("y", 0);
+    int width = node.attributeInt("width", 48);
+    int height = node.attributeInt("height", 10);
+    std::vector<GridKey> keys = {own};
+    for (const XmlNode* key : node.childrenNamed("key")) {
+        std::string where = "line " + std::to_string(key->line) + ", column " + std::to_string(key->column) + ": ";
+        std::string character = key->attribute("char");
+        if (character.size() != 1 || character == "." || isspace((unsigned char) character[0])) {
+            problem = where + "a <key> needs one char, other than '.' or a space";
+            return false;
+        }
+        BrickMaterial material = parseBrickMaterial(key->attribute("material", "standard").c_str());
+        int hitPoints = material == BrickMaterial::Metal ? 1 : std::clamp(key->attributeInt("hp", 1), 1, Brick::MAX_HIT_POINTS);
+        keys.push_back({character[0], material, hitPoints});
+    }
+
+    std::vector<Brick> parsed;
+    std::stringstream text(node.text);
+    std::string line;
+    int row = 0;
+    while (std::getline(text, line)) {
+        size_t first = line.find_first_not_of(" \t\r");
+        if (first == std::string::npos) {
+            continue;
+        }
+        line = line.substr(first, line.find_last_not_of(" \t\r") - first + 1);
+        for (size_t column = 0; column < line.size(); column++) {
+            char character = line[column];
+            if (character == '.' || character == ' ') {
+                continue;
+            }
+            // the last key for a character wins, so a <key char="#"> can change what '#' is
+            auto key = std::find_if(keys.rbegin(), keys.rend(), [&](const GridKey& entry) { return entry.character == character; });
+            if (key == keys.rend()) {
+                problem = "row " + std::to_string(row + 1) + " has '" + std::string(1, character) + "', which no <key> stands for";
+                return false;
+            }
+            parsed.push_back({x + spacing * (int) column, y + rowSpacing * row, width, height, key->material, key->hitPoints});
+        }
+        row++;
+    }
+    bricks.insert(bricks.end(), parsed.begin(), parsed.end());
+    return true;
+}
+
+static bool parseStars(const XmlNode& node, StarThresholds& stars, std::string& problem) {
+    StarThresholds parsed;
+    parsed.given = true;
+    if (!parseStarPair(node, "score", parsed.score, problem) || !parseStarPair(node, "seconds", parsed.seconds, problem)
+        || !parseStarPair(node, "lives_lost", parsed.livesLost, problem)) {
+        return false;
+    }
+    stars = parsed;
+    return true;
+}
+
+bool parseLevel(const std::string& source, const std::string& path, Level& level, std::string& error) {
+    LogSpan span("LEVEL", "parse");
+    XmlNode root;
+    XmlError xmlError;
+    if (!parseXml(source, root, xmlError)) {
+        error = path + ": " + xmlError.describe();
+        return false;
+    }
+    if (root.name != "level") {
+        error = path + ": root element must be <level>, found <" + root.name + ">";
+        return false;
+    }
+
+    level = Level();
+    level.path = path;
+    level.name = root.attribute("name", GetFileNameWithoutExt(path.c_str()));
+    level.theme = root.attribute("theme");
+    level.music = root.attribute("music");
+    level.background = root.attribute("background");
+    level.par = root.attributeInt("par", 0);
+    level.launches = root.attributeInt("launches", 0);
+    level.starsRequired = std::max(0, root.attributeInt("stars_required", 0));
+    if (level.launches < 0) {
+        TraceLog(LOG_WARNING, "LEVEL: %s: launches must be 0 or more, it isn't a puzzle", path.c_str());
+        level.launches = 0;
+    }
+    level.animations.loadFromXml(root);
+
+    for (const XmlNode& node : root.children) {
+        int width = node.attributeInt("width", 48);
+        int height = node.attributeInt("height", 10);
+        int x = node.attributeInt("x", 0);
+        int y = node.attributeInt("y", 0);
+        BrickMaterial material = parseBrickMaterial(node.attribute("material", "standard").c_str());
+        std::string animation = node.attribute("animation");
+        int hitPoints = node.attributeInt("hp", 1);
+        if (hitPoints < 1 || hitPoints > Brick::MAX_HIT_POINTS) {
+            TraceLog(LOG_WARNING, "LEVEL: %s:%d:%d: hp must be 1 to %d, not %d", path.c_str(), node.line, node.column, Brick::MAX_HIT_POINTS, hitPoints);
+            hitPoints = std::clamp(hitPoints, 1, Brick::MAX_HIT_POINTS);
+        }
+        if (material == BrickMaterial::Metal && node.hasAttribute("hp")) {
+            TraceLog(LOG_WARNING, "LEVEL: %s:%d:%d: metal bricks never break, ignoring hp", path.c_str(), node.line, node.column);
+            hitPoints = 1;
+        }
+        int coins = std::max(0, node.attributeInt("coins", 0));
+        int timeBonus = std::max(0, node.attributeInt("time", 0));
+        size_t firstNew = level.bricks.size();
+
+        if (node.name == "brick") {
+            level.bricks.push_back({x, y, width, height, material, hitPoints});
+        } else if (node.name == "row") {
+            int count = node.attributeInt("count", 1);
+            int spacing = node.attributeInt("spacing", width + 2);
+            for (int i = 0; i < count; i++) {
+                level.bricks.push_back({x + spacing * i, y, width, height, material, hitPoints});
+            }
+        } else if (node.name == "grid") {
+            std::string problem;
+            int spacing = node.attributeInt("spacing", width + 2);
+            int rowSpacing = node.attributeInt("row_spacing", height + 5);
+            if (!parseGrid(node, {'#', material, hitPoints}, spacing, rowSpacing, level.bricks, problem)) {
+                TraceLog(LOG_WARNING, "LEVEL: %s:%d:%d: %s, skipping the grid", path.c_str(), node.line, node.column, problem.c_str());
+            }
+        } else if (node.name == "physics") {
+            level.physics.ballSpeed = node.attributeFloat("ball_speed", 1);
+            level.physics.paddleScale = node.attributeFloat("paddle_width", 1);
+            if (level.physics.ballSpeed <= 0 || level.physics.paddleScale <= 0) {
+                TraceLog(LOG_WARNING, "LEVEL: %s:%d:%d: ball_speed and paddle_width must be more than 0, ignoring <physics>", path.c_str(), node.line, node.column);
+                level.physics = LevelPhysics();
+            }
+            continue;
+        } else if (node.name == "animation") {
+            continue;
+        } else if (node.name == "drops") {
+            for (const std::string& problem : level.drops.apply(node)) {
+                TraceLog(LOG_WARNING, "LEVEL: %s:%s", path.c_str(), problem.c_str());
+            }
+            continue;
+        } else if (node.name == "boss") {
+            std::string problem;
+            if (level.boss.present()) {
+                TraceLog(LOG_WARNING, "LEVEL: %s:%d:%d: a level has one boss, ignoring this one", path.c_str(), node.line, node.column);
+            } else if (!parseBoss(node, level.boss, problem)) {
+                TraceLog(LOG_WARNING, "LEVEL: %s:%d:%d: %s, the level has no boss", path.c_str(), node.line, node.column, problem.c_str());
+            }
+            continue;
+        } else if (node.name == "stars") {
+            std::string problem;
+            if (!parseStars(node, level.stars, problem)) {
+                TraceLog(LOG_WARNING, "LEVEL: %s:%d:%d: %s, going by par for stars", path.c_str(), node.line, node.column, problem.c_str());
+            }
+            continue;
+        } else if (node.name == "prompt") {
+            level.prompts.push_back({node.attribute("text"), node.attribute("until"), node.attributeInt("count", 1)});
+            continue;
+        } else {
+            TraceLog(LOG_WARNING, "LEVEL: %s:%d:%d: ignoring unknown element <%s>", path.c_str(), node.line, node.column, node.name.c_str());
+        }
+
+        BrickPath brickPath;
+        std::string problem;
+        if (!parseBrickPath(node, brickPath, problem)) {
+            TraceLog(LOG_WARNING, "LEVEL: %s:%d:%d: %s, the brick stays put", path.c_str(), node.line, node.column, problem.c_str());
+        }
+        GravityWell well;
+        std::string wellKind = node.attribute("well", "none");
+        if (wellKind == "attract" || wellKind == "repel") {
+            float pull = fabsf(node.attributeFloat("pull", WELL_PULL));
+            well.strength = wellKind == "attract" ? pull : -pull;
+            well.radius = node.attributeFloat("well_radius", well.radius);
+        } else if (wellKind != "none") {
+            TraceLog(LOG_WARNING, "LEVEL: %s:%d:%d: unknown well '%s', expected attract or repel", path.c_str(), node.line, node.column, wellKind.c_str());
+        }
+        for (size_t i = firstNew; i < level.bricks.size(); i++) {
+            level.bricks[i].path = brickPath;
+            level.bricks[i].coins = coins;
+            level.bricks[i].timeBonus = timeBonus;
+            level.bricks[i].well = well;
+            level.bricks[i].group = node.attribute("group");
+            level.bricks[i].switchGroup = node.attribute("switch");
+            level.bricks[i].phased = node.attribute("phased", "false") == "true";
+        }
+
+        if (!animation.empty()) {
+            if (!level.animations.has(animation)) {
+                TraceLog(LOG_WARNING, "LEVEL: %s:%d:%d: unknown animation '%s'", path.c_str(), node.line, node.column, animation.c_str());
+                continue;
+            }
+            for (size_t i = firstNew; i < level.bricks.size(); i++) {
+                level.bricks[i].animation.play(level.animations.get(animation));
+            }
+        }
+    }
+
+    if (level.bricks.empty() && !level.boss.present()) {
+        error = path + ": level has no bricks";
+        return false;
+    }
+    TraceLog(LOG_DEBUG, "LEVEL: %s: %d bricks", path.c_str(), (int) level.bricks.size());
+    return true;
+}
+
+std::vector<std::string> levelClearProblems(const Level& level) {
+    std::vector<std::string> problems;
+    if (destructibleCount(level.bricks) == 0 && !level.boss.present()) {
+        problems.push_back(level.path + ": every brick is metal, so the level can't be cleared");
+    }
+    for (const Brick& brick : level.bricks) {
+        if (!brick.phased || !brick.isDestructible()) {
+            continue;
+        }
+        bool switched = !brick.group.empty() && std::any_of(level.bricks.begin(), level.bricks.end(), [&](const Brick& other) { return other.switchGroup == brick.group; });
+        if (!switched) {
+            problems.push_back(TextFormat("%s: the brick at %d,%d starts phased out and no switch brings it back, so the level can't be cleared",
+                level.path.c_str(), (int) brick.origin.x, (int) brick.origin.y));
+        }
+    }
+    return problems;
+}
+
+std::vector<std::string> levelReferenceProblems(const Level& level) {
+    std::vector<std::string> problems;
+    for (const Brick& brick : level.bricks) {
+        if (brick.switchGroup.empty()) {
+            continue;
+        }
+        bool found = std::any_of(level.bricks.begin(), level.bricks.end(), [&](const Brick& other) { return other.group == brick.switchGroup; });
+        if (!found) {
+            problems.push_back(level.path + ": a switch toggles group '" + brick.switchGroup + "', which no brick is in");
+        }
+    }
+    if (!level.music.empty() && musicTrackPath(level.music).empty()) {
+        problems.push_back(level.path + ": music track '" + level.music + "' not found in music/ or mods/, the theme's music plays instead");
+    }
+    if (!level.background.empty()) {
+        std::string image = std::string(GetDirectoryPath(level.path.c_str())) + "/" + level.background;
+        if (!FileExists(image.c_str())) {
+            problems.push_back(level.path + ": background '" + level.background + "' not found next to the level, the theme's is drawn instead");
+        }
+    }
+    if (!level.boss.music.empty() && musicTrackPath(level.boss.music).empty()) {
+        problems.push_back(level.path + ": boss music track '" + level.boss.music + "' not found in music/ or mods/");
+    }
+    return problems;
+}
+
+std::vector<std::string> validateLevel(const Level& level) {
+    std::vector<std::string> problems = levelClearProblems(level);
+    std::vector<std::string> references = levelReferenceProblems(level);
+    problems.insert(problems.end(), references.begin(), references.end());
+    return problems;
+}
+
+int parSeconds(const Level& level, int brickCount) {
+    return level.par > 0 ? level.par : brickCount * 2;
+}
+
+int starRating(const Level& level, int brickCount, bool cleared, int score, int livesLost, uint64_t ticks) {
+    if (!cleared) {
+        return 0;
+    }
+    const StarThresholds& stars = level.stars;
+    if (!stars.given) {
+        bool flawless = livesLost <= 0;
+        bool withinPar = ticks <= (uint64_t) parSeconds(level, brickCount) * FramePacer::TICK_RATE;
+        return 1 + (flawless ? 1 : 0) + (withinPar ? 1 : 0);
+    }
+    int rating = 1;
+    for (int i = 0; i < 2 && rating == i + 1; i++) {
+        bool scored = score >= stars.score[i];
+        bool inTime = stars.seconds[i] <= 0 || ticks <= (uint64_t) stars.seconds[i] * FramePacer::TICK_RATE;
+        bool careful = stars.livesLost[i] < 0 || livesLost <= stars.livesLost[i];
+        rating += scored && inTime && careful ? 1 : 0;
+    }
+    return rating;
+}
+
+void createBricks(std::vector<Brick> &bricks) {
+    Level level;
+    std::string error;
+    if (!parseLevel(BUILT_IN_LEVEL, "classic", level, error)) {
+        TraceLog(LOG_WARNING, "LEVEL: The built-in level doesn't load: %s", error.c_str());
+        return;
+    }
+    bricks.insert(bricks.end(), level.bricks.begin(), level.bricks.end());
+}
@@ -0,0 +1,76 @@
+#pragma once
+#include <cstdint>
+#include <string>
+#include <vector>
+#include "Animation.hpp"
+#include "Boss.hpp"
+#include "Brick.hpp"
+#include "DropTable.hpp"
+
+
+// A line of instructions (tutorial levels) shown until the player has done
+// what it says; see Tutorial for what until can be.
+struct LevelPrompt {
+    std::string text;
+    std::string until;
+    int count = 1;
+};
+
+// What a cleared level takes for two stars and for three: at least score
+// points, within seconds, at most livesLost lives. 0 seconds and -1 lives
+// are no limit. Without any, two stars are for losing no life or beating
+// par and three for both, like the medals.
+struct StarThresholds {
+    bool given = false;
+    int score[2] = {0, 0};
+    int seconds[2] = {0, 0};
+    int livesLost[2] = {-1, -1};
+};
+
+// What a level changes about the ball and paddle, on top of what the
+// mutators do (see Simulation::physics): <physics ball_speed="1.2" paddle_width="0.8"/>.
+struct LevelPhysics {
+    float ballSpeed = 1;   // times the usual
+    float paddleScale = 1; // times the usual width
+};
+
+struct Level {
+    std::string name;
+    std::string path;
+    std::string theme; // empty to use the profile's theme
+    std::string music; // track id, empty to use the theme's
+    std::string background; // image path from the level's directory, empty to use the theme's layers
+    int par = 0;       // seconds for a gold medal, 0 for parSeconds()'s default
+    int launches = 0;  // a puzzle level's serves, 0 for an ordinary level (see Simulation::launchBudget)
+    LevelPhysics physics;
+    StarThresholds stars;
+    int starsRequired = 0; // stars over every level before the level select lets it be played
+    std::vector<Brick> bricks;
+    AnimationLibrary animations;
+    std::vector<LevelPrompt> prompts; // in order
+    DropTable drops = DropTable::standard();
+    Boss boss; // not present() in most levels
+};
+
+// The level's par, or two seconds a brick without one.
+int parSeconds(const Level& level, int brickCount);
+// 1 to 3 for a cleared level, 0 for one that wasn't.
+int starRating(const Level& level, int brickCount, bool cleared, int score, int livesLost, uint64_t ticks);
+// The built-in layout, played when no level is given. It is a level file
+// like any other, kept in the binary (see BUILT_IN_LEVEL in Level.cpp).
+void createBricks(std::vector<Brick>& bricks);
+// Bump whenever the level format gains something older games can't read;
+// level packs say which they were made for. Unlike Simulation::VERSION it
+// doesn't move when the game only plays differently.
+const int LEVEL_FORMAT = 1;
+bool loadLevel(const std::string& path, Level& level, std::string& error);
+// The same from text already in memory; path is only used for names and errors.
+bool parseLevel(const std::string& source, const std::string& path, Level& level, std::string& error);
+// Problems that don't stop the level from loading but would make it play
+// differently than authored, the clear problems and then the reference ones.
+std::vector<std::string> validateLevel(const Level& level);
+// Why the level could never be cleared (nothing but metal, a brick phased
+// out for good...).
+std::vector<std::string> levelClearProblems(const Level& level);
+// What the level names that isn't there: music, its background, a switch's group.
+std::vector<std::string> levelReferenceProblems(const Level& level);
@@ -0,0 +1,114 @@
+#include "raylib.h"
+#include "LevelPack.hpp"
+#include "Level.hpp"
+#include <algorithm>
+#include <cstdlib>
+#include <sstream>
+
+
+bool LevelPack::playable() const {
+    return levelFormat <= LEVEL_FORMAT;
+}
+
+std::vector<std::string> levelPackFiles() {
+    std::string root = std::string(GetApplicationDirectory()) + "levelpacks";
+    std::vector<std::string> files;
+    if (!DirectoryExists(root.c_str())) {
+        return files;
+    }
+    FilePathList directories = LoadDirectoryFiles(root.c_str());
+    for (unsigned int i = 0; i < directories.count; i++) {
+        std::string path = std::string(directories.paths[i]) + "/pack.xml";
+        if (FileExists(path.c_str())) {
+            files.push_back(path);
+        }
+    }
+    UnloadDirectoryFiles(directories);
+    // the same order on every machine
+    std::sort(files.begin(), files.end());
+    return files;
+}
+
+bool parseLevelPack(const XmlNode& root, const std::string& directory, LevelPack& pack, std::string& problem) {
+    if (root.name != "pack") {
+        problem = "the root element must be <pack>";
+        return false;
+    }
+    LevelPack parsed;
+    parsed.id = root.attribute("id");
+    parsed.name = root.attribute("name", parsed.id);
+    parsed.author = root.attribute("author");
+    parsed.directory = directory;
+    parsed.levelFormat = root.attributeInt("level_format", 0);
+    if (parsed.id.empty()) {
+        problem = "a pack needs an id";
+        return false;
+    }
+    for (const XmlNode* node : root.childrenNamed("level")) {
+        std::string where = "line " + std::to_string(node->line) + ", column " + std::to_string(node->column) + ": ";
+        std::string file = node->attribute("file");
+        std::string path = directory + "/" + file;
+        if (file.empty() || !FileExists(path.c_str())) {
+            problem = where + "no level file '" + file + "' in the pack";
+            return false;
+        }
+        parsed.levels.push_back(path);
+    }
+    if (parsed.levels.empty()) {
+        problem = "a pack needs at least one <level>";
+        return false;
+    }
+    pack = parsed;
+    return true;
+}
+
+std::vector<LevelPack> loadLevelPacks() {
+    std::vector<LevelPack> packs;
+    for (const std::string& path : levelPackFiles()) {
+        XmlNode root;
+        XmlError error;
+        std::string problem;
+        LevelPack pack;
+        if (!loadXmlFile(path, root, error)) {
+            TraceLog(LOG_WARNING, "LEVELPACK: Skipping %s: %s", path.c_str(), error.describe().c_str());
+        } else if (!parseLevelPack(root, GetDirectoryPath(path.c_str()), pack, problem)) {
+            TraceLog(LOG_WARNING, "LEVELPACK: Skipping %s: %s", path.c_str(), problem.c_str());
+        } else if (std::any_of(packs.begin(), packs.end(), [&](const LevelPack& other) { return other.id == pack.id; })) {
+            TraceLog(LOG_WARNING, "LEVELPACK: Skipping %s: there is already a pack '%s'", path.c_str(), pack.id.c_str());
+        } else {
+            packs.push_back(pack);
+        }
+    }
+    return packs;
+}
+
+int packProgress(const std::string& record, const std::string& id) {
+    std::stringstream list(record);
+    std::string entry;
+    while (std::getline(list, entry, ',')) {
+        size_t colon = entry.find(':');
+        if (colon != std::string::npos && entry.compare(0, colon, id) == 0) {
+            return std::max(0, std::atoi(entry.c_str() + colon + 1));
+        }
+    }
+    return 0;
+}
+
+bool recordPackClear(std::string& record, const std::string& id, int index) {
+    // clearing an earlier level again, or skipping ahead, moves nothing
+    if (index != packProgress(record, id)) {
+        return false;
+    }
+    std::string updated;
+    std::stringstream list(record);
+    std::string entry;
+    while (std::getline(list, entry, ',')) {
+        size_t colon = entry.find(':');
+        if (colon == std::string::npos || entry.compare(0, colon, id) == 0) {
+            continue;
+        }
+        updated += (updated.empty() ? "" : ",") + entry;
+    }
+    record = updated + (updated.empty() ? "" : ",") + id + ":" + std::to_string(index + 1);
+    return true;
+}
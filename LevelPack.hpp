@@ -0,0 +1,39 @@
+#pragma once
+#include <string>
+#include <vector>
+#include "Xml.hpp"
+
+
+// A campaign of levels that ships together, levelpacks/<pack>/pack.xml next
+// to its .blvl files:
+//   <pack id="desert" name="Desert Run" author="Sam" level_format="1">
+//     <level file="dunes.blvl"/>
+//     <level file="oasis.blvl"/>
+//   </pack>
+// The levels are played in order, each unlocked by clearing the one before.
+// level_format is the LEVEL_FORMAT the pack was made for; a game that reads an
+// older format lists it but won't play it.
+struct LevelPack {
+    std::string id;
+    std::string name;
+    std::string author;
+    std::string directory;
+    int levelFormat = 0;
+    std::vector<std::string> levels; // paths, in order
+
+    bool playable() const;
+};
+
+// levelpacks/<pack>/pack.xml for every pack, sorted.
+std::vector<std::string> levelPackFiles();
+// False with the problem when the manifest has no id or no levels, or lists
+// a level that isn't in directory.
+bool parseLevelPack(const XmlNode& root, const std::string& directory, LevelPack& pack, std::string& problem);
+// Every pack there is, a broken one or a second with the same id skipped with a warning.
+std::vector<LevelPack> loadLevelPacks();
+
+// The profile's progress through each pack, "desert:2,forest:5", how many of
+// its levels have been cleared in order.
+int packProgress(const std::string& record, const std::string& id);
+// Level index of pack id was cleared; true when that unlocked the next one.
+bool recordPackClear(std::string& record, const std::string& id, int index);
@@ -0,0 +1,209 @@
+#include "raylib.h"
+#include "LevelSelectScreen.hpp"
+#include "Level.hpp"
+#include "Localization.hpp"
+#include "Replay.hpp"
+#include "Ui.hpp"
+#include <algorithm>
+#include <cmath>
+
+
+void LevelSelectScreen::toggle() {
+    isOpen = !isOpen;
+    message.clear();
+    if (isOpen) {
+        scan();
+    }
+}
+
+void LevelSelectScreen::scan() {
+    packs = loadLevelPacks();
+    group = -1;
+    levels.clear();
+    selected = std::clamp(selected, 0, std::max(0, rowCount() - 1));
+}
+
+void LevelSelectScreen::openGroup(int index) {
+    group = index;
+    levels.clear();
+    selected = 0;
+    std::vector<std::string> paths;
+    if (index == 0) {
+        std::string directory = std::string(GetApplicationDirectory()) + "levels";
+        if (DirectoryExists(directory.c_str())) {
+            FilePathList files = LoadDirectoryFilesEx(directory.c_str(), ".blvl", false);
+            for (unsigned int i = 0; i < files.count; i++) {
+                paths.push_back(files.paths[i]);
+            }
+            UnloadDirectoryFiles(files);
+        }
+        std::sort(paths.begin(), paths.end());
+    } else {
+        // a pack's own order, not the files'
+        paths = packs[index - 1].levels;
+    }
+    for (size_t i = 0; i < paths.size(); i++) {
+        Level level;
+        std::string error;
+        if (!loadLevel(paths[i], level, error)) {
+            TraceLog(LOG_WARNING, "LEVEL: %s", error.c_str());
+            continue;
+        }
+        LevelChoice choice = {paths[i], level.name, levelHash(level.bricks), level.starsRequired};
+        if (index > 0) {
+            choice.pack = packs[index - 1].id;
+            choice.packIndex = (int) i;
+            choice.packSize = (int) paths.size();
+        }
+        levels.push_back(choice);
+    }
+}
+
+int LevelSelectScreen::rowCount() const {
+    return group < 0 ? (int) packs.size() + 1 : (int) levels.size();
+}
+
+std::string LevelSelectScreen::lockReason(int index, int starTotal, const std::string& packRecord) const {
+    const LevelChoice& level = levels[index];
+    if (!level.pack.empty() && level.packIndex > packProgress(packRecord, level.pack)) {
+        return tr("Clear the level before it to unlock it");
+    }
+    if (starTotal < level.starsRequired) {
+        int missing = level.starsRequired - starTotal;
+        return TextFormat(trn("%d more star to unlock it", "%d more stars to unlock it", missing).c_str(), missing);
+    }
+    return "";
+}
+
+bool LevelSelectScreen::update(int starTotal, const std::string& packRecord, LevelChoice& picked) {
+    if (!isOpen) {
+        return false;
+    }
+    if (group >= 0 && IsKeyPressed(KEY_BACKSPACE)) {
+        selected = group;
+        group = -1;
+        levels.clear();
+        message.clear();
+        return false;
+    }
+    int count = rowCount();
+    if (count == 0) {
+        return false;
+    }
+    if (IsKeyPressed(KEY_DOWN)) {
+        selected = (selected + 1) % count;
+        message.clear();
+    } else if (IsKeyPressed(KEY_UP)) {
+        selected = (selected - 1 + count) % count;
+        message.clear();
+    }
+    if (!IsKeyPressed(KEY_ENTER) && !IsKeyPressed(KEY_KP_ENTER)) {
+        return false;
+    }
+    message.clear();
+    if (group < 0) {
+        if (selected > 0 && !packs[selected - 1].playable()) {
+            message = tr("This pack needs a newer version of the game");
+            return false;
+        }
+        openGroup(selected);
+        return false;
+    }
+    message = lockReason(selected, starTotal, packRecord);
+    if (!message.empty()) {
+        return false;
+    }
+    picked = levels[selected];
+    return true;
+}
+
+// A five pointed star around center, filled for one earned
+static void drawStar(Vector2 center, float radius, bool filled, Color color) {
+    Vector2 points[12];
+    points[0] = center;
+    for (int i = 0; i <= 10; i++) {
+        // counter-clockwise on screen, for raylib's fan
+        float angle = -PI / 2 - i * PI / 5;
+        float reach = i % 2 == 0 ? radius : radius * 0.45f;
+        points[i + 1] = {center.x + cosf(angle) * reach, center.y + sinf(angle) * reach};
+    }
+    if (filled) {
+        DrawTriangleFan(points, 12, color);
+    } else {
+        DrawLineStrip(points + 1, 11, color);
+    }
+}
+
+void LevelSelectScreen::draw(TextRenderer& text, const PlayerStats& stats, const std::string& packRecord, Rectangle bounds, float scale) {
+    if (!isOpen) {
+        return;
+    }
+    int starTotal = stats.getStarTotal();
+    float rowHeight = 34 * scale;
+    int fontSize = 20 * scale;
+    Vector2 size = {620 * scale, 150 * scale + rowHeight * std::max(rowCount(), 1)};
+    Vector2 corner = anchorPoint(Anchor::Center, bounds, size);
+    float margin = 20 * scale;
+    auto drawRight = [&](const std::string& line, float rowY, Color color) {
+        Vector2 lineSize = text.measure(line, 16 * scale);
+        text.draw(line, {corner.x + size.x - margin - lineSize.x, rowY + 2 * scale}, 16 * scale, color);
+    };
+
+    DrawRectangle(0, 0, GetScreenWidth(), GetScreenHeight(), Fade(BLACK, 0.6f));
+    DrawRectangleRec({corner.x, corner.y, size.x, size.y}, Fade(DARKGRAY, 0.95f));
+    std::string title = group <= 0 ? tr("Levels") : packs[group - 1].name;
+    text.draw(title, {corner.x + margin, corner.y + 15 * scale}, 30 * scale, RAYWHITE);
+    std::string total = TextFormat(trn("%d star", "%d stars", starTotal).c_str(), starTotal);
+    Vector2 totalSize = text.measure(total, fontSize);
+    text.draw(total, {corner.x + size.x - margin - totalSize.x, corner.y + 22 * scale}, fontSize, GOLD);
+    float footerY = corner.y + size.y - 60 * scale;
+    if (!message.empty()) {
+        text.draw(message, {corner.x + margin, footerY}, fontSize, RAYWHITE);
+    }
+
+    if (group < 0) {
+        for (int i = 0; i < rowCount(); i++) {
+            float rowY = corner.y + 70 * scale + i * rowHeight;
+            Color color = i == selected ? YELLOW : LIGHTGRAY;
+            if (i == 0) {
+                text.draw(tr("Single levels"), {corner.x + margin, rowY}, fontSize, color);
+                continue;
+            }
+            const LevelPack& pack = packs[i - 1];
+            std::string name = pack.author.empty() ? pack.name : TextFormat(tr("%s by %s").c_str(), pack.name.c_str(), pack.author.c_str());
+            text.draw(name, {corner.x + margin, rowY}, fontSize, pack.playable() ? color : GRAY);
+            if (!pack.playable()) {
+                drawRight(tr("Needs a newer game"), rowY, GRAY);
+            } else {
+                int cleared = std::min(packProgress(packRecord, pack.id), (int) pack.levels.size());
+                drawRight(TextFormat(tr("%d / %d cleared").c_str(), cleared, (int) pack.levels.size()), rowY, cleared == (int) pack.levels.size() ? GOLD : GRAY);
+            }
+        }
+        text.draw(tr("Enter opens a group of levels"), {corner.x + margin, footerY + 30 * scale}, 16 * scale, GRAY);
+        return;
+    }
+
+    if (levels.empty()) {
+        text.draw(tr(group == 0 ? "No levels in levels/" : "No level of the pack loads"), {corner.x + margin, corner.y + 70 * scale}, fontSize, GRAY);
+    }
+    const std::map<uint64_t, LevelMedal>& medals = stats.getMedals();
+    for (size_t i = 0; i < levels.size(); i++) {
+        const LevelChoice& level = levels[i];
+        float rowY = corner.y + 70 * scale + i * rowHeight;
+        bool locked = !lockReason((int) i, starTotal, packRecord).empty();
+        Color color = (int) i == selected ? YELLOW : locked ? GRAY : LIGHTGRAY;
+        text.draw(level.name, {corner.x + margin, rowY}, fontSize, color);
+        if (locked) {
+            bool needsStars = starTotal < level.starsRequired;
+            drawRight(needsStars ? TextFormat(trn("Needs %d star", "Needs %d stars", level.starsRequired).c_str(), level.starsRequired) : tr("Locked"), rowY, GRAY);
+            continue;
+        }
+        auto found = medals.find(level.hash);
+        int stars = found == medals.end() ? 0 : found->second.stars;
+        for (int star = 0; star < 3; star++) {
+            Vector2 center = {corner.x + size.x - margin - (2.5f - star) * 28 * scale, rowY + 10 * scale};
+            drawStar(center, 11 * scale, star < stars, star < stars ? GOLD : GRAY);
+        }
+    }
+    text.draw(tr("Enter plays the level, backspace goes back"), {corner.x + margin, footerY + 30 * scale}, 16 * scale, GRAY);
+}
@@ -0,0 +1,51 @@
+#pragma once
+#include "raylib.h"
+#include <cstdint>
+#include <string>
+#include <vector>
+#include "LevelPack.hpp"
+#include "Stats.hpp"
+#include "Text.hpp"
+
+
+struct LevelChoice {
+    std::string path;
+    std::string name;
+    uint64_t hash = 0; // for its stars in PlayerStats
+    int starsRequired = 0;
+    std::string pack; // the LevelPack's id, empty for one of levels/
+    int packIndex = -1; // where it is in the pack
+    int packSize = 0;   // how many levels the pack has
+};
+
+// The level packs, and the levels in levels/ as a group of their own before
+// them, from the pause menu or once a run is over. Up/down picks, enter
+// opens a group and then plays a level, backspace goes back to the groups.
+// Each level shows the best stars the profile has on it; one that needs more
+// stars than the profile has (see Level::starsRequired) stays locked, and so
+// does a pack's level until the one before it is cleared.
+class LevelSelectScreen {
+  public:
+    bool isOpen = false;
+
+    // Opening reads levels/ and levelpacks/ again, so anything saved since shows up.
+    void toggle();
+    // True on the frame a level is picked, which picked is then. packRecord
+    // is the profile's progress through the packs (see packProgress()).
+    bool update(int starTotal, const std::string& packRecord, LevelChoice& picked);
+    // bounds is the UI layout's safe area, in window pixels.
+    void draw(TextRenderer& text, const PlayerStats& stats, const std::string& packRecord, Rectangle bounds, float scale);
+
+  private:
+    std::vector<LevelPack> packs;
+    int group = -1; // -1 choosing a group, 0 for levels/, 1... for packs[group - 1]
+    std::vector<LevelChoice> levels; // the open group's
+    int selected = 0;
+    std::string message;
+
+    void scan();
+    void openGroup(int index);
+    int rowCount() const;
+    // Why levels[index] can't be played yet, empty once it can.
+    std::string lockReason(int index, int starTotal, const std::string& packRecord) const;
+};
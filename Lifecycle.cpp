@@ -0,0 +1,34 @@
+#include "raylib.h"
+#include "Lifecycle.hpp"
+
+
+void WindowLifecycle::subscribe(Handler handler) {
+    handlers.push_back(handler);
+}
+
+void WindowLifecycle::emit(WindowEvent event) {
+    for (Handler& handler : handlers) {
+        handler(event);
+    }
+}
+
+void WindowLifecycle::update() {
+    bool nowFocused = IsWindowFocused();
+    if (nowFocused != focused) {
+        focused = nowFocused;
+        emit(focused ? WindowEvent::FocusGained : WindowEvent::FocusLost);
+    }
+    bool nowMinimized = IsWindowMinimized();
+    if (nowMinimized != minimized) {
+        minimized = nowMinimized;
+        emit(minimized ? WindowEvent::Minimized : WindowEvent::Restored);
+    }
+}
+
+bool WindowLifecycle::isFocused() {
+    return focused;
+}
+
+bool WindowLifecycle::isMinimized() {
+    return minimized;
+}
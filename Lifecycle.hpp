@@ -0,0 +1,32 @@
+#pragma once
+#include "raylib.h"
+#include <functional>
+#include <vector>
+
+
+enum class WindowEvent {
+    FocusLost,
+    FocusGained,
+    Minimized,
+    Restored,
+};
+
+// Turns raylib's window state into edge-triggered events (alt-tab, minimize)
+// so systems like audio can react once instead of polling every frame.
+class WindowLifecycle {
+  public:
+    using Handler = std::function<void(WindowEvent)>;
+
+    void subscribe(Handler handler);
+    // Call at the start of every frame.
+    void update();
+    bool isFocused();
+    bool isMinimized();
+
+  private:
+    std::vector<Handler> handlers;
+    bool focused = true;
+    bool minimized = false;
+
+    void emit(WindowEvent event);
+};
@@ -0,0 +1,165 @@
+#include "raylib.h"
+#include "Lighting.hpp"
+#include <cmath>
+
+
+void LightingPass::init(int screenWidth, int screenHeight, LightingQuality lightingQuality) {
+    width = screenWidth;
+    height = screenHeight;
+    setQuality(lightingQuality);
+}
+
+void LightingPass::unload() {
+    if (IsRenderTextureReady(lightMap)) {
+        UnloadRenderTexture(lightMap);
+    }
+    lightMap = {};
+}
+
+void LightingPass::setQuality(LightingQuality lightingQuality) {
+    if (lightingQuality == quality && (quality == LightingQuality::Off || IsRenderTextureReady(lightMap))) {
+        return;
+    }
+    quality = lightingQuality;
+    unload();
+    switch (quality) {
+    case LightingQuality::Off:
+        lights.clear();
+        return;
+    case LightingQuality::Low:
+        divisor = 4;
+        break;
+    case LightingQuality::Medium:
+        divisor = 2;
+        break;
+    case LightingQuality::High:
+        divisor = 1;
+        break;
+    }
+    lightMap = LoadRenderTexture(width / divisor, height / divisor);
+    SetTextureFilter(lightMap.texture, TEXTURE_FILTER_BILINEAR);
+}
+
+int LightingPass::maxLights() {
+    switch (quality) {
+    case LightingQuality::Low:
+        return 8;
+    case LightingQuality::Medium:
+        return 32;
+    case LightingQuality::High:
+        return 64;
+    default:
+        return 0;
+    }
+}
+
+void LightingPass::addLight(Vector2 position, float radius, Color color, float intensity) {
+    if ((int) lights.size() < maxLights()) {
+        lights.push_back({position, radius, color, intensity, -1, 1});
+    }
+}
+
+void LightingPass::setReducedMotion(bool enabled) {
+    reducedMotion = enabled;
+}
+
+void LightingPass::addFlash(Vector2 position, float radius, Color color, float duration) {
+    if ((int) lights.size() >= maxLights()) {
+        return;
+    }
+    if (!reducedMotion) {
+        lights.push_back({position, radius, color, 1, duration, duration});
+        return;
+    }
+    // at most one fresh glow at a time keeps the rate under 3 per second
+    for (const Light& light : lights) {
+        if (light.life > 0 && light.maxLife - light.life < 0.34f) {
+            return;
+        }
+    }
+    float gentle = fmaxf(duration * 3, 0.6f);
+    lights.push_back({position, radius, color, 0.3f, gentle, gentle});
+}
+
+float LightingPass::brightness(const Light& light) {
+    if (light.life < 0) {
+        return light.intensity;
+    }
+    return light.intensity * (light.life / light.maxLife);
+}
+
+// Call after drawing: one-frame lights are dropped, flashes fade.
+void LightingPass::update(float deltaTime) {
+    for (size_t i = 0; i < lights.size();) {
+        Light& light = lights[i];
+        light.life -= deltaTime;
+        if (light.life <= 0) {
+            lights[i] = lights.back();
+            lights.pop_back();
+            continue;
+        }
+        i++;
+    }
+}
+
+void LightingPass::render() {
+    if (quality == LightingQuality::Off || !IsRenderTextureReady(lightMap)) {
+        return;
+    }
+    BeginTextureMode(lightMap);
+    ClearBackground(BLANK);
+    BeginBlendMode(BLEND_ADDITIVE);
+    for (const Light& light : lights) {
+        Color color = light.color;
+        color.a = (unsigned char) (255 * fminf(brightness(light), 1.0f));
+        DrawCircleGradient((int) (light.position.x / divisor), (int) (light.position.y / divisor),
+            light.radius / divisor, color, BLANK);
+    }
+    EndBlendMode();
+    EndTextureMode();
+}
+
+// High quality only: tints each brick by the lights reaching its center, so
+// bricks pick up the glow more strongly than the empty space around them.
+void LightingPass::illuminateBricks(std::vector<Brick>& bricks) {
+    if (quality != LightingQuality::High || lights.empty()) {
+        return;
+    }
+    BeginBlendMode(BLEND_ADDITIVE);
+    for (Brick& brick : bricks) {
+        // lighting one up would give it away
+        if (brick.hidden) {
+            continue;
+        }
+        Rectangle rect = brick.getRectangle();
+        Vector2 center = {rect.x + rect.width / 2, rect.y + rect.height / 2};
+        float r = 0, g = 0, b = 0;
+        for (const Light& light : lights) {
+            float dx = light.position.x - center.x;
+            float dy = light.position.y - center.y;
+            float distance = sqrtf(dx * dx + dy * dy);
+            if (distance >= light.radius) {
+                continue;
+            }
+            float falloff = (1 - distance / light.radius) * brightness(light) * 0.6f;
+            r += light.color.r * falloff;
+            g += light.color.g * falloff;
+            b += light.color.b * falloff;
+        }
+        if (r + g + b > 1) {
+            Color tint = {(unsigned char) fminf(r, 255), (unsigned char) fminf(g, 255), (unsigned char) fminf(b, 255), 255};
+            DrawRectangleRec(rect, tint);
+        }
+    }
+    EndBlendMode();
+}
+
+void LightingPass::composite() {
+    if (quality == LightingQuality::Off || !IsRenderTextureReady(lightMap)) {
+        return;
+    }
+    Rectangle source = {0, 0, (float) lightMap.texture.width, (float) -lightMap.texture.height};
+    BeginBlendMode(BLEND_ADDITIVE);
+    DrawTexturePro(lightMap.texture, source, {0, 0, (float) width, (float) height}, {0, 0}, 0, WHITE);
+    EndBlendMode();
+}
@@ -0,0 +1,55 @@
+#pragma once
+#include "raylib.h"
+#include <vector>
+#include "Brick.hpp"
+
+
+enum class LightingQuality {
+    Off,
+    Low,    // quarter-resolution light map, few lights
+    Medium, // half resolution
+    High,   // full resolution, every light also lifts the bricks it touches
+};
+
+struct Light {
+    Vector2 position;
+    float radius;
+    Color color;
+    float intensity;
+    float life;    // seconds left, < 0 for lights that only last one frame
+    float maxLife;
+};
+
+// Additive 2D glow. Lights are gathered during the frame (persistent emitters
+// re-add themselves every frame, flashes fade out on their own), rendered into
+// a low-resolution light map before the world pass and composited on top of
+// it. render() must happen outside of any other texture mode.
+class LightingPass {
+  public:
+    void init(int width, int height, LightingQuality quality);
+    void unload();
+    void setQuality(LightingQuality quality);
+    // Flashes become dim, slow glows and can't retrigger faster than a few
+    // times a second (photosensitivity guidance).
+    void setReducedMotion(bool enabled);
+
+    void addLight(Vector2 position, float radius, Color color, float intensity = 1);
+    void addFlash(Vector2 position, float radius, Color color, float duration);
+
+    void update(float deltaTime);
+    void render();
+    void illuminateBricks(std::vector<Brick>& bricks);
+    void composite();
+
+  private:
+    LightingQuality quality = LightingQuality::Off;
+    RenderTexture2D lightMap = {};
+    int width = 0;
+    int height = 0;
+    int divisor = 1;
+    std::vector<Light> lights;
+    bool reducedMotion = false;
+
+    int maxLights();
+    float brightness(const Light& light);
+};
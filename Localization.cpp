@@ -0,0 +1,255 @@
+#include "raylib.h"
+#include "Localization.hpp"
+#include <algorithm>
+#include <fstream>
+#include <set>
+
+
+static Catalog active;
+static std::string activeCode = "en";
+static std::map<std::string, std::string> pseudoCache;
+static std::set<std::string> reportedMissing;
+
+static std::string trim(const std::string& text) {
+    size_t start = text.find_first_not_of(" \t\r");
+    if (start == std::string::npos) {
+        return "";
+    }
+    size_t end = text.find_last_not_of(" \t\r");
+    return text.substr(start, end - start + 1);
+}
+
+static bool parsePluralRule(const std::string& name, PluralRule& rule) {
+    static const std::pair<const char*, PluralRule> RULES[] = {
+        {"one_other", PluralRule::OneOther},
+        {"one_with_zero", PluralRule::OneWithZero},
+        {"slavic", PluralRule::Slavic},
+        {"none", PluralRule::None},
+    };
+    for (const auto& entry : RULES) {
+        if (name == entry.first) {
+            rule = entry.second;
+            return true;
+        }
+    }
+    return false;
+}
+
+static int formCount(PluralRule rule) {
+    switch (rule) {
+    case PluralRule::Slavic:
+        return 3;
+    case PluralRule::None:
+        return 1;
+    default:
+        return 2;
+    }
+}
+
+static int pluralForm(PluralRule rule, int count) {
+    int n = count < 0 ? -count : count;
+    switch (rule) {
+    case PluralRule::OneOther:
+        return n == 1 ? 0 : 1;
+    case PluralRule::OneWithZero:
+        return n <= 1 ? 0 : 1;
+    case PluralRule::Slavic:
+        if (n % 10 == 1 && n % 100 != 11) {
+            return 0;
+        }
+        return n % 10 >= 2 && n % 10 <= 4 && (n % 100 < 12 || n % 100 > 14) ? 1 : 2;
+    case PluralRule::None:
+        return 0;
+    }
+    return 0;
+}
+
+bool loadCatalog(const std::string& path, Catalog& catalog, std::string& error) {
+    std::ifstream file(path);
+    if (!file) {
+        error = "Could not open " + path;
+        return false;
+    }
+    catalog = Catalog();
+    catalog.code = GetFileNameWithoutExt(path.c_str());
+    catalog.name = catalog.code;
+    std::string line;
+    int lineNumber = 0;
+    while (std::getline(file, line)) {
+        lineNumber++;
+        line = trim(line);
+        if (line.empty() || line[0] == '#') {
+            continue;
+        }
+        // English text can hold a bare '=', so the separator has spaces around it
+        size_t separator = line.find(" = ");
+        if (separator == std::string::npos) {
+            error = path + ":" + std::to_string(lineNumber) + ": expected 'English = translation'";
+            return false;
+        }
+        std::string key = trim(line.substr(0, separator));
+        std::string value = trim(line.substr(separator + 3));
+        if (key == "@name") {
+            catalog.name = value;
+        } else if (key == "@plural") {
+            if (!parsePluralRule(value, catalog.plural)) {
+                error = path + ":" + std::to_string(lineNumber) + ": unknown plural rule '" + value + "'";
+                return false;
+            }
+        } else {
+            std::vector<std::string> forms;
+            size_t start = 0;
+            size_t bar;
+            while ((bar = value.find(" | ", start)) != std::string::npos) {
+                forms.push_back(trim(value.substr(start, bar - start)));
+                start = bar + 3;
+            }
+            forms.push_back(trim(value.substr(start)));
+            catalog.strings[key] = forms;
+        }
+    }
+    // checked after the whole file, @plural may come anywhere
+    for (const auto& entry : catalog.strings) {
+        if (entry.second.size() > 1 && (int) entry.second.size() != formCount(catalog.plural)) {
+            error = path + ": '" + entry.first + "' has " + std::to_string(entry.second.size()) + " forms, its plural rule needs "
+                + std::to_string(formCount(catalog.plural));
+            return false;
+        }
+    }
+    return true;
+}
+
+static std::string catalogDirectory() {
+    return std::string(GetApplicationDirectory()) + "lang";
+}
+
+std::vector<std::string> languageCodes() {
+    std::vector<std::string> codes = {"en"};
+    std::string directory = catalogDirectory();
+    if (DirectoryExists(directory.c_str())) {
+        FilePathList files = LoadDirectoryFilesEx(directory.c_str(), ".txt", false);
+        std::vector<std::string> found;
+        for (unsigned int i = 0; i < files.count; i++) {
+            found.push_back(GetFileNameWithoutExt(files.paths[i]));
+        }
+        UnloadDirectoryFiles(files);
+        std::sort(found.begin(), found.end());
+        for (const std::string& code : found) {
+            if (code != "en" && code != "pseudo") {
+                codes.push_back(code);
+            }
+        }
+    }
+    codes.push_back("pseudo");
+    return codes;
+}
+
+bool setLanguage(const std::string& code) {
+    pseudoCache.clear();
+    reportedMissing.clear();
+    active = Catalog();
+    activeCode = "en";
+    if (code == "en" || code == "pseudo") {
+        activeCode = code;
+        return true;
+    }
+    Catalog loaded;
+    std::string error;
+    if (!loadCatalog(catalogDirectory() + "/" + code + ".txt", loaded, error)) {
+        TraceLog(LOG_WARNING, "I18N: %s, showing English", error.c_str());
+        return false;
+    }
+    active = loaded;
+    activeCode = code;
+    TraceLog(LOG_INFO, "I18N: %s (%s), %d strings", active.name.c_str(), code.c_str(), (int) active.strings.size());
+    return true;
+}
+
+const std::string& currentLanguage() {
+    return activeCode;
+}
+
+std::string languageGlyphs() {
+    std::string glyphs;
+    for (const auto& entry : active.strings) {
+        for (const std::string& form : entry.second) {
+            glyphs += form;
+        }
+    }
+    return glyphs;
+}
+
+// "Paused" -> "[Þàüséð ~~~]": accents from Latin-1, which even the built-in
+// font has, plus roughly the third more length most languages need over English
+static std::string pseudoLocalize(const std::string& text) {
+    static const char* const LOWER[26] = {
+        "à", "b", "ç", "ð", "é", "f", "g", "h", "í", "j", "k", "l", "m", "ñ", "ö", "þ", "q", "r", "s", "t", "ü", "v", "w", "x", "ý", "z",
+    };
+    static const char* const UPPER[26] = {
+        "Å", "B", "Ç", "Ð", "É", "F", "G", "H", "Î", "J", "K", "L", "M", "Ñ", "Ö", "Þ", "Q", "R", "S", "T", "Û", "V", "W", "X", "Ý", "Z",
+    };
+    std::string result = "[";
+    for (size_t i = 0; i < text.size(); i++) {
+        char c = text[i];
+        // format specifiers and {placeholders} have to survive as they are
+        if (c == '%') {
+            size_t end = text.find_first_of("diufFeEgGxXoscpaA%", i + 1);
+            end = end == std::string::npos ? text.size() - 1 : end;
+            result += text.substr(i, end - i + 1);
+            i = end;
+        } else if (c == '{' && text.find('}', i) != std::string::npos) {
+            size_t end = text.find('}', i);
+            result += text.substr(i, end - i + 1);
+            i = end;
+        } else if (c >= 'a' && c <= 'z') {
+            result += LOWER[c - 'a'];
+        } else if (c >= 'A' && c <= 'Z') {
+            result += UPPER[c - 'A'];
+        } else {
+            result += c;
+        }
+    }
+    result += " " + std::string(text.size() / 3 + 1, '~') + "]";
+    return result;
+}
+
+std::string tr(const std::string& text) {
+    if (activeCode == "en" || text.empty()) {
+        return text;
+    }
+    if (activeCode == "pseudo") {
+        auto cached = pseudoCache.find(text);
+        if (cached == pseudoCache.end()) {
+            cached = pseudoCache.emplace(text, pseudoLocalize(text)).first;
+        }
+        return cached->second;
+    }
+    auto found = active.strings.find(text);
+    if (found == active.strings.end()) {
+        // once each, the HUD asks every frame
+        if (reportedMissing.insert(text).second) {
+            TraceLog(LOG_INFO, "I18N: No %s translation for '%s'", activeCode.c_str(), text.c_str());
+        }
+        return text;
+    }
+    return found->second[0];
+}
+
+std::string trn(const std::string& one, const std::string& other, int count) {
+    if (activeCode == "en") {
+        return count == 1 ? one : other;
+    }
+    if (activeCode == "pseudo") {
+        return tr(count == 1 ? one : other);
+    }
+    auto found = active.strings.find(other);
+    if (found == active.strings.end()) {
+        if (reportedMissing.insert(other).second) {
+            TraceLog(LOG_INFO, "I18N: No %s translation for '%s'", activeCode.c_str(), other.c_str());
+        }
+        return count == 1 ? one : other;
+    }
+    const std::vector<std::string>& forms = found->second;
+    size_t form = pluralForm(active.plural, count);
+    return forms[form < forms.size() ? form : forms.size() - 1];
+}
@@ -0,0 +1,49 @@
+#pragma once
+#include <map>
+#include <string>
+#include <vector>
+
+
+// How a language picks between the forms of a counted string.
+enum class PluralRule {
+    OneOther,    // 1 | everything else (English, German, ...)
+    OneWithZero, // 0 and 1 | everything else (French, ...)
+    Slavic,      // 1, 21, ... | 2-4, 22-24, ... | everything else (Russian, ...)
+    None,        // one form (Chinese, Japanese, Korean, ...)
+};
+
+// lang/<code>.txt, key = value lines keyed by the English text:
+//
+//   @name = Deutsch
+//   @plural = one_other
+//   Paused = Pausiert
+//   %d runs = %d Lauf | %d Läufe
+//
+// Counted strings are keyed by their English plural and list their forms in
+// the order of the language's plural rule, separated by " | ". Format
+// specifiers and {placeholders} stay as they are.
+struct Catalog {
+    std::string code; // the file name
+    std::string name; // what the language calls itself
+    PluralRule plural = PluralRule::OneOther;
+    std::map<std::string, std::vector<std::string>> strings;
+};
+
+bool loadCatalog(const std::string& path, Catalog& catalog, std::string& error);
+
+// "en" (the text in the code), every catalog in lang/, and "pseudo", which
+// accents and pads every string so text that skipped tr() or gets clipped
+// stands out.
+std::vector<std::string> languageCodes();
+// Switches what tr() returns from now on; false (and English) when code
+// has no catalog.
+bool setLanguage(const std::string& code);
+const std::string& currentLanguage();
+// Every translation of the current language, for TextRenderer::prepare().
+std::string languageGlyphs();
+
+// Player-facing text goes through these; debug and developer text doesn't.
+std::string tr(const std::string& text);
+// The form of one/other (the English forms) for count, format specifiers and
+// all: TextFormat(trn("%d run", "%d runs", n).c_str(), n).
+std::string trn(const std::string& one, const std::string& other, int count);
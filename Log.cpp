@@ -0,0 +1,164 @@
+#include "raylib.h"
+#include "Log.hpp"
+#include <cctype>
+#include <cstdarg>
+#include <map>
+#include <mutex>
+#include <sstream>
+
+
+static const char* LEVEL_PREFIXES[] = {"", "TRACE", "DEBUG", "INFO", "WARNING", "ERROR", "FATAL"};
+static const std::map<std::string, int> LEVEL_NAMES = {
+    {"trace", LOG_TRACE}, {"debug", LOG_DEBUG}, {"info", LOG_INFO}, {"warning", LOG_WARNING},
+    {"error", LOG_ERROR}, {"fatal", LOG_FATAL}, {"none", LOG_NONE},
+};
+
+static FILE* logOutput = stdout;
+static int defaultLevel = LOG_INFO;
+static std::map<std::string, int> moduleLevels;
+static std::vector<std::function<void(const LogRecord&)>> sinks;
+static std::mutex sinkLock;
+static thread_local std::vector<const char*> openSpans;
+
+std::string formatLogRecord(const LogRecord& record) {
+    std::string line = record.level >= LOG_TRACE && record.level <= LOG_FATAL ? LEVEL_PREFIXES[record.level] : "LOG";
+    line += ": ";
+    if (!record.module.empty()) {
+        line += record.module + ": ";
+    }
+    if (!record.span.empty()) {
+        line += "[" + record.span + "] ";
+    }
+    return line + record.message;
+}
+
+// "AUDIO: Missing sound" -> "AUDIO", "Missing sound"
+static void splitModule(const std::string& text, std::string& module, std::string& message) {
+    size_t length = 0;
+    while (length < text.size() && (isupper((unsigned char) text[length]) || isdigit((unsigned char) text[length]) || text[length] == '_')) {
+        length++;
+    }
+    if (length > 0 && text.compare(length, 2, ": ") == 0) {
+        module = text.substr(0, length);
+        message = text.substr(length + 2);
+    } else {
+        module.clear();
+        message = text;
+    }
+}
+
+static void logCallback(int level, const char* text, va_list args) {
+    char buffer[1024];
+    vsnprintf(buffer, sizeof(buffer), text, args);
+    LogRecord record;
+    record.level = level;
+    splitModule(buffer, record.module, record.message);
+    if (!logEnabled(record.module, level)) {
+        return;
+    }
+    for (const char* span : openSpans) {
+        record.span += (record.span.empty() ? "" : "/") + std::string(span);
+    }
+    std::lock_guard<std::mutex> guard(sinkLock);
+    fprintf(logOutput, "%s\n", formatLogRecord(record).c_str());
+    for (auto& sink : sinks) {
+        sink(record);
+    }
+}
+
+void installLogging(FILE* output) {
+    logOutput = output;
+    SetTraceLogCallback(logCallback);
+}
+
+void addLogSink(std::function<void(const LogRecord&)> sink) {
+    std::lock_guard<std::mutex> guard(sinkLock);
+    sinks.push_back(sink);
+}
+
+bool setLogFilter(const std::string& filter, std::string& error) {
+    int parsedDefault = LOG_INFO;
+    std::map<std::string, int> parsedModules;
+    std::istringstream rules(filter);
+    std::string rule;
+    while (std::getline(rules, rule, ',')) {
+        size_t start = rule.find_first_not_of(' ');
+        if (start == std::string::npos) {
+            continue;
+        }
+        rule = rule.substr(start, rule.find_last_not_of(' ') - start + 1);
+        size_t separator = rule.find('=');
+        std::string levelName = separator == std::string::npos ? rule : rule.substr(separator + 1);
+        auto level = LEVEL_NAMES.find(levelName);
+        if (level == LEVEL_NAMES.end()) {
+            error = "unknown log level '" + levelName + "' in '" + rule + "'";
+            return false;
+        }
+        if (separator == std::string::npos) {
+            parsedDefault = level->second;
+            continue;
+        }
+        std::string module = rule.substr(0, separator);
+        for (char& c : module) {
+            c = toupper((unsigned char) c);
+        }
+        parsedModules[module] = level->second;
+    }
+
+    defaultLevel = parsedDefault;
+    moduleLevels = parsedModules;
+    // raylib drops lines below its own level before they reach the callback
+    int lowest = defaultLevel;
+    for (auto& entry : moduleLevels) {
+        lowest = entry.second < lowest ? entry.second : lowest;
+    }
+    SetTraceLogLevel(lowest);
+    return true;
+}
+
+bool logEnabled(const std::string& module, int level) {
+    auto found = moduleLevels.find(module);
+    return level >= (found != moduleLevels.end() ? found->second : defaultLevel);
+}
+
+LogSpan::LogSpan(const char* spanModule, const char* spanName, int spanLevel)
+    : module(spanModule), name(spanName), level(spanLevel), enabled(logEnabled(spanModule, spanLevel)) {
+    if (enabled) {
+        TraceLog(level, "%s: > %s", module, name);
+        openSpans.push_back(name);
+        start = std::chrono::steady_clock::now();
+    }
+}
+
+LogSpan::~LogSpan() {
+    if (enabled) {
+        double elapsed = std::chrono::duration<double, std::milli>(std::chrono::steady_clock::now() - start).count();
+        openSpans.pop_back();
+        TraceLog(level, "%s: < %s (%.2f ms)", module, name, elapsed);
+    }
+}
+
+void LogConsole::push(const LogRecord& record) {
+    if (lines.size() < LINES) {
+        lines.push_back(record);
+        return;
+    }
+    lines[next] = record;
+    next = (next + 1) % LINES;
+}
+
+void LogConsole::draw(Rectangle bounds, float scale) {
+    if (lines.empty()) {
+        return;
+    }
+    int fontSize = (int) (10 * scale);
+    int line = fontSize + (int) (2 * scale);
+    DrawRectangleRec(bounds, Fade(BLACK, 0.6f));
+    float y = bounds.y + bounds.height - line * (float) lines.size();
+    for (size_t i = 0; i < lines.size(); i++) {
+        const LogRecord& record = lines[(next + i) % lines.size()];
+        Color color = record.level >= LOG_ERROR ? RED : record.level == LOG_WARNING ? YELLOW : record.level <= LOG_DEBUG ? GRAY : LIGHTGRAY;
+        DrawText(formatLogRecord(record).c_str(), bounds.x + 4 * scale, y, fontSize, color);
+        y += line;
+    }
+}
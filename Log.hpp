@@ -0,0 +1,61 @@
+#pragma once
+#include "raylib.h"
+#include <chrono>
+#include <cstdio>
+#include <functional>
+#include <string>
+#include <vector>
+
+
+// One TraceLog line taken apart. The module is the "MODULE: " prefix every
+// message in the game (and most of raylib's) starts with; span is the path of
+// the LogSpans open on this thread when it was logged, "load/parse".
+struct LogRecord {
+    int level;
+    std::string module; // upper case, empty when the line has none
+    std::string span;
+    std::string message;
+};
+
+// "WARNING: LEVEL: [load] message", the way raylib prints, plus the span.
+std::string formatLogRecord(const LogRecord& record);
+
+// Takes over TraceLog: lines are split into records, filtered per module
+// and printed to output, then handed to every sink.
+void installLogging(FILE* output = stdout);
+void addLogSink(std::function<void(const LogRecord&)> sink);
+
+// A default level plus per-module overrides, comma separated:
+//   "info,audio=warning,level=debug,sim=trace"
+// Levels are trace, debug, info, warning, error, fatal and none.
+bool setLogFilter(const std::string& filter, std::string& error);
+bool logEnabled(const std::string& module, int level);
+
+// Brackets a unit of work (a level load, a tick...) with enter and exit lines,
+// the exit one timed, and tags everything logged inside it. Spans the filter
+// hides cost one lookup and tag nothing.
+class LogSpan {
+  public:
+    LogSpan(const char* module, const char* name, int level = LOG_DEBUG);
+    ~LogSpan();
+
+  private:
+    const char* module;
+    const char* name;
+    int level;
+    bool enabled;
+    std::chrono::steady_clock::time_point start;
+};
+
+// The last LINES records, for the debug overlay.
+class LogConsole {
+  public:
+    static const int LINES = 12;
+
+    void push(const LogRecord& record);
+    void draw(Rectangle bounds, float scale);
+
+  private:
+    std::vector<LogRecord> lines; // ring, next is the oldest once full
+    size_t next = 0;
+};
@@ -0,0 +1,75 @@
+#include "raylib.h"
+#include "MouseControl.hpp"
+#include <cmath>
+
+
+void MouseControl::apply(const Settings& settings) {
+    enabled = settings.mouseControl;
+    sensitivity = settings.mouseSensitivity;
+    smoothing = fminf(fmaxf(settings.mouseSmoothing, 0), 0.95f);
+    prediction = settings.mousePrediction;
+    if (!enabled) {
+        setCaptured(false);
+        active = false;
+    }
+}
+
+bool MouseControl::isEnabled() {
+    return enabled;
+}
+
+void MouseControl::setCaptured(bool wanted) {
+    wanted = wanted && enabled;
+    if (wanted == captured) {
+        return;
+    }
+    captured = wanted;
+    if (captured) {
+        DisableCursor();
+    } else {
+        EnableCursor();
+    }
+}
+
+bool MouseControl::isCaptured() {
+    return captured;
+}
+
+void MouseControl::update(float deltaTime, float viewportScale, float paddleX, float minX, float maxX) {
+    float delta = 0;
+    if (captured && viewportScale > 0) {
+        delta = GetMouseDelta().x / viewportScale * sensitivity;
+    }
+
+    if (!active) {
+        if (delta == 0) {
+            return;
+        }
+        // pick up from wherever the keyboard or stick left the paddle
+        target = smoothed = paddleX;
+        velocity = 0;
+        active = true;
+    }
+
+    target = fminf(fmaxf(target + delta, minX), maxX);
+    if (deltaTime > 0) {
+        // light low-pass, so a burst of high-rate mouse reports reads as one motion
+        velocity += (delta / deltaTime - velocity) * 0.5f;
+    }
+    // framerate independent: smoothing is the share of the gap left after a 60 Hz frame
+    smoothed += (target - smoothed) * (1 - powf(smoothing, deltaTime * 60));
+    // prediction leads by one frame of pointer motion, hiding the smoothing lag
+    lead = prediction ? velocity * deltaTime : 0;
+
+    if (delta == 0 && fabsf(target - smoothed) < 0.5f && fabsf(paddleX - smoothed) < 1) {
+        active = false;
+    }
+}
+
+bool MouseControl::isActive() {
+    return active;
+}
+
+float MouseControl::getTarget() {
+    return smoothed + lead;
+}
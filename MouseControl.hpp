@@ -0,0 +1,42 @@
+#pragma once
+#include "raylib.h"
+#include "Settings.hpp"
+
+
+// Relative mouse steering for the paddle. While playing the pointer is
+// captured (hidden and locked to the window) and its motion moves a target
+// the paddle chases; menus and pause hand the pointer back. Motion arrives in
+// window pixels and is divided by the viewport scale, so the paddle travels as
+// far across the playfield as the pointer would have across the screen at any
+// window size or DPI.
+class MouseControl {
+  public:
+    void apply(const Settings& settings);
+    bool isEnabled();
+
+    // Call once per frame; only touches the OS cursor when the state changes.
+    void setCaptured(bool captured);
+    bool isCaptured();
+
+    // Call once per frame before the simulation ticks with the paddle's
+    // current center and the range the center may move in (virtual units).
+    void update(float deltaTime, float viewportScale, float paddleX, float minX, float maxX);
+    // True while the mouse is steering; the keyboard and stick take over
+    // again once it has been still and the paddle has caught up.
+    bool isActive();
+    // Where the paddle center should head this tick.
+    float getTarget();
+
+  private:
+    bool enabled = false;
+    bool captured = false;
+    bool active = false;
+    float sensitivity = 1;
+    float smoothing = 0;
+    bool prediction = false;
+
+    float target = 0;   // raw pointer position
+    float smoothed = 0; // what the paddle follows
+    float velocity = 0; // of the raw pointer, for prediction
+    float lead = 0;
+};
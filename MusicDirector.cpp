@@ -0,0 +1,130 @@
+#ifndef BREAKOUT_NO_AUDIO
+#include "raylib.h"
+#include "MusicDirector.hpp"
+#include <cmath>
+
+
+void MusicDirector::remember(int slot) {
+    if (tracks[slot].isLoaded()) {
+        positions[ids[slot]] = tracks[slot].getPosition();
+    }
+}
+
+bool MusicDirector::loadInto(int slot, const std::string& trackId) {
+    remember(slot);
+    tracks[slot].unload();
+    ids[slot] = trackId;
+    return tracks[slot].load(musicTrackPath(trackId));
+}
+
+void MusicDirector::preload(const std::string& trackId) {
+    // never evict the track that is audible
+    int idle = 1 - current;
+    if (trackId == ids[current] || trackId == ids[idle] || state == MusicState::Crossfading) {
+        return;
+    }
+    loadInto(idle, trackId);
+}
+
+void MusicDirector::play(const std::string& trackId) {
+    if (trackId == ids[current] && state != MusicState::Stopped) {
+        if (state == MusicState::Paused) {
+            resume();
+        }
+        return;
+    }
+    // the slot that was fading out (if any) is dropped for the new track,
+    // unless it already holds it preloaded
+    int next = 1 - current;
+    bool preloaded = ids[next] == trackId && tracks[next].isLoaded() && state != MusicState::Crossfading;
+    if (preloaded || loadInto(next, trackId)) {
+        auto position = positions.find(trackId);
+        tracks[next].play();
+        if (position != positions.end()) {
+            tracks[next].seek(position->second);
+        }
+    }
+    current = next;
+    fade = tracks[1 - current].isLoaded() ? 0 : 1;
+    state = fade < 1 ? MusicState::Crossfading : MusicState::Playing;
+}
+
+void MusicDirector::pause() {
+    if (state == MusicState::Paused || state == MusicState::Stopped) {
+        return;
+    }
+    pausedFrom = state;
+    state = MusicState::Paused;
+    tracks[0].pause();
+    tracks[1].pause();
+}
+
+void MusicDirector::resume() {
+    if (state != MusicState::Paused) {
+        return;
+    }
+    state = pausedFrom;
+    for (LayeredMusic& track : tracks) {
+        if (track.isLoaded()) {
+            track.resume();
+        }
+    }
+}
+
+void MusicDirector::duck(float seconds) {
+    duckTimer = fmaxf(duckTimer, seconds);
+}
+
+void MusicDirector::setIntensity(float intensity) {
+    tracks[current].setIntensity(intensity);
+}
+
+void MusicDirector::setPitch(float pitch) {
+    for (LayeredMusic& track : tracks) {
+        track.setPitch(pitch);
+    }
+}
+
+void MusicDirector::update(float deltaTime, float busGain) {
+    if (state == MusicState::Paused || state == MusicState::Stopped) {
+        return;
+    }
+    // quick dip, slower recovery
+    if (duckTimer > 0) {
+        duckTimer -= deltaTime;
+        duckGain = fmaxf(duckGain - deltaTime * 4, DUCK_GAIN);
+    } else {
+        duckGain = fminf(duckGain + deltaTime, 1);
+    }
+
+    if (state == MusicState::Crossfading) {
+        fade = fminf(fade + deltaTime / CROSSFADE_SECONDS, 1);
+        if (fade >= 1) {
+            remember(1 - current);
+            tracks[1 - current].unload();
+            ids[1 - current].clear();
+            state = MusicState::Playing;
+        }
+    }
+    float gain = busGain * duckGain;
+    tracks[current].update(deltaTime, gain * fade);
+    tracks[1 - current].update(deltaTime, gain * (1 - fade));
+}
+
+void MusicDirector::unload() {
+    tracks[0].unload();
+    tracks[1].unload();
+    ids[0].clear();
+    ids[1].clear();
+    state = MusicState::Stopped;
+}
+
+MusicState MusicDirector::getState() {
+    return state;
+}
+
+std::string MusicDirector::getTrack() {
+    return ids[current];
+}
+
+#endif
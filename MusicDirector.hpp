@@ -0,0 +1,54 @@
+#pragma once
+#include "raylib.h"
+#include <map>
+#include <string>
+#include "LayeredMusic.hpp"
+
+
+enum class MusicState {
+    Stopped,
+    Playing,
+    Crossfading,
+    Paused,
+};
+
+// Decides what music is heard. play() crossfades from the current track to
+// another (levels, menus); a track that was playing before picks up where it
+// left off. duck() dips the music under a sting such as losing the ball.
+class MusicDirector {
+  public:
+    static constexpr float CROSSFADE_SECONDS = 1.5f;
+    static constexpr float DUCK_GAIN = 0.3f;
+
+    // An id without a track fades to silence.
+    void play(const std::string& trackId);
+    // Loads a track into the idle slot ahead of time (during a level
+    // transition) so the following play() doesn't stall on file loading.
+    void preload(const std::string& trackId);
+    void pause();
+    void resume();
+    void duck(float seconds);
+    void setIntensity(float intensity);
+    // Both tracks, so a crossfade doesn't come in at the wrong speed.
+    void setPitch(float pitch);
+
+    void update(float deltaTime, float busGain);
+    void unload();
+
+    MusicState getState();
+    std::string getTrack();
+
+  private:
+    LayeredMusic tracks[2];
+    std::string ids[2];
+    int current = 0;
+    float fade = 1; // crossfade progress, 1 = finished
+    MusicState state = MusicState::Stopped;
+    MusicState pausedFrom = MusicState::Stopped;
+    float duckTimer = 0;
+    float duckGain = 1;
+    std::map<std::string, float> positions; // where each track was left
+
+    void remember(int slot);
+    bool loadInto(int slot, const std::string& trackId);
+};
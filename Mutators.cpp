@@ -0,0 +1,88 @@
+#include "Mutators.hpp"
+#include <algorithm>
+
+
+const std::vector<Mutator>& allMutators() {
+    static const std::vector<Mutator> MUTATORS = {
+        {"double_speed", "Double speed", "The ball moves twice as fast", 50, [](SimulationRules& rules) { rules.ballSpeed *= 2; }},
+        {"hardcore", "Hardcore", "One life, no restarts, and a death ends the hardcore streak", 50, [](SimulationRules& rules) { rules.startLives = 1; }},
+        {"invisible_bricks", "Invisible bricks", "Bricks only show once they've been hit", 50, [](SimulationRules& rules) { rules.invisibleBricks = true; }},
+        {"mirrored_controls", "Mirrored controls", "Left moves the paddle right and right moves it left", 25, [](SimulationRules& rules) { rules.mirroredControls = true; }},
+        {"one_life", "One life", "No second ball", 25, [](SimulationRules& rules) { rules.startLives = 1; }},
+        {"tiny_paddle", "Tiny paddle", "The paddle is just over half as wide", 25, [](SimulationRules& rules) { rules.paddleScale *= 0.6f; }},
+        {"wide_paddle", "Wide paddle", "The paddle is half again as wide", -25, [](SimulationRules& rules) { rules.paddleScale *= 1.5f; }},
+    };
+    return MUTATORS;
+}
+
+const Mutator* findMutator(const std::string& id) {
+    for (const Mutator& mutator : allMutators()) {
+        if (id == mutator.id) {
+            return &mutator;
+        }
+    }
+    return nullptr;
+}
+
+static std::vector<std::string> splitList(const std::string& list) {
+    std::vector<std::string> ids;
+    size_t start = 0;
+    while (start <= list.size()) {
+        size_t comma = list.find(',', start);
+        comma = comma == std::string::npos ? list.size() : comma;
+        std::string id = list.substr(start, comma - start);
+        id.erase(0, id.find_first_not_of(" \t"));
+        id.erase(id.find_last_not_of(" \t") + 1);
+        if (!id.empty()) {
+            ids.push_back(id);
+        }
+        start = comma + 1;
+    }
+    return ids;
+}
+
+bool parseMutators(const std::string& list, std::string& canonical, std::string& error) {
+    std::vector<std::string> ids = splitList(list);
+    for (const std::string& id : ids) {
+        if (!findMutator(id)) {
+            error = "unknown mutator '" + id + "'";
+            return false;
+        }
+    }
+    std::sort(ids.begin(), ids.end());
+    ids.erase(std::unique(ids.begin(), ids.end()), ids.end());
+    canonical.clear();
+    for (const std::string& id : ids) {
+        canonical += (canonical.empty() ? "" : ",") + id;
+    }
+    return true;
+}
+
+bool hasMutator(const std::string& canonical, const std::string& id) {
+    std::vector<std::string> ids = splitList(canonical);
+    return std::find(ids.begin(), ids.end(), id) != ids.end();
+}
+
+std::vector<const Mutator*> mutatorList(const std::string& canonical) {
+    std::vector<const Mutator*> mutators;
+    for (const std::string& id : splitList(canonical)) {
+        if (const Mutator* mutator = findMutator(id)) {
+            mutators.push_back(mutator);
+        }
+    }
+    return mutators;
+}
+
+void applyMutators(const std::string& canonical, SimulationRules& rules) {
+    SimulationRules defaults;
+    rules.ballSpeed = defaults.ballSpeed;
+    rules.paddleScale = defaults.paddleScale;
+    rules.startLives = defaults.startLives;
+    rules.invisibleBricks = defaults.invisibleBricks;
+    rules.mirroredControls = defaults.mirroredControls;
+    rules.scorePercent = defaults.scorePercent;
+    for (const Mutator* mutator : mutatorList(canonical)) {
+        mutator->apply(rules);
+        rules.scorePercent += mutator->scoreBonus;
+    }
+}
@@ -0,0 +1,33 @@
+#pragma once
+#include <string>
+#include <vector>
+#include "Simulation.hpp"
+
+
+// A run modifier, on for a whole run (--mutators, weekly challenges), and
+// any number of them together. Each one only changes SimulationRules, which
+// the simulation reads where bricks appear, the paddle moves and points are
+// scored, so a replay replays it from the ids in its header and the
+// leaderboards keep separate boards per combination.
+// Hardcore is the one main looks for as well: it takes the restarts away
+// and keeps the profile's hardcore streak.
+struct Mutator {
+    const char* id;
+    const char* name;
+    const char* description;
+    int scoreBonus; // percent on every point scored, negative for one that makes it easier
+    void (*apply)(SimulationRules& rules);
+};
+
+const std::vector<Mutator>& allMutators();
+const Mutator* findMutator(const std::string& id);
+
+// "double_speed, tiny_paddle" -> "double_speed,tiny_paddle": sorted and
+// without repeats, so one combination is always spelled the same. False on
+// an id this build doesn't have.
+bool parseMutators(const std::string& list, std::string& canonical, std::string& error);
+std::vector<const Mutator*> mutatorList(const std::string& canonical);
+bool hasMutator(const std::string& canonical, const std::string& id);
+// Puts the rules the mutators own back to their defaults, then applies the
+// ones in canonical (unknown ids are skipped), their score bonuses added up.
+void applyMutators(const std::string& canonical, SimulationRules& rules);
@@ -0,0 +1,140 @@
+#include "raylib.h"
+#include "Particles.hpp"
+#include <cmath>
+
+
+static float randomRange(float min, float max) {
+    return min + (max - min) * (GetRandomValue(0, 10000) / 10000.0f);
+}
+
+ParticleStyle defaultParticleStyle(BrickMaterial material) {
+    switch (material) {
+    case BrickMaterial::Glass:
+        return {SKYBLUE, 24, 80, 220, 0.2f, 0.5f, 2, 400};
+    case BrickMaterial::Stone:
+        return {GRAY, 10, 20, 90, 0.5f, 0.9f, 4, 600};
+    case BrickMaterial::Metal:
+        return {GOLD, 8, 120, 260, 0.1f, 0.3f, 2, 0};
+    default:
+        return {RAYWHITE, 12, 40, 140, 0.3f, 0.6f, 3, 300};
+    }
+}
+
+ParticleSystem::ParticleSystem(int capacity, int spawnBudgetPerFrame) {
+    pool.resize(capacity);
+    activeCount = 0;
+    spawnBudget = spawnBudgetPerFrame;
+    spawnedThisFrame = 0;
+
+    for (int i = 0; i < 4; i++) {
+        materialStyles[i] = defaultParticleStyle((BrickMaterial) i);
+    }
+}
+
+void ParticleSystem::setReducedMotion(bool enabled) {
+    reducedMotion = enabled;
+}
+
+void ParticleSystem::setMaterialStyle(BrickMaterial material, ParticleStyle style) {
+    materialStyles[(int) material] = style;
+}
+
+ParticleStyle ParticleSystem::getMaterialStyle(BrickMaterial material) {
+    return materialStyles[(int) material];
+}
+
+void ParticleSystem::emitBrickShatter(Rectangle brick, BrickMaterial material, Color brickColor) {
+    ParticleStyle style = materialStyles[(int) material];
+    if (material == BrickMaterial::Standard) {
+        style.color = brickColor;
+    }
+    emit(brick, style, 0, 2 * PI);
+}
+
+void ParticleSystem::emitPowerUpPickup(Vector2 position, Color color) {
+    ParticleStyle style = {color, 20, 60, 160, 0.4f, 0.7f, 3, -50};
+    emit({position.x, position.y, 0, 0}, style, PI, 2 * PI);
+}
+
+void ParticleSystem::emitLaserImpact(Vector2 position) {
+    ParticleStyle style = {RED, 6, 80, 200, 0.1f, 0.25f, 2, 200};
+    emit({position.x, position.y, 0, 0}, style, 0, PI);
+}
+
+void ParticleSystem::emitBallTrail(Vector2 position, Vector2 velocity, Color color) {
+    float backwards = atan2f(-velocity.y, -velocity.x);
+    ParticleStyle style = {Fade(color, 0.6f), 1, 5, 20, 0.15f, 0.3f, 2, 0};
+    emit({position.x, position.y, 0, 0}, style, backwards - 0.4f, backwards + 0.4f);
+}
+
+void ParticleSystem::emit(Rectangle area, const ParticleStyle& style, float minAngle, float maxAngle) {
+    int count = reducedMotion ? (style.count + 2) / 3 : style.count;
+    float speedScale = reducedMotion ? 0.4f : 1;
+    for (int i = 0; i < count; i++) {
+        float angle = randomRange(minAngle, maxAngle);
+        float speed = randomRange(style.minSpeed, style.maxSpeed) * speedScale;
+        float lifetime = randomRange(style.minLifetime, style.maxLifetime);
+
+        Particle particle;
+        particle.position = {randomRange(area.x, area.x + area.width), randomRange(area.y, area.y + area.height)};
+        particle.velocity = {cosf(angle) * speed, sinf(angle) * speed};
+        particle.color = style.color;
+        particle.size = style.size;
+        particle.life = lifetime;
+        particle.maxLife = lifetime;
+        particle.gravity = style.gravity;
+
+        if (!spawn(particle)) {
+            return;
+        }
+    }
+}
+
+bool ParticleSystem::spawn(const Particle& particle) {
+    if (activeCount >= (int) pool.size() || spawnedThisFrame >= spawnBudget) {
+        return false;
+    }
+    pool[activeCount++] = particle;
+    spawnedThisFrame++;
+    return true;
+}
+
+void ParticleSystem::update(float deltaTime) {
+    spawnedThisFrame = 0;
+
+    int i = 0;
+    while (i < activeCount) {
+        Particle& particle = pool[i];
+        particle.life -= deltaTime;
+        if (particle.life <= 0) {
+            // swap the last live particle into this slot
+            pool[i] = pool[--activeCount];
+            continue;
+        }
+        particle.velocity.y += particle.gravity * deltaTime;
+        particle.position.x += particle.velocity.x * deltaTime;
+        particle.position.y += particle.velocity.y * deltaTime;
+        i++;
+    }
+}
+
+void ParticleSystem::draw() {
+    for (int i = 0; i < activeCount; i++) {
+        const Particle& particle = pool[i];
+        Color color = particle.color;
+        color.a = (unsigned char) (color.a * (particle.life / particle.maxLife));
+        DrawRectangleV(particle.position, {particle.size, particle.size}, color);
+    }
+}
+
+void ParticleSystem::clear() {
+    activeCount = 0;
+}
+
+int ParticleSystem::getActiveCount() {
+    return activeCount;
+}
+
+int ParticleSystem::getCapacity() {
+    return pool.size();
+}
@@ -0,0 +1,64 @@
+#pragma once
+#include "raylib.h"
+#include <vector>
+#include "Brick.hpp"
+
+
+struct Particle {
+    Vector2 position;
+    Vector2 velocity;
+    Color color;
+    float size;
+    float life;
+    float maxLife;
+    float gravity;
+};
+
+// How a burst looks. Speeds are in pixels per second, lifetimes in seconds.
+struct ParticleStyle {
+    Color color;
+    int count;
+    float minSpeed;
+    float maxSpeed;
+    float minLifetime;
+    float maxLifetime;
+    float size;
+    float gravity;
+};
+
+ParticleStyle defaultParticleStyle(BrickMaterial material);
+
+// Fixed-size CPU particle pool. Live particles are kept packed at the front of
+// the pool so update/draw only touch what is alive; once the pool or the
+// per-frame spawn budget is exhausted further emissions are dropped.
+class ParticleSystem {
+  public:
+    ParticleSystem(int capacity = 2048, int spawnBudgetPerFrame = 256);
+
+    // Scales bursts down to a few slow particles.
+    void setReducedMotion(bool enabled);
+    void setMaterialStyle(BrickMaterial material, ParticleStyle style);
+    ParticleStyle getMaterialStyle(BrickMaterial material);
+
+    void emitBrickShatter(Rectangle brick, BrickMaterial material, Color brickColor);
+    void emitPowerUpPickup(Vector2 position, Color color);
+    void emitLaserImpact(Vector2 position);
+    void emitBallTrail(Vector2 position, Vector2 velocity, Color color);
+
+    void update(float deltaTime);
+    void draw();
+    void clear();
+    int getActiveCount();
+    int getCapacity();
+
+  private:
+    std::vector<Particle> pool;
+    int activeCount;
+    int spawnBudget;
+    int spawnedThisFrame;
+    bool reducedMotion = false;
+    ParticleStyle materialStyles[4];
+
+    void emit(Rectangle area, const ParticleStyle& style, float minAngle, float maxAngle);
+    bool spawn(const Particle& particle);
+};
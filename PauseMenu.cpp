@@ -0,0 +1,134 @@
+#include "raylib.h"
+#include "PauseMenu.hpp"
+#include "Localization.hpp"
+#include "Sanitize.hpp"
+#include "Ui.hpp"
+#include <cmath>
+
+
+std::string PauseMenu::suspendPath(const std::string& profile) {
+    return std::string(GetApplicationDirectory()) + "profiles/" + sanitizeFilename(profile) + ".suspend.bkrp";
+}
+
+void PauseMenu::open(bool canRestart, bool canSuspend, bool canPlace, bool canChooseLevel) {
+    items = {PauseItem::Resume};
+    if (canPlace) {
+        items.push_back(PauseItem::PlaceBall);
+    }
+    if (canRestart) {
+        items.push_back(PauseItem::Restart);
+    }
+    if (canChooseLevel) {
+        items.push_back(PauseItem::Levels);
+    }
+    items.insert(items.end(), {PauseItem::Settings, PauseItem::Stats, PauseItem::Shop, PauseItem::Quit});
+    suspendable = canSuspend;
+    selected = 0;
+    countdownStart = -1;
+}
+
+const char* PauseMenu::label(PauseItem item) const {
+    switch (item) {
+    case PauseItem::Resume: return "Resume";
+    case PauseItem::Restart: return "Restart level";
+    case PauseItem::Settings: return "Settings";
+    case PauseItem::Stats: return "Stats";
+    case PauseItem::Shop: return "Shop";
+    case PauseItem::Levels: return "Levels";
+    case PauseItem::PlaceBall: return "Place ball";
+    case PauseItem::Quit: return suspendable ? "Save and quit" : "Quit";
+    }
+    return "";
+}
+
+static bool padPressed(int button) {
+    for (int pad = 0; pad < 4; pad++) {
+        if (IsGamepadAvailable(pad) && IsGamepadButtonPressed(pad, button)) {
+            return true;
+        }
+    }
+    return false;
+}
+
+bool PauseMenu::update(PauseItem& picked) {
+    if (items.empty() || isCountingDown()) {
+        return false;
+    }
+    int count = items.size();
+    if (IsKeyPressed(KEY_DOWN) || padPressed(GAMEPAD_BUTTON_LEFT_FACE_DOWN)) {
+        selected = (selected + 1) % count;
+    } else if (IsKeyPressed(KEY_UP) || padPressed(GAMEPAD_BUTTON_LEFT_FACE_UP)) {
+        selected = (selected - 1 + count) % count;
+    }
+    if (IsKeyPressed(KEY_ENTER) || IsKeyPressed(KEY_KP_ENTER) || padPressed(GAMEPAD_BUTTON_RIGHT_FACE_DOWN)) {
+        picked = items[selected];
+        return true;
+    }
+    return false;
+}
+
+bool PauseMenu::tap(Vector2 point, PauseItem& picked) {
+    if (items.empty() || isCountingDown() || rowHeight <= 0 || !CheckCollisionPointRec(point, panel)) {
+        return false;
+    }
+    int row = (int) floorf((point.y - rowsTop) / rowHeight);
+    if (row < 0 || row >= (int) items.size()) {
+        return false;
+    }
+    selected = row;
+    picked = items[row];
+    return true;
+}
+
+void PauseMenu::startCountdown(double now) {
+    countdownStart = now;
+}
+
+void PauseMenu::cancelCountdown() {
+    countdownStart = -1;
+}
+
+bool PauseMenu::isCountingDown() const {
+    return countdownStart >= 0;
+}
+
+bool PauseMenu::countdownFinished(double now) {
+    if (!isCountingDown() || now - countdownStart < COUNTDOWN_SECONDS) {
+        return false;
+    }
+    countdownStart = -1;
+    return true;
+}
+
+void PauseMenu::draw(TextRenderer& text, const std::vector<std::string>& review, Rectangle bounds, float scale, Color color, double now) {
+    if (isCountingDown()) {
+        std::string count = std::to_string((int) ceil(COUNTDOWN_SECONDS - (now - countdownStart)));
+        Vector2 size = text.measure(count, 80 * scale);
+        text.draw(count, anchorPoint(Anchor::Center, bounds, size), 80 * scale, color);
+        return;
+    }
+    int fontSize = 24 * scale;
+    rowHeight = 40 * scale;
+    float margin = 20 * scale;
+    float reviewHeight = 22 * scale;
+    Vector2 size = {420 * scale, 90 * scale + rowHeight * items.size() + reviewHeight * review.size()};
+    Vector2 corner = anchorPoint(Anchor::Center, bounds, size);
+    panel = {corner.x, corner.y, size.x, size.y};
+    DrawRectangle(0, 0, GetScreenWidth(), GetScreenHeight(), Fade(BLACK, 0.4f));
+    DrawRectangleRec(panel, Fade(DARKGRAY, 0.95f));
+    text.draw(tr("Paused"), {corner.x + margin, corner.y + 15 * scale}, 30 * scale, RAYWHITE);
+
+    rowsTop = corner.y + 60 * scale;
+    for (size_t i = 0; i < items.size(); i++) {
+        float rowY = rowsTop + i * rowHeight;
+        bool isSelected = (int) i == selected;
+        if (isSelected) {
+            DrawRectangleRec({corner.x + margin / 2, rowY, size.x - margin, rowHeight - 4 * scale}, Fade(SKYBLUE, 0.3f));
+        }
+        text.draw(tr(label(items[i])), {corner.x + margin, rowY + (rowHeight - fontSize) / 2 - 2 * scale}, fontSize, isSelected ? YELLOW : LIGHTGRAY);
+    }
+    float reviewTop = rowsTop + rowHeight * items.size() + 15 * scale;
+    for (size_t i = 0; i < review.size(); i++) {
+        text.draw(review[i], {corner.x + margin, reviewTop + i * reviewHeight}, 16 * scale, GRAY);
+    }
+}
@@ -0,0 +1,59 @@
+#pragma once
+#include "raylib.h"
+#include <string>
+#include <vector>
+#include "Text.hpp"
+
+
+enum class PauseItem {
+    Resume,
+    Restart,  // the level from the start (a challenge from its first level)
+    Settings, // over the pause menu, which comes back when they close
+    Stats,
+    Shop,     // skins for the coins caught so far
+    Levels,   // another level instead of this one
+    PlaceBall, // practice: the menu goes and the ball can be moved and aimed (see Sandbox)
+    Quit,     // a run in progress is suspended and resumed next launch
+};
+
+// The Paused screen's menu. Up/down (or a pad's d-pad) selects, enter (or pad
+// A) picks; under the items it lists what the run is played with, its
+// mutators and assists. Resuming counts down first, so the ball doesn't
+// catch the player cold.
+class PauseMenu {
+  public:
+    static constexpr double COUNTDOWN_SECONDS = 3;
+
+    // A suspended run: its replay so far, profiles/<name>.suspend.bkrp.
+    static std::string suspendPath(const std::string& profile);
+
+    // Back on the first item; canRestart and canSuspend leave out or relabel
+    // what this run can't do (a replay can't be restarted or suspended), and
+    // canPlace adds practice's PlaceBall.
+    void open(bool canRestart, bool canSuspend, bool canPlace = false, bool canChooseLevel = false);
+    // Whether an item was picked this frame, and which.
+    bool update(PauseItem& picked);
+    // point is in window pixels.
+    bool tap(Vector2 point, PauseItem& picked);
+
+    void startCountdown(double now);
+    void cancelCountdown();
+    bool isCountingDown() const;
+    // True on the frame the countdown runs out, which also ends it.
+    bool countdownFinished(double now);
+
+    // bounds is the UI layout's safe area, in window pixels.
+    void draw(TextRenderer& text, const std::vector<std::string>& review, Rectangle bounds, float scale, Color color, double now);
+
+  private:
+    std::vector<PauseItem> items;
+    bool suspendable = false;
+    int selected = 0;
+    double countdownStart = -1;
+    // where the rows were last drawn, for hit-testing taps
+    float rowsTop = 0;
+    float rowHeight = 0;
+    Rectangle panel = {0, 0, 0, 0};
+
+    const char* label(PauseItem item) const;
+};
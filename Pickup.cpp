@@ -0,0 +1,22 @@
+#include "raylib.h"
+#include "Pickup.hpp"
+
+
+Pickup Pickup::powerUpAt(PowerUpType type, Vector2 position) {
+    return {PickupKind::PowerUp, type, position};
+}
+
+Pickup Pickup::coinAt(Vector2 position) {
+    return {PickupKind::Coin, PowerUpType::MultiBall, position};
+}
+
+Pickup Pickup::timeAt(Vector2 position, int seconds) {
+    return {PickupKind::Time, PowerUpType::MultiBall, position, seconds};
+}
+
+Rectangle Pickup::getRectangle() const {
+    if (kind == PickupKind::Coin || kind == PickupKind::Time) {
+        return {position.x - COIN_SIZE / 2, position.y - COIN_SIZE / 2, COIN_SIZE, COIN_SIZE};
+    }
+    return {position.x - WIDTH / 2, position.y - HEIGHT / 2, WIDTH, HEIGHT};
+}
@@ -0,0 +1,31 @@
+#pragma once
+#include "raylib.h"
+#include "PowerUp.hpp"
+
+
+enum class PickupKind {
+    PowerUp,
+    Coin,
+    Time, // time attack's extra seconds
+};
+
+// Something falling from a broken brick for the paddle to catch: a power-up
+// capsule, a coin or extra time. They fall and get caught the same way, so they share
+// the one list and the one catch; position is the center.
+struct Pickup {
+    PickupKind kind;
+    PowerUpType powerUp; // which, for a power-up
+    Vector2 position;
+    int seconds = 0; // for extra time
+
+    static constexpr float WIDTH = 36; // a power-up's capsule
+    static constexpr float HEIGHT = 14;
+    static constexpr float COIN_SIZE = 12;
+    static constexpr float SPEED = 2.5f; // pixels per tick
+
+    static Pickup powerUpAt(PowerUpType type, Vector2 position);
+    static Pickup coinAt(Vector2 position);
+    static Pickup timeAt(Vector2 position, int seconds);
+
+    Rectangle getRectangle() const;
+};
@@ -19,15 +19,28 @@ void Player::update() {
     updateVelocity();
 }
 
-void Player::checkInput() {
+void Player::checkInput(const InputFrame& input) {
     float accelerationValue = .1;
     float maxAcceleration = .3;
-    if (IsKeyDown(KEY_LEFT) || IsKeyDown(KEY_A)) {
+    const float analogSpeed = 9;
+    if (input.steering) {
+        steerTowards(input.getSteerTarget(), 30);
+        return;
+    }
+    float stick = input.getStick();
+    if (stick != 0) {
+        // holding either trigger slows the paddle down to a third for lining up shots
+        float fine = 1 - 0.65f * input.getFine();
+        acceleration.x = 0;
+        velocity.x = stick * analogSpeed * fine;
+        return;
+    }
+    if (input.isHeld(Action::MoveLeft)) {
         acceleration.x -= accelerationValue;
         if (acceleration.x < -1 * maxAcceleration) {
             acceleration.x = -1 * maxAcceleration;
         }
-    } else if (IsKeyDown(KEY_RIGHT) || IsKeyDown(KEY_D)) {
+    } else if (input.isHeld(Action::MoveRight)) {
         acceleration.x += accelerationValue;
 
         if (acceleration.x >  maxAcceleration) {
@@ -52,6 +65,34 @@ void Player::checkInput() {
 
 }
     
+void Player::scan(float speed, bool reverse, float minX, float maxX) {
+    float x = getCenter().x;
+    if (reverse) {
+        scanDirection = -scanDirection;
+    }
+    if (x <= minX) {
+        scanDirection = 1;
+    } else if (x >= maxX) {
+        scanDirection = -1;
+    }
+    acceleration.x = 0;
+    velocity.x = scanDirection * speed;
+}
+
+void Player::steerTowards(float x, float maxSpeed) {
+    float gap = x - getCenter().x;
+    acceleration.x = 0;
+    velocity.x = fminf(fmaxf(gap, -maxSpeed), maxSpeed);
+}
+
+void Player::setWidth(float width) {
+    // the rectangle hangs off position by half its width
+    float center = getCenter().x;
+    rectangle.width = width;
+    position.x = center - width;
+    updateRectangle();
+}
+
 void Player::preventLeft() {        
     if (velocity.x < 0) {
         velocity.x *= -1 / 2;
@@ -1,6 +1,7 @@
 #pragma once
 #include "raylib.h"
 #include "Entity.hpp"
+#include "InputRecord.hpp"
 
 
 class Player: public Entity {
@@ -11,11 +12,23 @@ class Player: public Entity {
 
     void init();
     void update();
-    void checkInput();
+    // Mouse/touch steering wins, then a controller stick drives the
+    // paddle's velocity directly; the digital move actions keep the original
+    // accelerate-and-coast feel.
+    void checkInput(const InputFrame& input);
+    // Switch scanning: sweeps between minX and maxX (paddle center), turning
+    // at either end or whenever reverse is set.
+    void scan(float speed, bool reverse, float minX, float maxX);
+    // Heads straight for x (the paddle center), at most maxSpeed per tick.
+    void steerTowards(float x, float maxSpeed);
+    // Resizes around the paddle's center, which stays put.
+    void setWidth(float width);
     void preventLeft();
     void preventRight();
 
   private:
+    int scanDirection = 1;
+
     void updateVelocity();
     void updateAcceleration();
 };
\ No newline at end of file
@@ -0,0 +1,91 @@
+#include "raylib.h"
+#include "Popups.hpp"
+#include "Localization.hpp"
+#include <cmath>
+
+
+PopupSystem::PopupSystem(int capacity) {
+    pool.resize(capacity);
+    activeCount = 0;
+    color = RAYWHITE;
+}
+
+void PopupSystem::setColor(Color textColor) {
+    color = textColor;
+}
+
+void PopupSystem::setReducedMotion(bool enabled) {
+    reducedMotion = enabled;
+}
+
+void PopupSystem::onEvent(const GameEvent& event) {
+    switch (event.type) {
+    case EventType::ScoreAwarded:
+        spawn(TextFormat("+%d", event.value), event.position, color);
+        break;
+    case EventType::ComboIncreased:
+        // offset so it doesn't sit on top of the score popup from the same brick
+        spawn(TextFormat(tr("x%d combo").c_str(), event.value), {event.position.x, event.position.y - 24}, GOLD, 24, 1.0f);
+        break;
+    case EventType::PerfectCatch:
+        spawn(tr("PERFECT catch"), {event.position.x, event.position.y - 30}, SKYBLUE, 28, 1.2f);
+        break;
+    default:
+        break;
+    }
+}
+
+void PopupSystem::spawn(const std::string& text, Vector2 position, Color popupColor, float size, float lifetime) {
+    if (activeCount >= (int) pool.size()) {
+        // recycle the oldest one rather than dropping new feedback
+        for (int i = 1; i < activeCount; i++) {
+            pool[i - 1] = pool[i];
+        }
+        activeCount--;
+    }
+    Popup& popup = pool[activeCount++];
+    popup.text = text;
+    popup.position = position;
+    popup.velocity = {0, reducedMotion ? 0.0f : -60};
+    popup.color = popupColor;
+    popup.size = size;
+    popup.life = lifetime;
+    popup.maxLife = lifetime;
+}
+
+void PopupSystem::update(float deltaTime) {
+    for (int i = 0; i < activeCount;) {
+        Popup& popup = pool[i];
+        popup.life -= deltaTime;
+        if (popup.life <= 0) {
+            pool[i] = pool[activeCount - 1];
+            activeCount--;
+            continue;
+        }
+        popup.position.x += popup.velocity.x * deltaTime;
+        popup.position.y += popup.velocity.y * deltaTime;
+        popup.velocity.y *= 0.92f; // rise quickly, then hang
+        i++;
+    }
+}
+
+void PopupSystem::draw(TextRenderer& text) {
+    for (int i = 0; i < activeCount; i++) {
+        Popup& popup = pool[i];
+        float t = popup.life / popup.maxLife;
+        // pop in slightly oversized, fade out over the last third
+        float size = popup.size * (t > 0.85f && !reducedMotion ? 1 + (t - 0.85f) * 2 : 1);
+        Color color = popup.color;
+        color.a = (unsigned char) (color.a * fminf(1, t * 3));
+        Vector2 extent = text.measure(popup.text, size);
+        text.draw(popup.text, {popup.position.x - extent.x / 2, popup.position.y - extent.y / 2}, size, color);
+    }
+}
+
+void PopupSystem::clear() {
+    activeCount = 0;
+}
+
+int PopupSystem::getActiveCount() {
+    return activeCount;
+}
@@ -0,0 +1,43 @@
+#pragma once
+#include "raylib.h"
+#include <string>
+#include <vector>
+#include "Events.hpp"
+#include "Text.hpp"
+
+
+struct Popup {
+    std::string text;
+    Vector2 position;
+    Vector2 velocity;
+    Color color;
+    float size;
+    float life;
+    float maxLife;
+};
+
+// Floating world-space text ("+1", "x3 combo", "PERFECT catch") spawned from
+// scoring events. Like the particle pool, live popups stay packed at the
+// front; they all draw through the text renderer's glyph atlas so they end up
+// in the same batch.
+class PopupSystem {
+  public:
+    PopupSystem(int capacity = 64);
+
+    void setColor(Color color);
+    // Popups fade in place instead of rising and popping in.
+    void setReducedMotion(bool enabled);
+    void onEvent(const GameEvent& event);
+    void spawn(const std::string& text, Vector2 position, Color color, float size = 20, float lifetime = 0.8f);
+
+    void update(float deltaTime);
+    void draw(TextRenderer& text);
+    void clear();
+    int getActiveCount();
+
+  private:
+    std::vector<Popup> pool;
+    int activeCount;
+    Color color;
+    bool reducedMotion = false;
+};
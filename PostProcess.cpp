@@ -0,0 +1,207 @@
+#include "raylib.h"
+#include "PostProcess.hpp"
+#include <string>
+
+
+static const char* BLOOM_SHADER = R"(
+#version 330
+in vec2 fragTexCoord;
+in vec4 fragColor;
+uniform sampler2D texture0;
+uniform vec4 colDiffuse;
+uniform vec2 resolution;
+out vec4 finalColor;
+
+const float threshold = 0.7;
+const float intensity = 0.8;
+
+void main() {
+    vec4 source = texture(texture0, fragTexCoord);
+    vec2 texel = 2.0 / resolution;
+    vec3 glow = vec3(0.0);
+    float total = 0.0;
+    for (int x = -4; x <= 4; x++) {
+        for (int y = -4; y <= 4; y++) {
+            float weight = max(0.0, 1.0 - length(vec2(x, y)) / 6.0);
+            vec3 tap = texture(texture0, fragTexCoord + vec2(x, y) * texel).rgb;
+            float brightness = max(tap.r, max(tap.g, tap.b));
+            glow += tap * step(threshold, brightness) * weight;
+            total += weight;
+        }
+    }
+    finalColor = vec4(source.rgb + glow / total * intensity, source.a) * colDiffuse;
+}
+)";
+
+static const char* CRT_SHADER = R"(
+#version 330
+in vec2 fragTexCoord;
+in vec4 fragColor;
+uniform sampler2D texture0;
+uniform vec4 colDiffuse;
+uniform vec2 resolution;
+out vec4 finalColor;
+
+void main() {
+    vec2 uv = fragTexCoord * 2.0 - 1.0;
+    uv *= 1.0 + vec2(uv.y * uv.y, uv.x * uv.x) * 0.04;
+    uv = uv * 0.5 + 0.5;
+    if (uv.x < 0.0 || uv.x > 1.0 || uv.y < 0.0 || uv.y > 1.0) {
+        finalColor = vec4(0.0, 0.0, 0.0, 1.0);
+        return;
+    }
+    vec3 color = texture(texture0, uv).rgb;
+    float scanline = 0.85 + 0.15 * sin(uv.y * resolution.y * 3.14159);
+    finalColor = vec4(color * scanline, 1.0) * colDiffuse;
+}
+)";
+
+static const char* VIGNETTE_SHADER = R"(
+#version 330
+in vec2 fragTexCoord;
+in vec4 fragColor;
+uniform sampler2D texture0;
+uniform vec4 colDiffuse;
+out vec4 finalColor;
+
+void main() {
+    vec4 color = texture(texture0, fragTexCoord);
+    float distance = length(fragTexCoord - vec2(0.5));
+    float vignette = smoothstep(0.8, 0.35, distance);
+    finalColor = vec4(color.rgb * mix(0.55, 1.0, vignette), color.a) * colDiffuse;
+}
+)";
+
+void PostProcessor::init(int targetWidth, int targetHeight) {
+    width = targetWidth;
+    height = targetHeight;
+    targets[0] = LoadRenderTexture(width, height);
+    targets[1] = LoadRenderTexture(width, height);
+    ready = IsRenderTextureReady(targets[0]) && IsRenderTextureReady(targets[1]);
+    if (!ready) {
+        TraceLog(LOG_WARNING, "POST: Offscreen targets unavailable, post-processing disabled");
+        return;
+    }
+
+    loadShaders();
+}
+
+void PostProcessor::loadShaders() {
+    struct { PostStage stage; const char* name; const char* source; } stages[] = {
+        {PostStage::Bloom, "bloom", BLOOM_SHADER},
+        {PostStage::Crt, "crt", CRT_SHADER},
+        {PostStage::Vignette, "vignette", VIGNETTE_SHADER},
+    };
+    std::string overrides = std::string(GetApplicationDirectory()) + "assets/shaders/";
+    for (auto& entry : stages) {
+        PostEffect effect;
+        effect.stage = entry.stage;
+        std::string path = overrides + entry.name + ".fs";
+        if (FileExists(path.c_str())) {
+            effect.shader = LoadShader(nullptr, path.c_str());
+        } else {
+            effect.shader = LoadShaderFromMemory(nullptr, entry.source);
+        }
+        effect.loaded = IsShaderReady(effect.shader);
+        if (!effect.loaded) {
+            TraceLog(LOG_WARNING, "POST: Shader for stage %d failed to compile, skipping it", (int) entry.stage);
+        }
+        effects.push_back(effect);
+    }
+}
+
+void PostProcessor::unloadShaders() {
+    for (PostEffect& effect : effects) {
+        if (effect.loaded) {
+            UnloadShader(effect.shader);
+        }
+    }
+    effects.clear();
+}
+
+void PostProcessor::reloadShaders() {
+    if (!ready) {
+        return;
+    }
+    unloadShaders();
+    loadShaders();
+    TraceLog(LOG_INFO, "POST: Reloaded shaders");
+}
+
+void PostProcessor::unload() {
+    unloadShaders();
+    if (ready) {
+        UnloadRenderTexture(targets[0]);
+        UnloadRenderTexture(targets[1]);
+    }
+    ready = false;
+}
+
+bool PostProcessor::stageEnabled(PostStage stage, const Settings& settings) {
+    switch (stage) {
+    case PostStage::Bloom:
+        return settings.bloom && themeBloom;
+    case PostStage::Crt:
+        return settings.crtFilter;
+    case PostStage::Vignette:
+        return settings.vignette;
+    }
+    return false;
+}
+
+RenderTexture2D PostProcessor::sceneTarget(const Settings& settings, RenderTexture2D output) {
+    active = ready && settings.postProcessing && !settings.lowEndGpu;
+    return active ? targets[0] : output;
+}
+
+void PostProcessor::process(const Settings& settings) {
+    if (active) {
+        result = runChain(settings);
+    }
+}
+
+void PostProcessor::drawResult() {
+    if (!active) {
+        return;
+    }
+    ClearBackground(BLACK);
+    DrawTextureRec(targets[result].texture, {0, 0, (float) width, (float) -height}, {0, 0}, WHITE);
+}
+
+void PostProcessor::setCommonUniforms(Shader shader) {
+    float resolution[2] = {(float) width, (float) height};
+    int location = GetShaderLocation(shader, "resolution");
+    if (location >= 0) {
+        SetShaderValue(shader, location, resolution, SHADER_UNIFORM_VEC2);
+    }
+}
+
+// Returns the index of the target holding the processed image.
+int PostProcessor::runChain(const Settings& settings) {
+    // render textures are stored upside down, hence the negative height
+    Rectangle source = {0, 0, (float) width, (float) -height};
+    int current = 0;
+
+    for (PostEffect& effect : effects) {
+        if (!effect.loaded || !stageEnabled(effect.stage, settings)) {
+            continue;
+        }
+        setCommonUniforms(effect.shader);
+        BeginTextureMode(targets[1 - current]);
+        ClearBackground(BLACK);
+        BeginShaderMode(effect.shader);
+        DrawTextureRec(targets[current].texture, source, {0, 0}, WHITE);
+        EndShaderMode();
+        EndTextureMode();
+        current = 1 - current;
+    }
+    return current;
+}
+
+bool PostProcessor::isActive() {
+    return active;
+}
+
+void PostProcessor::setThemeBloom(bool enabled) {
+    themeBloom = enabled;
+}
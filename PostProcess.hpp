@@ -0,0 +1,61 @@
+#pragma once
+#include "raylib.h"
+#include <vector>
+#include "Settings.hpp"
+
+
+enum class PostStage {
+    Bloom,
+    Crt,
+    Vignette,
+};
+
+struct PostEffect {
+    PostStage stage;
+    Shader shader;
+    bool loaded;
+};
+
+// Renders the scene into an offscreen target and runs it through the enabled
+// effects, ping-ponging between two targets. If the targets or a shader fail
+// to load (or the low-end GPU setting is on) stages are skipped and the scene
+// is drawn straight into the output canvas.
+//
+// A file in assets/shaders/ named after a stage (bloom.fs, crt.fs,
+// vignette.fs) replaces that stage's built-in fragment shader.
+class PostProcessor {
+  public:
+    void init(int width, int height);
+    void unload();
+    // Recompiles every stage, picking up edited override files.
+    void reloadShaders();
+
+    // Where the world should be drawn this frame: the offscreen target when
+    // post-processing is on, otherwise output itself.
+    RenderTexture2D sceneTarget(const Settings& settings, RenderTexture2D output);
+    // Runs the effect chain; must happen outside of any texture mode.
+    void process(const Settings& settings);
+    // Draws the processed image into whatever is bound (nothing if inactive,
+    // the scene is already there).
+    void drawResult();
+
+    bool isActive();
+    // Bloom is a theme effect; the setting can only veto it.
+    void setThemeBloom(bool enabled);
+
+  private:
+    RenderTexture2D targets[2];
+    std::vector<PostEffect> effects;
+    int width = 0;
+    int height = 0;
+    bool ready = false;
+    bool active = false;
+    bool themeBloom = false;
+    int result = 0;
+
+    void loadShaders();
+    void unloadShaders();
+    bool stageEnabled(PostStage stage, const Settings& settings);
+    int runChain(const Settings& settings);
+    void setCommonUniforms(Shader shader);
+};
@@ -0,0 +1,185 @@
+#include "raylib.h"
+#include "PowerUp.hpp"
+#include "FramePacer.hpp"
+#include "Simulation.hpp"
+#include <algorithm>
+#include <cmath>
+
+
+const std::vector<PowerUp>& allPowerUps() {
+    static const std::vector<PowerUp> POWER_UPS = {
+        {PowerUpType::MultiBall, "multi_ball", "Multi-ball", {80, 190, 255, 255}, "MB", false, PowerUpRarity::Common, 0, PowerUpStacking::Refresh, splitBall, nullptr},
+        {PowerUpType::Laser, "laser", "Laser", {230, 60, 60, 255}, "L", false, PowerUpRarity::Uncommon, 10, PowerUpStacking::Refresh, giveLaser, nullptr},
+        {PowerUpType::Catch, "catch", "Catch", {90, 210, 110, 255}, "C", false, PowerUpRarity::Common, 15, PowerUpStacking::Refresh, giveCatch, nullptr},
+        {PowerUpType::Grow, "grow", "Grow", {60, 120, 230, 255}, "+", false, PowerUpRarity::Common, 20, PowerUpStacking::Stack, growPaddle, nullptr},
+        {PowerUpType::Shrink, "shrink", "Shrink", {150, 80, 200, 255}, "-", true, PowerUpRarity::Common, 20, PowerUpStacking::Stack, shrinkPaddle, nullptr},
+        {PowerUpType::SlowMotion, "slow_motion", "Slow motion", {240, 200, 70, 255}, "SL", false, PowerUpRarity::Uncommon, 12, PowerUpStacking::Refresh, giveSlowMotion, nullptr},
+        {PowerUpType::Fireball, "fireball", "Fireball", {255, 130, 30, 255}, "F", false, PowerUpRarity::Rare, 10, PowerUpStacking::Refresh, giveFireball, nullptr},
+        // one shield at a time: a second would do nothing while the first is up
+        {PowerUpType::Shield, "shield", "Shield", {120, 230, 255, 255}, "SH", false, PowerUpRarity::Uncommon, 0, PowerUpStacking::Refresh, raiseShield,
+            [](const GameState& state) { return !state.barrier.up; }},
+        {PowerUpType::Magnet, "magnet", "Magnet", {200, 200, 210, 255}, "MG", false, PowerUpRarity::Uncommon, 12, PowerUpStacking::Refresh, giveMagnet, nullptr},
+        {PowerUpType::ReverseControls, "reverse_controls", "Reversed controls", {220, 40, 140, 255}, "RV", true, PowerUpRarity::Uncommon, 8, PowerUpStacking::Refresh, reverseControls, nullptr},
+        {PowerUpType::Flicker, "flicker", "Flicker", {110, 110, 130, 255}, "FL", true, PowerUpRarity::Uncommon, 10, PowerUpStacking::Refresh, giveFlicker, nullptr},
+        {PowerUpType::SpeedUp, "speed_up", "Speed-up", {240, 70, 40, 255}, "SP", true, PowerUpRarity::Uncommon, 10, PowerUpStacking::Refresh, giveSpeedUp, nullptr},
+    };
+    return POWER_UPS;
+}
+
+const PowerUp& powerUpInfo(PowerUpType type) {
+    for (const PowerUp& powerUp : allPowerUps()) {
+        if (powerUp.type == type) {
+            return powerUp;
+        }
+    }
+    return allPowerUps().front();
+}
+
+const PowerUp* findPowerUp(const std::string& id) {
+    for (const PowerUp& powerUp : allPowerUps()) {
+        if (id == powerUp.id) {
+            return &powerUp;
+        }
+    }
+    return nullptr;
+}
+
+const char* powerUpRarityName(PowerUpRarity rarity) {
+    switch (rarity) {
+    case PowerUpRarity::Common: return "common";
+    case PowerUpRarity::Uncommon: return "uncommon";
+    case PowerUpRarity::Rare: return "rare";
+    }
+    return "common";
+}
+
+bool parsePowerUpRarity(const std::string& name, PowerUpRarity& rarity) {
+    for (PowerUpRarity candidate : {PowerUpRarity::Common, PowerUpRarity::Uncommon, PowerUpRarity::Rare}) {
+        if (name == powerUpRarityName(candidate)) {
+            rarity = candidate;
+            return true;
+        }
+    }
+    return false;
+}
+
+float powerUpRarityWeight(PowerUpRarity rarity) {
+    switch (rarity) {
+    case PowerUpRarity::Common: return 1;
+    case PowerUpRarity::Uncommon: return 0.5f;
+    case PowerUpRarity::Rare: return 0.2f;
+    }
+    return 1;
+}
+
+// turned far enough to split visibly, not so far the new balls go flat
+static const float SPLIT_DEGREES = 25;
+
+static Vector2 rotated(Vector2 vector, float degrees) {
+    float radians = degrees * DEG2RAD;
+    float c = cosf(radians);
+    float s = sinf(radians);
+    return {vector.x * c - vector.y * s, vector.x * s + vector.y * c};
+}
+
+void splitBall(GameState& state, const SimulationRules&) {
+    // a ball on the paddle has no direction to split along
+    size_t first = 0;
+    while (first < state.balls.size() && !state.balls[first].isFree()) {
+        first++;
+    }
+    if (first >= state.balls.size()) {
+        return;
+    }
+    Ball source = state.balls[first];
+    for (float degrees : {-SPLIT_DEGREES, SPLIT_DEGREES}) {
+        if ((int) state.balls.size() >= MAX_BALLS) {
+            break;
+        }
+        Ball ball = source;
+        ball.id = state.nextBallId++;
+        ball.velocity = rotated(source.velocity, degrees);
+        state.balls.push_back(ball);
+    }
+}
+
+void giveLaser(GameState& state, const SimulationRules&) {
+    state.effects.start(powerUpInfo(PowerUpType::Laser));
+}
+
+void giveCatch(GameState& state, const SimulationRules&) {
+    state.effects.start(powerUpInfo(PowerUpType::Catch));
+}
+
+void growPaddle(GameState& state, const SimulationRules&) {
+    state.effects.start(powerUpInfo(PowerUpType::Grow));
+}
+
+void shrinkPaddle(GameState& state, const SimulationRules&) {
+    state.effects.start(powerUpInfo(PowerUpType::Shrink));
+}
+
+void giveSlowMotion(GameState& state, const SimulationRules&) {
+    state.effects.start(powerUpInfo(PowerUpType::SlowMotion));
+}
+
+void giveSpeedUp(GameState& state, const SimulationRules&) {
+    state.effects.start(powerUpInfo(PowerUpType::SpeedUp));
+}
+
+void giveFireball(GameState& state, const SimulationRules&) {
+    state.effects.start(powerUpInfo(PowerUpType::Fireball));
+    for (Ball& ball : state.balls) {
+        ball.piercing = true;
+    }
+}
+
+void raiseShield(GameState& state, const SimulationRules&) {
+    state.barrier.up = true;
+}
+
+void giveMagnet(GameState& state, const SimulationRules&) {
+    state.effects.start(powerUpInfo(PowerUpType::Magnet));
+}
+
+void reverseControls(GameState& state, const SimulationRules&) {
+    state.effects.start(powerUpInfo(PowerUpType::ReverseControls));
+}
+
+void giveFlicker(GameState& state, const SimulationRules&) {
+    state.effects.start(powerUpInfo(PowerUpType::Flicker));
+}
+
+float ballVisibility(const GameState& state, bool reducedMotion) {
+    int left = state.effects.ticksLeft(PowerUpType::Flicker);
+    if (left <= 0) {
+        return 1;
+    }
+    // counted from the catch, so every flicker starts out visible
+    int full = powerUpInfo(PowerUpType::Flicker).seconds * FramePacer::TICK_RATE;
+    int phase = std::max(0, full - left) % FLICKER_PERIOD_TICKS;
+    if (!reducedMotion) {
+        return phase < FLICKER_PERIOD_TICKS - FLICKER_HIDDEN_TICKS ? 1 : 0;
+    }
+    return 0.5f + 0.5f * cosf(2 * PI * phase / FLICKER_PERIOD_TICKS);
+}
+
+float paddleTargetWidth(const GameState& state) {
+    int steps = state.effects.stacks(PowerUpType::Grow) - state.effects.stacks(PowerUpType::Shrink);
+    // two steps down still leaves a paddle
+    return state.paddleBaseWidth * std::max(1 + steps * PADDLE_STEP, 0.25f);
+}
+
+// never quite straight up, which would bounce between the paddle and one
+// column of bricks forever
+static const float MIN_LAUNCH_DEGREES = 10;
+static const float MAX_LAUNCH_DEGREES = 60;
+
+Vector2 catchLaunchVelocity(float offset, float halfWidth, float ballSpeed) {
+    float along = halfWidth > 0 ? fmaxf(-1, fminf(offset / halfWidth, 1)) : 0;
+    float degrees = fmaxf(fabsf(along) * MAX_LAUNCH_DEGREES, MIN_LAUNCH_DEGREES);
+    float radians = degrees * DEG2RAD;
+    // as fast as a served ball
+    float speed = 2 * sqrtf(2) * ballSpeed;
+    return {(along < 0 ? -1 : 1) * speed * sinf(radians), -speed * cosf(radians)};
+}
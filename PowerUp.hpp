@@ -0,0 +1,116 @@
+#pragma once
+#include "raylib.h"
+#include <string>
+#include <vector>
+
+struct GameState;
+struct SimulationRules;
+
+
+enum class PowerUpType {
+    MultiBall,
+    Laser,
+    Catch,
+    Grow,
+    Shrink,
+    SlowMotion,
+    Fireball,
+    Shield,
+    Magnet,
+    // harmful ones, to dodge rather than catch
+    ReverseControls,
+    Flicker,
+    SpeedUp,
+};
+
+// How often a power-up drops against the others, before a drop table's own
+// weights (see DropTable).
+enum class PowerUpRarity {
+    Common,
+    Uncommon,
+    Rare,
+};
+
+const char* powerUpRarityName(PowerUpRarity rarity);
+bool parsePowerUpRarity(const std::string& name, PowerUpRarity& rarity);
+float powerUpRarityWeight(PowerUpRarity rarity);
+
+// What catching a timed power-up does to one already running (see
+// EffectManager).
+enum class PowerUpStacking {
+    Refresh, // starts over
+    Extend,  // adds its time to what's left, up to twice its own
+    Stack,   // Extend, and one more stack of it, up to EffectManager::MAX_STACKS
+};
+
+// A pickup a broken brick can drop. Catching it with the paddle applies it
+// to the state right there in the tick, so power-ups replay like everything
+// else the simulation does. Harmful ones (debuffs) drop and stack the same
+// way; they look different falling and on the HUD so there's a reason to
+// dodge them, and where one works against a helpful power-up the two cancel
+// out rather than fight (see the functions below).
+struct PowerUp {
+    PowerUpType type;
+    const char* id;   // stats and logs
+    const char* name;
+    Color color;      // of the falling capsule and its HUD icon
+    const char* badge; // the letters on its HUD icon
+    bool harmful;     // a debuff
+    PowerUpRarity rarity;
+    int seconds;      // how long it lasts; 0 for one that happens at once
+    PowerUpStacking stacking;
+    void (*apply)(GameState& state, const SimulationRules& rules);
+    // Whether it can drop right now; nullptr for always.
+    bool (*canDrop)(const GameState& state);
+};
+
+const std::vector<PowerUp>& allPowerUps();
+const PowerUp& powerUpInfo(PowerUpType type);
+const PowerUp* findPowerUp(const std::string& id);
+
+// Multi-ball never splits past this many balls in play.
+static constexpr int MAX_BALLS = 12;
+static constexpr int LASER_COOLDOWN_TICKS = 15;
+// how far in from either end of the paddle the bolts leave
+static constexpr float LASER_INSET = 8;
+// Grow and shrink move the paddle a step, a share of its width, per stack;
+// when the last runs out the paddle goes back to its own width.
+static constexpr float PADDLE_STEP = 0.3f;
+// how long the paddle takes to go one step wider or narrower
+static constexpr float PADDLE_RESIZE_SECONDS = 0.75f;
+// The magnet pulls falling balls sideways towards the paddle, this much per
+// pixel between them, per tick.
+static constexpr float MAGNET_PULL = 0.0003f;
+// Slow motion slows the ball (not the paddle) to this, easing in and out;
+// speed-up hurries it along the same way.
+static constexpr float SLOW_MOTION_SCALE = 0.5f;
+static constexpr float SPEED_UP_SCALE = 1.4f;
+static constexpr float TIME_SCALE_EASE_SECONDS = 0.4f;
+// Flicker hides the balls in play for the last part of every period.
+static constexpr int FLICKER_PERIOD_TICKS = 48;
+static constexpr int FLICKER_HIDDEN_TICKS = 20;
+
+// Splits the first free ball into three, the new two turned either way.
+void splitBall(GameState& state, const SimulationRules& rules);
+void giveLaser(GameState& state, const SimulationRules& rules);
+void giveCatch(GameState& state, const SimulationRules& rules);
+void growPaddle(GameState& state, const SimulationRules& rules);
+void shrinkPaddle(GameState& state, const SimulationRules& rules);
+void giveSlowMotion(GameState& state, const SimulationRules& rules);
+void giveSpeedUp(GameState& state, const SimulationRules& rules);
+// Every ball in play turns piercing; the ones multi-ball splits off after
+// are too, a new serve isn't.
+void giveFireball(GameState& state, const SimulationRules& rules);
+void raiseShield(GameState& state, const SimulationRules& rules);
+void giveMagnet(GameState& state, const SimulationRules& rules);
+void reverseControls(GameState& state, const SimulationRules& rules);
+void giveFlicker(GameState& state, const SimulationRules& rules);
+// How much of the balls in play to show under flicker, 0 to 1. With
+// reducedMotion they fade out and back instead of blinking.
+float ballVisibility(const GameState& state, bool reducedMotion);
+// The width the paddle is easing towards.
+float paddleTargetWidth(const GameState& state);
+// A caught ball picks its way back where it sits: straight up-ish from the
+// middle of the paddle, flatter towards either end, like Arkanoid's catch.
+// offset is from the paddle's center, halfWidth is half the paddle's width.
+Vector2 catchLaunchVelocity(float offset, float halfWidth, float ballSpeed);
@@ -0,0 +1,77 @@
+#include "raylib.h"
+#include "Profile.hpp"
+#include "Sanitize.hpp"
+#include <filesystem>
+
+
+static const char* const INPUT_MODE_OPTIONS[] = {"Standard", "Switch scanning"};
+
+std::vector<SettingField> profileFields(Profile& profile) {
+    std::vector<SettingField> fields = {
+        {"theme", "Theme", SettingType::String, &profile.theme},
+        {"layout", "Key layout", SettingType::String, &profile.layout},
+        {"input_mode", "Input mode", SettingType::Int, &profile.inputMode, 0, 1, 1, INPUT_MODE_OPTIONS},
+        {"auto_launch", "Auto-launch", SettingType::Bool, &profile.autoLaunch},
+        {"scan_speed", "Scanning speed", SettingType::Float, &profile.scanSpeed, 0.1f, 1, 0.1f},
+        {"skin", "Skin", SettingType::String, &profile.skin},
+        {"paddle", "Paddle", SettingType::String, &profile.paddle},
+        {"trail", "Ball trail", SettingType::String, &profile.trail},
+        {"hardcore", "Hardcore", SettingType::Bool, &profile.hardcore},
+        {"tutorial_done", "Tutorial finished", SettingType::Bool, &profile.tutorialDone},
+    };
+    fields[1].choices = layoutNames();
+    return fields;
+}
+
+std::string profilePath(const std::string& name) {
+    return std::string(GetApplicationDirectory()) + "profiles/" + sanitizeFilename(name) + ".cfg";
+}
+
+// Bindings go through the same key = value file as hidden string fields,
+// "bind_move_left = key:LEFT, key:A, pad:DPAD_LEFT"; text must outlive the fields.
+static std::vector<SettingField> storedFields(Profile& profile, std::vector<std::string>& text) {
+    std::vector<SettingField> fields = profileFields(profile);
+    text.resize(ACTION_COUNT);
+    for (int i = 0; i < ACTION_COUNT; i++) {
+        text[i] = profile.bindings.format((Action) i);
+        fields.push_back({actionKey((Action) i), nullptr, SettingType::String, &text[i]});
+    }
+    fields.push_back({"unlocked", nullptr, SettingType::String, &profile.unlocked});
+    fields.push_back({"cleared_levels", nullptr, SettingType::String, &profile.clearedLevels});
+    fields.push_back({"tutorial_level", nullptr, SettingType::Int, &profile.tutorialLevel, 0, 100});
+    fields.push_back({"weekly_last", nullptr, SettingType::String, &profile.weekly.lastWeek});
+    fields.push_back({"weekly_streak", nullptr, SettingType::Int, &profile.weekly.current, 0, 100000});
+    fields.push_back({"weekly_best", nullptr, SettingType::Int, &profile.weekly.best, 0, 100000});
+    fields.push_back({"daily_scores", nullptr, SettingType::String, &profile.dailyScores});
+    fields.push_back({"coins", nullptr, SettingType::Int, &profile.coins, 0, 100000000});
+    fields.push_back({"survival_best", nullptr, SettingType::Int, &profile.survivalBest, 0, 100000000});
+    fields.push_back({"hardcore_levels", nullptr, SettingType::Int, &profile.hardcoreLevels, 0, 100000000});
+    fields.push_back({"hardcore_best", nullptr, SettingType::Int, &profile.hardcoreBest, 0, 100000000});
+    fields.push_back({"achievements", nullptr, SettingType::String, &profile.achievements});
+    fields.push_back({"xp", nullptr, SettingType::Int, &profile.xp, 0, 2000000000});
+    fields.push_back({"leaderboard_name", nullptr, SettingType::String, &profile.leaderboardName});
+    fields.push_back({"pack_progress", nullptr, SettingType::String, &profile.packProgress});
+    return fields;
+}
+
+bool loadProfile(const std::string& name, Profile& profile) {
+    profile = Profile();
+    profile.name = name;
+    std::vector<std::string> text;
+    std::vector<SettingField> fields = storedFields(profile, text);
+    if (!loadFields(profilePath(name), fields)) {
+        return false;
+    }
+    for (int i = 0; i < ACTION_COUNT; i++) {
+        profile.bindings.parse((Action) i, text[i]);
+    }
+    return true;
+}
+
+bool saveProfile(Profile& profile) {
+    std::error_code error;
+    std::filesystem::create_directories(std::string(GetApplicationDirectory()) + "profiles", error);
+    std::vector<std::string> text;
+    std::vector<SettingField> fields = storedFields(profile, text);
+    return saveFields(profilePath(profile.name), fields);
+}
@@ -0,0 +1,49 @@
+#pragma once
+#include <string>
+#include <vector>
+#include "Bindings.hpp"
+#include "Challenge.hpp"
+#include "Settings.hpp"
+
+
+enum class InputMode {
+    Standard,
+    // the paddle sweeps on its own and one switch (Action::Launch) reverses it
+    SwitchScanning,
+};
+
+// Per-player preferences, stored as profiles/<name>.cfg next to the game.
+struct Profile {
+    std::string name = "default";
+    std::string theme = "default";
+    InputBindings bindings;
+    std::string layout = "standard"; // the layoutNames() preset last applied
+    int inputMode = 0; // InputMode
+    bool autoLaunch = false; // serve by itself a second after the ball is reset
+    float scanSpeed = 0.5f;
+    // cosmetics, see CosmeticLoadout; only unlocked ones take effect
+    std::string skin = "classic";
+    std::string paddle = "flat";
+    std::string trail = "standard";
+    std::string unlocked;      // comma separated Cosmetic ids earned so far
+    std::string clearedLevels; // comma separated level hashes, the campaign so far
+    bool tutorialDone = false;
+    int tutorialLevel = 0;     // the next one to play, while not done
+    ChallengeStreak weekly;
+    std::string dailyScores; // the best each day, see recordDailyScore()
+    int coins = 0; // caught over every run so far
+    int survivalBest = 0; // seconds, the longest survival run
+    bool hardcore = false; // runs started from now on get the hardcore mutator
+    int hardcoreLevels = 0; // cleared by hardcore runs since the last one lost
+    int hardcoreBest = 0;
+    std::string achievements; // progress on each, see AchievementTracker::format()
+    int xp = 0; // over every run, see Progression.hpp
+    std::string leaderboardName; // last typed in for a top run, empty for the profile's name
+    std::string packProgress; // each level pack's levels cleared in order, see recordPackClear()
+};
+
+std::vector<SettingField> profileFields(Profile& profile);
+
+std::string profilePath(const std::string& name);
+bool loadProfile(const std::string& name, Profile& profile);
+bool saveProfile(Profile& profile);
@@ -0,0 +1,116 @@
+#include "raylib.h"
+#include "Profiler.hpp"
+#include <algorithm>
+#include <cmath>
+#include <vector>
+#include "FramePacer.hpp"
+
+
+static const char* SECTION_NAMES[] = {"Update", "Physics", "Render", "Audio", "Net"};
+static const Color SECTION_COLORS[] = {SKYBLUE, ORANGE, LIME, VIOLET, GOLD};
+
+void Profiler::beginFrame() {
+    Clock::time_point now = Clock::now();
+    if (started) {
+        int slot = frames < HISTORY ? (head + frames) % HISTORY : head;
+        for (int i = 0; i < PROFILE_SECTION_COUNT; i++) {
+            sections[slot][i] = (float) current[i];
+            current[i] = 0;
+        }
+        totals[slot] = std::chrono::duration<float>(now - frameStart).count();
+        if (frames < HISTORY) {
+            frames++;
+        } else {
+            head = (head + 1) % HISTORY;
+        }
+    }
+    frameStart = now;
+    started = true;
+}
+
+void Profiler::add(ProfileSection section, double seconds) {
+    current[(int) section] += seconds;
+}
+
+void Profiler::draw(Rectangle bounds, float scale) {
+    if (!visible || frames == 0) {
+        return;
+    }
+    int fontSize = (int) (10 * scale);
+    int line = fontSize + (int) (4 * scale);
+    float padding = 6 * scale;
+    DrawRectangleRec(bounds, Fade(BLACK, 0.7f));
+
+    double averages[PROFILE_SECTION_COUNT] = {};
+    double frameAverage = 0;
+    std::vector<float> sorted;
+    for (int i = 0; i < frames; i++) {
+        int slot = (head + i) % HISTORY;
+        for (int section = 0; section < PROFILE_SECTION_COUNT; section++) {
+            averages[section] += sections[slot][section] / frames;
+        }
+        frameAverage += totals[slot] / frames;
+        sorted.push_back(totals[slot]);
+    }
+    // 1% low: the slowest 1% of frames, at least one
+    std::sort(sorted.begin(), sorted.end(), std::greater<float>());
+    int worst = std::max(1, frames / 100);
+    double low = 0;
+    for (int i = 0; i < worst; i++) {
+        low += sorted[i] / worst;
+    }
+
+    float x = bounds.x + padding;
+    float y = bounds.y + padding;
+    DrawText(TextFormat("Frame %.2f ms (%.0f fps)   1%% low %.2f ms (%.0f fps)", frameAverage * 1000, 1 / frameAverage, low * 1000, 1 / low),
+        x, y, fontSize, RAYWHITE);
+    y += line;
+    double covered = 0;
+    for (int section = 0; section < PROFILE_SECTION_COUNT; section++) {
+        float column = x + (section % 3) * 110 * scale;
+        float row = y + (section / 3) * line;
+        DrawRectangle(column, row, fontSize, fontSize, SECTION_COLORS[section]);
+        DrawText(TextFormat("%s %.2f ms", SECTION_NAMES[section], averages[section] * 1000), column + fontSize + 4 * scale, row, fontSize, RAYWHITE);
+        covered += averages[section];
+    }
+    float column = x + 2 * 110 * scale;
+    float row = y + line;
+    DrawRectangle(column, row, fontSize, fontSize, GRAY);
+    DrawText(TextFormat("Other %.2f ms", fmax(frameAverage - covered, 0) * 1000), column + fontSize + 4 * scale, row, fontSize, RAYWHITE);
+    y += 2 * line;
+
+    // stacked bars, newest on the right; full height is two ticks
+    Rectangle graph = {x, y, bounds.width - 2 * padding, bounds.y + bounds.height - padding - y};
+    float pixelsPerSecond = graph.height / (FramePacer::TICK_SECONDS * 2);
+    float barWidth = graph.width / HISTORY;
+    for (int i = 0; i < frames; i++) {
+        int slot = (head + i) % HISTORY;
+        float barX = graph.x + (HISTORY - frames + i) * barWidth;
+        float bottom = graph.y + graph.height;
+        float used = 0;
+        for (int section = 0; section < PROFILE_SECTION_COUNT; section++) {
+            float height = sections[slot][section] * pixelsPerSecond;
+            DrawRectangleV({barX, bottom - height}, {barWidth, height}, SECTION_COLORS[section]);
+            bottom -= height;
+            used += sections[slot][section];
+        }
+        float other = fmaxf(totals[slot] - used, 0) * pixelsPerSecond;
+        DrawRectangleV({barX, bottom - other}, {barWidth, other}, GRAY);
+    }
+    int tickLine = (int) (graph.y + graph.height - FramePacer::TICK_SECONDS * pixelsPerSecond);
+    DrawLine(graph.x, tickLine, graph.x + graph.width, tickLine, Fade(RAYWHITE, 0.5f));
+}
+
+ScopedTimer::ScopedTimer(Profiler& owner, ProfileSection timed) : profiler(owner), section(timed), start(std::chrono::steady_clock::now()) {
+}
+
+ScopedTimer::~ScopedTimer() {
+    stop();
+}
+
+void ScopedTimer::stop() {
+    if (running) {
+        profiler.add(section, std::chrono::duration<double>(std::chrono::steady_clock::now() - start).count());
+        running = false;
+    }
+}
@@ -0,0 +1,56 @@
+#pragma once
+#include "raylib.h"
+#include <chrono>
+
+
+enum class ProfileSection {
+    Update,  // input, menus, everything before the ticks
+    Physics, // the simulation ticks
+    Render,  // the render graph, present (and so any vsync wait) included
+    Audio,
+    Net,     // nothing online yet; kept so the layout doesn't change when it is
+};
+
+const int PROFILE_SECTION_COUNT = 5;
+
+// Where each frame's time goes, from ScopedTimers placed through the main
+// loop. Time that no timer covered shows up as "other". The overlay (F6) is
+// a stacked, scrolling frame graph plus averages and the 1% low: the mean of
+// the slowest 1% of frames over the history, which is what stutter looks like.
+class Profiler {
+  public:
+    static const int HISTORY = 240;
+
+    bool visible = false;
+
+    // Call once at the very start of every frame; closes the previous one.
+    void beginFrame();
+    void add(ProfileSection section, double seconds);
+    void draw(Rectangle bounds, float scale);
+
+  private:
+    using Clock = std::chrono::steady_clock;
+
+    Clock::time_point frameStart;
+    bool started = false;
+    double current[PROFILE_SECTION_COUNT] = {};
+    float sections[HISTORY][PROFILE_SECTION_COUNT] = {};
+    float totals[HISTORY] = {};
+    int head = 0;   // the oldest frame
+    int frames = 0; // how much of the history is filled
+};
+
+// Adds the time from construction to destruction to a section, or to stop()
+// where a scope would be awkward.
+class ScopedTimer {
+  public:
+    ScopedTimer(Profiler& profiler, ProfileSection section);
+    ~ScopedTimer();
+    void stop();
+
+  private:
+    Profiler& profiler;
+    ProfileSection section;
+    bool running = true;
+    std::chrono::steady_clock::time_point start;
+};
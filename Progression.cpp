@@ -0,0 +1,21 @@
+#include "Progression.hpp"
+#include <algorithm>
+
+
+int levelClearXp(int score, int livesLost) {
+    return XpRules::LEVEL_CLEARED + (livesLost <= 0 ? XpRules::FLAWLESS : 0) + std::max(score, 0) / XpRules::SCORE_PER_XP;
+}
+
+int xpForPlayerLevel(int level) {
+    // the sum of FIRST_LEVEL, FIRST_LEVEL + STEP, ... for the levels before it
+    int steps = std::max(level - 1, 0);
+    return steps * XpRules::FIRST_LEVEL + XpRules::STEP * steps * (steps - 1) / 2;
+}
+
+int playerLevel(int xp) {
+    int level = 1;
+    while (xpForPlayerLevel(level + 1) <= xp) {
+        level++;
+    }
+    return level;
+}
@@ -0,0 +1,19 @@
+#pragma once
+
+
+// Experience: every level cleared and every achievement earned adds XP to
+// the profile, and the player's level follows from the total. Each level
+// takes STEP more XP to get through than the one before it.
+struct XpRules {
+    static constexpr int LEVEL_CLEARED = 100;
+    static constexpr int FLAWLESS = 50;     // on top, for a clear that cost no life
+    static constexpr int SCORE_PER_XP = 10; // and one for every this many points
+    static constexpr int FIRST_LEVEL = 200; // from level 1 to 2
+    static constexpr int STEP = 100;
+};
+
+int levelClearXp(int score, int livesLost);
+// 1 at no XP at all.
+int playerLevel(int xp);
+// The total it takes to reach level.
+int xpForPlayerLevel(int level);
@@ -0,0 +1,7 @@
+#include "raylib.h"
+#include "Projectile.hpp"
+
+
+Projectile::Projectile(Vector2 position) : Entity(position, WIDTH, HEIGHT) {
+    velocity = {0, -SPEED};
+}
@@ -0,0 +1,17 @@
+#pragma once
+#include "raylib.h"
+#include "Entity.hpp"
+
+
+// A laser bolt fired from the paddle (see PowerUp.hpp). Flies straight up and
+// breaks the first brick it touches; it never bounces.
+class Projectile : public Entity {
+  public:
+    static constexpr int WIDTH = 4;
+    static constexpr int HEIGHT = 14;
+    static constexpr float SPEED = 9; // pixels per tick
+
+    int owner = 0; // whose paddle fired it, like Ball::owner
+
+    Projectile(Vector2 position);
+};
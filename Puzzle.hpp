@@ -0,0 +1,10 @@
+#pragma once
+
+
+// Puzzle levels (a <level launches="3">): the level has to be cleared with
+// that many serves. A lost ball costs a serve rather than a life, losing it
+// with none left ends the run, and every serve still to come when the last
+// brick breaks scores points.
+struct PuzzleRules {
+    static constexpr int POINTS_PER_LAUNCH_LEFT = 500;
+};
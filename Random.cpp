@@ -0,0 +1,34 @@
+#include "Random.hpp"
+
+
+Random::Random(uint64_t value) {
+    seed(value);
+}
+
+void Random::seed(uint64_t value) {
+    state = value;
+}
+
+uint64_t Random::next() {
+    uint64_t z = (state += 0x9e3779b97f4a7c15ull);
+    z = (z ^ (z >> 30)) * 0xbf58476d1ce4e5b9ull;
+    z = (z ^ (z >> 27)) * 0x94d049bb133111ebull;
+    return z ^ (z >> 31);
+}
+
+int Random::range(int min, int max) {
+    if (max <= min) {
+        return min;
+    }
+    uint64_t span = (uint64_t) ((int64_t) max - min) + 1;
+    return (int) (min + (int64_t) (next() % span));
+}
+
+float Random::unit() {
+    // top 24 bits, exactly representable in a float
+    return (next() >> 40) / 16777216.0f;
+}
+
+uint64_t Random::getState() const {
+    return state;
+}
@@ -0,0 +1,23 @@
+#pragma once
+#include <cstdint>
+
+
+// Seeded generator for anything that changes the simulation (drops, spawn
+// patterns, ...). Cosmetic randomness (particles, pitch variation) keeps using
+// GetRandomValue so it never shifts a replay. SplitMix64: tiny state, so it
+// snapshots with the rest of the game state for free.
+class Random {
+  public:
+    explicit Random(uint64_t seed = 0);
+
+    void seed(uint64_t value);
+    uint64_t next();
+    // Inclusive on both ends, like GetRandomValue.
+    int range(int min, int max);
+    // [0, 1)
+    float unit();
+    uint64_t getState() const;
+
+  private:
+    uint64_t state;
+};
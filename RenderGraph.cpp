@@ -0,0 +1,121 @@
+#include "raylib.h"
+#include "RenderGraph.hpp"
+#include <set>
+
+
+void RenderGraph::addTarget(const RenderTarget& target) {
+    targets[target.name] = target;
+}
+
+void RenderGraph::addPass(const RenderPass& pass) {
+    passes.push_back(pass);
+    dirty = true;
+}
+
+int RenderGraph::find(const std::string& name) {
+    for (int i = 0; i < (int) passes.size(); i++) {
+        if (passes[i].name == name) {
+            return i;
+        }
+    }
+    return -1;
+}
+
+void RenderGraph::insertBefore(const std::string& existing, const RenderPass& pass) {
+    int index = find(existing);
+    if (index < 0) {
+        TraceLog(LOG_WARNING, "RENDER: No pass named %s, appending %s", existing.c_str(), pass.name.c_str());
+        addPass(pass);
+        return;
+    }
+    passes.insert(passes.begin() + index, pass);
+    dirty = true;
+}
+
+void RenderGraph::insertAfter(const std::string& existing, const RenderPass& pass) {
+    int index = find(existing);
+    if (index < 0) {
+        TraceLog(LOG_WARNING, "RENDER: No pass named %s, appending %s", existing.c_str(), pass.name.c_str());
+        addPass(pass);
+        return;
+    }
+    passes.insert(passes.begin() + index + 1, pass);
+    dirty = true;
+}
+
+void RenderGraph::setEnabled(const std::string& name, bool enabled) {
+    int index = find(name);
+    if (index >= 0 && passes[index].enabled != enabled) {
+        passes[index].enabled = enabled;
+        dirty = true;
+    }
+}
+
+// Returns false if any enabled pass had to be dropped.
+bool RenderGraph::compile() {
+    schedule.clear();
+    std::set<std::string> available;
+    bool complete = true;
+
+    for (int i = 0; i < (int) passes.size(); i++) {
+        RenderPass& pass = passes[i];
+        if (!pass.enabled) {
+            continue;
+        }
+        if (!pass.target.empty() && !targets.count(pass.target)) {
+            TraceLog(LOG_WARNING, "RENDER: Pass %s draws into unknown target %s", pass.name.c_str(), pass.target.c_str());
+            complete = false;
+            continue;
+        }
+        bool ready = true;
+        for (const std::string& input : pass.inputs) {
+            if (!available.count(input)) {
+                TraceLog(LOG_WARNING, "RENDER: Pass %s skipped, nothing produces %s before it", pass.name.c_str(), input.c_str());
+                ready = false;
+            }
+        }
+        if (!ready) {
+            complete = false;
+            continue;
+        }
+        available.insert(pass.outputs.begin(), pass.outputs.end());
+        schedule.push_back(i);
+    }
+    dirty = false;
+    return complete;
+}
+
+void RenderGraph::execute() {
+    if (dirty) {
+        compile();
+    }
+    const RenderTarget* bound = nullptr;
+    for (int index : schedule) {
+        RenderPass& pass = passes[index];
+        const RenderTarget* target = pass.target.empty() ? nullptr : &targets[pass.target];
+        if (target != bound) {
+            if (bound && bound->unbind) {
+                bound->unbind();
+            }
+            if (target && target->bind) {
+                target->bind();
+            }
+            bound = target;
+        }
+        pass.execute();
+    }
+    if (bound && bound->unbind) {
+        bound->unbind();
+    }
+}
+
+std::vector<std::string> RenderGraph::passNames() {
+    if (dirty) {
+        compile();
+    }
+    std::vector<std::string> names;
+    for (int index : schedule) {
+        names.push_back(passes[index].name);
+    }
+    return names;
+}
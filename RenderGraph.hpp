@@ -0,0 +1,53 @@
+#pragma once
+#include "raylib.h"
+#include <functional>
+#include <map>
+#include <string>
+#include <vector>
+
+
+// Something passes draw into. bind/unbind wrap whatever has to be active
+// while drawing to it (a texture mode, the window's BeginDrawing...).
+struct RenderTarget {
+    std::string name;
+    std::function<void()> bind;
+    std::function<void()> unbind;
+};
+
+// One step of the frame. A pass draws into its target (or, with no target,
+// runs with nothing bound, e.g. to render its own offscreen textures), reads
+// the resources listed in inputs and produces those in outputs.
+struct RenderPass {
+    std::string name;
+    std::string target;
+    std::vector<std::string> inputs;
+    std::vector<std::string> outputs;
+    std::function<void()> execute;
+    bool enabled = true;
+};
+
+// Runs the frame's passes in declaration order, binding each pass's target
+// and only switching when the target changes. compile() checks that every
+// input is produced by an earlier enabled pass; passes missing an input are
+// skipped with a warning rather than drawing garbage. New passes (editor
+// gizmos, picture-in-picture...) slot in with insertBefore()/insertAfter().
+class RenderGraph {
+  public:
+    void addTarget(const RenderTarget& target);
+    void addPass(const RenderPass& pass);
+    void insertBefore(const std::string& existing, const RenderPass& pass);
+    void insertAfter(const std::string& existing, const RenderPass& pass);
+    void setEnabled(const std::string& name, bool enabled);
+
+    bool compile();
+    void execute();
+    std::vector<std::string> passNames();
+
+  private:
+    std::vector<RenderPass> passes;
+    std::map<std::string, RenderTarget> targets;
+    std::vector<int> schedule;
+    bool dirty = true;
+
+    int find(const std::string& name);
+};
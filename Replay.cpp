@@ -0,0 +1,477 @@
+#include "raylib.h"
+#include "Replay.hpp"
+#include <algorithm>
+#include <cstring>
+#include <fstream>
+#include <iterator>
+#include "Log.hpp"
+#include "Mutators.hpp"
+
+
+static const char MAGIC[4] = {'B', 'K', 'R', 'P'};
+static const uint8_t VERSION = 5;
+
+uint64_t levelHash(const std::vector<Brick>& bricks) {
+    // FNV-1a, like the state checksum
+    uint64_t hash = 14695981039346656037ull;
+    auto add = [&hash](const void* data, size_t size) {
+        const uint8_t* bytes = (const uint8_t*) data;
+        for (size_t i = 0; i < size; i++) {
+            hash = (hash ^ bytes[i]) * 1099511628211ull;
+        }
+    };
+    for (const Brick& brick : bricks) {
+        float rectangle[4] = {brick.rectangle.x, brick.rectangle.y, brick.rectangle.width, brick.rectangle.height};
+        int material = (int) brick.material;
+        add(rectangle, sizeof(rectangle));
+        add(&material, sizeof(material));
+        // only when it's more than one, moves, drops coins or time, pulls
+        // or switches, so levels from before any of those keep their hash (and
+        // the medals saved under it)
+        if (brick.maxHitPoints > 1) {
+            add(&brick.maxHitPoints, sizeof(brick.maxHitPoints));
+        }
+        if (brick.coins > 0) {
+            add(&brick.coins, sizeof(brick.coins));
+        }
+        if (brick.timeBonus > 0) {
+            add(&brick.timeBonus, sizeof(brick.timeBonus));
+        }
+        if (brick.well.active()) {
+            float well[2] = {brick.well.strength, brick.well.radius};
+            add(well, sizeof(well));
+        }
+        if (!brick.group.empty() || !brick.switchGroup.empty()) {
+            add(brick.group.data(), brick.group.size());
+            add(brick.switchGroup.data(), brick.switchGroup.size());
+            add(&brick.phased, sizeof(brick.phased));
+        }
+        if (brick.path.moves()) {
+            const BrickPath& path = brick.path;
+            float numbers[4] = {path.period, path.phase, path.range, path.radius};
+            add(&path.kind, sizeof(path.kind));
+            add(numbers, sizeof(numbers));
+            for (Vector2 point : path.points) {
+                add(&point, sizeof(point));
+            }
+        }
+    }
+    return hash;
+}
+
+// Little-endian fields, strings and blobs prefixed with a varint length.
+struct ReplayWriter {
+    std::vector<uint8_t> data;
+
+    void byte(uint8_t value) {
+        data.push_back(value);
+    }
+
+    void integer(uint64_t value, int bytes) {
+        for (int i = 0; i < bytes; i++) {
+            data.push_back((uint8_t) (value >> (8 * i)));
+        }
+    }
+
+    void varint(uint64_t value) {
+        while (value >= 0x80) {
+            data.push_back((uint8_t) (value | 0x80));
+            value >>= 7;
+        }
+        data.push_back((uint8_t) value);
+    }
+
+    void blob(const void* bytes, size_t size) {
+        varint(size);
+        data.insert(data.end(), (const uint8_t*) bytes, (const uint8_t*) bytes + size);
+    }
+
+    void rules(const SimulationRules& rules) {
+        uint32_t scanSpeed;
+        memcpy(&scanSpeed, &rules.scanSpeed, sizeof(scanSpeed));
+        integer((uint32_t) rules.width, 4);
+        integer((uint32_t) rules.height, 4);
+        integer((uint32_t) rules.inputMode, 4);
+        byte(rules.autoLaunch ? 1 : 0);
+        integer(scanSpeed, 4);
+        integer((uint32_t) rules.inputBufferTicks, 4);
+    }
+};
+
+struct ReplayReader {
+    const std::vector<uint8_t>& data;
+    size_t position = 0;
+
+    bool byte(uint8_t& value) {
+        if (position >= data.size()) {
+            return false;
+        }
+        value = data[position++];
+        return true;
+    }
+
+    template <typename T>
+    bool integer(T& value, int bytes) {
+        uint64_t result = 0;
+        for (int i = 0; i < bytes; i++) {
+            uint8_t part;
+            if (!byte(part)) {
+                return false;
+            }
+            result |= (uint64_t) part << (8 * i);
+        }
+        value = (T) result;
+        return true;
+    }
+
+    bool varint(uint64_t& value) {
+        value = 0;
+        for (int shift = 0; shift < 64; shift += 7) {
+            uint8_t part;
+            if (!byte(part)) {
+                return false;
+            }
+            value |= (uint64_t) (part & 0x7f) << shift;
+            if (!(part & 0x80)) {
+                return true;
+            }
+        }
+        return false;
+    }
+
+    bool blob(std::vector<uint8_t>& bytes) {
+        uint64_t size;
+        if (!varint(size) || size > data.size() - position) {
+            return false;
+        }
+        bytes.assign(data.begin() + position, data.begin() + position + size);
+        position += size;
+        return true;
+    }
+
+    bool string(std::string& text) {
+        std::vector<uint8_t> bytes;
+        if (!blob(bytes)) {
+            return false;
+        }
+        text.assign(bytes.begin(), bytes.end());
+        return true;
+    }
+
+    bool rules(SimulationRules& rules) {
+        uint8_t autoLaunch;
+        uint32_t scanSpeed;
+        if (!integer(rules.width, 4) || !integer(rules.height, 4) || !integer(rules.inputMode, 4) || !byte(autoLaunch)
+            || !integer(scanSpeed, 4) || !integer(rules.inputBufferTicks, 4)) {
+            return false;
+        }
+        rules.autoLaunch = autoLaunch != 0;
+        memcpy(&rules.scanSpeed, &scanSpeed, sizeof(scanSpeed));
+        return true;
+    }
+};
+
+void ReplayRecorder::start(const ReplayHeader& replayHeader) {
+    header = replayHeader;
+    header.simulationVersion = Simulation::VERSION;
+    rulesChanges.clear();
+    checkpoints.clear();
+    lastRules = header.rules;
+    input.start(header.tickRate);
+    partnerInput.start(header.tickRate);
+}
+
+bool ReplayRecorder::isRecording() {
+    return input.isRecording();
+}
+
+void ReplayRecorder::record(const InputFrame& frame, const Simulation& simulation, const InputFrame& partnerFrame) {
+    if (!input.isRecording()) {
+        return;
+    }
+    uint64_t tick = simulation.state.tick;
+    if (simulation.rules != lastRules) {
+        // settings changed mid-run; playback switches at the same tick
+        rulesChanges.push_back({tick, simulation.rules});
+        lastRules = simulation.rules;
+    }
+    if (tick > 0 && tick % CHECKPOINT_TICKS == 0) {
+        checkpoints.push_back({tick, simulation.checksum()});
+    }
+    input.record(tick, frame);
+    partnerInput.record(tick, partnerFrame);
+}
+
+bool ReplayRecorder::save(const std::string& path, const Simulation& simulation) {
+    if (!input.isRecording()) {
+        return false;
+    }
+    if (simulation.state.cheated) {
+        TraceLog(LOG_WARNING, "REPLAY: Not writing %s, developer cheats were used", path.c_str());
+        return false;
+    }
+    uint64_t endTick = simulation.state.tick;
+    const std::vector<uint8_t>& inputData = input.finish(endTick);
+    const std::vector<uint8_t>& partnerData = partnerInput.finish(endTick);
+
+    ReplayWriter writer;
+    writer.data.assign(MAGIC, MAGIC + 4);
+    writer.byte(VERSION);
+    writer.integer(header.simulationVersion, 4);
+    writer.integer(header.tickRate, 4);
+    writer.integer(header.seed, 8);
+    writer.integer(header.levelHash, 8);
+    writer.blob(header.levelPath.data(), header.levelPath.size());
+    writer.blob(header.mutators.data(), header.mutators.size());
+    writer.rules(header.rules);
+    writer.varint(rulesChanges.size());
+    for (const ReplayRulesChange& change : rulesChanges) {
+        writer.varint(change.tick);
+        writer.rules(change.rules);
+    }
+    writer.blob(inputData.data(), inputData.size());
+    writer.varint(checkpoints.size());
+    uint64_t previous = 0;
+    for (const ReplayCheckpoint& checkpoint : checkpoints) {
+        writer.varint(checkpoint.tick - previous);
+        writer.integer(checkpoint.checksum, 8);
+        previous = checkpoint.tick;
+    }
+    writer.integer((uint32_t) simulation.state.score, 4);
+    writer.integer((uint32_t) simulation.state.lives, 4);
+    writer.integer(simulation.checksum(), 8);
+    writer.blob(header.cosmetics.data(), header.cosmetics.size());
+    writer.blob(header.mode.data(), header.mode.size());
+    writer.blob(partnerData.data(), partnerData.size());
+
+    std::ofstream file(path, std::ios::binary);
+    if (!file) {
+        TraceLog(LOG_WARNING, "REPLAY: Could not write %s", path.c_str());
+        return false;
+    }
+    file.write((const char*) writer.data.data(), writer.data.size());
+    TraceLog(LOG_INFO, "REPLAY: Recorded %llu ticks to %s (%d bytes)", (unsigned long long) endTick, path.c_str(), (int) writer.data.size());
+    return true;
+}
+
+bool ReplayPlayer::load(const std::string& path, std::string& error) {
+    LogSpan span("REPLAY", "load");
+    playing = false;
+    diverged = false;
+    rulesChanges.clear();
+    checkpoints.clear();
+    snapshots.clear();
+    std::ifstream file(path, std::ios::binary);
+    if (!file) {
+        error = "could not open " + path;
+        return false;
+    }
+    std::vector<uint8_t> data((std::istreambuf_iterator<char>(file)), std::istreambuf_iterator<char>());
+    if (data.size() < 5 || memcmp(data.data(), MAGIC, 4) != 0) {
+        error = path + " is not a replay";
+        return false;
+    }
+    if (data[4] < 1 || data[4] > VERSION) {
+        error = path + " has unsupported version " + std::to_string(data[4]);
+        return false;
+    }
+    formatVersion = data[4];
+
+    ReplayReader reader{data, 5};
+    header = ReplayHeader();
+    uint64_t changes;
+    if (!reader.integer(header.simulationVersion, 4) || !reader.integer(header.tickRate, 4) || !reader.integer(header.seed, 8)
+        || !reader.integer(header.levelHash, 8) || !reader.string(header.levelPath) || !reader.string(header.mutators)
+        || !reader.rules(header.rules) || !reader.varint(changes)) {
+        error = path + " is truncated";
+        return false;
+    }
+    if (header.simulationVersion != Simulation::VERSION) {
+        // an older simulation would play the same inputs differently
+        error = path + " was recorded by simulation version " + std::to_string(header.simulationVersion)
+            + ", this build runs " + std::to_string(Simulation::VERSION);
+        return false;
+    }
+    // the mutators' rules aren't stored, the ids are
+    std::string canonical;
+    std::string mutatorError;
+    if (!parseMutators(header.mutators, canonical, mutatorError)) {
+        error = path + " was recorded with an " + mutatorError + " that this build doesn't have";
+        return false;
+    }
+    applyMutators(header.mutators, header.rules);
+    for (uint64_t i = 0; i < changes; i++) {
+        ReplayRulesChange change;
+        if (!reader.varint(change.tick) || !reader.rules(change.rules)) {
+            error = path + " is truncated";
+            return false;
+        }
+        applyMutators(header.mutators, change.rules);
+        rulesChanges.push_back(change);
+    }
+    std::vector<uint8_t> inputData;
+    uint64_t count;
+    if (!reader.blob(inputData) || !reader.varint(count)) {
+        error = path + " is truncated";
+        return false;
+    }
+    uint64_t tick = 0;
+    for (uint64_t i = 0; i < count; i++) {
+        uint64_t delta;
+        ReplayCheckpoint checkpoint;
+        if (!reader.varint(delta) || !reader.integer(checkpoint.checksum, 8)) {
+            error = path + " is truncated";
+            return false;
+        }
+        tick += delta;
+        checkpoint.tick = tick;
+        checkpoints.push_back(checkpoint);
+    }
+    result = ReplayResult();
+    resultStored = formatVersion >= 2;
+    if (resultStored) {
+        uint32_t score, lives;
+        if (!reader.integer(score, 4) || !reader.integer(lives, 4) || !reader.integer(result.checksum, 8)) {
+            error = path + " is truncated";
+            return false;
+        }
+        result.score = (int32_t) score;
+        result.lives = (int32_t) lives;
+    }
+    if (formatVersion >= 3 && !reader.string(header.cosmetics)) {
+        error = path + " is truncated";
+        return false;
+    }
+    if (formatVersion >= 4 && !reader.string(header.mode)) {
+        error = path + " is truncated";
+        return false;
+    }
+    std::vector<uint8_t> partnerData;
+    if (formatVersion >= 5 && !reader.blob(partnerData)) {
+        error = path + " is truncated";
+        return false;
+    }
+    // like the mutators, the mode goes by its id
+    GameMode mode = GameMode::Classic;
+    if (!parseGameMode(header.mode, mode)) {
+        error = path + " was recorded in a mode this build doesn't have, '" + header.mode + "'";
+        return false;
+    }
+    header.rules.mode = (int) mode;
+    for (ReplayRulesChange& change : rulesChanges) {
+        change.rules.mode = (int) mode;
+    }
+    if (!input.parse(inputData, path, error)) {
+        return false;
+    }
+    partnerInput.stop();
+    if (formatVersion >= 5 && !partnerInput.parse(partnerData, path, error)) {
+        return false;
+    }
+    playing = true;
+    return true;
+}
+
+const ReplayHeader& ReplayPlayer::getHeader() {
+    return header;
+}
+
+bool ReplayPlayer::isPlaying() {
+    return playing;
+}
+
+bool ReplayPlayer::isFinished(const Simulation& simulation) {
+    return simulation.state.tick >= input.getEndTick();
+}
+
+uint64_t ReplayPlayer::getEndTick() {
+    return input.getEndTick();
+}
+
+SimulationRules ReplayPlayer::rulesAt(uint64_t tick) {
+    SimulationRules rules = header.rules;
+    for (const ReplayRulesChange& change : rulesChanges) {
+        if (change.tick > tick) {
+            break;
+        }
+        rules = change.rules;
+    }
+    return rules;
+}
+
+InputFrame ReplayPlayer::partnerFrameAt(uint64_t tick) {
+    return partnerInput.isPlaying() ? partnerInput.frameAt(tick) : InputFrame();
+}
+
+void ReplayPlayer::step(Simulation& simulation, EventBus* events) {
+    uint64_t tick = simulation.state.tick;
+    if (tick % SNAPSHOT_TICKS == 0 && tick / SNAPSHOT_TICKS == snapshots.size()) {
+        snapshots.push_back(simulation.state);
+    }
+    auto checkpoint = std::lower_bound(checkpoints.begin(), checkpoints.end(), tick,
+        [](const ReplayCheckpoint& checkpoint, uint64_t value) { return checkpoint.tick < value; });
+    if (!diverged && checkpoint != checkpoints.end() && checkpoint->tick == tick && checkpoint->checksum != simulation.checksum()) {
+        diverged = true;
+        divergedTick = tick;
+        TraceLog(LOG_WARNING, "REPLAY: Diverged from the recording at tick %llu", (unsigned long long) tick);
+    }
+    simulation.rules = rulesAt(tick);
+    simulation.step(input.frameAt(tick), events, partnerFrameAt(tick));
+}
+
+void ReplayPlayer::seek(Simulation& simulation, uint64_t tick) {
+    LogSpan span("REPLAY", "seek");
+    tick = std::min(tick, input.getEndTick());
+    if (snapshots.empty()) {
+        return;
+    }
+    size_t index = std::min((size_t) (tick / SNAPSHOT_TICKS), snapshots.size() - 1);
+    // going back always needs a snapshot; going forward only when it skips ahead
+    if (tick < simulation.state.tick || snapshots[index].tick > simulation.state.tick) {
+        simulation.state = snapshots[index];
+        input.rewind();
+        if (formatVersion >= 5) {
+            partnerInput.rewind();
+        }
+    }
+    while (simulation.state.tick < tick) {
+        step(simulation, nullptr);
+    }
+}
+
+bool ReplayPlayer::continueInto(Simulation& simulation, ReplayRecorder& recorder) {
+    LogSpan span("REPLAY", "continue");
+    while (simulation.state.tick < input.getEndTick()) {
+        uint64_t tick = simulation.state.tick;
+        InputFrame frame = input.frameAt(tick);
+        InputFrame partnerFrame = partnerFrameAt(tick);
+        simulation.rules = rulesAt(tick);
+        recorder.record(frame, simulation, partnerFrame);
+        simulation.step(frame, nullptr, partnerFrame);
+    }
+    return !resultStored || simulation.checksum() == result.checksum;
+}
+
+bool ReplayPlayer::hasDiverged() {
+    return diverged;
+}
+
+uint64_t ReplayPlayer::getDivergedTick() {
+    return divergedTick;
+}
+
+int ReplayPlayer::getFormatVersion() {
+    return formatVersion;
+}
+
+size_t ReplayPlayer::getCheckpointCount() {
+    return checkpoints.size();
+}
+
+bool ReplayPlayer::hasResult() {
+    return resultStored;
+}
+
+const ReplayResult& ReplayPlayer::getResult() {
+    return result;
+}
@@ -0,0 +1,116 @@
+#pragma once
+#include <cstdint>
+#include <string>
+#include <vector>
+#include "Brick.hpp"
+#include "InputRecord.hpp"
+#include "Simulation.hpp"
+
+
+// What a replay has to reproduce before its inputs mean anything.
+struct ReplayHeader {
+    uint32_t simulationVersion = Simulation::VERSION;
+    uint32_t tickRate = 0;
+    uint64_t seed = 0;
+    uint64_t levelHash = 0;
+    std::string levelPath; // empty for the built-in layout
+    std::string mutators;  // comma separated
+    SimulationRules rules;
+    std::string cosmetics; // CosmeticLoadout::format(), so playback looks the same
+    std::string mode = "classic"; // GameModeInfo::id
+};
+
+struct ReplayRulesChange {
+    uint64_t tick;
+    SimulationRules rules;
+};
+
+struct ReplayCheckpoint {
+    uint64_t tick;
+    uint64_t checksum; // Simulation::checksum() at the start of that tick
+};
+
+// How the recorded run ended, as the recording machine saw it.
+struct ReplayResult {
+    int score = 0;
+    int lives = 0;
+    uint64_t checksum = 0; // Simulation::checksum() at the end tick
+};
+
+// Identifies a brick layout independent of where it was loaded from.
+uint64_t levelHash(const std::vector<Brick>& bricks);
+
+// Replay files ("BKRP") are the header, the rules whenever they changed, the
+// tick-stamped input stream (an embedded BKIN recording), a state checksum
+// every CHECKPOINT_TICKS, which is how playback notices it went off course,
+// (from format 2) the result the run ended with, (from 3) the cosmetics,
+// (from 4) the game mode, older ones being classic, and (from 5) co-op's
+// second player's input stream.
+class ReplayRecorder {
+  public:
+    static const int CHECKPOINT_TICKS = 60;
+
+    void start(const ReplayHeader& header);
+    bool isRecording();
+    // Call before every step with its inputs, and the simulation as it is now.
+    void record(const InputFrame& input, const Simulation& simulation, const InputFrame& partnerInput = InputFrame());
+    // Writes the replay up to the simulation's current tick and stops.
+    bool save(const std::string& path, const Simulation& simulation);
+
+  private:
+    ReplayHeader header;
+    std::vector<ReplayRulesChange> rulesChanges;
+    std::vector<ReplayCheckpoint> checkpoints;
+    InputRecorder input;
+    InputRecorder partnerInput;
+    SimulationRules lastRules;
+};
+
+// Drives a Simulation from a replay file. Seeking restores the nearest snapshot
+// taken on the way (every SNAPSHOT_TICKS) and fast-forwards quietly from there.
+class ReplayPlayer {
+  public:
+    static const int SNAPSHOT_TICKS = 300;
+
+    bool load(const std::string& path, std::string& error);
+    const ReplayHeader& getHeader();
+    bool isPlaying();
+    // Playback holds on the last tick, so it can still be seeked back from there.
+    bool isFinished(const Simulation& simulation);
+    uint64_t getEndTick();
+
+    // One tick with the recorded input and rules, checked against the recording.
+    void step(Simulation& simulation, EventBus* events);
+    // Jumps to tick (clamped to the recording); events are not raised on the way.
+    void seek(Simulation& simulation, uint64_t tick);
+    // Plays the rest of the recording quietly, handing every tick on to a
+    // recorder started with this header, which then carries on where this
+    // one ended (resuming a suspended run). False if the end state doesn't
+    // match what was recorded.
+    bool continueInto(Simulation& simulation, ReplayRecorder& recorder);
+    bool hasDiverged();
+    uint64_t getDivergedTick();
+
+    int getFormatVersion();
+    size_t getCheckpointCount();
+    // Format 1 files end without a result.
+    bool hasResult();
+    const ReplayResult& getResult();
+
+  private:
+    ReplayHeader header;
+    ReplayResult result;
+    int formatVersion = 0;
+    bool resultStored = false;
+    std::vector<ReplayRulesChange> rulesChanges;
+    std::vector<ReplayCheckpoint> checkpoints;
+    std::vector<GameState> snapshots; // snapshots[i] is the state at tick i * SNAPSHOT_TICKS
+    InputPlayback input;
+    InputPlayback partnerInput; // not playing before format 5
+    bool playing = false;
+    bool diverged = false;
+    uint64_t divergedTick = 0;
+
+    SimulationRules rulesAt(uint64_t tick);
+    InputFrame partnerFrameAt(uint64_t tick);
+};
@@ -0,0 +1,78 @@
+#include "raylib.h"
+#include "Sandbox.hpp"
+#include <cmath>
+
+
+Sandbox::Sandbox(Simulation& simulation) : simulation(simulation) {
+}
+
+Ball& Sandbox::ball() {
+    return simulation.state.balls[0];
+}
+
+void Sandbox::placeBall(Vector2 center) {
+    GameState& state = simulation.state;
+    Ball& placed = ball();
+    float width = placed.rectangle.width;
+    float height = placed.rectangle.height;
+    float lowest = state.player.getRectangle().y - height;
+    center.x = fminf(fmaxf(center.x, width), simulation.rules.width - width);
+    center.y = fminf(fmaxf(center.y, height), lowest);
+    // the center is a size off position (see Entity::updateRectangle)
+    placed.position = {center.x - width, center.y - height};
+    placed.rectangle.x = placed.position.x + width / 2;
+    placed.rectangle.y = placed.position.y + height / 2;
+    if (!placed.isFree()) {
+        placed.state = BallState::Free;
+        state.heldTicks = 0;
+    }
+    if (placed.velocity.x == 0 && placed.velocity.y == 0) {
+        float serve = simulation.ballSpeed();
+        placed.velocity = {2 * serve, -2 * serve};
+    }
+    // nothing else was on the paddle waiting, bar another held ball
+    state.ballHeld = false;
+    for (const Ball& other : state.balls) {
+        state.ballHeld = state.ballHeld || !other.isFree();
+    }
+}
+
+float Sandbox::speed() {
+    Vector2 velocity = ball().velocity;
+    return sqrtf(velocity.x * velocity.x + velocity.y * velocity.y);
+}
+
+float Sandbox::angle() {
+    Vector2 velocity = ball().velocity;
+    float degrees = atan2f(velocity.x, -velocity.y) * RAD2DEG;
+    return degrees < 0 ? degrees + 360 : degrees;
+}
+
+void Sandbox::aim(float angle, float speed) {
+    if (!ball().isFree()) {
+        placeBall(ball().getCenter());
+    }
+    speed = fminf(fmaxf(speed, SLOWEST), FASTEST);
+    Vector2 velocity = {sinf(angle * DEG2RAD) * speed, -cosf(angle * DEG2RAD) * speed};
+    if (fabsf(velocity.y) < speed * Ball::MIN_VERTICAL) {
+        velocity.y = (velocity.y > 0 ? 1 : -1) * speed * Ball::MIN_VERTICAL;
+        velocity.x = (velocity.x < 0 ? -1 : 1) * sqrtf(speed * speed - velocity.y * velocity.y);
+    }
+    ball().velocity = velocity;
+}
+
+Vector2 Sandbox::aimHandle() {
+    Vector2 center = ball().getCenter();
+    Vector2 velocity = ball().velocity;
+    return {center.x + velocity.x * HANDLE_TICKS, center.y + velocity.y * HANDLE_TICKS};
+}
+
+void Sandbox::dragAimHandle(Vector2 to) {
+    Vector2 center = ball().getCenter();
+    Vector2 along = {to.x - center.x, to.y - center.y};
+    if (along.x == 0 && along.y == 0) {
+        return;
+    }
+    float degrees = atan2f(along.x, -along.y) * RAD2DEG;
+    aim(degrees, sqrtf(along.x * along.x + along.y * along.y) / HANDLE_TICKS);
+}
@@ -0,0 +1,37 @@
+#pragma once
+#include "raylib.h"
+#include "Simulation.hpp"
+
+
+// Practice mode's hands on the state (GameMode::Practice), outside of
+// step() and every rule it plays by: the first ball can be put anywhere on
+// the field, going any way at any speed, and step() carries on from there.
+// Held or caught, placing it sets it free.
+class Sandbox {
+  public:
+    static constexpr float SLOWEST = 1;  // pixels per tick
+    static constexpr float FASTEST = 12;
+    // The aim handle sits where the ball will be this many ticks on.
+    static constexpr float HANDLE_TICKS = 20;
+
+    explicit Sandbox(Simulation& simulation);
+
+    Ball& ball();
+    // Centered at, kept inside the field and above the paddle. A ball that
+    // wasn't moving yet gets a serve's velocity.
+    void placeBall(Vector2 center);
+    float speed();
+    // Degrees clockwise from straight up.
+    float angle();
+    // Never flatter than a force may turn the ball (see Ball::advance),
+    // and between SLOWEST and FASTEST; sets a held ball free where it is.
+    void aim(float angle, float speed);
+
+    // The on-screen handle's end, and dragging it there: the way and speed
+    // to end up at to in HANDLE_TICKS.
+    Vector2 aimHandle();
+    void dragAimHandle(Vector2 to);
+
+  private:
+    Simulation& simulation;
+};
@@ -0,0 +1,150 @@
+#include "raylib.h"
+#include "Sanitize.hpp"
+#include <cctype>
+
+
+static const char* LEVEL_EXTENSION = ".blvl";
+static const char* LINK_PREFIX = "breakout://level/";
+
+static std::string trim(const std::string& text) {
+    size_t start = 0;
+    size_t end = text.size();
+    while (start < end && (std::isspace((unsigned char) text[start]) || text[start] == '"' || text[start] == '\'')) {
+        start++;
+    }
+    while (end > start && (std::isspace((unsigned char) text[end - 1]) || text[end - 1] == '"' || text[end - 1] == '\'')) {
+        end--;
+    }
+    return text.substr(start, end - start);
+}
+
+static std::string stripControlCharacters(const std::string& text) {
+    std::string cleaned;
+    for (char c : text) {
+        if ((unsigned char) c >= 0x20 && c != 0x7f) {
+            cleaned += c;
+        }
+    }
+    return cleaned;
+}
+
+static bool hasParentReference(const std::string& path) {
+    size_t start = 0;
+    while (start <= path.size()) {
+        size_t end = path.find_first_of("/\\", start);
+        if (end == std::string::npos) {
+            end = path.size();
+        }
+        if (path.compare(start, end - start, "..") == 0 && end - start == 2) {
+            return true;
+        }
+        start = end + 1;
+    }
+    return false;
+}
+
+std::string percentDecode(const std::string& text) {
+    std::string decoded;
+    for (size_t i = 0; i < text.size(); i++) {
+        if (text[i] == '%' && i + 2 < text.size()
+                && std::isxdigit((unsigned char) text[i + 1]) && std::isxdigit((unsigned char) text[i + 2])) {
+            decoded += (char) std::stoi(text.substr(i + 1, 2), nullptr, 16);
+            i += 2;
+        } else if (text[i] == '+') {
+            decoded += ' ';
+        } else {
+            decoded += text[i];
+        }
+    }
+    return decoded;
+}
+
+std::string sanitizeFilename(const std::string& name) {
+    static const char* reserved[] = {
+        "CON", "PRN", "AUX", "NUL",
+        "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9",
+        "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+    };
+    const size_t maxLength = 200;
+
+    std::string cleaned;
+    for (char c : stripControlCharacters(name)) {
+        cleaned += std::string("<>:\"/\\|?*").find(c) == std::string::npos ? c : '_';
+    }
+    // Windows silently drops trailing dots and spaces
+    while (!cleaned.empty() && (cleaned.back() == '.' || cleaned.back() == ' ')) {
+        cleaned.pop_back();
+    }
+    while (!cleaned.empty() && cleaned[0] == ' ') {
+        cleaned.erase(0, 1);
+    }
+    if (cleaned.size() > maxLength) {
+        cleaned.resize(maxLength);
+    }
+    if (cleaned.empty() || cleaned == "." || cleaned == "..") {
+        return "_";
+    }
+
+    std::string stem = cleaned.substr(0, cleaned.find('.'));
+    for (char& c : stem) {
+        c = std::toupper((unsigned char) c);
+    }
+    for (const char* device : reserved) {
+        if (stem == device) {
+            return "_" + cleaned;
+        }
+    }
+    return cleaned;
+}
+
+std::string sanitizeLevelPath(const std::string& rawPath) {
+    std::string path = stripControlCharacters(trim(rawPath));
+    if (path.rfind("file://", 0) == 0) {
+        path = percentDecode(path.substr(7));
+    }
+    if (path.empty() || !IsFileExtension(path.c_str(), LEVEL_EXTENSION)) {
+        return "";
+    }
+    if (!FileExists(path.c_str()) || !IsPathFile(path.c_str())) {
+        return "";
+    }
+    return path;
+}
+
+std::string sanitizeLevelLink(const std::string& link) {
+    std::string cleaned = stripControlCharacters(trim(link));
+    if (cleaned.rfind(LINK_PREFIX, 0) != 0) {
+        return "";
+    }
+    std::string name = percentDecode(cleaned.substr(std::char_traits<char>::length(LINK_PREFIX)));
+    size_t query = name.find_first_of("?#");
+    if (query != std::string::npos) {
+        name = name.substr(0, query);
+    }
+    while (!name.empty() && name.back() == '/') {
+        name.pop_back();
+    }
+    if (name.empty() || name[0] == '/' || name[0] == '\\' || name.find(':') != std::string::npos
+            || hasParentReference(name)) {
+        return "";
+    }
+    if (!IsFileExtension(name.c_str(), LEVEL_EXTENSION)) {
+        name += LEVEL_EXTENSION;
+    }
+    return sanitizeLevelPath(std::string(GetApplicationDirectory()) + "levels/" + stripControlCharacters(name));
+}
+
+std::string jsonString(const std::string& text) {
+    std::string quoted = "\"";
+    for (char c : text) {
+        if (c == '"' || c == '\\') {
+            quoted += '\\';
+            quoted += c;
+        } else if ((unsigned char) c < 0x20) {
+            quoted += TextFormat("\\u%04x", c);
+        } else {
+            quoted += c;
+        }
+    }
+    return quoted + "\"";
+}
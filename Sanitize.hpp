@@ -0,0 +1,20 @@
+#pragma once
+#include <string>
+
+
+// Returns a cleaned-up path to an existing .blvl file, or an empty string if
+// the input does not name one.
+std::string sanitizeLevelPath(const std::string& rawPath);
+
+// Resolves a breakout:// invite link to a level inside the bundled levels
+// directory. Links may never point outside of it.
+std::string sanitizeLevelLink(const std::string& link);
+
+// Turns arbitrary text (profile names, capture titles...) into a single file
+// name that is valid on every platform we ship on.
+std::string sanitizeFilename(const std::string& name);
+
+std::string percentDecode(const std::string& text);
+
+// Quoted and escaped for JSON output.
+std::string jsonString(const std::string& text);
@@ -0,0 +1,17 @@
+#include "Scoring.hpp"
+#include <algorithm>
+
+
+int comboMultiplier(int combo) {
+    return std::clamp(1 + (combo - 1) / ComboRules::STEP, 1, ComboRules::MAX_MULTIPLIER);
+}
+
+int pointsFor(EventType type) {
+    switch (type) {
+    case EventType::BrickHit: return 1;
+    case EventType::BrickDestroyed: return 1;
+    case EventType::BossHit: return 1;
+    case EventType::BossDefeated: return 25;
+    default: return 0;
+    }
+}
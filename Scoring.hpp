@@ -0,0 +1,22 @@
+#pragma once
+#include "Events.hpp"
+
+
+// Combos: bricks broken since a ball was last on the paddle (GameState's
+// combo). Every STEP of them adds one to the multiplier on what a break
+// scores, up to MAX_MULTIPLIER, and going DECAY_SECONDS without breaking
+// one takes a step off again, so a ball rattling round metal doesn't keep it.
+struct ComboRules {
+    static constexpr int STEP = 3;
+    static constexpr int MAX_MULTIPLIER = 5;
+    static constexpr int DECAY_SECONDS = 4;
+};
+
+// x1 for a combo's first STEP bricks, x2 for the next STEP...
+int comboMultiplier(int combo);
+
+// What each scoring event is worth before the combo and the mutators'
+// scorePercent; 0 for the ones that score nothing. The simulation scores a
+// hit by the event it publishes for it, so something new to score is a row
+// here rather than a constant in step().
+int pointsFor(EventType type);
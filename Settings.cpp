@@ -0,0 +1,146 @@
+#include "raylib.h"
+#include "Settings.hpp"
+#include <cstdlib>
+#include <fstream>
+
+
+static const char* const VSYNC_OPTIONS[] = {"Off", "On", "Adaptive"};
+static const char* const LIGHTING_OPTIONS[] = {"Off", "Low", "Medium", "High"};
+static const char* const COLOR_BLIND_OPTIONS[] = {"Off", "Deuteranopia", "Protanopia", "Tritanopia"};
+static const char* const SPEEDRUN_START_OPTIONS[] = {"Level start", "First serve"};
+
+std::vector<SettingField> settingFields(Settings& settings) {
+    return {
+        {"post_processing", "Post-processing", SettingType::Bool, &settings.postProcessing},
+        {"bloom", "Bloom", SettingType::Bool, &settings.bloom},
+        {"crt_filter", "CRT filter", SettingType::Bool, &settings.crtFilter},
+        {"vignette", "Vignette", SettingType::Bool, &settings.vignette},
+        {"low_end_gpu", "Low-end GPU mode", SettingType::Bool, &settings.lowEndGpu},
+        {"lighting_quality", "Glow lighting", SettingType::Int, &settings.lightingQuality, 0, 3, 1, LIGHTING_OPTIONS},
+        {"juice", "Screen shake and hit-stop", SettingType::Bool, &settings.juice},
+        {"juice_intensity", "Shake intensity", SettingType::Float, &settings.juiceIntensity, 0, 1, 0.1f},
+        {"vsync", "VSync", SettingType::Int, &settings.vsyncMode, 0, 2, 1, VSYNC_OPTIONS},
+        {"fps_cap", "FPS limit", SettingType::Int, &settings.fpsCap, 0, 360, 30, nullptr, "Unlimited"},
+        {"color_blind_mode", "Color-blind palette", SettingType::Int, &settings.colorBlindMode, 0, 3, 1, COLOR_BLIND_OPTIONS},
+        {"brick_patterns", "Brick patterns", SettingType::Bool, &settings.brickPatterns},
+        {"reduced_motion", "Reduced motion", SettingType::Bool, &settings.reducedMotion},
+        {"visual_sound_cues", "Visual sound cues", SettingType::Bool, &settings.visualSoundCues},
+        {"gamepad_deadzone", "Stick deadzone", SettingType::Float, &settings.gamepadDeadzone, 0.05f, 0.5f, 0.05f},
+        {"input_buffer_ms", "Input buffer (ms)", SettingType::Int, &settings.inputBufferMs, 0, 250, 25, nullptr, "Off"},
+        {"mouse_control", "Mouse paddle control", SettingType::Bool, &settings.mouseControl},
+        {"mouse_sensitivity", "Mouse sensitivity", SettingType::Float, &settings.mouseSensitivity, 0.25f, 3, 0.25f},
+        {"mouse_smoothing", "Mouse smoothing", SettingType::Float, &settings.mouseSmoothing, 0, 0.9f, 0.1f},
+        {"mouse_prediction", "Mouse prediction", SettingType::Bool, &settings.mousePrediction},
+        {"touch_controls", "Touch controls", SettingType::Bool, &settings.touchControls},
+        {"ui_scale", "UI scale", SettingType::Float, &settings.uiScale, 0.75f, 1.5f, 0.05f},
+        {"ui_safe_area", "Safe area", SettingType::Float, &settings.uiSafeArea, 0, 0.1f, 0.01f},
+        {"fullscreen", "Fullscreen", SettingType::Bool, &settings.fullscreen},
+        {"language", "Language", SettingType::String, &settings.language},
+        {"master_volume", "Master volume", SettingType::Float, &settings.masterVolume, 0, 1, 0.05f},
+        {"music_volume", "Music volume", SettingType::Float, &settings.musicVolume, 0, 1, 0.05f},
+        {"sfx_volume", "Effects volume", SettingType::Float, &settings.sfxVolume, 0, 1, 0.05f},
+        {"ui_volume", "Menu volume", SettingType::Float, &settings.uiVolume, 0, 1, 0.05f},
+        {"mute_on_focus_loss", "Mute when unfocused", SettingType::Bool, &settings.muteOnFocusLoss},
+        {"background_audio", "Play while minimized", SettingType::Bool, &settings.backgroundAudio},
+        {"low_latency_sfx", "Low-latency effects", SettingType::Bool, &settings.lowLatencySfx},
+        {"clip_seconds", "Clip length (s)", SettingType::Int, &settings.clipSeconds, 5, 30, 5},
+        {"speedrun_timer", "Speedrun timer", SettingType::Bool, &settings.speedrunTimer},
+        {"speedrun_start", "Timer starts at", SettingType::Int, &settings.speedrunStart, 0, 1, 1, SPEEDRUN_START_OPTIONS},
+        {"telemetry", "Session log (local)", SettingType::Bool, &settings.telemetry},
+        {"log_console", "Log in debug overlay", SettingType::Bool, &settings.logConsole},
+        {"log_filter", nullptr, SettingType::String, &settings.logFilter},
+        {"profile", nullptr, SettingType::String, &settings.profile},
+    };
+}
+
+std::string settingsPath() {
+    return std::string(GetApplicationDirectory()) + "settings.cfg";
+}
+
+static std::string trim(const std::string& text) {
+    size_t start = text.find_first_not_of(" \t\r");
+    if (start == std::string::npos) {
+        return "";
+    }
+    size_t end = text.find_last_not_of(" \t\r");
+    return text.substr(start, end - start + 1);
+}
+
+// The file is plain "key = value" lines; unknown keys are ignored so older
+// builds can read newer files.
+bool loadFields(const std::string& path, std::vector<SettingField>& fields) {
+    std::ifstream file(path);
+    if (!file) {
+        return false;
+    }
+    std::string line;
+    while (std::getline(file, line)) {
+        line = trim(line);
+        if (line.empty() || line[0] == '#') {
+            continue;
+        }
+        size_t separator = line.find('=');
+        if (separator == std::string::npos) {
+            continue;
+        }
+        std::string key = trim(line.substr(0, separator));
+        std::string value = trim(line.substr(separator + 1));
+        for (SettingField& field : fields) {
+            if (key != field.key) {
+                continue;
+            }
+            switch (field.type) {
+            case SettingType::Bool:
+                *(bool*) field.value = value == "true" || value == "1";
+                break;
+            case SettingType::Int:
+                *(int*) field.value = atoi(value.c_str());
+                break;
+            case SettingType::Float:
+                *(float*) field.value = atof(value.c_str());
+                break;
+            case SettingType::String:
+                *(std::string*) field.value = value;
+                break;
+            }
+        }
+    }
+    return true;
+}
+
+bool saveFields(const std::string& path, std::vector<SettingField>& fields) {
+    std::ofstream file(path);
+    if (!file) {
+        TraceLog(LOG_WARNING, "SETTINGS: Could not write %s", path.c_str());
+        return false;
+    }
+    for (SettingField& field : fields) {
+        file << field.key << " = ";
+        switch (field.type) {
+        case SettingType::Bool:
+            file << (*(bool*) field.value ? "true" : "false");
+            break;
+        case SettingType::Int:
+            file << *(int*) field.value;
+            break;
+        case SettingType::Float:
+            file << *(float*) field.value;
+            break;
+        case SettingType::String:
+            file << *(std::string*) field.value;
+            break;
+        }
+        file << "\n";
+    }
+    return true;
+}
+
+bool loadSettings(const std::string& path, Settings& settings) {
+    std::vector<SettingField> fields = settingFields(settings);
+    return loadFields(path, fields);
+}
+
+bool saveSettings(const std::string& path, Settings& settings) {
+    std::vector<SettingField> fields = settingFields(settings);
+    return saveFields(path, fields);
+}
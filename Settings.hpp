@@ -0,0 +1,104 @@
+#pragma once
+#include <string>
+#include <vector>
+
+
+struct Settings {
+    // Post-processing
+    bool postProcessing = true;
+    bool bloom = true;
+    bool crtFilter = false;
+    bool vignette = true;
+    bool lowEndGpu = false; // skips the whole post chain
+    // bloom only runs for themes that ask for it; this lets players opt out
+
+    int lightingQuality = 2; // LightingQuality
+
+    // Screen shake and hit-stop
+    bool juice = true;
+    float juiceIntensity = 1.0f;
+
+    // Frame pacing
+    int vsyncMode = 1; // VsyncMode
+    int fpsCap = 240;  // 0 = unlimited
+
+    // Accessibility
+    int colorBlindMode = 0; // ColorBlindMode
+    bool brickPatterns = false;
+    bool reducedMotion = false; // no shake, strobing or big bursts; see setReducedMotion()
+    bool visualSoundCues = false;
+
+    // Controls
+    float gamepadDeadzone = 0.15f;
+    int inputBufferMs = 100; // how early a launch/power press may come
+    bool mouseControl = false;
+    float mouseSensitivity = 1.0f;
+    float mouseSmoothing = 0.0f; // 0 = raw, higher trails the pointer more
+    bool mousePrediction = false;
+    bool touchControls = false; // drag/tap gameplay, bigger menu rows, on-screen menu button
+
+    // Interface
+    float uiScale = 1.0f;
+    float uiSafeArea = 0.0f; // fraction of each screen edge kept clear
+    bool fullscreen = false; // borderless, at the desktop resolution
+    std::string language = "en"; // see Localization
+
+    // Audio
+    float masterVolume = 1.0f;
+    float musicVolume = 0.8f;
+    float sfxVolume = 1.0f;
+    float uiVolume = 0.8f;
+    bool muteOnFocusLoss = false;
+    bool backgroundAudio = true; // keep running (and playing) while minimized
+    bool lowLatencySfx = true;   // mix effects in SfxMixer, timed to the contact
+
+    // Capture
+    int clipSeconds = 10; // how much gameplay F10 saves
+
+    // Speedrun
+    bool speedrunTimer = false;
+    int speedrunStart = 0; // SpeedrunStart
+
+    // Privacy
+    bool telemetry = false; // local session log, see Telemetry
+
+    // Logging
+    std::string logFilter = "info"; // see setLogFilter(); only set in the file
+    bool logConsole = false;        // mirror the log into the debug overlay
+
+    std::string profile = "default";
+};
+
+enum class SettingType {
+    Bool,
+    Int,
+    Float,
+    String,
+};
+
+// One named, persisted field of Settings. Used by the file reader/writer and
+// the settings menu, so adding a setting only means adding a line to
+// settingFields(). Numeric fields carry the range the menu may adjust them in;
+// Int fields with options are shown as the option at that index.
+// Everything else persisted as key = value (profiles, ...) reuses this too.
+struct SettingField {
+    const char* key;
+    const char* label;
+    SettingType type;
+    void* value;
+    float min = 0;
+    float max = 1;
+    float step = 1;
+    const char* const* options = nullptr;
+    const char* zeroLabel = nullptr;
+    std::vector<std::string> choices; // values a String field cycles through
+};
+
+std::vector<SettingField> settingFields(Settings& settings);
+
+bool loadFields(const std::string& path, std::vector<SettingField>& fields);
+bool saveFields(const std::string& path, std::vector<SettingField>& fields);
+
+std::string settingsPath();
+bool loadSettings(const std::string& path, Settings& settings);
+bool saveSettings(const std::string& path, Settings& settings);
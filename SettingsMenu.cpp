@@ -0,0 +1,167 @@
+#include "raylib.h"
+#include "SettingsMenu.hpp"
+#include "Localization.hpp"
+#include <cmath>
+#include <string>
+
+
+void SettingsMenu::toggle() {
+    isOpen = !isOpen;
+}
+
+static bool adjust(SettingField& field, int direction) {
+    switch (field.type) {
+    case SettingType::Bool:
+        *(bool*) field.value = !*(bool*) field.value;
+        return true;
+    case SettingType::Int: {
+        int* value = (int*) field.value;
+        int next = *value + direction * (int) field.step;
+        next = next < field.min ? (int) field.min : (next > field.max ? (int) field.max : next);
+        bool changed = next != *value;
+        *value = next;
+        return changed;
+    }
+    case SettingType::Float: {
+        float* value = (float*) field.value;
+        float next = fminf(fmaxf(*value + direction * field.step, field.min), field.max);
+        // keep slider values on the step grid despite float drift
+        next = roundf(next / field.step) * field.step;
+        bool changed = next != *value;
+        *value = next;
+        return changed;
+    }
+    case SettingType::String: {
+        std::string* value = (std::string*) field.value;
+        int count = field.choices.size();
+        if (count < 2) {
+            return false;
+        }
+        int current = 0;
+        for (int i = 0; i < count; i++) {
+            if (field.choices[i] == *value) {
+                current = i;
+            }
+        }
+        *value = field.choices[(current + direction + count) % count];
+        return true;
+    }
+    }
+    return false;
+}
+
+static std::vector<SettingField*> visibleFields(std::vector<SettingField>& fields) {
+    std::vector<SettingField*> visible;
+    for (SettingField& field : fields) {
+        if (field.label) {
+            visible.push_back(&field);
+        }
+    }
+    return visible;
+}
+
+bool SettingsMenu::update(std::vector<SettingField>& fields) {
+    if (!isOpen) {
+        return false;
+    }
+    std::vector<SettingField*> visible = visibleFields(fields);
+    int count = visible.size();
+    if (count == 0) {
+        return false;
+    }
+
+    if (IsKeyPressed(KEY_DOWN)) {
+        selected = (selected + 1) % count;
+    } else if (IsKeyPressed(KEY_UP)) {
+        selected = (selected - 1 + count) % count;
+    }
+    if (selected >= count) {
+        selected = count - 1;
+    }
+
+    SettingField& field = *visible[selected];
+    if (IsKeyPressed(KEY_RIGHT) || (IsKeyPressed(KEY_ENTER) && field.type == SettingType::Bool)) {
+        return adjust(field, 1);
+    }
+    if (IsKeyPressed(KEY_LEFT)) {
+        return adjust(field, -1);
+    }
+    return false;
+}
+
+bool SettingsMenu::tap(std::vector<SettingField>& fields, Vector2 point) {
+    if (!isOpen || rowHeight <= 0 || !CheckCollisionPointRec(point, panel) || point.y < rowsTop) {
+        return false;
+    }
+    std::vector<SettingField*> visible = visibleFields(fields);
+    int row = scroll + (int) ((point.y - rowsTop) / rowHeight);
+    if (row >= (int) visible.size() || row >= scroll + visibleRows) {
+        return false;
+    }
+    if (row != selected) {
+        selected = row;
+        return false;
+    }
+    return adjust(*visible[row], point.x < panel.x + panel.width / 2 ? -1 : 1);
+}
+
+static std::string describe(const SettingField& field) {
+    switch (field.type) {
+    case SettingType::Bool:
+        return tr(*(bool*) field.value ? "On" : "Off");
+    case SettingType::Int:
+        if (field.options) {
+            return tr(field.options[*(int*) field.value]);
+        }
+        if (field.zeroLabel && *(int*) field.value == 0) {
+            return tr(field.zeroLabel);
+        }
+        return std::to_string(*(int*) field.value);
+    case SettingType::Float:
+        return TextFormat("%.0f%%", *(float*) field.value * 100);
+    case SettingType::String:
+        return *(std::string*) field.value;
+    }
+    return "";
+}
+
+void SettingsMenu::draw(TextRenderer& text, std::vector<SettingField>& allFields, Rectangle bounds, float scale) {
+    if (!isOpen) {
+        return;
+    }
+    std::vector<SettingField*> fields = visibleFields(allFields);
+    rowHeight = (touchMode ? 48 : 28) * scale;
+    int fontSize = 20 * scale;
+    float header = 70 * scale;
+    int count = fields.size();
+    visibleRows = (int) ((bounds.height - header) / rowHeight);
+    visibleRows = visibleRows < 1 ? 1 : (visibleRows > count ? count : visibleRows);
+    // keep a row of context around the selection where there is one
+    int context = visibleRows > 2 ? 1 : 0;
+    if (selected - context < scroll) {
+        scroll = selected - context;
+    }
+    if (selected + context >= scroll + visibleRows) {
+        scroll = selected + context - visibleRows + 1;
+    }
+    scroll = scroll > count - visibleRows ? count - visibleRows : scroll;
+    scroll = scroll < 0 ? 0 : scroll;
+
+    Vector2 size = {520 * scale, header + rowHeight * visibleRows};
+    Vector2 corner = anchorPoint(Anchor::Center, bounds, size);
+    float margin = 20 * scale;
+    panel = {corner.x, corner.y, size.x, size.y};
+    rowsTop = corner.y + 60 * scale;
+
+    DrawRectangle(0, 0, GetScreenWidth(), GetScreenHeight(), Fade(BLACK, 0.6f));
+    DrawRectangleRec({corner.x, corner.y, size.x, size.y}, Fade(DARKGRAY, 0.95f));
+    text.draw(tr("Settings"), {corner.x + margin, corner.y + 15 * scale}, 30 * scale, RAYWHITE);
+
+    for (int i = scroll; i < scroll + visibleRows; i++) {
+        int rowY = rowsTop + (i - scroll) * rowHeight + (rowHeight - fontSize) / 2 - 4 * scale;
+        Color color = i == selected ? YELLOW : LIGHTGRAY;
+        text.draw(tr(fields[i]->label), {corner.x + margin, (float) rowY}, fontSize, color);
+        std::string value = describe(*fields[i]);
+        text.draw(value, {corner.x + size.x - margin - text.measure(value, fontSize).x, (float) rowY}, fontSize, color);
+    }
+}
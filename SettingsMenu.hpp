@@ -0,0 +1,37 @@
+#pragma once
+#include "raylib.h"
+#include <vector>
+#include "Settings.hpp"
+#include "Text.hpp"
+#include "Ui.hpp"
+
+
+// Overlay listing the given fields (settingFields() plus whatever else the
+// caller wants adjustable); fields without a label are hidden. Up/down
+// selects, left/right (or enter for toggles) changes the value. The list
+// scrolls to keep the selection in view. Labels and option names are
+// translated as they are drawn; String values are data and shown as they are.
+class SettingsMenu {
+  public:
+    bool isOpen = false;
+    // Finger-sized rows; tapping a row selects it, tapping it again changes
+    // the value (left half down, right half up).
+    bool touchMode = false;
+
+    void toggle();
+    // Returns true if a value was changed this frame.
+    bool update(std::vector<SettingField>& fields);
+    // point is in window pixels; returns true if a value was changed.
+    bool tap(std::vector<SettingField>& fields, Vector2 point);
+    // bounds is the UI layout's safe area, in window pixels.
+    void draw(TextRenderer& text, std::vector<SettingField>& fields, Rectangle bounds, float scale);
+
+  private:
+    int selected = 0;
+    int scroll = 0; // first visible row
+    // where the rows were last drawn, for hit-testing taps
+    Rectangle panel = {0, 0, 0, 0};
+    float rowsTop = 0;
+    float rowHeight = 0;
+    int visibleRows = 0;
+};
@@ -0,0 +1,172 @@
+#ifndef BREAKOUT_NO_AUDIO
+#include "raylib.h"
+#include "SfxMixer.hpp"
+#include <cmath>
+#include <cstring>
+
+
+// raylib's stream callback carries no user pointer
+SfxMixer* SfxMixer::current = nullptr;
+
+bool SfxMixer::init() {
+    if (current || !IsAudioDeviceReady()) {
+        return false;
+    }
+    SetAudioStreamBufferSizeDefault(BUFFER_FRAMES);
+    stream = LoadAudioStream(SAMPLE_RATE, 32, 2);
+    SetAudioStreamBufferSizeDefault(0);
+    if (!IsAudioStreamReady(stream)) {
+        TraceLog(LOG_WARNING, "AUDIO: Low-latency stream unavailable, effects use regular sounds");
+        return false;
+    }
+    current = this;
+    SetAudioStreamCallback(stream, render);
+    PlayAudioStream(stream);
+    ready = true;
+    return true;
+}
+
+void SfxMixer::unload() {
+    if (!ready) {
+        return;
+    }
+    StopAudioStream(stream);
+    UnloadAudioStream(stream);
+    current = nullptr;
+    ready = false;
+    samples.clear();
+}
+
+bool SfxMixer::isReady() {
+    return ready;
+}
+
+int SfxMixer::addSample(Wave wave) {
+    if (!ready || !IsWaveReady(wave)) {
+        return -1;
+    }
+    Wave copy = WaveCopy(wave);
+    WaveFormat(&copy, SAMPLE_RATE, 32, 2);
+    float* data = LoadWaveSamples(copy);
+    std::vector<float> sample(data, data + copy.frameCount * 2);
+    UnloadWaveSamples(data);
+    UnloadWave(copy);
+
+    std::lock_guard<std::mutex> guard(lock);
+    samples.push_back(std::move(sample));
+    return samples.size() - 1;
+}
+
+void SfxMixer::clearSamples() {
+    std::lock_guard<std::mutex> guard(lock);
+    pending.clear();
+    for (Voice& voice : voices) {
+        voice.sample = -1;
+    }
+    samples.clear();
+}
+
+void SfxMixer::schedule(int sample, double time, float volume, float pan, float pitch) {
+    if (!ready || sample < 0) {
+        return;
+    }
+    Voice voice;
+    voice.sample = sample;
+    voice.pitch = pitch > 0 ? pitch : 1;
+    // equal-power pan
+    voice.left = volume * cosf(pan * PI / 2);
+    voice.right = volume * sinf(pan * PI / 2);
+    voice.startFrame = 0;
+    if (time >= 0) {
+        double ahead = time + SCHEDULE_DELAY - clockTime.load();
+        voice.startFrame = clockFrames.load() + (long long) (ahead * SAMPLE_RATE);
+    }
+    std::lock_guard<std::mutex> guard(lock);
+    pending.push_back(voice);
+}
+
+void SfxMixer::setGain(float value) {
+    gain = value;
+}
+
+void SfxMixer::stopAll() {
+    stopRequested = true;
+}
+
+int SfxMixer::getActiveVoices() {
+    return activeVoices;
+}
+
+void SfxMixer::render(void* buffer, unsigned int frames) {
+    if (current) {
+        current->mix((float*) buffer, frames);
+    } else {
+        memset(buffer, 0, frames * 2 * sizeof(float));
+    }
+}
+
+// Runs on the audio thread
+void SfxMixer::mix(float* output, unsigned int frames) {
+    memset(output, 0, frames * 2 * sizeof(float));
+    long long start = clockFrames.load();
+    std::lock_guard<std::mutex> guard(lock);
+
+    if (stopRequested.exchange(false)) {
+        pending.clear();
+        for (Voice& voice : voices) {
+            voice.sample = -1;
+        }
+    }
+    for (const Voice& incoming : pending) {
+        // take a free slot, or cut the voice that has been playing longest
+        Voice* slot = &voices[0];
+        for (Voice& voice : voices) {
+            if (voice.sample < 0) {
+                slot = &voice;
+                break;
+            }
+            if (voice.startFrame < slot->startFrame) {
+                slot = &voice;
+            }
+        }
+        *slot = incoming;
+        if (slot->startFrame < start) {
+            slot->startFrame = start;
+        }
+    }
+    pending.clear();
+
+    float busGain = gain.load();
+    int active = 0;
+    for (Voice& voice : voices) {
+        if (voice.sample < 0) {
+            continue;
+        }
+        active++;
+        const std::vector<float>& data = samples[voice.sample];
+        long long length = data.size() / 2;
+        unsigned int first = voice.startFrame > start ? voice.startFrame - start : 0;
+        for (unsigned int i = first; i < frames; i++) {
+            long long index = (long long) voice.position;
+            if (index + 1 >= length) {
+                voice.sample = -1;
+                break;
+            }
+            float t = voice.position - index;
+            float left = data[index * 2] + (data[index * 2 + 2] - data[index * 2]) * t;
+            float right = data[index * 2 + 1] + (data[index * 2 + 3] - data[index * 2 + 1]) * t;
+            output[i * 2] += left * voice.left * busGain;
+            output[i * 2 + 1] += right * voice.right * busGain;
+            voice.position += voice.pitch;
+        }
+    }
+    for (unsigned int i = 0; i < frames * 2; i++) {
+        output[i] = output[i] > 1 ? 1 : (output[i] < -1 ? -1 : output[i]);
+    }
+
+    activeVoices = active;
+    clockFrames = start + frames;
+    clockTime = GetTime();
+}
+
+#endif
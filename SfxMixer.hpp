@@ -0,0 +1,62 @@
+#pragma once
+#include "raylib.h"
+#include <atomic>
+#include <mutex>
+#include <vector>
+
+
+// Low-latency effects path. Instead of handing sounds to raylib one by one,
+// effects are mixed into a single small-buffer stream (~5 ms per callback)
+// and each one is scheduled against the audio clock using the simulation
+// time of the contact that caused it. Contacts from several ticks processed
+// in one frame therefore keep their spacing instead of all firing at once.
+class SfxMixer {
+  public:
+    static constexpr int SAMPLE_RATE = 48000;
+    static constexpr int BUFFER_FRAMES = 256;
+    static constexpr int MAX_VOICES = 32;
+    // How far behind the contact a sound is scheduled; absorbs the delay
+    // between the simulation tick and the frame's event dispatch.
+    static constexpr double SCHEDULE_DELAY = 0.008;
+
+    bool init();
+    void unload();
+    bool isReady();
+
+    // Converts the wave to the mixer's format; returns the sample id or -1.
+    int addSample(Wave wave);
+    // Stops everything and forgets every sample; ids start over from 0.
+    void clearSamples();
+    // time is GetTime()-based; pass a negative time to play right away.
+    void schedule(int sample, double time, float volume, float pan, float pitch);
+    // Bus gain applied to everything the mixer plays.
+    void setGain(float gain);
+    void stopAll();
+    int getActiveVoices();
+
+  private:
+    struct Voice {
+        int sample = -1;
+        double position = 0; // in frames, fractional when pitched
+        long long startFrame = 0;
+        float left = 0;
+        float right = 0;
+        float pitch = 1;
+    };
+
+    AudioStream stream = {};
+    bool ready = false;
+    std::vector<std::vector<float>> samples; // interleaved stereo
+    Voice voices[MAX_VOICES];
+    std::vector<Voice> pending;
+    std::mutex lock;
+    std::atomic<float> gain{1};
+    std::atomic<long long> clockFrames{0};
+    std::atomic<double> clockTime{0};
+    std::atomic<int> activeVoices{0};
+    std::atomic<bool> stopRequested{false};
+
+    static SfxMixer* current;
+    static void render(void* buffer, unsigned int frames);
+    void mix(float* output, unsigned int frames);
+};
@@ -0,0 +1,105 @@
+#include "raylib.h"
+#include "ShopScreen.hpp"
+#include "Cosmetics.hpp"
+#include "Localization.hpp"
+#include "Ui.hpp"
+#include <algorithm>
+
+
+static std::vector<const Cosmetic*> skins() {
+    std::vector<const Cosmetic*> list;
+    for (const Cosmetic& cosmetic : allCosmetics()) {
+        if (cosmetic.slot == CosmeticSlot::Skin) {
+            list.push_back(&cosmetic);
+        }
+    }
+    return list;
+}
+
+void ShopScreen::toggle() {
+    isOpen = !isOpen;
+    message.clear();
+}
+
+bool ShopScreen::update(Profile& profile) {
+    if (!isOpen) {
+        return false;
+    }
+    std::vector<const Cosmetic*> list = skins();
+    int count = list.size();
+    if (IsKeyPressed(KEY_DOWN)) {
+        selected = (selected + 1) % count;
+        message.clear();
+    } else if (IsKeyPressed(KEY_UP)) {
+        selected = (selected - 1 + count) % count;
+        message.clear();
+    }
+    selected = std::min(selected, count - 1);
+    if (!IsKeyPressed(KEY_ENTER) && !IsKeyPressed(KEY_KP_ENTER)) {
+        return false;
+    }
+    const Cosmetic& skin = *list[selected];
+    if (isUnlocked(profile.unlocked, skin.id)) {
+        if (profile.skin == skin.id) {
+            return false;
+        }
+        profile.skin = skin.id;
+        message = TextFormat(tr("Wearing %s").c_str(), tr(skin.name).c_str());
+        return true;
+    }
+    if (skin.price == 0) {
+        message = tr(skin.requirement);
+        return false;
+    }
+    if (!buyCosmetic(skin.id, profile.coins, profile.unlocked)) {
+        message = TextFormat(tr("%d more coins needed").c_str(), skin.price - profile.coins);
+        return false;
+    }
+    TraceLog(LOG_INFO, "SHOP: Bought %s for %d coins", skin.id.c_str(), skin.price);
+    profile.skin = skin.id;
+    message = TextFormat(tr("Bought %s").c_str(), tr(skin.name).c_str());
+    return true;
+}
+
+void ShopScreen::draw(TextRenderer& text, const Profile& profile, Rectangle bounds, float scale) {
+    if (!isOpen) {
+        return;
+    }
+    std::vector<const Cosmetic*> list = skins();
+    float rowHeight = 34 * scale;
+    int fontSize = 20 * scale;
+    Vector2 size = {620 * scale, 150 * scale + rowHeight * list.size()};
+    Vector2 corner = anchorPoint(Anchor::Center, bounds, size);
+    float margin = 20 * scale;
+
+    DrawRectangle(0, 0, GetScreenWidth(), GetScreenHeight(), Fade(BLACK, 0.6f));
+    DrawRectangleRec({corner.x, corner.y, size.x, size.y}, Fade(DARKGRAY, 0.95f));
+    text.draw(tr("Shop"), {corner.x + margin, corner.y + 15 * scale}, 30 * scale, RAYWHITE);
+    std::string coins = TextFormat(tr("Coins %d").c_str(), profile.coins);
+    Vector2 coinsSize = text.measure(coins, fontSize);
+    text.draw(coins, {corner.x + size.x - margin - coinsSize.x, corner.y + 22 * scale}, fontSize, GOLD);
+
+    for (size_t i = 0; i < list.size(); i++) {
+        const Cosmetic& skin = *list[i];
+        float rowY = corner.y + 70 * scale + i * rowHeight;
+        // the ball over the paddle, in the skin's colors (classic's are the theme's)
+        Color ball = skin.ball.a > 0 ? skin.ball : RAYWHITE;
+        Color paddle = skin.paddle.a > 0 ? skin.paddle : LIGHTGRAY;
+        DrawRectangleRec({corner.x + margin, rowY + 16 * scale, 40 * scale, 8 * scale}, paddle);
+        DrawCircleV({corner.x + margin + 20 * scale, rowY + 8 * scale}, 6 * scale, ball);
+
+        bool owned = isUnlocked(profile.unlocked, skin.id);
+        Color color = (int) i == selected ? YELLOW : owned ? LIGHTGRAY : GRAY;
+        text.draw(tr(skin.name), {corner.x + margin + 60 * scale, rowY}, fontSize, color);
+        std::string status = profile.skin == skin.id ? tr("Wearing") : owned ? tr("Owned")
+            : skin.price > 0 ? TextFormat(tr("%d coins").c_str(), skin.price) : tr(skin.requirement);
+        Color statusColor = !owned && skin.price > 0 ? (profile.coins >= skin.price ? GOLD : GRAY) : color;
+        Vector2 statusSize = text.measure(status, 16 * scale);
+        text.draw(status, {corner.x + size.x - margin - statusSize.x, rowY + 2 * scale}, 16 * scale, statusColor);
+    }
+    float footerY = corner.y + size.y - 60 * scale;
+    if (!message.empty()) {
+        text.draw(message, {corner.x + margin, footerY}, fontSize, RAYWHITE);
+    }
+    text.draw(tr("Enter buys or puts on a skin, coins are caught in play"), {corner.x + margin, footerY + 30 * scale}, 16 * scale, GRAY);
+}
@@ -0,0 +1,25 @@
+#pragma once
+#include "raylib.h"
+#include <string>
+#include "Profile.hpp"
+#include "Text.hpp"
+
+
+// Skins, from the pause menu. Up/down picks one, enter buys it with the
+// profile's coins or, once it's owned, puts it on. Every skin is listed, the
+// earned ones with what earns them while locked.
+class ShopScreen {
+  public:
+    bool isOpen = false;
+
+    void toggle();
+    // True when the profile changed (coins spent, a skin put on), for the
+    // caller to save.
+    bool update(Profile& profile);
+    // bounds is the UI layout's safe area, in window pixels.
+    void draw(TextRenderer& text, const Profile& profile, Rectangle bounds, float scale);
+
+  private:
+    int selected = 0;
+    std::string message;
+};
@@ -0,0 +1,924 @@
+#include "Simulation.hpp"
+#include <algorithm>
+#include <cmath>
+#include <cstdlib>
+#include "FramePacer.hpp"
+#include "Log.hpp"
+#include "Profile.hpp"
+#include "Scoring.hpp"
+
+
+// how much of a moving brick's velocity a ball bouncing off it picks up
+static const float MOVING_BRICK_CARRY = 0.5f;
+static const int RAIN_DEBRIS = 4;
+static const int SPREAD_DEBRIS = 5;
+static const float SPREAD_DEGREES = 20; // between two of a spread
+
+// Puts the ball back outside the rectangle the shortest way and sends it
+// off away from it.
+static void bounceOut(Ball& ball, Rectangle b) {
+    Rectangle r = ball.getRectangle();
+    float left = r.x + r.width - b.x;
+    float right = b.x + b.width - r.x;
+    float up = r.y + r.height - b.y;
+    float down = b.y + b.height - r.y;
+    float least = fminf(fminf(left, right), fminf(up, down));
+    Vector2 shift = {0, 0};
+    if (least == left) {
+        shift.x = -left;
+        ball.velocity.x = -fabsf(ball.velocity.x);
+    } else if (least == right) {
+        shift.x = right;
+        ball.velocity.x = fabsf(ball.velocity.x);
+    } else if (least == up) {
+        shift.y = -up;
+        ball.velocity.y = -fabsf(ball.velocity.y);
+    } else {
+        shift.y = down;
+        ball.velocity.y = fabsf(ball.velocity.y);
+    }
+    ball.position.x += shift.x;
+    ball.position.y += shift.y;
+    ball.rectangle.x += shift.x;
+    ball.rectangle.y += shift.y;
+}
+
+// A moving brick can run into the ball as well as the other way round, so
+// the ball is bounced out of it, turned some way along with it (see
+// Ball::addForce: its speed stays).
+static void bounceOffMoving(Ball& ball, Brick& brick) {
+    bounceOut(ball, brick.getRectangle());
+    ball.addForce({brick.velocity.x * MOVING_BRICK_CARRY, brick.velocity.y * MOVING_BRICK_CARRY});
+}
+
+// The boss's next attack, from under its middle; what it threw.
+static BossAttack throwDebris(GameState& state, const SimulationRules& rules) {
+    Boss& boss = state.boss;
+    BossAttack attack = boss.attacks[boss.nextAttack];
+    boss.nextAttack = (boss.nextAttack + 1) % boss.attacks.size();
+    Rectangle bounds = boss.bounds();
+    Vector2 from = {bounds.x + bounds.width / 2, bounds.y + bounds.height};
+    switch (attack) {
+    case BossAttack::Rain:
+        for (int i = 0; i < RAIN_DEBRIS; i++) {
+            state.debris.push_back({{state.random.unit() * rules.width, from.y}, {0, Debris::SPEED}});
+        }
+        break;
+    case BossAttack::Aimed: {
+        // in co-op, either paddle
+        bool partner = rules.mode == (int) GameMode::Coop && state.random.unit() < 0.5f;
+        Vector2 target = (partner ? state.partner : state.player).getCenter();
+        float dx = target.x - from.x;
+        float dy = fmaxf(target.y - from.y, 1);
+        float length = sqrtf(dx * dx + dy * dy);
+        state.debris.push_back({from, {dx / length * Debris::SPEED, dy / length * Debris::SPEED}});
+        break;
+    }
+    case BossAttack::Spread:
+        for (int i = 0; i < SPREAD_DEBRIS; i++) {
+            float angle = (90 + (i - (SPREAD_DEBRIS - 1) / 2.0f) * SPREAD_DEGREES) * DEG2RAD;
+            state.debris.push_back({from, {cosf(angle) * Debris::SPEED, sinf(angle) * Debris::SPEED}});
+        }
+        break;
+    }
+    return attack;
+}
+
+bool SimulationRules::operator==(const SimulationRules& other) const {
+    return width == other.width && height == other.height && inputMode == other.inputMode && autoLaunch == other.autoLaunch
+        && scanSpeed == other.scanSpeed && inputBufferTicks == other.inputBufferTicks && ballSpeed == other.ballSpeed
+        && paddleScale == other.paddleScale && startLives == other.startLives && invisibleBricks == other.invisibleBricks
+        && mirroredControls == other.mirroredControls && scorePercent == other.scorePercent && mode == other.mode;
+}
+
+bool SimulationRules::operator!=(const SimulationRules& other) const {
+    return !(*this == other);
+}
+
+void Simulation::reset(const std::vector<Brick>& bricks, uint64_t seed) {
+    levelBricks = bricks;
+    state = GameState();
+    state.player = initPlayer(rules.width, rules.height);
+    state.player.rectangle.width *= rules.paddleScale * physics.paddleScale;
+    state.paddleBaseWidth = state.player.rectangle.width;
+    if (rules.mode == (int) GameMode::Coop) {
+        // each in the middle of its half
+        state.partner = state.player;
+        float quarter = rules.width / 4.0f;
+        for (Player* paddle : {&state.player, &state.partner}) {
+            float shift = (paddle == &state.player ? quarter : 3 * quarter) - paddle->getCenter().x;
+            paddle->position.x += shift;
+            paddle->rectangle.x += shift;
+        }
+    }
+    state.balls = {initBall(rules.width, rules.height)};
+    state.balls[0].id = state.nextBallId++;
+    state.bricks = bricks;
+    prepareBricks(state.bricks);
+    state.boss = boss;
+    state.bricksLeft = destructibleCount(bricks) + (boss.alive() ? 1 : 0);
+    state.lives = rules.startLives;
+    state.random.seed(seed);
+    state.shiftTicks = EndlessRules::shiftSeconds(0) * FramePacer::TICK_RATE;
+    state.timeTicks = TimeAttackRules::SECONDS * FramePacer::TICK_RATE;
+    state.launchesLeft = launchBudget;
+}
+
+void Simulation::reloadBricks(const std::vector<Brick>& bricks) {
+    std::vector<Brick> reloaded;
+    for (const Brick& brick : bricks) {
+        auto sameSpot = [&](const Brick& other) {
+            return other.origin.x == brick.origin.x && other.origin.y == brick.origin.y && other.material == brick.material;
+        };
+        auto live = std::find_if(state.bricks.begin(), state.bricks.end(), sameSpot);
+        if (live == state.bricks.end()) {
+            if (std::none_of(levelBricks.begin(), levelBricks.end(), sameSpot)) {
+                std::vector<Brick> added = {brick};
+                prepareBricks(added);
+                reloaded.push_back(added[0]);
+            }
+            continue;
+        }
+        Brick kept = brick;
+        kept.hitPoints = std::clamp(brick.maxHitPoints - live->damage(), 1, brick.maxHitPoints);
+        kept.phased = live->phased;
+        kept.hidden = live->hidden;
+        if (kept.path.moves()) {
+            kept.move(live->pathTime);
+        }
+        reloaded.push_back(kept);
+    }
+    levelBricks = bricks;
+    state.bricks = reloaded;
+    state.bricksLeft = destructibleCount(state.bricks) + (state.boss.alive() ? 1 : 0);
+    state.cheated = true;
+}
+
+void Simulation::prepareBricks(std::vector<Brick>& fresh) const {
+    for (Brick& brick : fresh) {
+        brick.hidden = rules.invisibleBricks;
+    }
+}
+
+bool controlsReversed(const GameState& state, const SimulationRules& rules) {
+    return state.effects.has(PowerUpType::ReverseControls) != rules.mirroredControls;
+}
+
+float Simulation::ballSpeed() const {
+    float speed = rules.ballSpeed * physics.ballSpeed;
+    if (rules.mode != (int) GameMode::Survival) {
+        return speed;
+    }
+    return speed * survivalCurve().at(state.survivalStage).ballSpeed;
+}
+
+void Simulation::step(const InputFrame& input, EventBus* events, const InputFrame& partnerInput) {
+    LogSpan span("SIM", "tick", LOG_TRACE);
+    auto publish = [&](EventType type, Vector2 position, Vector2 velocity, int value) {
+        if (events) {
+            events->publish(type, position, velocity, value);
+        }
+    };
+    Player& player = state.player;
+    Player& partner = state.partner;
+    std::vector<Ball>& balls = state.balls;
+    std::vector<Brick>& bricks = state.bricks;
+    Boss& boss = state.boss;
+    bool endless = rules.mode == (int) GameMode::Endless;
+    bool timeAttack = rules.mode == (int) GameMode::TimeAttack;
+    bool puzzle = launchBudget > 0;
+    bool survival = rules.mode == (int) GameMode::Survival;
+    bool coop = rules.mode == (int) GameMode::Coop;
+    // co-op's second paddle joins in everywhere the first one counts, on
+    // the right half of the field
+    std::vector<Player*> paddles = {&player};
+    if (coop) {
+        paddles.push_back(&partner);
+    }
+    auto paddleOf = [&](int owner) -> Player& { return *paddles[std::min(owner, (int) paddles.size() - 1)]; };
+    auto laneOf = [&](size_t index, float& low, float& high) {
+        low = coop && index == 1 ? rules.width / 2.0f : 0;
+        high = coop && index == 0 ? rules.width / 2.0f : rules.width;
+    };
+
+    // Borders
+    Rectangle top = {0, 0, (float) rules.width, 1};
+    Rectangle bottom = {0, (float) rules.height - 1, (float) rules.width, 1};
+    Rectangle left = {0, 0, 1, (float) rules.height};
+    Rectangle right = {(float) rules.width - 1, 0, 1, (float) rules.height};
+    float paddleHalf = player.rectangle.width / 2;
+
+    state.tick++;
+    state.cheated = state.cheated || cheats.any();
+    InputBuffer& buffer = state.inputBuffers[0];
+    InputBuffer& partnerBuffer = state.inputBuffers[1];
+    buffer.setWindow(rules.inputBufferTicks);
+    buffer.update(input);
+    partnerBuffer.setWindow(rules.inputBufferTicks);
+    partnerBuffer.update(partnerInput);
+    // with scanning and auto-launch together the one switch only ever steers
+    bool scanning = rules.inputMode == (int) InputMode::SwitchScanning;
+    bool serve = state.ballHeld && !(scanning && rules.autoLaunch) && buffer.consume(Action::Launch);
+    if (scanning) {
+        bool reverse = input.wasPressed(Action::Launch) && !serve;
+        if (reverse) {
+            // a steering press must not serve the next ball from the buffer
+            buffer.consume(Action::Launch);
+        }
+        float low, high;
+        laneOf(0, low, high);
+        player.scan(2 + 8 * rules.scanSpeed, reverse, low + paddleHalf, high - paddleHalf);
+    } else {
+        // a switch only ever picks when to turn, so reversed controls leave
+        // scanning alone
+        player.checkInput(controlsReversed(state, rules) ? input.mirrored(rules.width) : input);
+    }
+    player.update();
+    bool partnerServe = false;
+    if (coop) {
+        partnerServe = state.ballHeld && partnerBuffer.consume(Action::Launch);
+        partner.checkInput(controlsReversed(state, rules) ? partnerInput.mirrored(rules.width) : partnerInput);
+        partner.update();
+    }
+    std::vector<PowerUpType> expired;
+    state.effects.tick(expired);
+    for (PowerUpType type : expired) {
+        if (type == PowerUpType::Fireball) {
+            for (Ball& ball : balls) {
+                ball.piercing = false;
+            }
+        }
+    }
+    // the two never run at once (see EffectManager::opposite)
+    float targetScale = state.effects.has(PowerUpType::SlowMotion) ? SLOW_MOTION_SCALE
+        : state.effects.has(PowerUpType::SpeedUp)                 ? SPEED_UP_SCALE
+                                                                  : 1;
+    float easeStep = (1 - SLOW_MOTION_SCALE) / (TIME_SCALE_EASE_SECONDS * FramePacer::TICK_RATE);
+    state.timeScale = state.timeScale < targetScale ? fminf(state.timeScale + easeStep, targetScale) : fmaxf(state.timeScale - easeStep, targetScale);
+    // grow and shrink ease the paddle to its new width instead of snapping
+    float targetWidth = paddleTargetWidth(state);
+    for (size_t i = 0; i < paddles.size(); i++) {
+        Player& resized = *paddles[i];
+        if (resized.rectangle.width == targetWidth) {
+            continue;
+        }
+        float rate = state.paddleBaseWidth * PADDLE_STEP / (PADDLE_RESIZE_SECONDS * FramePacer::TICK_RATE);
+        float width = resized.rectangle.width;
+        resized.setWidth(width < targetWidth ? fminf(width + rate, targetWidth) : fmaxf(width - rate, targetWidth));
+        // growing against a wall (or the middle, in co-op) pushes away from it
+        float low, high;
+        laneOf(i, low, high);
+        Rectangle paddle = resized.getRectangle();
+        float shift = fmaxf(0, low - paddle.x) - fmaxf(0, paddle.x + paddle.width - high);
+        resized.position.x += shift;
+        resized.rectangle.x += shift;
+    }
+    if (state.ballHeld) {
+        // balls ride on the paddle until they are launched, each by the
+        // player whose paddle it's on
+        bool autoLaunch = rules.autoLaunch && state.heldTicks + 1 >= FramePacer::TICK_RATE;
+        bool stillHeld = false;
+        for (Ball& ball : balls) {
+            if (ball.isFree()) {
+                continue;
+            }
+            Player& holder = paddleOf(ball.owner);
+            Rectangle paddle = holder.getRectangle();
+            ball.position = {holder.getCenter().x + ball.paddleOffset - ball.rectangle.width, paddle.y - 1 - ball.rectangle.height * 1.5f};
+            ball.velocity = {0, 0};
+            if (autoLaunch || (ball.owner == 1 && coop ? partnerServe : serve)) {
+                float speed = ballSpeed();
+                // a serve, not a ball the catch power-up let go, uses up one of a puzzle's
+                if (puzzle && ball.state == BallState::Serving) {
+                    state.launchesLeft = std::max(state.launchesLeft - 1, 0);
+                }
+                ball.velocity = ball.state == BallState::Serving ? Vector2{2 * speed, -2 * speed}
+                                                                 : catchLaunchVelocity(ball.paddleOffset, paddle.width / 2, speed);
+                ball.state = BallState::Free;
+            } else {
+                stillHeld = true;
+            }
+        }
+        state.heldTicks++;
+        if (!stillHeld) {
+            state.ballHeld = false;
+            state.heldTicks = 0;
+        }
+    }
+
+    // External forces, gathered before anything moves
+    if (state.effects.has(PowerUpType::Magnet)) {
+        for (Ball& ball : balls) {
+            // only on the way down, towards the paddle
+            if (ball.isFree() && ball.velocity.y > 0) {
+                ball.addForce({(paddleOf(ball.owner).getCenter().x - ball.getCenter().x) * MAGNET_PULL, 0});
+            }
+        }
+    }
+    for (Ball& ball : balls) {
+        if (ball.isFree()) {
+            ball.addForce(wellForce(bricks, ball.getCenter()));
+        }
+    }
+
+    for (size_t b = 0; b < balls.size();) {
+        Ball& ball = balls[b];
+        ball.advance(state.timeScale);
+        if (!ball.isFree()) {
+            b++;
+            continue;
+        }
+        int hitPaddle = -1;
+        for (size_t i = 0; i < paddles.size() && hitPaddle < 0; i++) {
+            hitPaddle = CheckCollisionRecs(ball.getRectangle(), paddles[i]->getRectangle()) ? (int) i : -1;
+        }
+
+        if (state.barrier.up && ball.velocity.y > 0 && CheckCollisionRecs(ball.getRectangle(), state.barrier.getRectangle(rules.width, rules.height))) {
+            ballBounce(&ball, 'u');
+            state.barrier.up = false;
+            publish(EventType::BarrierHit, ball.getCenter(), ball.velocity, 0);
+        } else if (CheckCollisionRecs(ball.getRectangle(), bottom) && cheats.invincibleBall) {
+            ballBounce(&ball, 'u');
+            publish(EventType::WallBounce, ball.getCenter(), ball.velocity, 0);
+        } else if (CheckCollisionRecs(ball.getRectangle(), bottom) && balls.size() > 1) {
+            // one of several: the rest play on
+            publish(EventType::ExtraBallLost, ball.getCenter(), ball.velocity, (int) balls.size() - 1);
+            balls.erase(balls.begin() + b);
+            continue;
+        } else if (CheckCollisionRecs(ball.getRectangle(), bottom)) {
+            publish(EventType::BallLost, ball.getCenter(), ball.velocity, 0);
+            if (timeAttack) {
+                // against the clock it costs time rather than a life
+                state.timeTicks = std::max(state.timeTicks - TimeAttackRules::LOST_BALL_SECONDS * FramePacer::TICK_RATE, 0);
+            } else if (puzzle) {
+                // the serve it took is gone; with none left so is the run
+                if (state.launchesLeft <= 0 && !cheats.infiniteLives) {
+                    state.lives = 0;
+                    publish(EventType::LaunchesSpent, ball.getCenter(), {0, 0}, 0);
+                }
+            } else {
+                // practice is for trying again
+                state.lives -= cheats.infiniteLives || rules.mode == (int) GameMode::Practice ? 0 : 1;
+            }
+            state.combo = 0;
+            // served again by whoever lost it
+            int owner = ball.owner;
+            ball = initBall(rules.width, rules.height);
+            ball.id = state.nextBallId++;
+            ball.owner = owner;
+            state.ballHeld = true;
+            // power-ups go with the life
+            state.drops.clear();
+            state.projectiles.clear();
+            state.debris.clear();
+            state.effects.clear();
+            buffer.clear();
+            partnerBuffer.clear();
+        } else if (CheckCollisionRecs(ball.getRectangle(), top)) {
+            ballBounce(&ball, 't');
+            publish(EventType::WallBounce, ball.getCenter(), ball.velocity, 0);
+        } else if (CheckCollisionRecs(ball.getRectangle(), left)) {
+            ballBounce(&ball, 'l');
+            publish(EventType::WallBounce, ball.getCenter(), ball.velocity, 0);
+        } else if (CheckCollisionRecs(ball.getRectangle(), right)) {
+            ballBounce(&ball, 'r');
+            publish(EventType::WallBounce, ball.getCenter(), ball.velocity, 0);
+        } else if (hitPaddle >= 0) {
+            ballBounce(&ball, 'u');
+            ball.owner = hitPaddle;
+            Rectangle paddle = paddles[hitPaddle]->getRectangle();
+            float offset = (ball.getCenter().x - (paddle.x + paddle.width / 2)) / (paddle.width / 2);
+            publish(EventType::PaddleBounce, ball.getCenter(), ball.velocity, (int) roundf(fmaxf(-1, fminf(offset, 1)) * 100));
+            // within the middle tenth of the paddle counts as a perfect catch
+            if (fabsf(ball.getCenter().x - (paddle.x + paddle.width / 2)) < paddle.width * 0.05f) {
+                publish(EventType::PerfectCatch, ball.getCenter(), ball.velocity, 0);
+            }
+            state.combo = 0;
+            if (state.effects.has(PowerUpType::Catch)) {
+                // stays where it landed, on top of the paddle
+                ball.state = BallState::Caught;
+                ball.paddleOffset = fmaxf(-paddle.width / 2, fminf(ball.getCenter().x - (paddle.x + paddle.width / 2), paddle.width / 2));
+                ball.velocity = {0, 0};
+                state.ballHeld = true;
+                publish(EventType::BallCaught, ball.getCenter(), ball.velocity, 0);
+            }
+        }
+        b++;
+    }
+
+    for (size_t i = 0; i < paddles.size(); i++) {
+        float low, high;
+        laneOf(i, low, high);
+        Rectangle laneLeft = {low, 0, 1, (float) rules.height};
+        Rectangle laneRight = {high - 1, 0, 1, (float) rules.height};
+        if (CheckCollisionRecs(paddles[i]->getRectangle(), laneLeft)) {
+            paddles[i]->preventLeft();
+        } else if (paddles[i]->checkCollision(laneRight)) {
+            paddles[i]->preventRight();
+        }
+        if (coop) {
+            // the middle is a hard line: the two paddles never overlap
+            Rectangle paddle = paddles[i]->getRectangle();
+            float shift = i == 0 ? fminf(0, high - (paddle.x + paddle.width)) : fmaxf(0, low - paddle.x);
+            paddles[i]->position.x += shift;
+            paddles[i]->rectangle.x += shift;
+        }
+    }
+    for (Brick& brick : bricks) {
+        brick.animation.update(FramePacer::TICK_SECONDS * state.timeScale);
+        if (brick.path.moves()) {
+            brick.move(FramePacer::TICK_SECONDS * state.timeScale);
+        }
+    }
+    // The mutators' multiplier goes on every point, what it rounds off
+    // carried over to the next ones
+    auto scaled = [&](int points) {
+        int hundredths = points * rules.scorePercent + state.scoreCarry;
+        state.scoreCarry = hundredths % 100;
+        return hundredths / 100;
+    };
+    // Points go to the scorer of the ball or bolt doing the hitting, in
+    // co-op's playerScores as well as the shared score
+    int scorer = 0;
+    auto award = [&](int points, Vector2 at, Vector2 velocity) {
+        points = scaled(points);
+        state.score += points;
+        if (coop) {
+            state.playerScores[scorer] += points;
+        }
+        publish(EventType::ScoreAwarded, at, velocity, points);
+    };
+    // Every hit on a brick scores, whatever hit it, and the last breaks it
+    // for the combo's multiple of a break; a fireball takes it whole, the
+    // hits it skipped scoring too. Only breaking one with a ball builds a
+    // combo. Metal takes no hits, but a metal switch still switches, as
+    // often as it's hit.
+    auto hitBrick = [&](int i, Vector2 velocity, bool byBall, bool whole) {
+        Vector2 brickCenter = bricks[i].getCenter();
+        bricks[i].hidden = false;
+        if (!bricks[i].switchGroup.empty()) {
+            int solid = 0;
+            for (Brick& brick : bricks) {
+                if (brick.group == bricks[i].switchGroup) {
+                    brick.phased = !brick.phased;
+                    solid += brick.phased ? 0 : 1;
+                }
+            }
+            publish(EventType::BricksSwitched, brickCenter, velocity, solid);
+        }
+        if (!bricks[i].isDestructible()) {
+            publish(EventType::BrickHit, brickCenter, velocity, -1);
+            return;
+        }
+        int hits = whole ? bricks[i].hitPoints : 1;
+        bricks[i].hitPoints -= hits;
+        if (bricks[i].hitPoints > 0) {
+            publish(EventType::BrickHit, brickCenter, velocity, bricks[i].hitPoints);
+            award(pointsFor(EventType::BrickHit), brickCenter, velocity);
+            return;
+        }
+        if (events && onBrickBroken) {
+            onBrickBroken(bricks[i]);
+        }
+        state.bricksLeft--;
+        state.combo += byBall ? 1 : 0;
+        state.comboTicks = 0;
+        publish(EventType::BrickDestroyed, brickCenter, velocity, state.combo);
+        int coins = bricks[i].coins;
+        int timeBonus = timeAttack ? bricks[i].timeBonus : 0;
+        // delete brick; the last one stays for the clear, unless the boss
+        // is still to beat or more are coming, when it would only be in the way
+        if (bricks.size() > 1 || boss.alive() || endless || survival) {
+            // bricks.erase(bricks[i]);
+            bricks.erase(bricks.begin() + i);
+        }
+        award(pointsFor(EventType::BrickHit) * (hits - 1) + pointsFor(EventType::BrickDestroyed) * comboMultiplier(state.combo), brickCenter, velocity);
+        if (byBall && state.combo > 1) {
+            publish(EventType::ComboIncreased, brickCenter, velocity, state.combo);
+        }
+        Pickup drop;
+        if (dropTable.roll(state, brickCenter, drop)) {
+            state.drops.push_back(drop);
+            publish(EventType::PowerUpSpawned, brickCenter, {0, Pickup::SPEED}, (int) drop.powerUp);
+        }
+        // a brick's coins fall side by side
+        for (int c = 0; c < coins; c++) {
+            float x = brickCenter.x + (c - (coins - 1) / 2.0f) * Pickup::COIN_SIZE * 1.5f;
+            state.drops.push_back(Pickup::coinAt({x, brickCenter.y}));
+        }
+        if (timeBonus > 0) {
+            state.drops.push_back(Pickup::timeAt(brickCenter, timeBonus));
+        }
+        // survival's debuffs come on top of the level's drops
+        float debuffChance = survival ? survivalCurve().at(state.survivalStage).debuffChance : 0;
+        if (debuffChance > 0 && state.random.unit() < debuffChance) {
+            auto allowed = [&](PowerUpType candidate) {
+                const PowerUp& powerUp = powerUpInfo(candidate);
+                return powerUp.harmful && (!powerUp.canDrop || powerUp.canDrop(state));
+            };
+            PowerUpType type;
+            if (dropTable.pick(state.random.unit(), -1, allowed, type)) {
+                state.drops.push_back(Pickup::powerUpAt(type, brickCenter));
+                publish(EventType::PowerUpSpawned, brickCenter, {0, Pickup::SPEED}, (int) type);
+            }
+        }
+    };
+    // Check collision between bricks, one brick per ball per tick; a fireball
+    // breaks every brick it overlaps and keeps going
+    for (Ball& ball : balls) {
+        scorer = ball.owner;
+        for (int i = 0; i < bricks.size() && ball.isFree(); i++) {
+            if (bricks[i].phased || !CheckCollisionRecs(ball.getRectangle(), bricks[i].getRectangle())) {
+                continue;
+            }
+            if (ball.piercing && bricks[i].isDestructible()) {
+                size_t before = bricks.size();
+                hitBrick(i, ball.velocity, true, true);
+                // the next brick moved into this slot
+                i -= bricks.size() < before ? 1 : 0;
+                continue;
+            }
+            // check if below
+            if (ball.position.y > bricks[i].position.y + bricks[i].rectangle.height / 2) {
+                ballBounce(&ball, 't');
+            }
+            // check if above
+            if (ball.position.y < bricks[i].position.y - bricks[i].rectangle.height / 2) {
+                ballBounce(&ball, 'u');
+            }
+            // check if left
+            if (ball.position.x < bricks[i].position.x - bricks[i].rectangle.width / 2) {
+                ballBounce(&ball, 'l');
+            }
+            // chick if right
+            if (ball.position.x > bricks[i].position.x + bricks[i].rectangle.width / 2) {
+                ballBounce(&ball, 'r');
+            }
+            if (bricks[i].path.moves()) {
+                bounceOffMoving(ball, bricks[i]);
+            }
+            hitBrick(i, ball.velocity, true, false);
+            break;
+        }
+    }
+
+    // a combo that goes too long without a break loses a step, while it has one to lose
+    if (comboMultiplier(state.combo) > 1 && ++state.comboTicks >= ComboRules::DECAY_SECONDS * FramePacer::TICK_RATE) {
+        state.combo = std::max(0, state.combo - ComboRules::STEP);
+        state.comboTicks = 0;
+        publish(EventType::ComboDecayed, player.getCenter(), {0, 0}, state.combo);
+    }
+
+    // The boss: only its weak point takes hits, one at a time, a fireball's
+    // too; the rest of it is armor. It holds its attacks while a ball waits
+    // to be served.
+    auto hitBoss = [&](size_t s, Vector2 velocity) {
+        Rectangle rect = boss.segmentRect(boss.segments[s]);
+        Vector2 center = {rect.x + rect.width / 2, rect.y + rect.height / 2};
+        if ((int) s != boss.weakPoint()) {
+            publish(EventType::BossHit, center, velocity, -1);
+            return;
+        }
+        boss.segments[s].hitPoints--;
+        boss.health--;
+        publish(EventType::BossHit, center, velocity, boss.health);
+        award(pointsFor(EventType::BossHit), center, velocity);
+        if (!boss.alive()) {
+            state.bricksLeft--;
+            state.debris.clear();
+            publish(EventType::BossDefeated, center, velocity, boss.maxHealth);
+            award(pointsFor(EventType::BossDefeated), center, velocity);
+        }
+    };
+    if (boss.alive()) {
+        float seconds = FramePacer::TICK_SECONDS * state.timeScale;
+        boss.move(seconds);
+        if (!state.ballHeld && !boss.attacks.empty()) {
+            boss.attackTimer -= seconds;
+            if (boss.attackTimer <= 0) {
+                boss.attackTimer += boss.attackPeriod;
+                Rectangle bounds = boss.bounds();
+                BossAttack attack = throwDebris(state, rules);
+                publish(EventType::BossAttacked, {bounds.x + bounds.width / 2, bounds.y + bounds.height}, {0, Debris::SPEED}, (int) attack);
+            }
+        }
+        for (Ball& ball : balls) {
+            scorer = ball.owner;
+            for (size_t s = 0; s < boss.segments.size() && ball.isFree() && boss.alive(); s++) {
+                Rectangle rect = boss.segmentRect(boss.segments[s]);
+                if (CheckCollisionRecs(ball.getRectangle(), rect)) {
+                    bounceOut(ball, rect);
+                    hitBoss(s, ball.velocity);
+                    break;
+                }
+            }
+        }
+    }
+
+    // Laser: a pair of bolts from the paddle's ends per press; in co-op
+    // either player fires from their own, the cooldown shared
+    if (state.effects.has(PowerUpType::Laser)) {
+        state.laserCooldown -= state.laserCooldown > 0 ? 1 : 0;
+        for (size_t shooter = 0; shooter < paddles.size() && state.laserCooldown == 0; shooter++) {
+            bool fire = state.inputBuffers[shooter].consume(Action::ActivatePower);
+            if (!fire) {
+                continue;
+            }
+            Rectangle paddle = paddles[shooter]->getRectangle();
+            for (float x : {paddle.x + LASER_INSET, paddle.x + paddle.width - LASER_INSET}) {
+                state.projectiles.push_back(Projectile({x - Projectile::WIDTH, paddle.y - Projectile::HEIGHT}));
+                state.projectiles.back().owner = (int) shooter;
+            }
+            state.laserCooldown = LASER_COOLDOWN_TICKS;
+            publish(EventType::LaserFired, {paddle.x + paddle.width / 2, paddle.y}, {0, -Projectile::SPEED}, 0);
+        }
+    }
+    for (size_t p = 0; p < state.projectiles.size();) {
+        Projectile& projectile = state.projectiles[p];
+        projectile.update();
+        scorer = projectile.owner;
+        bool spent = projectile.getRectangle().y + Projectile::HEIGHT < 0;
+        for (int i = 0; i < bricks.size() && !spent; i++) {
+            if (!bricks[i].phased && CheckCollisionRecs(projectile.getRectangle(), bricks[i].getRectangle())) {
+                hitBrick(i, projectile.velocity, false, false);
+                spent = true;
+            }
+        }
+        for (size_t s = 0; s < boss.segments.size() && !spent && boss.alive(); s++) {
+            if (CheckCollisionRecs(projectile.getRectangle(), boss.segmentRect(boss.segments[s]))) {
+                hitBoss(s, projectile.velocity);
+                spent = true;
+            }
+        }
+        if (spent) {
+            state.projectiles.erase(state.projectiles.begin() + p);
+        } else {
+            p++;
+        }
+    }
+
+    auto onPaddle = [&](Rectangle rect) {
+        return std::any_of(paddles.begin(), paddles.end(), [&](Player* paddle) { return CheckCollisionRecs(rect, paddle->getRectangle()); });
+    };
+    // Falling pickups: caught by a paddle or gone off the bottom
+    for (size_t i = 0; i < state.drops.size();) {
+        Pickup& drop = state.drops[i];
+        drop.position.y += Pickup::SPEED * state.timeScale;
+        if (onPaddle(drop.getRectangle())) {
+            Pickup caught = drop;
+            state.drops.erase(state.drops.begin() + i);
+            if (caught.kind == PickupKind::Coin) {
+                state.coins++;
+                publish(EventType::CoinCollected, caught.position, {0, 0}, state.coins);
+            } else if (caught.kind == PickupKind::Time) {
+                state.timeTicks += caught.seconds * FramePacer::TICK_RATE;
+                state.timeCollected += caught.seconds;
+                publish(EventType::TimeCollected, caught.position, {0, 0}, caught.seconds);
+            } else {
+                powerUpInfo(caught.powerUp).apply(state, rules);
+                publish(EventType::PowerUpCollected, caught.position, {0, 0}, (int) caught.powerUp);
+            }
+        } else if (drop.getRectangle().y > rules.height) {
+            state.drops.erase(state.drops.begin() + i);
+        } else {
+            i++;
+        }
+    }
+
+    // Endless: the field moves down a row on its timer, or straight away
+    // once it's empty, with a new row at the top; bricks at the paddle's
+    // line end the run
+    if (endless && !state.ballHeld) {
+        state.shiftTicks -= 1;
+    }
+    if (endless && (state.shiftTicks <= 0 || state.bricksLeft <= 0)) {
+        shiftField(bricks, EndlessRules::ROW_STEP);
+        std::vector<Brick> row = generateRow(state.rowsAdded++, state.random);
+        prepareBricks(row);
+        bricks.insert(bricks.end(), row.begin(), row.end());
+        state.bricksLeft += destructibleCount(row);
+        state.shiftTicks = EndlessRules::shiftSeconds(state.rowsAdded) * FramePacer::TICK_RATE;
+        publish(EventType::FieldShifted, {rules.width / 2.0f, (float) EndlessRules::ROW_TOP}, {0, 0}, state.rowsAdded);
+        if (fieldBottom(bricks) >= player.getRectangle().y) {
+            state.lives = 0;
+            publish(EventType::FieldOverran, player.getCenter(), {0, 0}, state.rowsAdded);
+        }
+    }
+
+    // Time attack: the clock runs while the ball is in play. Clearing the
+    // level turns what's left of it into points; running out ends the run
+    if (timeAttack && !state.ballHeld && state.timeTicks > 0 && state.bricksLeft > 0) {
+        state.timeTicks -= 1;
+    }
+    if (timeAttack && state.bricksLeft <= 0 && state.timeTicks > 0) {
+        int points = scaled(state.timeTicks / FramePacer::TICK_RATE * TimeAttackRules::POINTS_PER_SECOND_LEFT);
+        state.score += points;
+        state.timeTicks = 0;
+        publish(EventType::ScoreAwarded, player.getCenter(), {0, 0}, points);
+    } else if (timeAttack && state.timeTicks <= 0 && state.bricksLeft > 0 && state.lives > 0) {
+        state.lives = 0;
+        publish(EventType::TimeUp, player.getCenter(), {0, 0}, 0);
+    }
+
+    // Puzzle: the serves left over score once the last brick breaks
+    if (puzzle && state.bricksLeft <= 0 && state.launchesLeft > 0 && state.spareBonus == 0) {
+        state.spareBonus = scaled(state.launchesLeft * PuzzleRules::POINTS_PER_LAUNCH_LEFT);
+        state.score += state.spareBonus;
+        publish(EventType::ScoreAwarded, player.getCenter(), {0, 0}, state.spareBonus);
+    }
+
+    // Survival: every stage speeds the balls up and narrows the paddle, and
+    // a cleared field comes back as the level started
+    if (survival && !state.ballHeld) {
+        state.survivedTicks++;
+        int stage = survivalCurve().stageAt(state.survivedTicks);
+        if (stage != state.survivalStage) {
+            DifficultyStage before = survivalCurve().at(state.survivalStage);
+            DifficultyStage after = survivalCurve().at(stage);
+            state.survivalStage = stage;
+            float faster = after.ballSpeed / before.ballSpeed;
+            for (Ball& ball : balls) {
+                ball.velocity = {ball.velocity.x * faster, ball.velocity.y * faster};
+            }
+            // the paddle eases to its new width like it does for a shrink
+            state.paddleBaseWidth *= after.paddleScale / before.paddleScale;
+            publish(EventType::DifficultyRaised, player.getCenter(), {0, 0}, stage);
+        }
+    }
+    if (survival && state.bricksLeft <= 0) {
+        bricks = levelBricks;
+        prepareBricks(bricks);
+        boss = this->boss;
+        state.bricksLeft = destructibleCount(bricks) + (boss.alive() ? 1 : 0);
+        publish(EventType::FieldRefilled, {rules.width / 2.0f, rules.height / 4.0f}, {0, 0}, state.survivalStage);
+    }
+
+    // Debris: landing on the paddle shrinks it, the way catching a shrink does
+    for (size_t i = 0; i < state.debris.size();) {
+        Debris& debris = state.debris[i];
+        debris.position.x += debris.velocity.x * state.timeScale;
+        debris.position.y += debris.velocity.y * state.timeScale;
+        Rectangle rect = debris.getRectangle();
+        if (onPaddle(rect)) {
+            Vector2 at = debris.position;
+            state.debris.erase(state.debris.begin() + i);
+            powerUpInfo(PowerUpType::Shrink).apply(state, rules);
+            publish(EventType::PaddleDamaged, at, {0, 0}, 0);
+        } else if (rect.y > rules.height || rect.x + rect.width < 0 || rect.x > rules.width) {
+            state.debris.erase(state.debris.begin() + i);
+        } else {
+            i++;
+        }
+    }
+}
+
+// FNV-1a over the raw bytes; floats hash by bit pattern, which is exactly what
+// has to match.
+struct StateHash {
+    uint64_t value = 14695981039346656037ull;
+
+    void bytes(const void* data, size_t size) {
+        const uint8_t* bytes = (const uint8_t*) data;
+        for (size_t i = 0; i < size; i++) {
+            value = (value ^ bytes[i]) * 1099511628211ull;
+        }
+    }
+
+    template <typename T>
+    void add(T field) {
+        bytes(&field, sizeof(field));
+    }
+
+    void add(Vector2 vector) {
+        add(vector.x);
+        add(vector.y);
+    }
+};
+
+uint64_t Simulation::checksum() const {
+    StateHash hash;
+    hash.add(state.tick);
+    hash.add(state.player.position);
+    hash.add(state.player.velocity);
+    hash.add(state.player.acceleration);
+    hash.add(state.partner.position);
+    hash.add(state.partner.velocity);
+    hash.add(state.partner.rectangle.width);
+    hash.add(state.balls.size());
+    for (const Ball& ball : state.balls) {
+        hash.add(ball.id);
+        hash.add(ball.position);
+        hash.add(ball.velocity);
+        hash.add(ball.state);
+        hash.add(ball.paddleOffset);
+        hash.add(ball.piercing);
+    }
+    hash.add(state.nextBallId);
+    hash.add(state.bricks.size());
+    hash.add(state.bricksLeft);
+    for (const Brick& brick : state.bricks) {
+        hash.add(brick.position);
+        hash.add(brick.hitPoints);
+        hash.add(brick.phased);
+        hash.add(brick.hidden);
+    }
+    hash.add(state.lastDrop);
+    hash.add(state.drops.size());
+    for (const Pickup& drop : state.drops) {
+        hash.add(drop.kind);
+        hash.add(drop.powerUp);
+        hash.add(drop.position);
+        hash.add(drop.seconds);
+    }
+    hash.add(state.coins);
+    hash.add(state.projectiles.size());
+    for (const Projectile& projectile : state.projectiles) {
+        hash.add(projectile.position);
+    }
+    if (state.boss.present()) {
+        hash.add(state.boss.position);
+        hash.add(state.boss.health);
+        hash.add(state.boss.attackTimer);
+        hash.add(state.boss.nextAttack);
+    }
+    hash.add(state.shiftTicks);
+    hash.add(state.rowsAdded);
+    hash.add(state.timeTicks);
+    hash.add(state.timeCollected);
+    hash.add(state.survivedTicks);
+    hash.add(state.survivalStage);
+    hash.add(state.launchesLeft);
+    hash.add(state.spareBonus);
+    hash.add(state.debris.size());
+    for (const Debris& debris : state.debris) {
+        hash.add(debris.position);
+    }
+    hash.add(state.effects.active().size());
+    for (const ActiveEffect& effect : state.effects.active()) {
+        hash.add(effect.type);
+        hash.add(effect.ticks);
+        hash.add(effect.stacks);
+    }
+    hash.add(state.player.rectangle.width);
+    hash.add(state.barrier.up);
+    hash.add(state.timeScale);
+    hash.add(state.laserCooldown);
+    hash.add(state.lives);
+    hash.add(state.score);
+    hash.add(state.scoreCarry);
+    hash.add(state.playerScores[0]);
+    hash.add(state.playerScores[1]);
+    hash.add(state.combo);
+    hash.add(state.comboTicks);
+    hash.add(state.ballHeld);
+    hash.add(state.heldTicks);
+    for (const InputBuffer& buffer : state.inputBuffers) {
+        for (int i = 0; i < ACTION_COUNT; i++) {
+            hash.add(buffer.isBuffered((Action) i));
+        }
+    }
+    hash.add(state.random.getState());
+    return hash.value;
+}
+
+Ball initBall(int screenWidth, int screenHeight) {
+    Vector2 startPosition;
+    startPosition.x = screenWidth / 2;
+    startPosition.y = screenHeight / 2;
+    Ball entity(startPosition, 10, 10);
+    entity.state = BallState::Serving;
+
+    float randx = rand() * -2;
+    float randy = rand();
+    Vector2 startVelocity = {2, 2};
+    entity.velocity = startVelocity;
+    return entity;
+}
+
+Player initPlayer(int screenWidth, int screenHeight) {
+    Vector2 startPosition;
+    startPosition.x = screenWidth / 2.0;
+    startPosition.y = (screenHeight - 50 );
+
+    Player player(startPosition, 100, 20);
+    player.init();
+    return player;
+}
+
+void ballBounce(Entity* entity, char direction) {
+    switch (direction) {
+    case 't':
+        entity->velocity.y *= -1;
+        break;
+
+    case 'l':
+        entity->velocity.x *= -1;
+        break;
+
+    case 'r':
+        entity->velocity.x *= -1;
+        break;
+    default:
+        entity->velocity.y = -1 * abs(entity->velocity.y);
+    }
+}
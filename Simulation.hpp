@@ -0,0 +1,165 @@
+#pragma once
+#include "raylib.h"
+#include <cstdint>
+#include <functional>
+#include <vector>
+#include "Ball.hpp"
+#include "Barrier.hpp"
+#include "Boss.hpp"
+#include "Brick.hpp"
+#include "DropTable.hpp"
+#include "EffectManager.hpp"
+#include "Endless.hpp"
+#include "Events.hpp"
+#include "GameMode.hpp"
+#include "InputBuffer.hpp"
+#include "InputRecord.hpp"
+#include "Level.hpp"
+#include "Pickup.hpp"
+#include "Player.hpp"
+#include "PowerUp.hpp"
+#include "Projectile.hpp"
+#include "Puzzle.hpp"
+#include "Survival.hpp"
+#include "TimeAttack.hpp"
+#include "Random.hpp"
+
+
+// The options that change what a tick does. They come from the profile and
+// settings during play and from the file during a replay.
+struct SimulationRules {
+    int width = 1280;
+    int height = 720;
+    int inputMode = 0; // InputMode
+    bool autoLaunch = false;
+    float scanSpeed = 0.5f;
+    int inputBufferTicks = 0;
+    // set by mutators (see Mutators.hpp), which replays carry instead of these
+    float ballSpeed = 1;
+    float paddleScale = 1;
+    int startLives = 3;
+    bool invisibleBricks = false;  // every brick shows up at its first hit
+    bool mirroredControls = false; // as if ReverseControls never ran out
+    int scorePercent = 100;        // of every point scored
+    int mode = 0; // GameMode; replays carry its id, like the mutators
+
+    bool operator==(const SimulationRules& other) const;
+    bool operator!=(const SimulationRules& other) const;
+};
+
+// Developer cheats (see DevTools). Not rules: they are never recorded, and a
+// run that used one is marked as cheated instead.
+struct SimulationCheats {
+    bool invincibleBall = false; // the bottom edge bounces the ball like a wall
+    bool infiniteLives = false;
+
+    bool any() const { return invincibleBall || infiniteLives; }
+};
+
+// Everything a tick reads and writes. Plain values all the way down, so a copy
+// is a complete snapshot (replays seek by restoring one).
+struct GameState {
+    Player player{{0, 0}, 100, 20};
+    Player partner{{0, 0}, 100, 20}; // co-op's second paddle; each keeps to its half
+    std::vector<Ball> balls; // never empty; balls[0] is the one held on the paddle
+    uint32_t nextBallId = 1;
+    std::vector<Brick> bricks;
+    // destructible ones still to break, and the boss while it stands; the
+    // level is cleared at 0 (the last brick is never taken out of bricks,
+    // and a brick's points no longer add up to one)
+    int bricksLeft = 0;
+    std::vector<Pickup> drops; // power-ups, coins and time on their way down
+    int lastDrop = -1;     // PowerUpType, for DropTable::repeats
+    std::vector<Projectile> projectiles;
+    Boss boss; // not present() in most levels
+    std::vector<Debris> debris;
+    // endless mode: until the field next moves down, and rows so far
+    int shiftTicks = 0;
+    int rowsAdded = 0;
+    // time attack: left on the clock, and the seconds caught so far
+    int timeTicks = 0;
+    int timeCollected = 0;
+    // survival: play so far, and the stage of survivalCurve() it has reached
+    int survivedTicks = 0;
+    int survivalStage = 0;
+    // puzzle levels: serves still to come, and what the ones left over scored
+    int launchesLeft = 0;
+    int spareBonus = 0;
+    // The timed power-ups and debuffs. The laser fires on
+    // Action::ActivatePower, the fireball makes the balls piercing, reversed
+    // controls swap left and right and flicker blinks the balls out (see
+    // ballVisibility).
+    EffectManager effects;
+    int laserCooldown = 0; // until the next shot
+    float paddleBaseWidth = 100; // before grow and shrink
+    Barrier barrier;
+    // How fast the world runs against the tick: balls, drops and brick
+    // animations move this much of a tick's worth, the paddle always a full
+    // one. Particles and the music's pitch follow it too, so it is the one
+    // place slow motion and speed-up live.
+    float timeScale = 1;
+    int lives = 3;
+    int score = 0;
+    int scoreCarry = 0; // hundredths of a point scorePercent rounded off, for the next points
+    int playerScores[2] = {0, 0}; // co-op: each player's share of score
+    int coins = 0;         // caught this run; they go to the profile when it ends
+    int combo = 0;         // bricks broken since a ball last touched the paddle, see ComboRules
+    int comboTicks = 0;    // since the last of them
+    bool ballHeld = true;  // a ball is waiting on the paddle for Action::Launch (see BallState)
+    int heldTicks = 0;     // how long it has been waiting, for auto-launch
+    InputBuffer inputBuffers[2]; // one per paddle, co-op's partner second
+    Random random;
+    uint64_t tick = 0;
+    bool cheated = false;  // a cheat was on for at least one tick; no replays or records
+};
+
+// One fixed-timestep game tick as a function of the state and an InputFrame
+// and nothing else, so the same seed, level, rules and inputs always end up in
+// the same state.
+class Simulation {
+  public:
+    // Bump whenever step() plays differently; replays from other versions are refused.
+    static const uint32_t VERSION = 29;
+
+    SimulationRules rules;
+    SimulationCheats cheats;
+    // From the level; reset() leaves it alone.
+    DropTable dropTable = DropTable::standard();
+    // The same; reset() puts a fresh copy of it in the state.
+    Boss boss;
+    // The same; a puzzle level's serves (see Puzzle.hpp), 0 for no limit.
+    int launchBudget = 0;
+    // The same; the level's ball speed and paddle width, on top of the rules'.
+    LevelPhysics physics;
+    // What reset() was given, which survival puts back each time it's cleared.
+    std::vector<Brick> levelBricks;
+    GameState state;
+    // Cosmetics that want the brick before it is removed (shatter particles...).
+    std::function<void(Brick&)> onBrickBroken;
+
+    void reset(const std::vector<Brick>& bricks, uint64_t seed);
+    // The level's bricks swapped for bricks mid-run (an edited level file,
+    // see AssetKind::Level), the balls and paddle left where they are. A
+    // brick still at the same spot keeps its damage and where it is on its
+    // path, one broken already stays broken, and new ones appear. No replay
+    // could play that back, so the run counts as cheated.
+    void reloadBricks(const std::vector<Brick>& bricks);
+    // Events go to the bus; without one the tick runs quietly (fast-forwarding
+    // a replay) and onBrickBroken is skipped as well. partnerInput drives
+    // co-op's second paddle and is ignored in every other mode.
+    void step(const InputFrame& input, EventBus* events, const InputFrame& partnerInput = InputFrame());
+    // The rules' ball speed times the level's, and survival's on top.
+    float ballSpeed() const;
+    // What the mutators do to bricks as they appear, the level's, endless's
+    // rows and versus's junk alike.
+    void prepareBricks(std::vector<Brick>& fresh) const;
+    // Hash of the state, compared between a replay and its recording.
+    uint64_t checksum() const;
+};
+
+// ReverseControls and the mirrored_controls mutator undo each other.
+bool controlsReversed(const GameState& state, const SimulationRules& rules);
+
+Player initPlayer(int, int);
+Ball initBall(int, int);
+void ballBounce(Entity* , char);
@@ -0,0 +1,155 @@
+#include "raylib.h"
+#include "SoundCues.hpp"
+#include "Localization.hpp"
+#include "PowerUp.hpp"
+#include <cmath>
+
+
+static const int MAX_CUES = 16;
+static const int MAX_CAPTIONS = 3;
+
+bool visualCueFor(const GameEvent& event, VisualCueStyle& style) {
+    switch (event.type) {
+    case EventType::WallBounce:
+        style = {nullptr, SKYBLUE, 0.4f, false};
+        return true;
+    case EventType::PaddleBounce:
+        style = {nullptr, GREEN, 0.4f, false};
+        return true;
+    case EventType::BallLost:
+        style = {"Ball lost", RED, 1.5f, true};
+        return true;
+    case EventType::ExtraBallLost:
+        style = {nullptr, ORANGE, 0.6f, false};
+        return true;
+    case EventType::BallCaught:
+        style = {nullptr, GREEN, 0.6f, false};
+        return true;
+    case EventType::BarrierHit:
+        style = {"Shield", SKYBLUE, 1.0f, true};
+        return true;
+    case EventType::LaserFired:
+        style = {nullptr, RED, 0.3f, false};
+        return true;
+    case EventType::BossDefeated:
+        style = {"Boss beaten", GOLD, 1.5f, true};
+        return true;
+    case EventType::BossAttacked:
+        style = {"Boss attack", ORANGE, 0.8f, true};
+        return true;
+    case EventType::PaddleDamaged:
+        style = {"Paddle hit", RED, 1.0f, true};
+        return true;
+    case EventType::FieldShifted:
+        style = {"Rows move down", LIGHTGRAY, 0.8f, true};
+        return true;
+    case EventType::FieldOverran:
+        style = {"The bricks reached the paddle", RED, 1.5f, true};
+        return true;
+    case EventType::ComboDecayed:
+        style = {"Combo fading", GOLD, 0.8f, false};
+        return true;
+    case EventType::TimeCollected:
+        style = {"Extra time", SKYBLUE, 0.8f, false};
+        return true;
+    case EventType::TimeUp:
+        style = {"Time's up", RED, 1.5f, true};
+        return true;
+    case EventType::LaunchesSpent:
+        style = {"Out of balls", RED, 1.5f, true};
+        return true;
+    case EventType::DifficultyRaised:
+        style = {"Faster", ORANGE, 1.0f, true};
+        return true;
+    case EventType::FieldRefilled:
+        style = {"The bricks are back", LIGHTGRAY, 1.0f, true};
+        return true;
+    case EventType::GarbageReceived:
+        style = {"Junk incoming", BROWN, 1.0f, true};
+        return true;
+    case EventType::BricksSwitched:
+        style = {"Switch", VIOLET, 1.0f, true};
+        return true;
+    case EventType::CoinCollected:
+        style = {"Coin", GOLD, 0.6f, false};
+        return true;
+    case EventType::PowerUpCollected:
+        if (powerUpInfo((PowerUpType) event.value).harmful) {
+            style = {"Power-down", MAGENTA, 1.0f, true};
+        } else {
+            style = {"Power-up", GOLD, 1.0f, true};
+        }
+        return true;
+    default:
+        return false;
+    }
+}
+
+void VisualSoundCues::onEvent(const GameEvent& event) {
+    VisualCueStyle style;
+    if (!enabled || !visualCueFor(event, style)) {
+        return;
+    }
+    if ((int) cues.size() >= MAX_CUES) {
+        cues.erase(cues.begin());
+    }
+    cues.push_back({event.position, style, style.duration});
+}
+
+void VisualSoundCues::update(float deltaTime) {
+    for (size_t i = 0; i < cues.size();) {
+        cues[i].life -= deltaTime;
+        if (cues[i].life <= 0) {
+            cues.erase(cues.begin() + i);
+            continue;
+        }
+        i++;
+    }
+}
+
+void VisualSoundCues::drawIndicators(int fieldWidth, int fieldHeight) {
+    const float margin = 24;
+    Vector2 center = {fieldWidth / 2.0f, fieldHeight / 2.0f};
+    for (const Cue& cue : cues) {
+        float t = cue.life / cue.style.duration;
+        Color color = cue.style.color;
+        color.a = (unsigned char) (color.a * t);
+
+        bool inside = cue.position.x >= 0 && cue.position.x <= fieldWidth && cue.position.y >= 0 && cue.position.y <= fieldHeight;
+        if (inside) {
+            float radius = 10 + 20 * (1 - t);
+            DrawRing(cue.position, radius - 3, radius, 0, 360, 32, color);
+            continue;
+        }
+
+        // arrow on the edge, pointing out towards the source
+        Vector2 edge = {
+            fminf(fmaxf(cue.position.x, margin), fieldWidth - margin),
+            fminf(fmaxf(cue.position.y, margin), fieldHeight - margin),
+        };
+        float angle = atan2f(cue.position.y - center.y, cue.position.x - center.x);
+        Vector2 tip = {edge.x + cosf(angle) * 14, edge.y + sinf(angle) * 14};
+        Vector2 left = {edge.x + cosf(angle + 2.5f) * 14, edge.y + sinf(angle + 2.5f) * 14};
+        Vector2 right = {edge.x + cosf(angle - 2.5f) * 14, edge.y + sinf(angle - 2.5f) * 14};
+        DrawTriangle(tip, right, left, color);
+    }
+}
+
+void VisualSoundCues::drawCaptions(TextRenderer& text, Vector2 position, float size, Color color) {
+    int shown = 0;
+    for (auto cue = cues.rbegin(); cue != cues.rend() && shown < MAX_CAPTIONS; ++cue) {
+        if (!cue->style.important || !cue->style.caption) {
+            continue;
+        }
+        std::string caption = "[" + tr(cue->style.caption) + "]";
+        Vector2 extent = text.measure(caption, size);
+        Vector2 at = {position.x - extent.x / 2, position.y - (shown + 1) * (extent.y + 4)};
+        DrawRectangle(at.x - 6, at.y - 2, extent.x + 12, extent.y + 4, Fade(BLACK, 0.6f));
+        text.draw(caption, at, size, color);
+        shown++;
+    }
+}
+
+void VisualSoundCues::clear() {
+    cues.clear();
+}
@@ -0,0 +1,46 @@
+#pragma once
+#include "raylib.h"
+#include <string>
+#include <vector>
+#include "Events.hpp"
+#include "Text.hpp"
+
+
+// How an audible event is shown to players who can't hear it.
+struct VisualCueStyle {
+    const char* caption; // nullptr = indicator only
+    Color color;
+    float duration;
+    bool important;      // also shown as a caption at the bottom of the screen
+};
+
+// Looks up the cue for an event; returns false for events that make no
+// sound worth showing.
+bool visualCueFor(const GameEvent& event, VisualCueStyle& style);
+
+// Accessibility option: every sound-making event gets an on-screen marker.
+// Sources inside the playfield get a ring at the spot; sources outside it
+// (off-screen balls, things above the visible field) get an arrow on the
+// nearest edge pointing at them. Subscribes to the same events the audio
+// system plays from, so the two never disagree.
+class VisualSoundCues {
+  public:
+    bool enabled = false;
+
+    void onEvent(const GameEvent& event);
+    void update(float deltaTime);
+    // World space, inside the playfield of the given size.
+    void drawIndicators(int fieldWidth, int fieldHeight);
+    // Screen space captions (Ui layer); position is the bottom center.
+    void drawCaptions(TextRenderer& text, Vector2 position, float size, Color color);
+    void clear();
+
+  private:
+    struct Cue {
+        Vector2 position;
+        VisualCueStyle style;
+        float life;
+    };
+
+    std::vector<Cue> cues;
+};
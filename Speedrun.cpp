@@ -0,0 +1,364 @@
+#include "raylib.h"
+#include "Speedrun.hpp"
+#include <cmath>
+#include <filesystem>
+#include <fstream>
+#include "FramePacer.hpp"
+#include "Leaderboard.hpp"
+#include "Localization.hpp"
+#include "Sanitize.hpp"
+#include "Xml.hpp"
+
+
+bool SplitTime::isSet() const {
+    return game >= 0;
+}
+
+std::string SpeedrunTimer::splitsPath(const std::string& category) {
+    return std::string(GetApplicationDirectory()) + "splits/" + sanitizeFilename(category) + ".lss";
+}
+
+// LiveSplit writes "hh:mm:ss.fffffff", with "d." in front past a day
+static double parseLssTime(const std::string& text) {
+    double seconds = 0;
+    size_t start = 0;
+    size_t dot = text.find('.');
+    size_t colon = text.find(':');
+    if (dot != std::string::npos && colon != std::string::npos && dot < colon) {
+        seconds = atof(text.substr(0, dot).c_str()) * 86400;
+        start = dot + 1;
+    }
+    std::string rest = text.substr(start);
+    double part = 0;
+    size_t from = 0;
+    size_t next;
+    while ((next = rest.find(':', from)) != std::string::npos) {
+        part = part * 60 + atof(rest.substr(from, next - from).c_str());
+        from = next + 1;
+    }
+    return seconds + part * 60 + atof(rest.substr(from).c_str());
+}
+
+static std::string formatLssTime(double seconds) {
+    long long whole = (long long) seconds;
+    int fraction = (int) llround((seconds - whole) * 10000000);
+    if (fraction >= 10000000) {
+        whole++;
+        fraction -= 10000000;
+    }
+    return TextFormat("%02lld:%02d:%02d.%07d", whole / 3600, (int) (whole / 60 % 60), (int) (whole % 60), fraction);
+}
+
+static double ticksToSeconds(int64_t ticks) {
+    return (double) ticks / FramePacer::TICK_RATE;
+}
+
+static SplitTime readTimes(const XmlNode* node) {
+    SplitTime time;
+    if (!node) {
+        return time;
+    }
+    if (const XmlNode* real = node->child("RealTime")) {
+        time.real = parseLssTime(real->text);
+    }
+    if (const XmlNode* game = node->child("GameTime")) {
+        time.game = llround(parseLssTime(game->text) * FramePacer::TICK_RATE);
+    }
+    return time;
+}
+
+static std::string escape(const std::string& text) {
+    std::string escaped;
+    for (char c : text) {
+        switch (c) {
+        case '&':
+            escaped += "&amp;";
+            break;
+        case '<':
+            escaped += "&lt;";
+            break;
+        case '>':
+            escaped += "&gt;";
+            break;
+        default:
+            escaped += c;
+        }
+    }
+    return escaped;
+}
+
+static void writeTimes(std::ofstream& file, const char* indent, const SplitTime& time) {
+    if (time.real >= 0) {
+        file << indent << "<RealTime>" << formatLssTime(time.real) << "</RealTime>\n";
+    }
+    if (time.game >= 0) {
+        file << indent << "<GameTime>" << formatLssTime(ticksToSeconds(time.game)) << "</GameTime>\n";
+    }
+}
+
+void SpeedrunTimer::prepare(const std::string& runCategory, const std::vector<std::string>& segmentNames) {
+    category = runCategory;
+    segments.clear();
+    for (const std::string& name : segmentNames) {
+        segments.push_back({name, {}, {}, {}});
+    }
+    attempts = 0;
+    invalid = false;
+    current = 0;
+    startTick = 0;
+    completedTicks = 0;
+    load();
+    state = segments.empty() ? State::Idle : State::Ready;
+}
+
+bool SpeedrunTimer::load() {
+    std::string path = splitsPath(category);
+    if (!FileExists(path.c_str())) {
+        return false;
+    }
+    XmlNode root;
+    XmlError error;
+    if (!loadXmlFile(path, root, error) || root.name != "Run") {
+        TraceLog(LOG_WARNING, "SPLITS: %s: %s", path.c_str(), root.name != "Run" && error.message.empty() ? "not a LiveSplit file" : error.describe().c_str());
+        return false;
+    }
+    if (const XmlNode* count = root.child("AttemptCount")) {
+        attempts = atoi(count->text.c_str());
+    }
+    const XmlNode* list = root.child("Segments");
+    if (!list) {
+        return true;
+    }
+    // matched by name, so levels added to (or taken out of) a route keep theirs
+    for (const XmlNode* node : list->childrenNamed("Segment")) {
+        const XmlNode* name = node->child("Name");
+        for (SplitSegment& segment : segments) {
+            if (!name || segment.name != name->text) {
+                continue;
+            }
+            segment.bestSegment = readTimes(node->child("BestSegmentTime"));
+            if (const XmlNode* times = node->child("SplitTimes")) {
+                for (const XmlNode* time : times->childrenNamed("SplitTime")) {
+                    if (time->attribute("name") == "Personal Best") {
+                        segment.personalBest = readTimes(time);
+                    }
+                }
+            }
+        }
+    }
+    return true;
+}
+
+bool SpeedrunTimer::save() const {
+    std::error_code error;
+    std::filesystem::create_directories(std::string(GetApplicationDirectory()) + "splits", error);
+    std::string path = splitsPath(category);
+    std::ofstream file(path);
+    if (!file) {
+        TraceLog(LOG_WARNING, "SPLITS: Could not write %s", path.c_str());
+        return false;
+    }
+    file << "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n";
+    file << "<Run version=\"1.7.0\">\n";
+    file << "  <GameIcon />\n";
+    file << "  <GameName>Breakout</GameName>\n";
+    file << "  <CategoryName>" << escape(category) << "</CategoryName>\n";
+    file << "  <Offset>00:00:00</Offset>\n";
+    file << "  <AttemptCount>" << attempts << "</AttemptCount>\n";
+    file << "  <AttemptHistory />\n";
+    file << "  <Segments>\n";
+    for (const SplitSegment& segment : segments) {
+        file << "    <Segment>\n";
+        file << "      <Name>" << escape(segment.name) << "</Name>\n";
+        file << "      <Icon />\n";
+        file << "      <SplitTimes>\n";
+        if (segment.personalBest.isSet()) {
+            file << "        <SplitTime name=\"Personal Best\">\n";
+            writeTimes(file, "          ", segment.personalBest);
+            file << "        </SplitTime>\n";
+        } else {
+            file << "        <SplitTime name=\"Personal Best\" />\n";
+        }
+        file << "      </SplitTimes>\n";
+        if (segment.bestSegment.isSet()) {
+            file << "      <BestSegmentTime>\n";
+            writeTimes(file, "        ", segment.bestSegment);
+            file << "      </BestSegmentTime>\n";
+        } else {
+            file << "      <BestSegmentTime />\n";
+        }
+        file << "      <SegmentHistory />\n";
+        file << "    </Segment>\n";
+    }
+    file << "  </Segments>\n";
+    file << "  <AutoSplitterSettings />\n";
+    file << "</Run>\n";
+    return true;
+}
+
+void SpeedrunTimer::update(uint64_t levelTick, bool served, SpeedrunStart rule, double now) {
+    if (state != State::Ready) {
+        return;
+    }
+    bool start = rule == SpeedrunStart::FirstServe ? served : levelTick > 0;
+    if (!start) {
+        return;
+    }
+    state = State::Running;
+    // the first tick is when play began, so level start counts from zero
+    startTick = rule == SpeedrunStart::FirstServe ? levelTick : 0;
+    startTime = now;
+    attempts++;
+}
+
+void SpeedrunTimer::keepBestSegments() {
+    for (int i = 0; i < current && i < (int) segments.size(); i++) {
+        SplitSegment& segment = segments[i];
+        SplitTime previous = i > 0 ? segments[i - 1].current : SplitTime{0, 0};
+        double real = segment.current.real - previous.real;
+        int64_t game = segment.current.game - previous.game;
+        if (segment.bestSegment.real < 0 || real < segment.bestSegment.real) {
+            segment.bestSegment.real = real;
+        }
+        if (segment.bestSegment.game < 0 || game < segment.bestSegment.game) {
+            segment.bestSegment.game = game;
+        }
+    }
+}
+
+void SpeedrunTimer::split(uint64_t levelTick, double now) {
+    if (state != State::Running) {
+        return;
+    }
+    SplitSegment& segment = segments[current];
+    segment.current.game = getGameTicks(levelTick);
+    segment.current.real = getRealTime(now);
+    completedTicks = segment.current.game;
+    startTick = 0;
+    current++;
+    if (current < (int) segments.size()) {
+        return;
+    }
+    state = State::Finished;
+    if (invalid) {
+        return;
+    }
+    keepBestSegments();
+    const SplitTime& best = segments.back().personalBest;
+    if (!best.isSet() || segment.current.game < best.game) {
+        TraceLog(LOG_INFO, "SPLITS: New personal best for %s: %s", category.c_str(), formatRunTime(segment.current.game).c_str());
+        for (SplitSegment& each : segments) {
+            each.personalBest = each.current;
+        }
+    }
+    save();
+}
+
+void SpeedrunTimer::restartLevel(uint64_t levelTick) {
+    if (state != State::Running) {
+        return;
+    }
+    completedTicks = getGameTicks(levelTick);
+    startTick = 0;
+}
+
+void SpeedrunTimer::reset() {
+    if (state == State::Running && !invalid) {
+        keepBestSegments();
+        save();
+    }
+    state = State::Idle;
+}
+
+void SpeedrunTimer::invalidate() {
+    if (!invalid && (state == State::Ready || state == State::Running)) {
+        TraceLog(LOG_INFO, "SPLITS: Cheats were on, this run's times aren't kept");
+    }
+    invalid = true;
+}
+
+bool SpeedrunTimer::isPrepared() const {
+    return state != State::Idle;
+}
+
+bool SpeedrunTimer::isRunning() const {
+    return state == State::Running;
+}
+
+bool SpeedrunTimer::isFinished() const {
+    return state == State::Finished;
+}
+
+double SpeedrunTimer::getRealTime(double now) const {
+    if (state == State::Finished) {
+        return segments.back().current.real;
+    }
+    return state == State::Running ? now - startTime : 0;
+}
+
+int64_t SpeedrunTimer::getGameTicks(uint64_t levelTick) const {
+    if (state == State::Finished) {
+        return segments.back().current.game;
+    }
+    return state == State::Running ? completedTicks + (int64_t) levelTick - (int64_t) startTick : 0;
+}
+
+const std::vector<SplitSegment>& SpeedrunTimer::getSegments() const {
+    return segments;
+}
+
+// "-1.25", "+0.40"; game time, the one runs are ranked by
+static std::string formatDelta(int64_t ticks) {
+    return TextFormat("%c%.2f", ticks < 0 ? '-' : '+', fabs(ticksToSeconds(ticks)));
+}
+
+static std::string formatSeconds(double seconds) {
+    int hundredths = (int) (seconds * 100);
+    return TextFormat("%d:%02d.%02d", hundredths / 6000, hundredths / 100 % 60, hundredths % 100);
+}
+
+void SpeedrunTimer::draw(TextRenderer& text, Rectangle bounds, float scale, Color color, uint64_t levelTick, double now) {
+    if (state == State::Idle) {
+        return;
+    }
+    float rowHeight = 22 * scale;
+    float fontSize = 18 * scale;
+    float margin = 8 * scale;
+    float height = rowHeight * segments.size() + 70 * scale;
+    DrawRectangleRec({bounds.x, bounds.y, bounds.width, height}, Fade(BLACK, 0.5f));
+    int64_t gameTicks = getGameTicks(levelTick);
+    float right = bounds.x + bounds.width - margin;
+    for (int i = 0; i < (int) segments.size(); i++) {
+        const SplitSegment& segment = segments[i];
+        float y = bounds.y + margin + i * rowHeight;
+        bool done = i < current;
+        bool active = i == current && state == State::Running;
+        text.draw(segment.name, {bounds.x + margin, y}, fontSize, active ? YELLOW : color);
+
+        int64_t time = done ? segment.current.game : segment.personalBest.game;
+        std::string shown = time >= 0 ? formatRunTime(time) : "-";
+        Vector2 size = text.measure(shown, fontSize);
+        text.draw(shown, {right - size.x, y}, fontSize, done ? color : Fade(color, 0.6f));
+
+        // a live delta only once the split is already lost
+        bool behindLive = active && segment.personalBest.isSet() && gameTicks > segment.personalBest.game;
+        if ((done || behindLive) && segment.personalBest.isSet()) {
+            int64_t delta = (done ? segment.current.game : gameTicks) - segment.personalBest.game;
+            int64_t previous = i > 0 ? segments[i - 1].current.game : 0;
+            bool gold = done && (!segment.bestSegment.isSet() || segment.current.game - previous <= segment.bestSegment.game);
+            Color deltaColor = gold ? GOLD : (delta < 0 ? GREEN : RED);
+            std::string deltaText = formatDelta(delta);
+            Vector2 deltaSize = text.measure(deltaText, fontSize);
+            text.draw(deltaText, {right - 90 * scale - deltaSize.x, y}, fontSize, deltaColor);
+        }
+    }
+    float y = bounds.y + margin + segments.size() * rowHeight + 4 * scale;
+    std::string total = formatRunTime(gameTicks);
+    Vector2 size = text.measure(total, 36 * scale);
+    text.draw(total, {right - size.x, y}, 36 * scale, state == State::Finished ? GOLD : color);
+    std::string real = TextFormat(tr("Real time %s").c_str(), formatSeconds(getRealTime(now)).c_str());
+    text.draw(real, {bounds.x + margin, y + 12 * scale}, 16 * scale, Fade(color, 0.7f));
+    if (invalid) {
+        text.draw(tr("Cheats on"), {bounds.x + margin, y + 32 * scale}, 14 * scale, RED);
+    }
+}
@@ -0,0 +1,79 @@
+#pragma once
+#include "raylib.h"
+#include <cstdint>
+#include <string>
+#include <vector>
+#include "Text.hpp"
+
+
+// One timing at a split, cumulative from the start of the run. Game time is
+// in simulation ticks, so it is exact and the same on every machine; real
+// time is wall-clock seconds and includes pauses and menus.
+struct SplitTime {
+    double real = -1;  // -1 = none
+    int64_t game = -1; // ticks, -1 = none
+
+    bool isSet() const;
+};
+
+struct SplitSegment {
+    std::string name;
+    SplitTime personalBest; // where the best finished run was at this split
+    SplitTime bestSegment;  // fastest this one segment has ever been, not cumulative
+    SplitTime current;      // this run
+};
+
+enum class SpeedrunStart {
+    LevelStart, // the first tick of the first level
+    FirstServe, // the first serve
+};
+
+// Times a run through a fixed list of levels (one segment each) and compares
+// it, split by split, with the personal best and the best segments. A run
+// starts by itself per the start rule, splits when a level is cleared,
+// finishes on the last split and is reset when it dies. The comparisons live
+// in splits/<category>.lss, the LiveSplit format, which other split tools
+// read too; it is rewritten whenever a run finishes or resets.
+class SpeedrunTimer {
+  public:
+    static std::string splitsPath(const std::string& category);
+
+    // Gets ready for a new run, loading the comparisons of category.
+    void prepare(const std::string& category, const std::vector<std::string>& segmentNames);
+    // Once a frame while playing; levelTick is the current level's tick.
+    void update(uint64_t levelTick, bool served, SpeedrunStart rule, double now);
+    // The current level was cleared at levelTick.
+    void split(uint64_t levelTick, double now);
+    // The current level starts over; the ticks spent on it still count.
+    void restartLevel(uint64_t levelTick);
+    // Ends the run unfinished (or drops an unstarted one). Best segments it
+    // beat are kept.
+    void reset();
+    // Cheats were on; the run still shows but its times are not kept.
+    void invalidate();
+
+    bool isPrepared() const;
+    bool isRunning() const;
+    bool isFinished() const;
+    double getRealTime(double now) const;
+    int64_t getGameTicks(uint64_t levelTick) const;
+    const std::vector<SplitSegment>& getSegments() const;
+
+    void draw(TextRenderer& text, Rectangle bounds, float scale, Color color, uint64_t levelTick, double now);
+
+  private:
+    enum class State { Idle, Ready, Running, Finished };
+    State state = State::Idle;
+    std::string category;
+    std::vector<SplitSegment> segments;
+    int attempts = 0;
+    bool invalid = false;
+    int current = 0;            // segment being run
+    uint64_t startTick = 0;     // level tick the current segment started at
+    int64_t completedTicks = 0; // game time before the current segment
+    double startTime = 0;
+
+    bool load();
+    bool save() const;
+    void keepBestSegments();
+};
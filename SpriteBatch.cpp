@@ -0,0 +1,92 @@
+#include "raylib.h"
+#include "rlgl.h"
+#include "SpriteBatch.hpp"
+#include <algorithm>
+
+
+void SpriteBatch::begin() {
+    sprites.clear();
+}
+
+void SpriteBatch::add(Texture2D texture, Rectangle source, Rectangle dest, Color tint, int layer) {
+    sprites.push_back({texture.id, texture.width, texture.height, source, dest, tint, layer});
+}
+
+void SpriteBatch::addRectangle(Rectangle dest, Color color, int layer) {
+    sprites.push_back({rlGetTextureIdDefault(), 1, 1, {0, 0, 1, 1}, dest, color, layer});
+}
+
+void SpriteBatch::addRegion(TextureAtlas& atlas, const std::string& name, Rectangle dest, Color tint, int layer) {
+    if (atlas.isReady() && atlas.has(name)) {
+        add(atlas.getTexture(), atlas.region(name), dest, tint, layer);
+    } else {
+        addRectangle(dest, tint, layer);
+    }
+}
+
+void SpriteBatch::prepare() {
+    std::stable_sort(sprites.begin(), sprites.end(), [](const Sprite& a, const Sprite& b) {
+        if (a.layer != b.layer) {
+            return a.layer < b.layer;
+        }
+        return a.textureId < b.textureId;
+    });
+}
+
+void SpriteBatch::end() {
+    prepare();
+
+    stats = {(int) sprites.size(), 0, 0};
+    if (sprites.empty()) {
+        return;
+    }
+
+    unsigned int currentTexture = 0;
+    int currentLayer = 0;
+    for (size_t i = 0; i < sprites.size(); i++) {
+        const Sprite& sprite = sprites[i];
+        if (i == 0 || sprite.layer != currentLayer) {
+            currentLayer = sprite.layer;
+            stats.layers++;
+        }
+        if (i == 0 || sprite.textureId != currentTexture) {
+            if (i > 0) {
+                rlEnd();
+            }
+            currentTexture = sprite.textureId;
+            rlSetTexture(currentTexture);
+            rlBegin(RL_QUADS);
+            stats.batches++;
+        }
+        // rlgl flushes and keeps the current texture when its buffer fills up
+        if (rlCheckRenderBatchLimit(4)) {
+            stats.batches++;
+        }
+        submit(sprite);
+    }
+    rlEnd();
+    rlSetTexture(0);
+}
+
+void SpriteBatch::submit(const Sprite& sprite) {
+    float u0 = sprite.source.x / sprite.textureWidth;
+    float v0 = sprite.source.y / sprite.textureHeight;
+    float u1 = (sprite.source.x + sprite.source.width) / sprite.textureWidth;
+    float v1 = (sprite.source.y + sprite.source.height) / sprite.textureHeight;
+    const Rectangle& dest = sprite.dest;
+
+    rlColor4ub(sprite.tint.r, sprite.tint.g, sprite.tint.b, sprite.tint.a);
+    rlNormal3f(0, 0, 1);
+    rlTexCoord2f(u0, v0);
+    rlVertex2f(dest.x, dest.y);
+    rlTexCoord2f(u0, v1);
+    rlVertex2f(dest.x, dest.y + dest.height);
+    rlTexCoord2f(u1, v1);
+    rlVertex2f(dest.x + dest.width, dest.y + dest.height);
+    rlTexCoord2f(u1, v0);
+    rlVertex2f(dest.x + dest.width, dest.y);
+}
+
+BatchStats SpriteBatch::getStats() {
+    return stats;
+}
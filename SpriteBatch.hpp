@@ -0,0 +1,44 @@
+#pragma once
+#include "raylib.h"
+#include <string>
+#include <vector>
+#include "Atlas.hpp"
+
+
+struct BatchStats {
+    int sprites;
+    int batches; // draw calls actually submitted to the GPU
+    int layers;
+};
+
+struct Sprite {
+    unsigned int textureId;
+    int textureWidth;
+    int textureHeight;
+    Rectangle source;
+    Rectangle dest;
+    Color tint;
+    int layer;
+};
+
+// Collects sprites for a frame, sorts them by layer and texture and submits
+// each run of same-texture sprites as a single batch of quads.
+class SpriteBatch {
+  public:
+    void begin();
+    void add(Texture2D texture, Rectangle source, Rectangle dest, Color tint, int layer = 0);
+    void addRectangle(Rectangle dest, Color color, int layer = 0);
+    // Draws a named atlas region, or a plain rectangle if the atlas lacks it.
+    void addRegion(TextureAtlas& atlas, const std::string& name, Rectangle dest, Color tint, int layer = 0);
+    // Puts the collected sprites in submission order; end() does this first.
+    void prepare();
+    void end();
+
+    BatchStats getStats();
+
+  private:
+    std::vector<Sprite> sprites;
+    BatchStats stats = {0, 0, 0};
+
+    void submit(const Sprite& sprite);
+};
@@ -0,0 +1,316 @@
+#include "raylib.h"
+#include "Stats.hpp"
+#include "FramePacer.hpp"
+#include "PowerUp.hpp"
+#include "Sanitize.hpp"
+#include <algorithm>
+#include <cstdlib>
+#include <ctime>
+#include <filesystem>
+#include <fstream>
+#include <sstream>
+
+
+static const char* const HEADER = "# breakout stats 1";
+
+const char* medalName(Medal medal) {
+    switch (medal) {
+    case Medal::None: return "None";
+    case Medal::Bronze: return "Bronze";
+    case Medal::Silver: return "Silver";
+    case Medal::Gold: return "Gold";
+    }
+    return "None";
+}
+
+float LevelSummary::bricksPerMinute() const {
+    float minutes = (float) ticks / FramePacer::TICK_RATE / 60;
+    return minutes > 0 ? bricksBroken / minutes : 0;
+}
+
+int LevelSummary::accuracy() const {
+    return paddleBounces > 0 ? 100 - offCenter / paddleBounces : 0;
+}
+
+void LevelRecorder::start() {
+    summary = LevelSummary();
+}
+
+void LevelRecorder::onEvent(const GameEvent& event) {
+    switch (event.type) {
+    case EventType::PaddleBounce:
+        summary.paddleBounces++;
+        summary.offCenter += std::min(std::abs(event.value), 100);
+        break;
+    case EventType::PerfectCatch:
+        summary.perfectCatches++;
+        break;
+    case EventType::BrickDestroyed:
+        summary.bricksBroken++;
+        summary.longestCombo = std::max(summary.longestCombo, event.value);
+        break;
+    case EventType::BallLost:
+        summary.ballsLost++;
+        break;
+    case EventType::PowerUpCollected:
+        summary.powerUps++;
+        break;
+    default:
+        break;
+    }
+}
+
+void LevelRecorder::finish(uint64_t ticks) {
+    summary.ticks = ticks;
+}
+
+const LevelSummary& LevelRecorder::getSummary() const {
+    return summary;
+}
+
+Medal medalFor(bool cleared, int livesLost, uint64_t ticks, int parSeconds) {
+    if (!cleared) {
+        return Medal::None;
+    }
+    bool flawless = livesLost <= 0;
+    bool withinPar = ticks <= (uint64_t) parSeconds * FramePacer::TICK_RATE;
+    if (flawless && withinPar) {
+        return Medal::Gold;
+    }
+    return flawless || withinPar ? Medal::Silver : Medal::Bronze;
+}
+
+std::string PlayerStats::statsPath(const std::string& profile) {
+    return std::string(GetApplicationDirectory()) + "profiles/" + sanitizeFilename(profile) + ".stats";
+}
+
+static std::vector<std::string> splitTabs(const std::string& line) {
+    std::vector<std::string> fields;
+    std::istringstream stream(line);
+    std::string field;
+    while (std::getline(stream, field, '\t')) {
+        fields.push_back(field);
+    }
+    return fields;
+}
+
+// One record per line, tab separated, its kind first:
+//   play  seconds  ticks  runs  clears
+//   accuracy  paddle bounces  perfect catches  bricks broken  balls lost
+//   paddle  count per bin, left to right
+//   field  count per cell, row by row
+//   power  id  uses
+//   run  date  score  cleared
+//   medal  level hash  medal  level name
+//   stars  level hash  stars
+bool PlayerStats::load(const std::string& name, Vector2 size) {
+    *this = PlayerStats();
+    profile = name;
+    fieldSize = size;
+    std::ifstream file(statsPath(profile));
+    if (!file) {
+        return true;
+    }
+    std::string line;
+    if (!std::getline(file, line) || line != HEADER) {
+        TraceLog(LOG_WARNING, "STATS: %s is not a stats file, starting over", statsPath(profile).c_str());
+        return false;
+    }
+    auto fill = [](std::vector<int>& counts, const std::vector<std::string>& fields) {
+        for (size_t i = 0; i < counts.size() && i + 1 < fields.size(); i++) {
+            counts[i] = atoi(fields[i + 1].c_str());
+        }
+    };
+    while (std::getline(file, line)) {
+        std::vector<std::string> fields = splitTabs(line);
+        if (fields.empty()) {
+            continue;
+        }
+        const std::string& kind = fields[0];
+        if (kind == "play" && fields.size() >= 5) {
+            playSeconds = atof(fields[1].c_str());
+            playTicks = strtoull(fields[2].c_str(), nullptr, 10);
+            runCount = atoi(fields[3].c_str());
+            clearCount = atoi(fields[4].c_str());
+        } else if (kind == "accuracy" && fields.size() >= 5) {
+            paddleBounces = atoi(fields[1].c_str());
+            perfectCatches = atoi(fields[2].c_str());
+            bricksBroken = atoi(fields[3].c_str());
+            ballsLost = atoi(fields[4].c_str());
+        } else if (kind == "paddle") {
+            fill(paddleHits, fields);
+        } else if (kind == "field") {
+            fill(fieldHits, fields);
+        } else if (kind == "power" && fields.size() >= 3) {
+            powerUps[fields[1]] = atoi(fields[2].c_str());
+        } else if (kind == "run" && fields.size() >= 4) {
+            runs.push_back({fields[1], atoi(fields[2].c_str()), fields[3] == "1"});
+        } else if (kind == "medal" && fields.size() >= 4) {
+            LevelMedal& medal = medals[strtoull(fields[1].c_str(), nullptr, 16)];
+            medal.medal = (Medal) std::clamp(atoi(fields[2].c_str()), 0, (int) Medal::Gold);
+            medal.name = fields[3];
+        } else if (kind == "stars" && fields.size() >= 3) {
+            medals[strtoull(fields[1].c_str(), nullptr, 16)].stars = std::clamp(atoi(fields[2].c_str()), 0, 3);
+        }
+    }
+    if ((int) runs.size() > MAX_RUNS) {
+        runs.erase(runs.begin(), runs.end() - MAX_RUNS);
+    }
+    return true;
+}
+
+bool PlayerStats::save() const {
+    std::error_code error;
+    std::filesystem::create_directories(std::string(GetApplicationDirectory()) + "profiles", error);
+    std::string path = statsPath(profile);
+    std::ofstream file(path);
+    if (!file) {
+        TraceLog(LOG_WARNING, "STATS: Could not write %s", path.c_str());
+        return false;
+    }
+    file << HEADER << "\n";
+    file << "play\t" << TextFormat("%.1f", playSeconds) << '\t' << playTicks << '\t' << runCount << '\t' << clearCount << "\n";
+    file << "accuracy\t" << paddleBounces << '\t' << perfectCatches << '\t' << bricksBroken << '\t' << ballsLost << "\n";
+    file << "paddle";
+    for (int count : paddleHits) {
+        file << '\t' << count;
+    }
+    file << "\nfield";
+    for (int count : fieldHits) {
+        file << '\t' << count;
+    }
+    file << "\n";
+    for (const auto& power : powerUps) {
+        file << "power\t" << power.first << '\t' << power.second << "\n";
+    }
+    for (const StatsRun& run : runs) {
+        file << "run\t" << run.date << '\t' << run.score << '\t' << (run.cleared ? 1 : 0) << "\n";
+    }
+    for (const auto& medal : medals) {
+        file << "medal\t" << TextFormat("%016llx", (unsigned long long) medal.first) << '\t' << (int) medal.second.medal << '\t'
+             << medal.second.name << "\n";
+        if (medal.second.stars > 0) {
+            file << "stars\t" << TextFormat("%016llx", (unsigned long long) medal.first) << '\t' << medal.second.stars << "\n";
+        }
+    }
+    return true;
+}
+
+void PlayerStats::onEvent(const GameEvent& event) {
+    switch (event.type) {
+    case EventType::PaddleBounce: {
+        paddleBounces++;
+        int bin = (event.value + 100) * PADDLE_BINS / 201;
+        paddleHits[std::clamp(bin, 0, PADDLE_BINS - 1)]++;
+        break;
+    }
+    case EventType::PerfectCatch:
+        perfectCatches++;
+        break;
+    case EventType::BrickDestroyed: {
+        bricksBroken++;
+        int column = std::clamp((int) (event.position.x / fieldSize.x * FIELD_COLUMNS), 0, FIELD_COLUMNS - 1);
+        int row = std::clamp((int) (event.position.y / fieldSize.y * FIELD_ROWS), 0, FIELD_ROWS - 1);
+        fieldHits[row * FIELD_COLUMNS + column]++;
+        break;
+    }
+    case EventType::BallLost:
+        ballsLost++;
+        break;
+    case EventType::PowerUpCollected:
+        usePowerUp(powerUpInfo((PowerUpType) event.value).id);
+        break;
+    default:
+        break;
+    }
+}
+
+void PlayerStats::addPlayTime(double seconds, int ticks) {
+    playSeconds += seconds;
+    playTicks += ticks;
+}
+
+void PlayerStats::usePowerUp(const std::string& id) {
+    powerUps[id]++;
+}
+
+Medal PlayerStats::finishRun(const std::string& levelName, uint64_t levelHash, int score, bool cleared, int livesLost, uint64_t ticks, int parSeconds,
+    int stars) {
+    runCount++;
+    clearCount += cleared ? 1 : 0;
+    char date[16];
+    time_t now = time(nullptr);
+    strftime(date, sizeof(date), "%Y-%m-%d", localtime(&now));
+    runs.push_back({date, score, cleared});
+    if ((int) runs.size() > MAX_RUNS) {
+        runs.erase(runs.begin());
+    }
+    Medal medal = medalFor(cleared, livesLost, ticks, parSeconds);
+    LevelMedal& best = medals[levelHash];
+    // the file is tab separated
+    best.name = levelName;
+    std::replace(best.name.begin(), best.name.end(), '\t', ' ');
+    best.medal = std::max(best.medal, medal);
+    best.stars = std::max(best.stars, stars);
+    return medal;
+}
+
+double PlayerStats::getPlaySeconds() const {
+    return playSeconds;
+}
+
+uint64_t PlayerStats::getPlayTicks() const {
+    return playTicks;
+}
+
+int PlayerStats::getRunCount() const {
+    return runCount;
+}
+
+int PlayerStats::getClearCount() const {
+    return clearCount;
+}
+
+int PlayerStats::getPaddleBounces() const {
+    return paddleBounces;
+}
+
+int PlayerStats::getPerfectCatches() const {
+    return perfectCatches;
+}
+
+int PlayerStats::getBricksBroken() const {
+    return bricksBroken;
+}
+
+int PlayerStats::getBallsLost() const {
+    return ballsLost;
+}
+
+const std::vector<int>& PlayerStats::getPaddleHits() const {
+    return paddleHits;
+}
+
+const std::vector<int>& PlayerStats::getFieldHits() const {
+    return fieldHits;
+}
+
+const std::map<std::string, int>& PlayerStats::getPowerUps() const {
+    return powerUps;
+}
+
+const std::vector<StatsRun>& PlayerStats::getRuns() const {
+    return runs;
+}
+
+const std::map<uint64_t, LevelMedal>& PlayerStats::getMedals() const {
+    return medals;
+}
+
+int PlayerStats::getStarTotal() const {
+    int total = 0;
+    for (const auto& medal : medals) {
+        total += medal.second.stars;
+    }
+    return total;
+}
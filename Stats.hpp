@@ -0,0 +1,118 @@
+#pragma once
+#include "raylib.h"
+#include <cstdint>
+#include <map>
+#include <string>
+#include <vector>
+#include "Events.hpp"
+
+
+enum class Medal {
+    None,
+    Bronze, // cleared
+    Silver, // cleared without losing a life, or within par
+    Gold,   // both
+};
+
+const char* medalName(Medal medal);
+Medal medalFor(bool cleared, int livesLost, uint64_t ticks, int parSeconds);
+
+struct StatsRun {
+    std::string date; // YYYY-MM-DD
+    int score = 0;
+    bool cleared = false;
+};
+
+struct LevelMedal {
+    std::string name;
+    Medal medal = Medal::None; // the best one so far
+    int stars = 0;             // and the best star rating, see starRating()
+};
+
+// What one level's play came to, for the breakdown when it ends.
+struct LevelSummary {
+    uint64_t ticks = 0;
+    int bricksBroken = 0;
+    int longestCombo = 0;
+    int powerUps = 0; // collected
+    int paddleBounces = 0;
+    int perfectCatches = 0;
+    int offCenter = 0; // every bounce's distance from the paddle's middle, 0 to 100, added up
+    int ballsLost = 0;
+
+    float bricksPerMinute() const;
+    // 0 to 100: how near the middle of the paddle the ball met it, on average
+    int accuracy() const;
+};
+
+// Fed with the same events as PlayerStats, but only from start() to the
+// level's end.
+class LevelRecorder {
+  public:
+    void start();
+    void onEvent(const GameEvent& event);
+    void finish(uint64_t ticks);
+    const LevelSummary& getSummary() const;
+
+  private:
+    LevelSummary summary;
+};
+
+// Everything one profile has done over all its sessions, kept in
+// profiles/<name>.stats: play time, where the ball meets the paddle, where
+// bricks break, power-ups used, the scores of recent runs and the best medal
+// and stars per level. Fed with the game's events; replays and the tutorial don't count.
+class PlayerStats {
+  public:
+    static const int PADDLE_BINS = 20;
+    // the field heatmap, in cells over the virtual resolution
+    static const int FIELD_COLUMNS = 32;
+    static const int FIELD_ROWS = 18;
+    static const int MAX_RUNS = 200;
+
+    static std::string statsPath(const std::string& profile);
+
+    // A profile without a file yet starts from nothing, not an error.
+    bool load(const std::string& profile, Vector2 fieldSize);
+    bool save() const;
+
+    void onEvent(const GameEvent& event);
+    void addPlayTime(double seconds, int ticks);
+    void usePowerUp(const std::string& id);
+    // A run that ended on levelHash, rated stars; returns the medal it earned.
+    Medal finishRun(const std::string& levelName, uint64_t levelHash, int score, bool cleared, int livesLost, uint64_t ticks, int parSeconds,
+        int stars);
+
+    double getPlaySeconds() const;
+    uint64_t getPlayTicks() const;
+    int getRunCount() const;
+    int getClearCount() const;
+    int getPaddleBounces() const;
+    int getPerfectCatches() const;
+    int getBricksBroken() const;
+    int getBallsLost() const;
+    const std::vector<int>& getPaddleHits() const;
+    const std::vector<int>& getFieldHits() const; // row by row
+    const std::map<std::string, int>& getPowerUps() const;
+    const std::vector<StatsRun>& getRuns() const; // oldest first, the last MAX_RUNS
+    const std::map<uint64_t, LevelMedal>& getMedals() const;
+    // The best of every level's, added up.
+    int getStarTotal() const;
+
+  private:
+    std::string profile;
+    Vector2 fieldSize = {1280, 720};
+    double playSeconds = 0;
+    uint64_t playTicks = 0;
+    int runCount = 0;
+    int clearCount = 0;
+    int paddleBounces = 0;
+    int perfectCatches = 0;
+    int bricksBroken = 0;
+    int ballsLost = 0;
+    std::vector<int> paddleHits = std::vector<int>(PADDLE_BINS);
+    std::vector<int> fieldHits = std::vector<int>(FIELD_COLUMNS * FIELD_ROWS);
+    std::map<std::string, int> powerUps;
+    std::vector<StatsRun> runs;
+    std::map<uint64_t, LevelMedal> medals;
+};
@@ -0,0 +1,169 @@
+#include "raylib.h"
+#include "StatsScreen.hpp"
+#include "Localization.hpp"
+#include "PowerUp.hpp"
+#include "Progression.hpp"
+#include <algorithm>
+
+
+static const Color MEDAL_COLORS[] = {{60, 60, 60, 255}, {205, 127, 50, 255}, {200, 200, 210, 255}, {255, 203, 0, 255}};
+// the middle tenth of the paddle, which is a perfect catch
+static const int PERFECT_BINS = 2;
+
+StatsScreen::StatsScreen() {
+    UiNode& panel = layout.root.add({"panel", Anchor::Center, {0, 0}, {1040, 640}});
+    panel.padding = 20;
+    panel.add({"summary", Anchor::TopLeft, {0, 40}, {1000, 50}});
+    panel.add({"paddle", Anchor::TopLeft, {0, 100}, {480, 130}});
+    panel.add({"powers", Anchor::TopLeft, {0, 240}, {480, 130}});
+    panel.add({"field", Anchor::TopRight, {0, 100}, {480, 290}});
+    panel.add({"scores", Anchor::TopLeft, {0, 400}, {480, 200}});
+    panel.add({"medals", Anchor::TopRight, {0, 400}, {480, 200}});
+}
+
+void StatsScreen::toggle() {
+    isOpen = !isOpen;
+}
+
+static std::string formatPlayTime(double seconds) {
+    int minutes = (int) (seconds / 60);
+    return TextFormat("%dh %02dm", minutes / 60, minutes % 60);
+}
+
+// Each section is a label over whatever it shows; returns the area under it.
+static Rectangle section(TextRenderer& text, const std::string& label, Rectangle rect, float scale) {
+    text.draw(label, {rect.x, rect.y}, 16 * scale, GRAY);
+    float labelHeight = 22 * scale;
+    return {rect.x, rect.y + labelHeight, rect.width, rect.height - labelHeight};
+}
+
+void StatsScreen::draw(TextRenderer& text, const PlayerStats& stats, const Profile& profile, Rectangle bounds, float scale) {
+    if (!isOpen) {
+        return;
+    }
+    layout.update(bounds, 0, scale * UiLayout::REFERENCE_HEIGHT / bounds.height);
+    int fontSize = 20 * scale;
+    Rectangle panel = layout.rect("panel");
+    DrawRectangle(0, 0, GetScreenWidth(), GetScreenHeight(), Fade(BLACK, 0.6f));
+    DrawRectangleRec(panel, Fade(DARKGRAY, 0.95f));
+    text.draw(tr("Stats"), {panel.x + 20 * scale, panel.y + 15 * scale}, 30 * scale, RAYWHITE);
+    text.draw(profile.name, {panel.x + 220 * scale, panel.y + 25 * scale}, 16 * scale, GRAY);
+    // how far into this player level, as a bar under the line that says so
+    int level = playerLevel(profile.xp);
+    int from = xpForPlayerLevel(level), to = xpForPlayerLevel(level + 1);
+    std::string progress = TextFormat(tr("Player level %d, %d / %d XP").c_str(), level, profile.xp, to);
+    Vector2 progressSize = text.measure(progress, 16 * scale);
+    Vector2 progressAt = {panel.x + panel.width - 20 * scale - std::max(progressSize.x, 200 * scale), panel.y + 15 * scale};
+    text.draw(progress, progressAt, 16 * scale, LIGHTGRAY);
+    Rectangle bar = {progressAt.x, progressAt.y + 22 * scale, 200 * scale, 6 * scale};
+    DrawRectangleRec(bar, Fade(BLACK, 0.5f));
+    DrawRectangleRec({bar.x, bar.y, bar.width * (profile.xp - from) / std::max(1, to - from), bar.height}, GOLD);
+
+    Rectangle summary = layout.rect("summary");
+    std::string totals = TextFormat(tr("Played %s   Runs %d   Cleared %d").c_str(), formatPlayTime(stats.getPlaySeconds()).c_str(),
+        stats.getRunCount(), stats.getClearCount());
+    text.draw(totals, {summary.x, summary.y}, fontSize, LIGHTGRAY);
+    int bounces = stats.getPaddleBounces();
+    std::string accuracy = TextFormat(tr("%d%% perfect catches   %.1f bricks per bounce   %d balls lost").c_str(),
+        bounces > 0 ? stats.getPerfectCatches() * 100 / bounces : 0, bounces > 0 ? (float) stats.getBricksBroken() / bounces : 0.0f,
+        stats.getBallsLost());
+    text.draw(accuracy, {summary.x, summary.y + 25 * scale}, fontSize, LIGHTGRAY);
+
+    // where the ball met the paddle, left end to right end
+    Rectangle paddle = section(text, tr("Where the ball meets the paddle"), layout.rect("paddle"), scale);
+    const std::vector<int>& paddleHits = stats.getPaddleHits();
+    int paddleMost = std::max(1, *std::max_element(paddleHits.begin(), paddleHits.end()));
+    float barWidth = paddle.width / paddleHits.size();
+    float paddleHeight = 8 * scale;
+    for (size_t i = 0; i < paddleHits.size(); i++) {
+        float height = (paddle.height - paddleHeight - 4 * scale) * paddleHits[i] / paddleMost;
+        bool perfect = std::abs((int) i * 2 + 1 - (int) paddleHits.size()) <= PERFECT_BINS;
+        DrawRectangleRec({paddle.x + i * barWidth + 1, paddle.y + paddle.height - paddleHeight - 4 * scale - height, barWidth - 2, height},
+            perfect ? GOLD : SKYBLUE);
+    }
+    DrawRectangleRec({paddle.x, paddle.y + paddle.height - paddleHeight, paddle.width, paddleHeight}, LIGHTGRAY);
+
+    // where bricks broke, over the whole field
+    Rectangle field = section(text, tr("Where bricks break"), layout.rect("field"), scale);
+    const std::vector<int>& fieldHits = stats.getFieldHits();
+    int fieldMost = std::max(1, *std::max_element(fieldHits.begin(), fieldHits.end()));
+    float cellWidth = field.width / PlayerStats::FIELD_COLUMNS;
+    float cellHeight = field.height / PlayerStats::FIELD_ROWS;
+    DrawRectangleRec(field, Fade(BLACK, 0.5f));
+    for (int row = 0; row < PlayerStats::FIELD_ROWS; row++) {
+        for (int column = 0; column < PlayerStats::FIELD_COLUMNS; column++) {
+            int count = fieldHits[row * PlayerStats::FIELD_COLUMNS + column];
+            if (count == 0) {
+                continue;
+            }
+            float heat = (float) count / fieldMost;
+            DrawRectangleRec({field.x + column * cellWidth, field.y + row * cellHeight, cellWidth, cellHeight},
+                ColorFromHSV(60 * (1 - heat), 0.9f, 0.4f + 0.6f * heat));
+        }
+    }
+
+    // power-ups by how often they were used
+    Rectangle powers = section(text, tr("Power-ups used"), layout.rect("powers"), scale);
+    std::vector<std::pair<std::string, int>> used(stats.getPowerUps().begin(), stats.getPowerUps().end());
+    std::stable_sort(used.begin(), used.end(), [](const auto& a, const auto& b) { return a.second > b.second; });
+    if (used.empty()) {
+        text.draw(tr("None yet"), {powers.x, powers.y}, fontSize, LIGHTGRAY);
+    }
+    float rowHeight = 22 * scale;
+    int powerRows = std::min((int) used.size(), (int) (powers.height / rowHeight));
+    for (int i = 0; i < powerRows; i++) {
+        float rowY = powers.y + i * rowHeight;
+        float width = (powers.width - 200 * scale) * used[i].second / used.front().second;
+        // ids this build no longer has are still counted, under the id
+        const PowerUp* powerUp = findPowerUp(used[i].first);
+        text.draw(powerUp ? tr(powerUp->name) : used[i].first, {powers.x, rowY}, 16 * scale, LIGHTGRAY);
+        DrawRectangleRec({powers.x + 140 * scale, rowY + 3 * scale, width, rowHeight - 8 * scale}, SKYBLUE);
+        text.draw(std::to_string(used[i].second), {powers.x + 150 * scale + width, rowY}, 16 * scale, LIGHTGRAY);
+    }
+
+    // recent scores, oldest on the left; cleared runs get a dot
+    const std::vector<StatsRun>& runs = stats.getRuns();
+    Rectangle scores = section(text, TextFormat(trn("Score over the last %d run", "Score over the last %d runs", (int) runs.size()).c_str(), (int) runs.size()),
+        layout.rect("scores"), scale);
+    DrawRectangleRec(scores, Fade(BLACK, 0.5f));
+    int scoreMost = 1;
+    for (const StatsRun& run : runs) {
+        scoreMost = std::max(scoreMost, run.score);
+    }
+    auto point = [&](size_t i) {
+        float x = runs.size() > 1 ? scores.x + scores.width * i / (runs.size() - 1) : scores.x + scores.width / 2;
+        return Vector2{x, scores.y + scores.height - scores.height * runs[i].score / scoreMost};
+    };
+    for (size_t i = 0; i < runs.size(); i++) {
+        if (i > 0) {
+            DrawLineEx(point(i - 1), point(i), 2 * scale, SKYBLUE);
+        }
+        if (runs[i].cleared) {
+            DrawCircleV(point(i), 3 * scale, GREEN);
+        }
+    }
+    if (!runs.empty()) {
+        text.draw(std::to_string(scoreMost), {scores.x + 4 * scale, scores.y + 4 * scale}, 14 * scale, GRAY);
+    }
+
+    // the best medal per level played, by name
+    Rectangle medals = section(text, tr("Medals"), layout.rect("medals"), scale);
+    std::vector<LevelMedal> levels;
+    for (const auto& medal : stats.getMedals()) {
+        levels.push_back(medal.second);
+    }
+    std::sort(levels.begin(), levels.end(), [](const LevelMedal& a, const LevelMedal& b) { return a.name < b.name; });
+    if (levels.empty()) {
+        text.draw(tr("No levels played yet"), {medals.x, medals.y}, fontSize, LIGHTGRAY);
+    }
+    const int columns = 3;
+    float medalWidth = medals.width / columns;
+    float medalHeight = 36 * scale;
+    int medalCells = std::min((int) levels.size(), columns * (int) (medals.height / medalHeight));
+    for (int i = 0; i < medalCells; i++) {
+        Rectangle cell = {medals.x + (i % columns) * medalWidth, medals.y + (i / columns) * medalHeight, medalWidth - 4 * scale, medalHeight - 4 * scale};
+        DrawRectangleRec(cell, Fade(BLACK, 0.5f));
+        DrawCircleV({cell.x + cell.height / 2, cell.y + cell.height / 2}, cell.height / 3, MEDAL_COLORS[(int) levels[i].medal]);
+        text.draw(levels[i].name, {cell.x + cell.height, cell.y + cell.height / 2 - 8 * scale}, 16 * scale, LIGHTGRAY);
+    }
+}
@@ -0,0 +1,24 @@
+#pragma once
+#include "raylib.h"
+#include "Profile.hpp"
+#include "Stats.hpp"
+#include "Text.hpp"
+#include "Ui.hpp"
+
+
+// The profile's stats on Shift+F11: totals, a histogram of where the ball met
+// the paddle, a heatmap of where bricks broke, power-ups used, recent scores
+// and the best medal per level, under the profile's name and player level. Laid out with its own UiLayout in reference
+// pixels, so it scales with the HUD.
+class StatsScreen {
+  public:
+    bool isOpen = false;
+
+    StatsScreen();
+    void toggle();
+    // bounds is the UI layout's safe area, in window pixels.
+    void draw(TextRenderer& text, const PlayerStats& stats, const Profile& profile, Rectangle bounds, float scale);
+
+  private:
+    UiLayout layout;
+};
@@ -0,0 +1,22 @@
+#include "raylib.h"
+#include "Survival.hpp"
+#include "FramePacer.hpp"
+#include <cmath>
+
+
+int DifficultyCurve::stageAt(int ticks) const {
+    return (int) (ticks / (stageSeconds * FramePacer::TICK_RATE));
+}
+
+DifficultyStage DifficultyCurve::at(int stage) const {
+    DifficultyStage result;
+    result.ballSpeed = fminf(1 + stage * speedStep, fastest);
+    result.paddleScale = fmaxf(1 - stage * paddleStep, narrowest);
+    result.debuffChance = fminf(stage * debuffStep, mostDebuffs);
+    return result;
+}
+
+const DifficultyCurve& survivalCurve() {
+    static const DifficultyCurve CURVE;
+    return CURVE;
+}
@@ -0,0 +1,32 @@
+#pragma once
+
+
+// One step of a DifficultyCurve: what the rules are multiplied by for now.
+struct DifficultyStage {
+    float ballSpeed = 1;
+    float paddleScale = 1;
+    float debuffChance = 0; // that a broken brick drops a debuff besides its own roll
+};
+
+// How survival mode (GameMode::Survival) gets harder: every stageSeconds of
+// play the ball goes faster, the paddle narrower and debuffs drop more
+// often, each up to a limit. It only looks at how long the run has lasted,
+// never at the level, so any level can be survived and the same curve is
+// played on all of them.
+struct DifficultyCurve {
+    float stageSeconds = 20;
+    float speedStep = 0.08f;  // added to the ball's speed a stage
+    float fastest = 2;
+    float paddleStep = 0.06f; // taken off the paddle's width a stage
+    float narrowest = 0.5f;
+    float debuffStep = 0.03f;
+    float mostDebuffs = 0.3f;
+
+    // Where the run is at after ticks of play; stage 0 is the rules as they are.
+    int stageAt(int ticks) const;
+    DifficultyStage at(int stage) const;
+};
+
+// The one survival plays. A constant like the other modes' rules, so a replay
+// doesn't need to carry it.
+const DifficultyCurve& survivalCurve();
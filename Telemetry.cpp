@@ -0,0 +1,83 @@
+#include "raylib.h"
+#include "Telemetry.hpp"
+#include <ctime>
+#include <filesystem>
+#include "Sanitize.hpp"
+
+
+bool Telemetry::start(const std::string& level, uint64_t seed) {
+    std::string directory = std::string(GetApplicationDirectory()) + "telemetry/";
+    std::error_code error;
+    std::filesystem::create_directories(directory, error);
+    char stamp[32];
+    time_t now = time(nullptr);
+    strftime(stamp, sizeof(stamp), "%Y%m%d-%H%M%S", localtime(&now));
+    std::string path = directory + "session-" + stamp + ".jsonl";
+
+    file.open(path, std::ios::app);
+    if (!file) {
+        TraceLog(LOG_WARNING, "TELEMETRY: Could not open %s", path.c_str());
+        return false;
+    }
+    startTime = GetTime();
+    sampleTime = 0;
+    frames = ticks = 0;
+    frameTotal = frameMax = 0;
+    write("session", startTime, TextFormat(", \"version\": %d, \"level\": %s, \"seed\": %llu, \"simulation\": %u",
+        VERSION, jsonString(level).c_str(), (unsigned long long) seed, Simulation::VERSION));
+    TraceLog(LOG_INFO, "TELEMETRY: Logging this session to %s", path.c_str());
+    return true;
+}
+
+void Telemetry::stop(GameState& game) {
+    if (!file.is_open()) {
+        return;
+    }
+    write("end", GetTime(), TextFormat(", \"score\": %d, \"lives\": %d, \"ticks\": %llu", game.score, game.lives, (unsigned long long) game.tick));
+    file.close();
+}
+
+bool Telemetry::isActive() {
+    return file.is_open();
+}
+
+void Telemetry::onEvent(const GameEvent& event) {
+    if (!file.is_open()) {
+        return;
+    }
+    // events raised by ticks carry the moment of their tick
+    double time = event.time >= 0 ? event.time : GetTime();
+    write("event", time, TextFormat(", \"name\": \"%s\", \"x\": %.1f, \"y\": %.1f, \"value\": %d",
+        eventTypeName(event.type), event.position.x, event.position.y, event.value));
+}
+
+void Telemetry::sampleFrame(float frameTime, int frameTicks) {
+    if (!file.is_open()) {
+        return;
+    }
+    frames++;
+    ticks += frameTicks;
+    frameTotal += frameTime;
+    frameMax = frameTime > frameMax ? frameTime : frameMax;
+    sampleTime += frameTime;
+    if (sampleTime < SAMPLE_SECONDS) {
+        return;
+    }
+    write("perf", GetTime(), TextFormat(", \"fps\": %.1f, \"frame_ms\": %.2f, \"frame_ms_max\": %.2f, \"ticks\": %d",
+        frames / frameTotal, frameTotal * 1000 / frames, frameMax * 1000, ticks));
+    sampleTime = 0;
+    frames = ticks = 0;
+    frameTotal = frameMax = 0;
+}
+
+void Telemetry::decision(const std::string& system, const std::string& choice, float value) {
+    if (!file.is_open()) {
+        return;
+    }
+    write("decision", GetTime(), TextFormat(", \"system\": %s, \"choice\": %s, \"value\": %.3f",
+        jsonString(system).c_str(), jsonString(choice).c_str(), value));
+}
+
+void Telemetry::write(const char* type, double time, const std::string& fields) {
+    file << "{\"type\": \"" << type << "\", \"t\": " << TextFormat("%.3f", time - startTime) << fields << "}\n";
+}
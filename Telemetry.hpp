@@ -0,0 +1,46 @@
+#pragma once
+#include <cstdint>
+#include <fstream>
+#include <string>
+#include "Events.hpp"
+#include "Simulation.hpp"
+
+
+// Opt-in session log: only runs with the "Session log" setting on or
+// --telemetry given, and nothing leaves the machine. Each session is one
+// telemetry/session-<time>.jsonl next to the game, one JSON object per line,
+// each with a "type" and "t" (seconds into the session):
+//
+//   session   level, seed, simulation version; the first line
+//   event     every game event (name, x, y, value)
+//   perf      frame time average/max and fps over the last SAMPLE_SECONDS
+//   decision  a choice a system made for the player (system, choice, value)
+//   end       final score, lives and ticks; missing if the game crashed
+//
+// The analytics, calibration and difficulty-model tools read these files.
+class Telemetry {
+  public:
+    static constexpr float SAMPLE_SECONDS = 5;
+    static const int VERSION = 1;
+
+    bool start(const std::string& level, uint64_t seed);
+    void stop(GameState& game);
+    bool isActive();
+
+    void onEvent(const GameEvent& event);
+    // Once per frame; writes a perf line every SAMPLE_SECONDS.
+    void sampleFrame(float frameTime, int ticks);
+    void decision(const std::string& system, const std::string& choice, float value = 0);
+
+  private:
+    std::ofstream file;
+    double startTime = 0;
+    float sampleTime = 0;
+    int frames = 0;
+    int ticks = 0;
+    float frameTotal = 0;
+    float frameMax = 0;
+
+    // fields: the rest of the object after type and t, starting with a comma
+    void write(const char* type, double time, const std::string& fields);
+};
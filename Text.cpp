@@ -0,0 +1,266 @@
+#include "raylib.h"
+#include "Text.hpp"
+#include <fstream>
+#include <sstream>
+
+
+static std::vector<int> decode(const std::string& text) {
+    std::vector<int> codepoints;
+    const char* cursor = text.c_str();
+    while (*cursor) {
+        int size = 0;
+        codepoints.push_back(GetCodepointNext(cursor, &size));
+        cursor += size > 0 ? size : 1;
+    }
+    return codepoints;
+}
+
+static void collectGlyphs(FontFace& face) {
+    face.codepoints.clear();
+    for (int i = 0; i < face.font.glyphCount; i++) {
+        face.codepoints.insert(face.font.glyphs[i].value);
+    }
+}
+
+bool TextRenderer::addFont(const std::string& path) {
+    if (!FileExists(path.c_str())) {
+        return false;
+    }
+    FontFace face;
+    face.path = path;
+    face.font = LoadFontEx(path.c_str(), BASE_SIZE, nullptr, 0);
+    if (!IsFontReady(face.font)) {
+        TraceLog(LOG_WARNING, "TEXT: Could not load font %s", path.c_str());
+        return false;
+    }
+    face.owned = true;
+    collectGlyphs(face);
+
+    std::ifstream kern(path + ".kern");
+    std::string line;
+    while (std::getline(kern, line)) {
+        std::istringstream fields(line);
+        int first, second;
+        float offset;
+        if (fields >> std::hex >> first >> second >> std::dec >> offset) {
+            face.kerning[{first, second}] = offset;
+        }
+    }
+
+    // keep the built-in font last
+    if (!faces.empty() && faces.back().path.empty()) {
+        faces.insert(faces.end() - 1, face);
+    } else {
+        faces.push_back(face);
+    }
+    return true;
+}
+
+void TextRenderer::loadDefaultChain() {
+    std::string directory = std::string(GetApplicationDirectory()) + "assets/fonts/";
+    std::ifstream list(directory + "fallback.txt");
+    std::string line;
+    while (std::getline(list, line)) {
+        if (!line.empty() && line[0] != '#') {
+            addFont(directory + line);
+        }
+    }
+    FontFace builtin;
+    builtin.font = GetFontDefault();
+    collectGlyphs(builtin);
+    faces.push_back(builtin);
+}
+
+void TextRenderer::unload() {
+    for (FontFace& face : faces) {
+        if (face.owned) {
+            UnloadFont(face.font);
+        }
+    }
+    faces.clear();
+    requested.clear();
+}
+
+int TextRenderer::faceFor(int codepoint) {
+    for (size_t i = 0; i < faces.size(); i++) {
+        if (faces[i].codepoints.count(codepoint)) {
+            return i;
+        }
+    }
+    return faces.empty() ? -1 : faces.size() - 1;
+}
+
+// Reloads every file-backed face with the default glyph set plus everything
+// requested so far. Faces only keep glyphs they really contain, which is what
+// lets the next face in the chain pick up the rest.
+void TextRenderer::rebuild() {
+    std::vector<int> codepoints;
+    for (int codepoint = 32; codepoint < 127; codepoint++) {
+        codepoints.push_back(codepoint);
+    }
+    for (int codepoint : requested) {
+        if (codepoint >= 127) {
+            codepoints.push_back(codepoint);
+        }
+    }
+
+    for (FontFace& face : faces) {
+        if (!face.owned) {
+            continue;
+        }
+        Font reloaded = LoadFontEx(face.path.c_str(), BASE_SIZE, codepoints.data(), codepoints.size());
+        if (!IsFontReady(reloaded)) {
+            continue;
+        }
+        UnloadFont(face.font);
+        face.font = reloaded;
+        collectGlyphs(face);
+        SetTextureFilter(face.font.texture, TEXTURE_FILTER_BILINEAR);
+
+        // LoadFontEx fills missing glyphs with empty images; drop those so
+        // the codepoint falls through to the next face
+        for (int i = 0; i < face.font.glyphCount; i++) {
+            GlyphInfo& glyph = face.font.glyphs[i];
+            if (glyph.value > 32 && glyph.image.width == 0) {
+                face.codepoints.erase(glyph.value);
+            }
+        }
+    }
+}
+
+void TextRenderer::prepare(const std::string& text) {
+    bool missing = false;
+    for (int codepoint : decode(text)) {
+        if (codepoint >= 127 && requested.insert(codepoint).second) {
+            missing = true;
+        }
+    }
+    if (missing) {
+        rebuild();
+    }
+}
+
+float TextRenderer::advance(int face, int codepoint, float scale) {
+    Font& font = faces[face].font;
+    int index = GetGlyphIndex(font, codepoint);
+    if (font.glyphs[index].advanceX > 0) {
+        return font.glyphs[index].advanceX * scale;
+    }
+    return font.recs[index].width * scale;
+}
+
+float TextRenderer::kerning(int face, int previous, int codepoint, float scale) {
+    const auto& pairs = faces[face].kerning;
+    auto it = pairs.find({previous, codepoint});
+    return it == pairs.end() ? 0 : it->second * scale;
+}
+
+Vector2 TextRenderer::measure(const std::string& text, float size, float spacing) {
+    prepare(text);
+    float width = 0;
+    float lineWidth = 0;
+    int lines = 1;
+    int previous = 0;
+    for (int codepoint : decode(text)) {
+        if (codepoint == '\n') {
+            width = lineWidth > width ? lineWidth : width;
+            lineWidth = 0;
+            previous = 0;
+            lines++;
+            continue;
+        }
+        int face = faceFor(codepoint);
+        if (face < 0) {
+            continue;
+        }
+        float scale = size / faces[face].font.baseSize;
+        lineWidth += kerning(face, previous, codepoint, scale) + advance(face, codepoint, scale) + spacing;
+        previous = codepoint;
+    }
+    width = lineWidth > width ? lineWidth : width;
+    return {width, lines * size * 1.2f};
+}
+
+void TextRenderer::draw(const std::string& text, Vector2 position, float size, Color color, float spacing) {
+    prepare(text);
+    Vector2 pen = position;
+    int previous = 0;
+    for (int codepoint : decode(text)) {
+        if (codepoint == '\n') {
+            pen.x = position.x;
+            pen.y += size * 1.2f;
+            previous = 0;
+            continue;
+        }
+        int face = faceFor(codepoint);
+        if (face < 0) {
+            continue;
+        }
+        float scale = size / faces[face].font.baseSize;
+        pen.x += kerning(face, previous, codepoint, scale);
+        if (codepoint != ' ' && codepoint != '\t') {
+            DrawTextCodepoint(faces[face].font, codepoint, pen, size, color);
+        }
+        pen.x += advance(face, codepoint, scale) + spacing;
+        previous = codepoint;
+    }
+}
+
+std::vector<std::string> TextRenderer::wrap(const std::string& text, float size, float maxWidth, float spacing) {
+    std::vector<std::string> lines;
+    std::string line;
+    size_t lastSpace = std::string::npos;
+    const char* cursor = text.c_str();
+
+    while (*cursor) {
+        int byteCount = 0;
+        int codepoint = GetCodepointNext(cursor, &byteCount);
+        byteCount = byteCount > 0 ? byteCount : 1;
+        std::string character(cursor, byteCount);
+        cursor += byteCount;
+
+        if (codepoint == '\n') {
+            lines.push_back(line);
+            line.clear();
+            lastSpace = std::string::npos;
+            continue;
+        }
+        std::string candidate = line + character;
+        if (!line.empty() && measure(candidate, size, spacing).x > maxWidth) {
+            if (codepoint == ' ') {
+                lines.push_back(line);
+                line.clear();
+                lastSpace = std::string::npos;
+                continue;
+            }
+            if (lastSpace != std::string::npos) {
+                lines.push_back(line.substr(0, lastSpace));
+                line = line.substr(lastSpace + 1) + character;
+            } else {
+                lines.push_back(line);
+                line = character;
+            }
+            lastSpace = std::string::npos;
+            continue;
+        }
+        if (codepoint == ' ') {
+            lastSpace = line.size();
+        }
+        line = candidate;
+    }
+    if (!line.empty()) {
+        lines.push_back(line);
+    }
+    return lines;
+}
+
+void TextRenderer::drawWrapped(const std::string& text, Rectangle bounds, float size, Color color, float spacing) {
+    float y = bounds.y;
+    for (const std::string& line : wrap(text, size, bounds.width, spacing)) {
+        if (y + size > bounds.y + bounds.height) {
+            break;
+        }
+        draw(line, {bounds.x, y}, size, color, spacing);
+        y += size * 1.2f;
+    }
+}
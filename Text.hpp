@@ -0,0 +1,53 @@
+#pragma once
+#include "raylib.h"
+#include <map>
+#include <set>
+#include <string>
+#include <vector>
+
+
+struct FontFace {
+    std::string path; // empty for raylib's built-in font
+    Font font;
+    std::set<int> codepoints; // glyphs actually present in the atlas
+    std::map<std::pair<int, int>, float> kerning; // pair -> offset at base size
+    bool owned = false;
+};
+
+// Draws UTF-8 text through an ordered chain of fonts: each codepoint comes
+// from the first face that has it, so e.g. CJK falls through from the UI font
+// to a CJK font instead of drawing tofu. Glyph atlases are built lazily for
+// the codepoints that are actually used and rebuilt when new ones show up.
+class TextRenderer {
+  public:
+    static constexpr int BASE_SIZE = 32;
+
+    // Adds a face at the end of the fallback chain. A "<font>.kern" file next
+    // to it can list "first second offset" kerning pairs (codepoints in hex).
+    bool addFont(const std::string& path);
+    // Loads assets/fonts/fallback.txt (one font file per line, in order) and
+    // always ends the chain with the built-in font.
+    void loadDefaultChain();
+    void unload();
+
+    // Makes sure every codepoint of text has a glyph; call before drawing new
+    // strings (draw() does it too, but doing it up front avoids a mid-frame
+    // atlas rebuild).
+    void prepare(const std::string& text);
+
+    Vector2 measure(const std::string& text, float size, float spacing = 1);
+    void draw(const std::string& text, Vector2 position, float size, Color color, float spacing = 1);
+    // Breaks text into lines no wider than maxWidth, on spaces where possible
+    // and between any two characters for scripts without spaces.
+    std::vector<std::string> wrap(const std::string& text, float size, float maxWidth, float spacing = 1);
+    void drawWrapped(const std::string& text, Rectangle bounds, float size, Color color, float spacing = 1);
+
+  private:
+    std::vector<FontFace> faces;
+    std::set<int> requested;
+
+    int faceFor(int codepoint);
+    float advance(int face, int codepoint, float scale);
+    float kerning(int face, int previous, int codepoint, float scale);
+    void rebuild();
+};
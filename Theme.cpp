@@ -0,0 +1,167 @@
+#include "raylib.h"
+#include "Theme.hpp"
+#include "Log.hpp"
+#include "Xml.hpp"
+#include <cstdlib>
+
+
+static const char* MATERIAL_NAMES[] = {"standard", "glass", "stone", "metal"};
+
+std::string Theme::sprite(const std::string& role) const {
+    auto it = sprites.find(role);
+    return it == sprites.end() ? role : it->second;
+}
+
+// "#rrggbb" or "#rrggbbaa"
+Color parseColor(const std::string& text, Color fallback) {
+    if (text.size() != 7 && text.size() != 9) {
+        return fallback;
+    }
+    if (text[0] != '#') {
+        return fallback;
+    }
+    char* end = nullptr;
+    unsigned long value = strtoul(text.c_str() + 1, &end, 16);
+    if (*end != '\0') {
+        return fallback;
+    }
+    if (text.size() == 7) {
+        value = (value << 8) | 0xff;
+    }
+    return GetColor((unsigned int) value);
+}
+
+void ThemeRegistry::scan() {
+    LogSpan span("THEME", "scan");
+    themes.clear();
+    themes["default"] = Theme();
+
+    std::string root = GetApplicationDirectory();
+    scanDirectory(root + "themes");
+
+    std::string mods = root + "mods";
+    if (DirectoryExists(mods.c_str())) {
+        FilePathList modDirectories = LoadDirectoryFiles(mods.c_str());
+        for (unsigned int i = 0; i < modDirectories.count; i++) {
+            scanDirectory(std::string(modDirectories.paths[i]) + "/themes");
+        }
+        UnloadDirectoryFiles(modDirectories);
+    }
+}
+
+void ThemeRegistry::scanDirectory(const std::string& directory) {
+    if (!DirectoryExists(directory.c_str())) {
+        return;
+    }
+    FilePathList entries = LoadDirectoryFiles(directory.c_str());
+    for (unsigned int i = 0; i < entries.count; i++) {
+        std::string themeDirectory = entries.paths[i];
+        std::string path = themeDirectory + "/theme.xml";
+        if (!FileExists(path.c_str())) {
+            continue;
+        }
+        Theme theme;
+        theme.directory = themeDirectory;
+        if (loadTheme(path, theme)) {
+            if (themes.count(theme.id) && theme.id != "default") {
+                TraceLog(LOG_INFO, "THEME: %s overrides theme '%s'", path.c_str(), theme.id.c_str());
+            }
+            themes[theme.id] = theme;
+        }
+    }
+    UnloadDirectoryFiles(entries);
+}
+
+//   <theme id="neon" name="Neon" bloom="true" font="fonts/neon.ttf" music="neon">
+//     <palette background="#05001a" paddle="#ff2bd6" ball="#ffffff" text="#9ef"
+//              wall="#30305a" standard="#00f0ff" glass="#9d4dff" stone="#ff8a00" metal="#f5f5f5"/>
+//     <sprite role="paddle" region="neon_paddle"/>
+//     <layer image="bg/stars.png" speed-x="0" speed-y="12" ball-parallax="0.01" tint="#ffffffaa"/>
+//     <particles material="glass" color="#9d4dff" count="30" min-speed="80" max-speed="240"
+//                min-life="0.2" max-life="0.5" size="2" gravity="300"/>
+//   </theme>
+bool ThemeRegistry::loadTheme(const std::string& path, Theme& theme) {
+    XmlNode root;
+    XmlError error;
+    if (!loadXmlFile(path, root, error) || root.name != "theme") {
+        TraceLog(LOG_WARNING, "THEME: Skipping %s: %s", path.c_str(),
+            error.message.empty() ? "root element must be <theme>" : error.describe().c_str());
+        return false;
+    }
+    theme.id = root.attribute("id", GetFileName(theme.directory.c_str()));
+    theme.name = root.attribute("name", theme.id);
+    theme.bloom = root.attribute("bloom") == "true";
+    theme.music = root.attribute("music", theme.music);
+    if (root.hasAttribute("font")) {
+        theme.font = theme.directory + "/" + root.attribute("font");
+    }
+
+    if (const XmlNode* palette = root.child("palette")) {
+        ThemePalette& colors = theme.palette;
+        colors.background = parseColor(palette->attribute("background"), colors.background);
+        colors.paddle = parseColor(palette->attribute("paddle"), colors.paddle);
+        colors.ball = parseColor(palette->attribute("ball"), colors.ball);
+        colors.text = parseColor(palette->attribute("text"), colors.text);
+        colors.wall = parseColor(palette->attribute("wall"), colors.wall);
+        for (int i = 0; i < 4; i++) {
+            colors.bricks[i] = parseColor(palette->attribute(MATERIAL_NAMES[i]), colors.bricks[i]);
+        }
+    }
+
+    // a plain background="..." is shorthand for a single static layer
+    if (root.hasAttribute("background")) {
+        BackgroundLayer layer;
+        layer.image = root.attribute("background");
+        theme.background.push_back(layer);
+    }
+    for (const XmlNode* node : root.childrenNamed("layer")) {
+        BackgroundLayer layer;
+        layer.image = node->attribute("image");
+        layer.scrollSpeed = {node->attributeFloat("speed-x", 0), node->attributeFloat("speed-y", 0)};
+        layer.ballParallax = node->attributeFloat("ball-parallax", 0);
+        layer.tint = parseColor(node->attribute("tint"), WHITE);
+        theme.background.push_back(layer);
+    }
+
+    for (const XmlNode* node : root.childrenNamed("sprite")) {
+        theme.sprites[node->attribute("role")] = node->attribute("region");
+    }
+
+    for (const XmlNode* node : root.childrenNamed("particles")) {
+        BrickMaterial material = parseBrickMaterial(node->attribute("material", "standard").c_str());
+        ParticleStyle style = defaultParticleStyle(material);
+        style.color = parseColor(node->attribute("color"), style.color);
+        style.count = node->attributeInt("count", style.count);
+        style.minSpeed = node->attributeFloat("min-speed", style.minSpeed);
+        style.maxSpeed = node->attributeFloat("max-speed", style.maxSpeed);
+        style.minLifetime = node->attributeFloat("min-life", style.minLifetime);
+        style.maxLifetime = node->attributeFloat("max-life", style.maxLifetime);
+        style.size = node->attributeFloat("size", style.size);
+        style.gravity = node->attributeFloat("gravity", style.gravity);
+        theme.particles[material] = style;
+    }
+    return true;
+}
+
+bool ThemeRegistry::has(const std::string& id) {
+    return themes.count(id) > 0;
+}
+
+const Theme& ThemeRegistry::get(const std::string& id) {
+    auto it = themes.find(id);
+    if (it == themes.end()) {
+        if (themes.empty()) {
+            themes["default"] = Theme();
+        }
+        return themes["default"];
+    }
+    return it->second;
+}
+
+std::vector<std::string> ThemeRegistry::ids() {
+    std::vector<std::string> result;
+    for (auto& entry : themes) {
+        result.push_back(entry.first);
+    }
+    return result;
+}
@@ -0,0 +1,55 @@
+#pragma once
+#include "raylib.h"
+#include <map>
+#include <string>
+#include <vector>
+#include "Atlas.hpp"
+#include "Background.hpp"
+#include "Brick.hpp"
+#include "Particles.hpp"
+
+
+struct ThemePalette {
+    Color background = BLACK;
+    Color paddle = RAYWHITE;
+    Color ball = RAYWHITE;
+    Color text = LIGHTGRAY;
+    Color wall = BLANK;
+    Color bricks[4] = {RAYWHITE, RAYWHITE, RAYWHITE, RAYWHITE}; // by BrickMaterial
+};
+
+// Everything that decides how the game looks. Sprite names refer to regions
+// of the theme's own atlas (packed from its sprites/ folder) or, if it has
+// none, of the game atlas.
+struct Theme {
+    std::string id = "default";
+    std::string name = "Default";
+    std::string directory;
+    ThemePalette palette;
+    std::vector<BackgroundLayer> background; // back to front, drawn over palette.background
+    std::map<std::string, std::string> sprites; // role ("paddle", "brick_glass"...) -> region
+    std::map<BrickMaterial, ParticleStyle> particles;
+    std::string font; // path, empty to keep the default chain
+    bool bloom = false;
+    std::string music = "default"; // track id, see musicTrackPath()
+
+    std::string sprite(const std::string& role) const;
+};
+
+// Themes live in themes/<id>/theme.xml, plus mods/<mod>/themes/<id>/theme.xml
+// for user content. The built-in default is always available.
+class ThemeRegistry {
+  public:
+    void scan();
+    bool has(const std::string& id);
+    const Theme& get(const std::string& id); // falls back to "default"
+    std::vector<std::string> ids();
+
+  private:
+    std::map<std::string, Theme> themes;
+
+    void scanDirectory(const std::string& directory);
+    bool loadTheme(const std::string& path, Theme& theme);
+};
+
+Color parseColor(const std::string& text, Color fallback);
@@ -0,0 +1,12 @@
+#pragma once
+
+
+// Time attack (GameMode::TimeAttack): the run is against a clock rather
+// than lives. Bricks with a time bonus drop extra seconds to catch, a lost
+// ball costs seconds instead of a life, and the run is over when the clock
+// runs out or the level is cleared, what's left of it then scoring points.
+struct TimeAttackRules {
+    static constexpr int SECONDS = 90;           // on the clock at the start
+    static constexpr int LOST_BALL_SECONDS = 5;
+    static constexpr int POINTS_PER_SECOND_LEFT = 10;
+};
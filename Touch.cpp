@@ -0,0 +1,70 @@
+#include "raylib.h"
+#include "Touch.hpp"
+#include <cmath>
+
+
+static const float TAP_SECONDS = 0.25f;
+static const float TAP_SLOP = 12; // virtual units a tap may wander before it is a drag
+
+void TouchControls::update(float deltaTime, float viewportScale, float paddleX, float minX, float maxX) {
+    tap = false;
+    twoFingerTap = false;
+    int count = enabled ? GetTouchPointCount() : 0;
+    if (viewportScale <= 0) {
+        viewportScale = 1;
+    }
+
+    if (count > 0 && lastCount == 0) {
+        touchTime = 0;
+        moved = 0;
+        maxCount = count;
+        start = GetTouchPosition(0);
+        dragStartPaddle = paddleX;
+    }
+    if (count > 0) {
+        touchTime += deltaTime;
+        maxCount = count > maxCount ? count : maxCount;
+        // touch points are raw window pixels, unlike the viewport-mapped mouse
+        Vector2 point = GetTouchPosition(0);
+        float offset = (point.x - start.x) / viewportScale;
+        moved = fmaxf(moved, hypotf(point.x - start.x, point.y - start.y) / viewportScale);
+        if (maxCount == 1 && moved > TAP_SLOP) {
+            dragging = true;
+        }
+        if (dragging) {
+            target = fminf(fmaxf(dragStartPaddle + offset, minX), maxX);
+        }
+    }
+    // only judged once every finger is up, so two fingers lifting a frame
+    // apart still count as one two-finger tap
+    if (count == 0 && lastCount > 0) {
+        if (!dragging && touchTime < TAP_SECONDS && moved <= TAP_SLOP) {
+            if (maxCount >= 2) {
+                twoFingerTap = true;
+            } else {
+                tap = true;
+            }
+        }
+        dragging = false;
+    }
+    lastCount = count;
+}
+
+bool TouchControls::isDragging() {
+    return dragging;
+}
+
+float TouchControls::getTarget() {
+    return target;
+}
+
+bool TouchControls::tapped(Vector2* position) {
+    if (tap && position) {
+        *position = start;
+    }
+    return tap;
+}
+
+bool TouchControls::twoFingerTapped() {
+    return twoFingerTap;
+}
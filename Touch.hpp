@@ -0,0 +1,36 @@
+#pragma once
+#include "raylib.h"
+
+
+// Touch backend, read straight from raylib's touch points rather than its
+// gesture module so a tap that becomes a drag is never reported as both.
+// Dragging anywhere moves the paddle by the finger's offset (the finger never
+// has to cover the paddle), a quick one-finger tap launches and a two-finger
+// tap pauses. Windows tablets deliver touch as the primary pointer, web and
+// mobile builds as real multi-touch; both end up here.
+class TouchControls {
+  public:
+    bool enabled = false;
+
+    // Call once per frame before the simulation ticks, with the paddle's
+    // center and the range it may move in (virtual units).
+    void update(float deltaTime, float viewportScale, float paddleX, float minX, float maxX);
+    bool isDragging();
+    float getTarget();
+    // A one-finger tap ended this frame; position is where it started, in
+    // window pixels, so menus and on-screen buttons can hit-test it.
+    bool tapped(Vector2* position = nullptr);
+    bool twoFingerTapped();
+
+  private:
+    int lastCount = 0;
+    int maxCount = 0;
+    float touchTime = 0;
+    float moved = 0;
+    Vector2 start = {0, 0};
+    float dragStartPaddle = 0;
+    float target = 0;
+    bool dragging = false;
+    bool tap = false;
+    bool twoFingerTap = false;
+};
@@ -0,0 +1,181 @@
+#include "raylib.h"
+#include "Tutorial.hpp"
+#include "Localization.hpp"
+#include <algorithm>
+#include <cmath>
+
+
+static const char* const CONDITIONS[] = {
+    "move", "launch", "pause", "paddle_bounce", "brick", "combo", "perfect_catch", "ball_lost", "clear",
+};
+// how far the paddle has to travel for "move"
+static const float MOVE_DISTANCE = 200;
+
+std::vector<std::string> Tutorial::levelPaths() {
+    std::vector<std::string> found;
+    std::string directory = std::string(GetApplicationDirectory()) + "levels/tutorial";
+    if (!DirectoryExists(directory.c_str())) {
+        return found;
+    }
+    FilePathList files = LoadDirectoryFilesEx(directory.c_str(), ".blvl", false);
+    for (unsigned int i = 0; i < files.count; i++) {
+        found.push_back(files.paths[i]);
+    }
+    UnloadDirectoryFiles(files);
+    std::sort(found.begin(), found.end());
+    return found;
+}
+
+bool Tutorial::start(int index) {
+    paths = levelPaths();
+    if (index < 0 || index >= (int) paths.size()) {
+        levelIndex = -1;
+        return false;
+    }
+    levelIndex = index;
+    return true;
+}
+
+void Tutorial::stop() {
+    levelIndex = -1;
+    prompts.clear();
+}
+
+bool Tutorial::isActive() const {
+    return levelIndex >= 0;
+}
+
+int Tutorial::getLevelIndex() const {
+    return levelIndex;
+}
+
+int Tutorial::getLevelCount() const {
+    return (int) paths.size();
+}
+
+const std::string& Tutorial::getLevelPath() const {
+    static const std::string none;
+    return isActive() ? paths[levelIndex] : none;
+}
+
+void Tutorial::beginLevel(const Level& level, const GameState& state) {
+    prompts = level.prompts;
+    if (prompts.empty()) {
+        prompts.push_back({"Break every brick", "clear", 1});
+    }
+    for (const LevelPrompt& prompt : prompts) {
+        if (std::find(std::begin(CONDITIONS), std::end(CONDITIONS), prompt.until) == std::end(CONDITIONS)) {
+            TraceLog(LOG_WARNING, "TUTORIAL: %s: prompt waits for unknown '%s', it can only be skipped", level.path.c_str(), prompt.until.c_str());
+        }
+    }
+    step = 0;
+    progress = 0;
+    paddleStart = state.player.position.x;
+}
+
+void Tutorial::advance(const std::string& condition, int amount) {
+    if (step >= (int) prompts.size() || prompts[step].until != condition) {
+        return;
+    }
+    progress += amount;
+    if (progress >= prompts[step].count) {
+        step++;
+        progress = 0;
+        paddleStart = NAN;
+    }
+}
+
+void Tutorial::onEvent(const GameEvent& event) {
+    if (!isActive()) {
+        return;
+    }
+    switch (event.type) {
+        case EventType::PaddleBounce:
+            advance("paddle_bounce");
+            break;
+        case EventType::BrickDestroyed:
+            advance("brick");
+            break;
+        case EventType::ComboIncreased:
+            // count is the combo to reach, not how many combos
+            if (step < (int) prompts.size() && event.value >= prompts[step].count) {
+                advance("combo", prompts[step].count);
+            }
+            break;
+        case EventType::PerfectCatch:
+            advance("perfect_catch");
+            break;
+        case EventType::BallLost:
+            advance("ball_lost");
+            break;
+        default:
+            break;
+    }
+}
+
+void Tutorial::update(const GameState& state, bool paused, bool cleared) {
+    if (!isActive()) {
+        return;
+    }
+    // measured from wherever the paddle was when the prompt came up
+    if (std::isnan(paddleStart)) {
+        paddleStart = state.player.position.x;
+    }
+    if (fabsf(state.player.position.x - paddleStart) >= MOVE_DISTANCE) {
+        advance("move");
+    }
+    if (!state.ballHeld) {
+        advance("launch");
+    }
+    if (paused) {
+        advance("pause");
+    }
+    if (cleared) {
+        advance("clear");
+    }
+}
+
+bool Tutorial::isLevelDone() const {
+    return isActive() && step >= (int) prompts.size();
+}
+
+bool Tutorial::nextLevel() {
+    if (!isActive() || levelIndex + 1 >= (int) paths.size()) {
+        stop();
+        return false;
+    }
+    levelIndex++;
+    return true;
+}
+
+// "Press {launch}" -> "Press Space"
+static std::string fillBindings(const std::string& text, const InputBindings& bindings) {
+    std::string filled = text;
+    for (int i = 0; i < ACTION_COUNT; i++) {
+        // "bind_move_left" -> "{move_left}"
+        std::string name = "{" + std::string(actionKey((Action) i) + 5) + "}";
+        size_t found;
+        while ((found = filled.find(name)) != std::string::npos) {
+            filled.replace(found, name.size(), describeInput(bindings.get((Action) i, 0)));
+        }
+    }
+    return filled;
+}
+
+void Tutorial::draw(TextRenderer& text, const InputBindings& bindings, Rectangle bounds, float scale, Color color) {
+    if (!isActive() || step >= (int) prompts.size()) {
+        return;
+    }
+    const LevelPrompt& prompt = prompts[step];
+    // translated before the bindings go in, so catalogs see the {action}
+    std::string line = fillBindings(tr(prompt.text), bindings);
+    if (prompt.count > 1 && prompt.until != "combo") {
+        line += TextFormat("  (%d/%d)", progress, prompt.count);
+    }
+    DrawRectangleRec(bounds, Fade(BLACK, 0.5f));
+    Vector2 size = text.measure(line, 20 * scale);
+    text.draw(line, {bounds.x + (bounds.width - size.x) / 2, bounds.y + 6 * scale}, 20 * scale, color);
+    std::string footer = TextFormat(tr("Tutorial %d/%d, step %d/%d   F1 to skip it").c_str(), levelIndex + 1, (int) paths.size(), step + 1, (int) prompts.size());
+    size = text.measure(footer, 14 * scale);
+    text.draw(footer, {bounds.x + (bounds.width - size.x) / 2, bounds.y + bounds.height - 20 * scale}, 14 * scale, Fade(color, 0.7f));
+}
@@ -0,0 +1,61 @@
+#pragma once
+#include "raylib.h"
+#include <string>
+#include <vector>
+#include "Bindings.hpp"
+#include "Events.hpp"
+#include "Level.hpp"
+#include "Simulation.hpp"
+#include "Text.hpp"
+
+
+// The levels in levels/tutorial, played in file name order for a fresh
+// profile (or with --tutorial). Each one walks through its <prompt>s one at a
+// time; a prompt stays up until the player has done what it asks:
+//
+//   move            moved the paddle a good way
+//   launch          served the ball
+//   pause           paused the game
+//   paddle_bounce   returned the ball count times
+//   brick           broke count bricks
+//   combo           broke count bricks before the ball came back
+//   perfect_catch   caught the ball on the middle of the paddle count times
+//   ball_lost       let the ball past count times
+//   clear           cleared the level
+//
+// A level is done once its last prompt is. Prompt text may name an action in
+// braces ("Press {launch}") and gets the player's current binding for it.
+// Ticking "Tutorial finished" in the settings skips the rest.
+class Tutorial {
+  public:
+    static std::vector<std::string> levelPaths();
+
+    // Plays from the level at index; false when there is no such level.
+    bool start(int index);
+    void stop();
+    bool isActive() const;
+    int getLevelIndex() const;
+    int getLevelCount() const;
+    const std::string& getLevelPath() const;
+
+    // The prompts of the tutorial level that was just loaded.
+    void beginLevel(const Level& level, const GameState& state);
+    void onEvent(const GameEvent& event);
+    void update(const GameState& state, bool paused, bool cleared);
+    // Whether every prompt of the level has been done.
+    bool isLevelDone() const;
+    // Moves on to the next level; false after the last one.
+    bool nextLevel();
+
+    void draw(TextRenderer& text, const InputBindings& bindings, Rectangle bounds, float scale, Color color);
+
+  private:
+    std::vector<std::string> paths;
+    int levelIndex = -1;
+    std::vector<LevelPrompt> prompts;
+    int step = 0;
+    int progress = 0;
+    float paddleStart = 0;
+
+    void advance(const std::string& condition, int amount = 1);
+};
@@ -0,0 +1,97 @@
+#include "raylib.h"
+#include "Ui.hpp"
+
+
+UiNode& UiNode::add(const UiNode& child) {
+    children.push_back(child);
+    return children.back();
+}
+
+// Top-left corner for something of the given size anchored inside area
+Vector2 anchorPoint(Anchor anchor, Rectangle area, Vector2 size) {
+    float left = area.x;
+    float centerX = area.x + (area.width - size.x) / 2;
+    float right = area.x + area.width - size.x;
+    float top = area.y;
+    float centerY = area.y + (area.height - size.y) / 2;
+    float bottom = area.y + area.height - size.y;
+
+    switch (anchor) {
+    case Anchor::TopLeft: return {left, top};
+    case Anchor::Top: return {centerX, top};
+    case Anchor::TopRight: return {right, top};
+    case Anchor::Left: return {left, centerY};
+    case Anchor::Center: return {centerX, centerY};
+    case Anchor::Right: return {right, centerY};
+    case Anchor::BottomLeft: return {left, bottom};
+    case Anchor::Bottom: return {centerX, bottom};
+    case Anchor::BottomRight: return {right, bottom};
+    case Anchor::Stretch: return {left, top};
+    }
+    return {left, top};
+}
+
+void UiLayout::update(Rectangle bounds, float safeArea, float userScale) {
+    scale = bounds.height / REFERENCE_HEIGHT * userScale;
+    Rectangle safe = {
+        bounds.x + bounds.width * safeArea,
+        bounds.y + bounds.height * safeArea,
+        bounds.width * (1 - 2 * safeArea),
+        bounds.height * (1 - 2 * safeArea),
+    };
+    root.anchor = Anchor::Stretch;
+    layout(root, safe);
+}
+
+void UiLayout::layout(UiNode& node, Rectangle parent) {
+    if (node.anchor == Anchor::Stretch) {
+        node.rect = {
+            parent.x + node.offset.x * scale,
+            parent.y + node.offset.y * scale,
+            parent.width - node.offset.x * scale * 2,
+            parent.height - node.offset.y * scale * 2,
+        };
+    } else {
+        Vector2 size = {node.size.x * scale, node.size.y * scale};
+        Vector2 corner = anchorPoint(node.anchor, parent, size);
+        // offsets push away from the anchored edge
+        float directionX = node.anchor == Anchor::TopRight || node.anchor == Anchor::Right || node.anchor == Anchor::BottomRight ? -1 : 1;
+        float directionY = node.anchor == Anchor::BottomLeft || node.anchor == Anchor::Bottom || node.anchor == Anchor::BottomRight ? -1 : 1;
+        node.rect = {corner.x + node.offset.x * scale * directionX, corner.y + node.offset.y * scale * directionY, size.x, size.y};
+    }
+
+    float padding = node.padding * scale;
+    Rectangle content = {node.rect.x + padding, node.rect.y + padding, node.rect.width - 2 * padding, node.rect.height - 2 * padding};
+    for (UiNode& child : node.children) {
+        layout(child, content);
+    }
+}
+
+UiNode* UiLayout::find(UiNode& node, const std::string& id) {
+    if (node.id == id) {
+        return &node;
+    }
+    for (UiNode& child : node.children) {
+        if (UiNode* found = find(child, id)) {
+            return found;
+        }
+    }
+    return nullptr;
+}
+
+Rectangle UiLayout::rect(const std::string& id) {
+    UiNode* node = find(root, id);
+    return node ? node->rect : Rectangle{0, 0, 0, 0};
+}
+
+float UiLayout::getScale() {
+    return scale;
+}
+
+Vector2 UiLayout::place(const std::string& id, Vector2 contentSize) {
+    UiNode* node = find(root, id);
+    if (!node) {
+        return {0, 0};
+    }
+    return anchorPoint(node->anchor, node->rect, contentSize);
+}
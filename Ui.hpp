@@ -0,0 +1,59 @@
+#pragma once
+#include "raylib.h"
+#include <string>
+#include <vector>
+
+
+enum class Anchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+    Stretch, // fills the parent's content area
+};
+
+// A retained layout node. Sizes, offsets and padding are in reference pixels
+// (720p) and are scaled with the layout; rect is filled in by layout().
+struct UiNode {
+    std::string id;
+    Anchor anchor = Anchor::TopLeft;
+    Vector2 offset = {0, 0};
+    Vector2 size = {0, 0};
+    float padding = 0;
+    std::vector<UiNode> children;
+    Rectangle rect = {0, 0, 0, 0};
+
+    UiNode& add(const UiNode& child);
+};
+
+// Owns the UI tree for a screen (HUD, menus, editor panels). The root covers
+// the drawable area minus the safe-area inset, and everything scales with
+// its height so the same tree works at any resolution and aspect ratio.
+class UiLayout {
+  public:
+    static constexpr float REFERENCE_HEIGHT = 720;
+
+    UiNode root;
+
+    // safeArea is the fraction of each edge kept clear (TV overscan, notches);
+    // userScale is the player's UI size preference on top of the automatic one.
+    void update(Rectangle bounds, float safeArea, float userScale = 1);
+    Rectangle rect(const std::string& id);
+    float getScale();
+    // Positions content of the given size inside a node, aligned the same
+    // way the node is anchored to its parent.
+    Vector2 place(const std::string& id, Vector2 contentSize);
+
+  private:
+    float scale = 1;
+
+    void layout(UiNode& node, Rectangle parent);
+    UiNode* find(UiNode& node, const std::string& id);
+};
+
+Vector2 anchorPoint(Anchor anchor, Rectangle area, Vector2 size);
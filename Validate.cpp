@@ -0,0 +1,233 @@
+#include "raylib.h"
+#include "Validate.hpp"
+#include <cmath>
+#include <cstdio>
+#include "Level.hpp"
+#include "LevelPack.hpp"
+#include "Log.hpp"
+#include "Sanitize.hpp"
+#include "Simulation.hpp"
+#include "Theme.hpp"
+
+
+struct Diagnostic {
+    bool error = true;
+    int line = 0;   // 0 when it isn't about one place in the file
+    int column = 0;
+    std::string message;
+};
+
+// what the loaders log while a file is read, which is where every problem
+// they work around (and so every schema problem) ends up
+static std::vector<LogRecord>* captured = nullptr;
+
+// "4:3: message", "4: message", "line 4, column 3: message" or
+// "line 4: message", the ways the loaders put where a problem is
+static Diagnostic located(const std::string& text, bool error) {
+    int line = 0, column = 0, used = 0;
+    const char* start = text.c_str();
+    bool found = (sscanf(start, "%d:%d: %n", &line, &column, &used) == 2 && used > 0)
+        || (sscanf(start, "line %d, column %d: %n", &line, &column, &used) == 2 && used > 0);
+    if (!found) {
+        column = 0;
+        found = (sscanf(start, "%d: %n", &line, &used) == 1 && used > 0) || (sscanf(start, "line %d: %n", &line, &used) == 1 && used > 0);
+    }
+    if (!found) {
+        line = 0;
+        used = 0;
+    }
+    return {error, line, column, text.substr(used)};
+}
+
+// the path a problem starts with, which is the file's own
+static std::string withoutPath(const std::string& text, const std::string& path) {
+    if (text.compare(0, path.size() + 1, path + ":") != 0) {
+        return text;
+    }
+    size_t start = text.find_first_not_of(' ', path.size() + 1);
+    return start == std::string::npos ? "" : text.substr(start);
+}
+
+static bool printResult(const std::string& path, const char* type, const std::vector<Diagnostic>& diagnostics) {
+    bool valid = true;
+    for (const Diagnostic& diagnostic : diagnostics) {
+        valid = valid && !diagnostic.error;
+    }
+    printf("{\"file\": %s, \"type\": \"%s\", \"valid\": %s, \"diagnostics\": [", jsonString(path).c_str(), type, valid ? "true" : "false");
+    const char* separator = "";
+    for (const Diagnostic& diagnostic : diagnostics) {
+        printf("%s{\"severity\": \"%s\", \"line\": %s, \"column\": %s, \"message\": %s}", separator, diagnostic.error ? "error" : "warning",
+            diagnostic.line > 0 ? TextFormat("%d", diagnostic.line) : "null", diagnostic.column > 0 ? TextFormat("%d", diagnostic.column) : "null",
+            jsonString(diagnostic.message).c_str());
+        separator = ", ";
+    }
+    printf("]}\n");
+    return valid;
+}
+
+// Destructible bricks no ball can get to: the field is flooded from just
+// above the paddle in ball sized cells, through everything but metal that
+// stays put (whatever breaks or moves opens up in the end), and a brick is
+// reachable when a cell next to it was reached. Bricks below the paddle or
+// off the field never are.
+static std::vector<Diagnostic> unreachableBricks(const Level& level) {
+    SimulationRules rules;
+    Ball ball = initBall(rules.width, rules.height);
+    Player paddle = initPlayer(rules.width, rules.height);
+    float cell = ball.rectangle.width;
+    int columns = (int) ceilf(rules.width / cell);
+    int rows = (int) (paddle.rectangle.y / cell);
+
+    std::vector<Rectangle> walls;
+    for (const Brick& brick : level.bricks) {
+        if (!brick.isDestructible() && !brick.path.moves() && !brick.phased) {
+            walls.push_back(brick.rectangle);
+        }
+    }
+    auto blocked = [&](int column, int row) {
+        Rectangle area = {column * cell, row * cell, cell, cell};
+        for (const Rectangle& wall : walls) {
+            if (CheckCollisionRecs(area, wall)) {
+                return true;
+            }
+        }
+        return false;
+    };
+    std::vector<char> reached(columns * rows, 0);
+    std::vector<int> open;
+    for (int column = 0; column < columns && rows > 0; column++) {
+        if (!blocked(column, rows - 1)) {
+            reached[(rows - 1) * columns + column] = 1;
+            open.push_back((rows - 1) * columns + column);
+        }
+    }
+    while (!open.empty()) {
+        int at = open.back();
+        open.pop_back();
+        int column = at % columns;
+        int row = at / columns;
+        const int steps[4][2] = {{1, 0}, {-1, 0}, {0, 1}, {0, -1}};
+        for (const auto& step : steps) {
+            int nextColumn = column + step[0];
+            int nextRow = row + step[1];
+            if (nextColumn < 0 || nextColumn >= columns || nextRow < 0 || nextRow >= rows) {
+                continue;
+            }
+            int next = nextRow * columns + nextColumn;
+            if (!reached[next] && !blocked(nextColumn, nextRow)) {
+                reached[next] = 1;
+                open.push_back(next);
+            }
+        }
+    }
+
+    std::vector<Diagnostic> diagnostics;
+    for (const Brick& brick : level.bricks) {
+        if (!brick.isDestructible()) {
+            continue;
+        }
+        Rectangle rect = brick.rectangle;
+        bool touched = false;
+        for (int row = (int) floorf(rect.y / cell) - 1; row <= (int) floorf((rect.y + rect.height) / cell) && !touched; row++) {
+            for (int column = (int) floorf(rect.x / cell) - 1; column <= (int) floorf((rect.x + rect.width) / cell) && !touched; column++) {
+                touched = row >= 0 && row < rows && column >= 0 && column < columns && reached[row * columns + column];
+            }
+        }
+        if (!touched) {
+            diagnostics.push_back({true, 0, 0, TextFormat("the brick at %d,%d can't be reached by the ball, so the level can't be cleared", (int) brick.origin.x, (int) brick.origin.y)});
+        }
+    }
+    return diagnostics;
+}
+
+static bool validateLevelFile(const std::string& path, ThemeRegistry& themes) {
+    std::vector<Diagnostic> diagnostics;
+    XmlNode root;
+    XmlError xmlError;
+    if (!loadXmlFile(path, root, xmlError)) {
+        diagnostics.push_back({true, xmlError.line, xmlError.column, xmlError.message});
+        return printResult(path, "level", diagnostics);
+    }
+    std::vector<LogRecord> warnings;
+    captured = &warnings;
+    Level level;
+    std::string error;
+    bool loaded = loadLevel(path, level, error);
+    captured = nullptr;
+    for (const LogRecord& record : warnings) {
+        if (record.module == "LEVEL" || record.module == "ANIMATION") {
+            diagnostics.push_back(located(withoutPath(record.message, path), true));
+        }
+    }
+    if (!loaded) {
+        diagnostics.push_back(located(withoutPath(error, path), true));
+        return printResult(path, "level", diagnostics);
+    }
+
+    for (const std::string& problem : levelClearProblems(level)) {
+        diagnostics.push_back({true, 0, 0, withoutPath(problem, path)});
+    }
+    for (const Diagnostic& diagnostic : unreachableBricks(level)) {
+        diagnostics.push_back(diagnostic);
+    }
+    for (const std::string& problem : levelReferenceProblems(level)) {
+        diagnostics.push_back({false, 0, 0, withoutPath(problem, path)});
+    }
+    if (!level.theme.empty() && !themes.has(level.theme)) {
+        diagnostics.push_back({false, 0, 0, "theme '" + level.theme + "' not found in themes/ or mods/, the profile's is used instead"});
+    }
+    return printResult(path, "level", diagnostics);
+}
+
+// The manifest, then every level it lists; false when any of them has an error.
+static bool validatePack(const std::string& manifest, ThemeRegistry& themes) {
+    std::vector<Diagnostic> diagnostics;
+    XmlNode root;
+    XmlError xmlError;
+    LevelPack pack;
+    std::string problem;
+    if (!loadXmlFile(manifest, root, xmlError)) {
+        diagnostics.push_back({true, xmlError.line, xmlError.column, xmlError.message});
+        return printResult(manifest, "pack", diagnostics);
+    }
+    if (!parseLevelPack(root, GetDirectoryPath(manifest.c_str()), pack, problem)) {
+        diagnostics.push_back(located(problem, true));
+        return printResult(manifest, "pack", diagnostics);
+    }
+    if (!pack.playable()) {
+        diagnostics.push_back({false, root.line, root.column,
+            TextFormat("made for level format %d, which is newer than this game's %d", pack.levelFormat, LEVEL_FORMAT)});
+    }
+    bool valid = printResult(manifest, "pack", diagnostics);
+    for (const std::string& level : pack.levels) {
+        valid = validateLevelFile(level, themes) && valid;
+    }
+    return valid;
+}
+
+int runValidate(const LaunchOptions& options) {
+    // stdout carries the JSON only
+    installLogging(stderr);
+    addLogSink([](const LogRecord& record) {
+        if (captured && record.level >= LOG_WARNING) {
+            captured->push_back(record);
+        }
+    });
+    if (options.validatePaths.empty()) {
+        fprintf(stderr, "Usage: breakout validate <level.blvl | pack>...\n");
+        return 2;
+    }
+    ThemeRegistry themes;
+    themes.scan();
+    bool valid = true;
+    for (const std::string& path : options.validatePaths) {
+        if (DirectoryExists(path.c_str())) {
+            valid = validatePack(path + "/pack.xml", themes) && valid;
+        } else if (std::string(GetFileName(path.c_str())) == "pack.xml") {
+            valid = validatePack(path, themes) && valid;
+        } else {
+            valid = validateLevelFile(path, themes) && valid;
+        }
+    }
+    return valid ? 0 : 1;
+}
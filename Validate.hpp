@@ -0,0 +1,20 @@
+#pragma once
+#include "Args.hpp"
+
+
+// `breakout validate <file>...`: checks levels and level packs the way a
+// level creator (or a pre-commit hook on a community pack) wants, and prints
+// one JSON object a file on stdout:
+//
+//   {"file": "levels/x.blvl", "type": "level", "valid": false, "diagnostics": [
+//     {"severity": "error", "line": 4, "column": 3, "message": "..."}]}
+//
+// A file can be a .blvl, a pack's pack.xml or the pack's directory; a pack
+// is its manifest and then every level in it. Errors are what stops the
+// level playing as written: XML or schema problems (anything the loader
+// would skip or change), bricks no ball can get to and levels that can't be
+// cleared. Warnings are missing assets and other references to nothing.
+// line and column are null where a diagnostic isn't about one place in the
+// file. Returns 0 with no errors, 1 with any and 2 when there is nothing to
+// check.
+int runValidate(const LaunchOptions& options);
@@ -0,0 +1,85 @@
+#include "raylib.h"
+#include "Versus.hpp"
+#include "Endless.hpp"
+#include "Simulation.hpp"
+
+
+std::vector<Brick> junkRow(Random& random) {
+    std::vector<Brick> row;
+    for (int column = 0; column < EndlessRules::COLUMNS; column++) {
+        if (random.unit() < VersusRules::JUNK_FILL) {
+            row.push_back({EndlessRules::COLUMN_SPACING * (column + 1), EndlessRules::ROW_TOP, EndlessRules::BRICK_WIDTH,
+                EndlessRules::BRICK_HEIGHT, BrickMaterial::Stone, VersusRules::JUNK_HIT_POINTS});
+        }
+    }
+    return row;
+}
+
+void VersusMatch::start(const Simulation& first, const Simulation& second) {
+    bricksLeft[0] = first.state.bricksLeft;
+    bricksLeft[1] = second.state.bricksLeft;
+    for (int i = 0; i < 2; i++) {
+        broken[i] = 0;
+        sent[i] = 0;
+    }
+}
+
+void VersusMatch::exchange(Simulation& first, Simulation& second, EventBus* firstEvents, EventBus* secondEvents) {
+    Simulation* fields[2] = {&first, &second};
+    EventBus* buses[2] = {firstEvents, secondEvents};
+    // both counted before either gets junk, so neither side goes first
+    int rows[2] = {0, 0};
+    for (int i = 0; i < 2; i++) {
+        broken[i] += bricksLeft[i] - fields[i]->state.bricksLeft;
+        rows[i] = broken[i] / VersusRules::GARBAGE_BRICKS;
+        broken[i] %= VersusRules::GARBAGE_BRICKS;
+        sent[i] += rows[i];
+    }
+    for (int i = 0; i < 2; i++) {
+        GameState& target = fields[1 - i]->state;
+        // a field already out of it takes no more
+        for (int row = 0; row < rows[i] && target.lives > 0 && target.bricksLeft > 0; row++) {
+            shiftField(target.bricks, EndlessRules::ROW_STEP);
+            std::vector<Brick> junk = junkRow(target.random);
+            fields[1 - i]->prepareBricks(junk);
+            target.bricks.insert(target.bricks.end(), junk.begin(), junk.end());
+            target.bricksLeft += destructibleCount(junk);
+            EventBus* bus = buses[1 - i];
+            if (bus) {
+                bus->publish(EventType::GarbageReceived, {fields[1 - i]->rules.width / 2.0f, (float) EndlessRules::ROW_TOP}, {0, 0}, (int) junk.size());
+            }
+            if (fieldBottom(target.bricks) >= target.player.getRectangle().y) {
+                target.lives = 0;
+                if (bus) {
+                    bus->publish(EventType::FieldOverran, target.player.getCenter(), {0, 0}, 0);
+                }
+            }
+        }
+    }
+    bricksLeft[0] = first.state.bricksLeft;
+    bricksLeft[1] = second.state.bricksLeft;
+}
+
+VersusResult VersusMatch::result(const Simulation& first, const Simulation& second) const {
+    // clearing your field wins even on the tick the other side would have
+    bool firstWon = first.state.bricksLeft <= 0 || (second.state.lives <= 0 && first.state.lives > 0);
+    bool secondWon = second.state.bricksLeft <= 0 || (first.state.lives <= 0 && second.state.lives > 0);
+    if (firstWon && secondWon) {
+        return VersusResult::Draw;
+    }
+    if (firstWon) {
+        return VersusResult::FirstWins;
+    }
+    if (secondWon) {
+        return VersusResult::SecondWins;
+    }
+    return first.state.lives <= 0 && second.state.lives <= 0 ? VersusResult::Draw : VersusResult::Playing;
+}
+
+int VersusMatch::progress(int player) const {
+    return broken[player];
+}
+
+int VersusMatch::rowsSent(int player) const {
+    return sent[player];
+}
@@ -0,0 +1,50 @@
+#pragma once
+#include <vector>
+#include "Brick.hpp"
+#include "Events.hpp"
+#include "Random.hpp"
+
+class Simulation;
+
+
+// Versus (GameMode::Versus): two players, each on a field of their own
+// with the same level and seed. Every GARBAGE_BRICKS bricks one of them
+// breaks, a row of junk lands on the other's field, pushing it down a row
+// the way endless mode's rows do. A field that runs out of lives, or whose
+// bricks reach its paddle, loses; clearing your own wins.
+struct VersusRules {
+    static constexpr int GARBAGE_BRICKS = 6;
+    static constexpr int JUNK_HIT_POINTS = 2;
+    static constexpr float JUNK_FILL = 0.7f; // of the row, the rest gaps to aim through
+};
+
+enum class VersusResult {
+    Playing,
+    FirstWins,
+    SecondWins,
+    Draw, // both out on the same tick
+};
+
+// A junk row, across the top of the field in endless mode's columns.
+std::vector<Brick> junkRow(Random& random);
+
+// Keeps score of the bricks each side breaks and sends the garbage. The two
+// Simulations stay ordinary ones, stepped one after the other; exchange()
+// runs after both.
+class VersusMatch {
+  public:
+    // Both fields just reset.
+    void start(const Simulation& first, const Simulation& second);
+    // Junk for what each broke this tick, published on the receiving
+    // field's bus (GarbageReceived, and FieldOverran when that ends it).
+    void exchange(Simulation& first, Simulation& second, EventBus* firstEvents, EventBus* secondEvents);
+    VersusResult result(const Simulation& first, const Simulation& second) const;
+    // Bricks broken towards the next row sent, for the HUD.
+    int progress(int player) const;
+    int rowsSent(int player) const;
+
+  private:
+    int bricksLeft[2] = {0, 0};
+    int broken[2] = {0, 0};
+    int sent[2] = {0, 0};
+};
@@ -0,0 +1,60 @@
+#include "raylib.h"
+#include "Viewport.hpp"
+#include <cmath>
+
+
+void Viewport::init(int virtualWidth, int virtualHeight) {
+    width = virtualWidth;
+    height = virtualHeight;
+    canvas = LoadRenderTexture(width, height);
+    SetTextureFilter(canvas.texture, TEXTURE_FILTER_BILINEAR);
+    update();
+}
+
+void Viewport::unload() {
+    UnloadRenderTexture(canvas);
+}
+
+void Viewport::update() {
+    float windowWidth = GetScreenWidth();
+    float windowHeight = GetScreenHeight();
+    scale = fminf(windowWidth / width, windowHeight / height);
+    destination.width = width * scale;
+    destination.height = height * scale;
+    destination.x = floorf((windowWidth - destination.width) / 2);
+    destination.y = floorf((windowHeight - destination.height) / 2);
+
+    SetMouseOffset((int) -destination.x, (int) -destination.y);
+    SetMouseScale(1 / scale, 1 / scale);
+}
+
+void Viewport::beginOverlay() {
+    BeginDrawing();
+    ClearBackground(BLACK);
+    Rectangle source = {0, 0, (float) width, (float) -height};
+    DrawTexturePro(canvas.texture, source, destination, {0, 0}, 0, WHITE);
+}
+
+void Viewport::endFrame() {
+    EndDrawing();
+}
+
+RenderTexture2D Viewport::getCanvas() {
+    return canvas;
+}
+
+Rectangle Viewport::getDestination() {
+    return destination;
+}
+
+float Viewport::getScale() {
+    return scale;
+}
+
+Vector2 Viewport::screenToVirtual(Vector2 point) {
+    return {(point.x - destination.x) / scale, (point.y - destination.y) / scale};
+}
+
+Vector2 Viewport::virtualToScreen(Vector2 point) {
+    return {point.x * scale + destination.x, point.y * scale + destination.y};
+}
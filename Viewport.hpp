@@ -0,0 +1,35 @@
+#pragma once
+#include "raylib.h"
+
+
+// The game is drawn into a fixed virtual-resolution canvas which is then
+// scaled uniformly into the window, with black bars on whichever axis has
+// room to spare. Mouse coordinates are remapped so gameplay code only ever
+// sees virtual coordinates; the UI layer is drawn at window resolution on top.
+class Viewport {
+  public:
+    void init(int virtualWidth, int virtualHeight);
+    void unload();
+    // Recomputes scale and letterbox offsets; call once per frame.
+    void update();
+
+    // Presents the canvas letterboxed (the canvas must no longer be bound);
+    // anything drawn between this and endFrame() is in window pixels.
+    void beginOverlay();
+    void endFrame();
+
+    RenderTexture2D getCanvas();
+    Rectangle getDestination();
+    float getScale();
+    // For raw window pixels (touch points, say); GetMousePosition() is
+    // already remapped by update() and must not go through this again.
+    Vector2 screenToVirtual(Vector2 point);
+    Vector2 virtualToScreen(Vector2 point);
+
+  private:
+    RenderTexture2D canvas;
+    int width = 0;
+    int height = 0;
+    float scale = 1;
+    Rectangle destination = {0, 0, 0, 0};
+};
@@ -0,0 +1,321 @@
+#include "Xml.hpp"
+#include <cctype>
+#include <cstdlib>
+#include <fstream>
+#include <sstream>
+
+
+const XmlNode* XmlNode::child(const std::string& childName) const {
+    for (const XmlNode& node : children) {
+        if (node.name == childName) {
+            return &node;
+        }
+    }
+    return nullptr;
+}
+
+std::vector<const XmlNode*> XmlNode::childrenNamed(const std::string& childName) const {
+    std::vector<const XmlNode*> found;
+    for (const XmlNode& node : children) {
+        if (node.name == childName) {
+            found.push_back(&node);
+        }
+    }
+    return found;
+}
+
+bool XmlNode::hasAttribute(const std::string& key) const {
+    return attributes.count(key) > 0;
+}
+
+std::string XmlNode::attribute(const std::string& key, const std::string& fallback) const {
+    auto it = attributes.find(key);
+    return it == attributes.end() ? fallback : it->second;
+}
+
+float XmlNode::attributeFloat(const std::string& key, float fallback) const {
+    auto it = attributes.find(key);
+    if (it == attributes.end()) {
+        return fallback;
+    }
+    char* end = nullptr;
+    float value = std::strtof(it->second.c_str(), &end);
+    return end == it->second.c_str() ? fallback : value;
+}
+
+int XmlNode::attributeInt(const std::string& key, int fallback) const {
+    auto it = attributes.find(key);
+    if (it == attributes.end()) {
+        return fallback;
+    }
+    char* end = nullptr;
+    long value = std::strtol(it->second.c_str(), &end, 10);
+    return end == it->second.c_str() ? fallback : (int) value;
+}
+
+std::string XmlError::describe() const {
+    return "line " + std::to_string(line) + ", column " + std::to_string(column) + ": " + message;
+}
+
+namespace {
+
+class XmlReader {
+  public:
+    XmlReader(const std::string& text, XmlError& err) : source(text), error(err) {}
+
+    bool parseDocument(XmlNode& root) {
+        skipMisc();
+        if (atEnd() || peek() != '<') {
+            return fail("expected root element");
+        }
+        if (!parseElement(root)) {
+            return false;
+        }
+        skipMisc();
+        if (!atEnd()) {
+            return fail("unexpected content after root element");
+        }
+        return true;
+    }
+
+  private:
+    const std::string& source;
+    XmlError& error;
+    size_t pos = 0;
+    int line = 1;
+    int column = 1;
+
+    bool atEnd() const { return pos >= source.size(); }
+    char peek() const { return source[pos]; }
+
+    bool startsWith(const char* token) const {
+        return source.compare(pos, std::char_traits<char>::length(token), token) == 0;
+    }
+
+    void advance(size_t count = 1) {
+        for (size_t i = 0; i < count && !atEnd(); i++) {
+            if (source[pos] == '\n') {
+                line++;
+                column = 1;
+            } else {
+                column++;
+            }
+            pos++;
+        }
+    }
+
+    bool fail(const std::string& message) {
+        error.message = message;
+        error.line = line;
+        error.column = column;
+        return false;
+    }
+
+    void skipWhitespace() {
+        while (!atEnd() && std::isspace((unsigned char) peek())) {
+            advance();
+        }
+    }
+
+    // Skips whitespace, comments, the XML declaration and doctype.
+    bool skipMisc() {
+        while (true) {
+            skipWhitespace();
+            if (startsWith("<!--")) {
+                if (!skipUntil("-->")) return false;
+            } else if (startsWith("<?")) {
+                if (!skipUntil("?>")) return false;
+            } else if (startsWith("<!")) {
+                if (!skipUntil(">")) return false;
+            } else {
+                return true;
+            }
+        }
+    }
+
+    bool skipUntil(const char* terminator) {
+        size_t end = source.find(terminator, pos);
+        if (end == std::string::npos) {
+            return fail(std::string("unterminated markup, expected '") + terminator + "'");
+        }
+        advance(end - pos + std::char_traits<char>::length(terminator));
+        return true;
+    }
+
+    static bool isNameChar(char c) {
+        return std::isalnum((unsigned char) c) || c == '_' || c == '-' || c == '.' || c == ':';
+    }
+
+    bool parseName(std::string& name) {
+        size_t start = pos;
+        while (!atEnd() && isNameChar(peek())) {
+            advance();
+        }
+        if (pos == start) {
+            return fail("expected a name");
+        }
+        name = source.substr(start, pos - start);
+        return true;
+    }
+
+    bool decodeEntities(const std::string& raw, std::string& out) {
+        out.clear();
+        for (size_t i = 0; i < raw.size(); i++) {
+            if (raw[i] != '&') {
+                out += raw[i];
+                continue;
+            }
+            size_t end = raw.find(';', i);
+            if (end == std::string::npos) {
+                return fail("unterminated character entity");
+            }
+            std::string entity = raw.substr(i + 1, end - i - 1);
+            if (entity == "amp") out += '&';
+            else if (entity == "lt") out += '<';
+            else if (entity == "gt") out += '>';
+            else if (entity == "quot") out += '"';
+            else if (entity == "apos") out += '\'';
+            else if (!entity.empty() && entity[0] == '#') {
+                long code = entity.size() > 1 && entity[1] == 'x'
+                    ? std::strtol(entity.c_str() + 2, nullptr, 16)
+                    : std::strtol(entity.c_str() + 1, nullptr, 10);
+                if (code < 0x80) {
+                    out += (char) code;
+                } else if (code < 0x800) {
+                    out += (char) (0xC0 | (code >> 6));
+                    out += (char) (0x80 | (code & 0x3F));
+                } else if (code < 0x10000) {
+                    out += (char) (0xE0 | (code >> 12));
+                    out += (char) (0x80 | ((code >> 6) & 0x3F));
+                    out += (char) (0x80 | (code & 0x3F));
+                } else {
+                    out += (char) (0xF0 | (code >> 18));
+                    out += (char) (0x80 | ((code >> 12) & 0x3F));
+                    out += (char) (0x80 | ((code >> 6) & 0x3F));
+                    out += (char) (0x80 | (code & 0x3F));
+                }
+            } else {
+                return fail("unknown character entity '&" + entity + ";'");
+            }
+            i = end;
+        }
+        return true;
+    }
+
+    bool parseAttributes(XmlNode& node) {
+        while (true) {
+            skipWhitespace();
+            if (atEnd()) {
+                return fail("unterminated tag <" + node.name + ">");
+            }
+            if (peek() == '>' || peek() == '/') {
+                return true;
+            }
+            std::string key;
+            if (!parseName(key)) return false;
+            skipWhitespace();
+            if (atEnd() || peek() != '=') {
+                return fail("expected '=' after attribute '" + key + "'");
+            }
+            advance();
+            skipWhitespace();
+            if (atEnd() || (peek() != '"' && peek() != '\'')) {
+                return fail("expected quoted value for attribute '" + key + "'");
+            }
+            char quote = peek();
+            advance();
+            size_t end = source.find(quote, pos);
+            if (end == std::string::npos) {
+                return fail("unterminated value for attribute '" + key + "'");
+            }
+            std::string value;
+            if (!decodeEntities(source.substr(pos, end - pos), value)) return false;
+            if (node.attributes.count(key)) {
+                return fail("duplicate attribute '" + key + "'");
+            }
+            node.attributes[key] = value;
+            advance(end - pos + 1);
+        }
+    }
+
+    bool parseElement(XmlNode& node) {
+        node.line = line;
+        node.column = column;
+        advance(); // '<'
+        if (!parseName(node.name)) return false;
+        if (!parseAttributes(node)) return false;
+        if (peek() == '/') {
+            advance();
+            if (atEnd() || peek() != '>') {
+                return fail("expected '>' to close <" + node.name + "/>");
+            }
+            advance();
+            return true;
+        }
+        advance(); // '>'
+
+        while (true) {
+            if (atEnd()) {
+                return fail("missing closing tag </" + node.name + ">");
+            }
+            if (startsWith("<!--")) {
+                if (!skipUntil("-->")) return false;
+            } else if (startsWith("<![CDATA[")) {
+                advance(9);
+                size_t end = source.find("]]>", pos);
+                if (end == std::string::npos) {
+                    return fail("unterminated CDATA section");
+                }
+                node.text += source.substr(pos, end - pos);
+                advance(end - pos + 3);
+            } else if (startsWith("</")) {
+                advance(2);
+                std::string closing;
+                if (!parseName(closing)) return false;
+                if (closing != node.name) {
+                    return fail("mismatched closing tag </" + closing + ">, expected </" + node.name + ">");
+                }
+                skipWhitespace();
+                if (atEnd() || peek() != '>') {
+                    return fail("expected '>' after </" + closing);
+                }
+                advance();
+                return true;
+            } else if (peek() == '<') {
+                XmlNode childNode;
+                if (!parseElement(childNode)) return false;
+                node.children.push_back(childNode);
+            } else {
+                size_t end = source.find('<', pos);
+                if (end == std::string::npos) {
+                    end = source.size();
+                }
+                std::string decoded;
+                if (!decodeEntities(source.substr(pos, end - pos), decoded)) return false;
+                node.text += decoded;
+                advance(end - pos);
+            }
+        }
+    }
+};
+
+}
+
+bool parseXml(const std::string& source, XmlNode& root, XmlError& error) {
+    root = XmlNode();
+    XmlReader reader(source, error);
+    return reader.parseDocument(root);
+}
+
+bool loadXmlFile(const std::string& path, XmlNode& root, XmlError& error) {
+    std::ifstream file(path, std::ios::binary);
+    if (!file) {
+        error.message = "cannot open '" + path + "'";
+        error.line = 0;
+        error.column = 0;
+        return false;
+    }
+    std::stringstream buffer;
+    buffer << file.rdbuf();
+    return parseXml(buffer.str(), root, error);
+}
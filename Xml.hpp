@@ -0,0 +1,34 @@
+#pragma once
+#include <map>
+#include <string>
+#include <vector>
+
+
+// Small DOM-style XML reader used for level files. Supports elements,
+// attributes, text, comments and the standard character entities.
+struct XmlNode {
+    std::string name;
+    std::map<std::string, std::string> attributes;
+    std::vector<XmlNode> children;
+    std::string text;
+    int line = 0;
+    int column = 0;
+
+    const XmlNode* child(const std::string& childName) const;
+    std::vector<const XmlNode*> childrenNamed(const std::string& childName) const;
+    bool hasAttribute(const std::string& key) const;
+    std::string attribute(const std::string& key, const std::string& fallback = "") const;
+    float attributeFloat(const std::string& key, float fallback) const;
+    int attributeInt(const std::string& key, int fallback) const;
+};
+
+struct XmlError {
+    std::string message;
+    int line = 0;
+    int column = 0;
+
+    std::string describe() const;
+};
+
+bool parseXml(const std::string& source, XmlNode& root, XmlError& error);
+bool loadXmlFile(const std::string& path, XmlNode& root, XmlError& error);
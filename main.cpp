@@ -1,219 +1,2186 @@
 #include "raylib.h"
+#include <algorithm>
 #include <vector>
 #include <random>
 #include <stdio.h>
 #include <cmath>
+#include <ctime>
 
+#include "Accessibility.hpp"
+#include "Achievements.hpp"
+#include "Args.hpp"
+#include "Atlas.hpp"
+#include "Background.hpp"
+#include "Benchmark.hpp"
+#include "BallTrail.hpp"
+#include "Brick.hpp"
+#include "BrickField.hpp"
+#include "Capture.hpp"
+#include "Challenge.hpp"
+#include "ControlsMenu.hpp"
+#include "Cosmetics.hpp"
+#include "Crash.hpp"
+#include "DevTools.hpp"
+#include "Devices.hpp"
 #include "Entity.hpp"
+#include "Events.hpp"
+#include "FramePacer.hpp"
+#include "GameAudio.hpp"
+#include "GameFlow.hpp"
+#include "Golden.hpp"
+#include "Headless.hpp"
+#include "HotReload.hpp"
+#include "InputRecord.hpp"
+#include "Inspect.hpp"
+#include "Juice.hpp"
+#include "LayeredMusic.hpp"
+#include "Leaderboard.hpp"
+#include "LeaderboardScreen.hpp"
+#include "Level.hpp"
+#include "LevelSelectScreen.hpp"
+#include "Lifecycle.hpp"
+#include "Log.hpp"
+#include "Lighting.hpp"
+#include "Localization.hpp"
+#include "MouseControl.hpp"
+#include "Mutators.hpp"
+#include "Particles.hpp"
+#include "PauseMenu.hpp"
 #include "Player.hpp"
+#include "Popups.hpp"
+#include "PowerUp.hpp"
+#include "PostProcess.hpp"
+#include "Profile.hpp"
+#include "Profiler.hpp"
+#include "Progression.hpp"
+#include "RenderGraph.hpp"
+#include "Replay.hpp"
+#include "Sandbox.hpp"
+#include "Scoring.hpp"
+#include "Settings.hpp"
+#include "SettingsMenu.hpp"
+#include "ShopScreen.hpp"
+#include "Simulation.hpp"
+#include "SoundCues.hpp"
+#include "Speedrun.hpp"
+#include "SpriteBatch.hpp"
+#include "Stats.hpp"
+#include "StatsScreen.hpp"
+#include "Telemetry.hpp"
+#include "Text.hpp"
+#include "Theme.hpp"
+#include "Touch.hpp"
+#include "Tutorial.hpp"
+#include "Ui.hpp"
+#include "Validate.hpp"
+#include "Versus.hpp"
+#include "Viewport.hpp"
 
-Player initPlayer(int, int);
-Entity initBall(int, int);
-void ballBounce(Entity* , char);
-void drawBricks(std::vector<Entity>&);
-void createBricks(std::vector<Entity>&);
+void drawBricks(SpriteBatch&, BrickField&, TextureAtlas&, const Theme&, const ThemePalette&, bool, std::vector<Brick>&);
+void drawSprite(TextureAtlas&, const std::string&, Rectangle, Color);
+void drawWalls(TextureAtlas&, const Theme&, int, int);
+void drawEffectIcon(TextRenderer&, const PowerUp&, Vector2, float, float, int);
+void drawBoss(const Boss&, const ThemePalette&, bool);
 
 
 
-int main() {
+int main(int argc, char** argv) {
+    installLogging();
+    LaunchOptions options = parseArgs(argc, argv);
+    if (options.help) {
+        printUsage();
+        return 0;
+    }
+    if (options.headless) {
+        return runHeadless(options);
+    }
+    if (options.benchmark) {
+        return runBenchmarkCli(options);
+    }
+    if (options.inspect) {
+        return runInspect(options);
+    }
+    if (options.validate) {
+        return runValidate(options);
+    }
+    CrashHandler crash;
+    crash.install();
+    Settings settings;
+    // golden scenes are rendered with the defaults, not this machine's settings
+    if (!options.golden) {
+        loadSettings(settingsPath(), settings);
+    }
+    std::string logFilterError;
+    if (!setLogFilter(options.logFilter.empty() ? settings.logFilter : options.logFilter, logFilterError)) {
+        TraceLog(LOG_WARNING, "LOG: Ignoring log filter: %s", logFilterError.c_str());
+    }
+    LogConsole logConsole;
+    addLogSink([&logConsole](const LogRecord& record) { logConsole.push(record); });
+    setLanguage(settings.language);
 
 // Initialization
     //--------------------------------------------------------------------------------------
     
     const int screenWidth = 1280;
     const int screenHeight = 720;
-    SetConfigFlags(FLAG_WINDOW_HIGHDPI);
+    if (!options.golden) {
+        SetConfigFlags(FLAG_WINDOW_HIGHDPI);
+    }
+    if (options.packAtlas) {
+        // packing needs a GL context but no visible window
+        SetConfigFlags(FLAG_WINDOW_HIDDEN);
+        InitWindow(screenWidth, screenHeight, "BreakOut");
+        std::string assets = std::string(GetApplicationDirectory()) + "assets/";
+        TextureAtlas atlas;
+        bool packed = atlas.buildFromDirectory(assets + "sprites") && atlas.save(assets + "atlas.png", assets + "atlas.manifest");
+        atlas.unload();
+        CloseWindow();
+        return packed ? 0 : 1;
+    }
+    // golden frames have to come out the same size everywhere
+    SetConfigFlags(options.golden ? FLAG_WINDOW_HIDDEN : FLAG_WINDOW_RESIZABLE);
     InitWindow(screenWidth, screenHeight, "BreakOut");
+    SetWindowMinSize(320, 180);
+    auto applyWindowMode = [](bool fullscreen) {
+        if (fullscreen != IsWindowState(FLAG_BORDERLESS_WINDOWED_MODE)) {
+            ToggleBorderlessWindowed();
+        }
+    };
+    applyWindowMode(settings.fullscreen && !options.windowed && !options.golden);
+    if (!options.connectHost.empty()) {
+        TraceLog(LOG_WARNING, "ARGS: Online play isn't available in this build, not connecting to %s", options.connectHost.c_str());
+    }
 
-    SetTargetFPS(60);               // Set our game to run at 60 frames-per-second
+    FramePacer pacer;               // The simulation ticks at FramePacer::TICK_RATE regardless of FPS
+    pacer.apply(settings);
+    // screenWidth/screenHeight are the virtual resolution everything is laid out in
+    Viewport viewport;
+    viewport.init(screenWidth, screenHeight);
+    PostProcessor postProcessor;
+    postProcessor.init(screenWidth, screenHeight);
+    TextureAtlas atlas;
+    loadGameAtlas(atlas);
+    TextRenderer text;
+    text.loadDefaultChain();
+    text.prepare(languageGlyphs());
+    LightingPass lighting;
+    lighting.init(screenWidth, screenHeight, (LightingQuality) settings.lightingQuality);
     std::string actualFPS;
     //--------------------------------------------------------------------------------------
-    int lifes = 3;
-    int score = 0;
-    
     std::string livesLeft;
     std::string scorePrintable;
 
-    Entity ball = initBall(screenWidth, screenHeight);
-    Player player = initPlayer(screenWidth, screenHeight);
+    // a replay brings its own level, seed and rules
+    ReplayPlayer replay;
+    if (!options.replayPath.empty()) {
+        std::string error;
+        if (replay.load(options.replayPath, error)) {
+            options.levelPath = replay.getHeader().levelPath;
+        } else {
+            TraceLog(LOG_WARNING, "REPLAY: %s", error.c_str());
+        }
+    }
+    // this week's challenge brings its own first level, seed and mutators
+    Challenge challenge;
+    bool weekly = options.weekly && !replay.isPlaying() && !options.golden;
+    int challengeLevel = 0;
+    if (weekly) {
+        challenge = weeklyChallenge(time(nullptr));
+        TraceLog(LOG_INFO, "CHALLENGE: %s, \"%s\": %d levels, mutators %s", challenge.id.c_str(), challenge.name.c_str(),
+            (int) challenge.levels.size(), challenge.mutators.empty() ? "none" : challenge.mutators.c_str());
+        options.levelPath = challenge.levels.front();
+        options.mutators = challenge.mutators;
+        options.hasSeed = true;
+        options.seed = challenge.seed;
+        if (!options.recordReplay.empty()) {
+            TraceLog(LOG_WARNING, "CHALLENGE: A replay only holds one level, not recording %s", options.recordReplay.c_str());
+        }
+    }
+    // and today's its generated level (see loadDailyLevel), which replays of it load too
+    DailyChallenge today;
+    bool daily = options.daily && !weekly && !replay.isPlaying() && !options.golden;
+    if (daily) {
+        today = dailyChallenge(time(nullptr));
+        TraceLog(LOG_INFO, "CHALLENGE: %s, mutators %s", today.id.c_str(), today.mutators.empty() ? "none" : today.mutators.c_str());
+        options.levelPath = today.levelPath;
+        options.mutators = today.mutators;
+        options.hasSeed = true;
+        options.seed = today.seed;
+    }
+
+    // --profile only lasts the run; the settings keep remembering the usual one
+    Profile profile;
+    bool freshProfile = false;
+    if (!options.golden) {
+        freshProfile = !loadProfile(options.profile.empty() ? settings.profile : options.profile, profile);
+    }
+    PlayerStats playerStats;
+    if (!options.golden) {
+        playerStats.load(profile.name, {(float) screenWidth, (float) screenHeight});
+    }
+    // a run left with "Save and quit" carries on where it stopped, unless
+    // something else was asked for; the file only resumes once
+    ReplayPlayer suspended;
+    bool resuming = false;
+    std::string suspendPath = PauseMenu::suspendPath(profile.name);
+    bool resumable = !replay.isPlaying() && !weekly && !options.tutorial && !options.golden && options.levelPath.empty()
+        && options.playInput.empty() && !options.hasSeed && options.mutators.empty() && options.mode == "classic";
+    if (resumable && FileExists(suspendPath.c_str())) {
+        std::string error;
+        if (suspended.load(suspendPath, error)) {
+            options.levelPath = suspended.getHeader().levelPath;
+            options.mutators = suspended.getHeader().mutators;
+            options.mode = suspended.getHeader().mode;
+            options.hasSeed = true;
+            options.seed = suspended.getHeader().seed;
+            resuming = true;
+        } else {
+            TraceLog(LOG_WARNING, "SUSPEND: %s", error.c_str());
+        }
+        remove(suspendPath.c_str());
+    }
+    std::string runMutators = replay.isPlaying() ? replay.getHeader().mutators : "";
+    std::string mutatorError;
+    if (!replay.isPlaying() && !parseMutators(options.mutators, runMutators, mutatorError)) {
+        TraceLog(LOG_WARNING, "ARGS: %s, playing without mutators", mutatorError.c_str());
+    }
+    // challenges are classic; a replay says what it was
+    GameMode runMode = GameMode::Classic;
+    std::string modeId = replay.isPlaying() ? replay.getHeader().mode : weekly || daily ? "classic" : options.mode;
+    if (!parseGameMode(modeId, runMode)) {
+        TraceLog(LOG_WARNING, "ARGS: Unknown mode '%s', playing classic", modeId.c_str());
+    }
+    // a new player gets the tutorial first (or carries on with it), unless a
+    // level, mode, replay, recording or challenge was asked for
+    Tutorial tutorial;
+    bool tutorialWanted = options.tutorial || (!profile.tutorialDone && (freshProfile || profile.tutorialLevel > 0));
+    if (tutorialWanted && options.levelPath.empty() && options.mode == "classic" && !weekly && !resuming && !replay.isPlaying() && options.playInput.empty() && !options.golden) {
+        tutorial.start(options.tutorial ? 0 : profile.tutorialLevel);
+    }
+    // the profile's hardcore toggle goes on every run it starts, a resumed one
+    // brings it in its mutators; a match or practice has no streak to keep
+    bool hardcoreWanted = profile.hardcore && !replay.isPlaying() && !options.golden && !tutorial.isActive();
+    if (hardcoreWanted && runMode != GameMode::Versus && runMode != GameMode::Practice) {
+        parseMutators(runMutators + ",hardcore", runMutators, mutatorError);
+    }
+    bool hardcore = hasMutator(runMutators, "hardcore");
     
     //Create Some bricks
 
-    std::vector<Entity> bricks;
-    createBricks(bricks);
-    
+    std::vector<Brick> levelBricks;
+    std::string levelTheme;
+    std::string levelName;
+    std::string levelMusic;
+    Level currentLevel;
+    long levelFileTime = 0; // the level file's modification time when it was read, for hot reloading
+    // empty path for the built-in layout
+    auto readLevel = [&](const std::string& path) {
+        currentLevel = Level();
+        levelBricks.clear();
+        levelTheme.clear();
+        levelName = "classic";
+        levelMusic.clear();
+        SetWindowTitle("BreakOut");
+        if (!path.empty()) {
+            std::string error;
+            if (loadDailyLevel(path, currentLevel) || loadLevel(path, currentLevel, error)) {
+                levelFileTime = FileExists(path.c_str()) ? GetFileModTime(path.c_str()) : 0;
+                levelBricks = currentLevel.bricks;
+                levelTheme = currentLevel.theme;
+                levelName = currentLevel.name;
+                levelMusic = currentLevel.music;
+                for (const std::string& problem : validateLevel(currentLevel)) {
+                    TraceLog(LOG_WARNING, "LEVEL: %s", problem.c_str());
+                }
+                SetWindowTitle(("BreakOut - " + currentLevel.name).c_str());
+            } else {
+                TraceLog(LOG_WARNING, "LEVEL: %s", error.c_str());
+            }
+        }
+        // a level can be nothing but a boss
+        if (levelBricks.empty() && !currentLevel.boss.present()) {
+            createBricks(levelBricks);
+        }
+    };
+    readLevel(tutorial.isActive() ? tutorial.getLevelPath() : options.levelPath);
+    // what has to go for a clear, the boss counting as one
+    auto clearTargets = [&]() { return destructibleCount(levelBricks) + (currentLevel.boss.present() ? 1 : 0); };
+    int brickTotal = clearTargets();
+    if (replay.isPlaying() && levelHash(levelBricks) != replay.getHeader().levelHash) {
+        TraceLog(LOG_WARNING, "REPLAY: The level has changed since %s was recorded; it will not play back the same", options.replayPath.c_str());
+    }
 
+    // Everything gameplay lives in the simulation; the rest of main only
+    // reads it to draw, play sounds and show the HUD
+    Simulation simulation;
+    uint64_t seed = replay.isPlaying() ? replay.getHeader().seed : options.hasSeed ? options.seed : (uint64_t) time(nullptr);
+    if (replay.isPlaying()) {
+        simulation.rules = replay.getHeader().rules;
+    }
+    GameState& game = simulation.state;
+    simulation.dropTable = currentLevel.drops;
+    simulation.boss = currentLevel.boss;
+    simulation.launchBudget = currentLevel.launches;
+    simulation.physics = currentLevel.physics;
+    // versus's second field, the same level and seed as the first
+    bool versus = runMode == GameMode::Versus;
+    // practice moves the ball by hand, which no replay could play back
+    bool practice = runMode == GameMode::Practice;
+    bool placingBall = false; // the PlaceBall overlay is on top
+    bool draggingBall = false;
+    bool draggingAim = false;
+    Sandbox sandbox(simulation);
+    Simulation rival;
+    VersusMatch versusMatch;
+
+    ParticleSystem particles;
+    PopupSystem popups;
+    BallTrails ballTrails;
+    SpriteBatch batch;
+    BrickField brickField; // instanced path for huge fields
+    brickField.init();
+    BrickField rivalBrickField;
+    if (versus) {
+        rivalBrickField.init();
+    }
+    Capture capture;
+    capture.init(settings.clipSeconds);
+    bool showDebugOverlay = false;
+    GameFlow flow;
+    // a game or a replay can be paused; unpausing goes back to it, a game
+    // after a countdown
+    PauseMenu pauseMenu;
+    auto pauseGame = [&]() {
+        if (flow.is(GameScreen::Playing) || flow.is(GameScreen::Replay)) {
+            flow.change(GameScreen::Paused);
+        }
+    };
+    auto resumeGame = [&]() {
+        if (flow.getPrevious() == GameScreen::Playing) {
+            pauseMenu.startCountdown(GetTime());
+        } else {
+            flow.change(flow.getPrevious());
+        }
+    };
+    // what the pause menu reviews under its items
+    auto pauseReview = [&]() {
+        std::string mutators;
+        for (const Mutator* mutator : mutatorList(runMutators)) {
+            mutators += (mutators.empty() ? "" : ", ") + tr(mutator->name);
+        }
+        std::string assists;
+        auto addAssist = [&assists](const std::string& assist) { assists += (assists.empty() ? "" : ", ") + assist; };
+        if (simulation.rules.inputMode == (int) InputMode::SwitchScanning) {
+            addAssist(tr("Switch scanning"));
+        }
+        if (simulation.rules.autoLaunch) {
+            addAssist(tr("Auto-launch"));
+        }
+        if (settings.inputBufferMs > 0) {
+            addAssist(TextFormat(tr("Input buffer %d ms").c_str(), settings.inputBufferMs));
+        }
+        std::vector<std::string> review = {
+            TextFormat(tr("Mutators: %s").c_str(), mutators.empty() ? tr("none").c_str() : mutators.c_str()),
+            TextFormat(tr("Assists: %s").c_str(), assists.empty() ? tr("none").c_str() : assists.c_str()),
+            TextFormat(tr("Player level %d, %d / %d XP").c_str(), playerLevel(profile.xp), profile.xp, xpForPlayerLevel(playerLevel(profile.xp) + 1)),
+        };
+        if (simulation.rules.scorePercent != 100) {
+            review.insert(review.begin() + 1, TextFormat(tr("Score x%.2f").c_str(), simulation.rules.scorePercent / 100.0f));
+        }
+        if (weekly) {
+            review.insert(review.begin(), TextFormat(tr("%s, level %d of %d").c_str(), tr(challenge.name).c_str(), challengeLevel + 1, (int) challenge.levels.size()));
+        } else if (daily) {
+            review.insert(review.begin(), TextFormat(tr("Daily challenge, %s").c_str(), today.date.c_str()));
+        }
+        return review;
+    };
+
+    std::string appliedLayout = profile.layout;
+    // the shop's skins, before anything looks the profile's up
+    loadCosmeticCatalog();
+    ThemeRegistry themes;
+    themes.scan();
+    const Theme* theme = &themes.get("default");
+    TextureAtlas themeAtlas;
+    Background background;
+
+    GameAudio audio;
+    audio.init(settings);
+    WindowLifecycle lifecycle;
+    lifecycle.subscribe([&audio](WindowEvent event) { audio.onWindowEvent(event); });
+    SettingsMenu settingsMenu;
+    ControlsMenu controlsMenu;
+    DeviceAssignment devices;
+    GamepadWatcher gamepads;
+    int activeGamepad = -1;  // the controller player one last touched
+    std::string lostGamepad; // its name while we wait for it to come back
+    gamepads.subscribe([&](GamepadEvent event, int gamepad) {
+        const std::string& name = gamepads.getName(gamepad);
+        devices.onGamepadEvent(event, gamepad, name);
+        if (devices.getJoinedCount() > 0) {
+            // the join screen comes up for multiplayer; just hold the game
+            if (devices.hasDisconnected()) {
+                pauseGame();
+            }
+            return;
+        }
+        if (event == GamepadEvent::Disconnected && gamepad == activeGamepad) {
+            pauseGame();
+            lostGamepad = name;
+            activeGamepad = -1;
+        } else if (event == GamepadEvent::Connected && name == lostGamepad) {
+            // single player reads any controller, so its bindings are back as is
+            lostGamepad.clear();
+        }
+    });
+    MouseControl mouse;
+    mouse.apply(settings);
+    InputRecorder recorder;
+    if (!options.recordInput.empty()) {
+        recorder.start(FramePacer::TICK_RATE);
+    }
+    InputPlayback playback;
+    if (!options.playInput.empty()) {
+        std::string error;
+        if (!playback.load(options.playInput, error)) {
+            TraceLog(LOG_WARNING, "INPUT: %s", error.c_str());
+        } else if (playback.getTickRate() != FramePacer::TICK_RATE) {
+            TraceLog(LOG_WARNING, "INPUT: %s was recorded at %u ticks per second, not %d", options.playInput.c_str(), playback.getTickRate(), FramePacer::TICK_RATE);
+        }
+    }
+    // replays play under the rules they recorded instead
+    auto applyRules = [&]() {
+        if (replay.isPlaying()) {
+            return;
+        }
+        simulation.rules.inputMode = profile.inputMode;
+        simulation.rules.autoLaunch = profile.autoLaunch;
+        simulation.rules.scanSpeed = profile.scanSpeed;
+        simulation.rules.inputBufferTicks = settings.inputBufferMs * FramePacer::TICK_RATE / 1000;
+        applyMutators(runMutators, simulation.rules);
+        simulation.rules.mode = (int) runMode;
+    };
+    applyRules();
+    // after the rules: the mutators decide the lives and paddle a level starts with
+    simulation.reset(levelBricks, seed);
+    auto resetRival = [&]() {
+        if (!versus) {
+            return;
+        }
+        rival.rules = simulation.rules;
+        rival.dropTable = simulation.dropTable;
+        rival.boss = simulation.boss;
+        rival.launchBudget = simulation.launchBudget;
+        rival.physics = simulation.physics;
+        rival.reset(levelBricks, seed);
+        rivalBrickField.markDirty();
+        versusMatch.start(simulation, rival);
+    };
+    resetRival();
+    // replays show what the recorded player had on, live play the profile's
+    // picks as far as they are unlocked
+    auto cosmetics = [&]() {
+        CosmeticLoadout loadout;
+        if (replay.isPlaying()) {
+            loadout.parse(replay.getHeader().cosmetics);
+        } else {
+            loadout.parse(profile.skin + "," + profile.paddle + "," + profile.trail);
+            loadout.restrictTo(profile.unlocked);
+        }
+        return loadout;
+    };
+    // always recording, so a run that makes the leaderboard can keep its replay
+    ReplayRecorder replayRecorder;
+    auto startReplayRecording = [&](const std::string& levelPath) {
+        ReplayHeader header;
+        header.tickRate = FramePacer::TICK_RATE;
+        header.seed = seed;
+        header.levelHash = levelHash(levelBricks);
+        header.levelPath = levelPath;
+        header.rules = simulation.rules;
+        header.mutators = runMutators;
+        header.mode = gameModeInfo(runMode).id;
+        header.cosmetics = cosmetics().format();
+        replayRecorder.start(header);
+    };
+    if (resuming && suspended.getHeader().levelHash != levelHash(levelBricks)) {
+        TraceLog(LOG_WARNING, "SUSPEND: The level has changed since the run was suspended, starting it over");
+        resuming = false;
+    }
+    // the tutorial isn't recorded, and a replay only holds one level, so a
+    // challenge's gauntlet isn't either; the game after the tutorial is. A
+    // resumed run plays its suspended part into the recording again. Versus
+    // isn't: a replay has the one field.
+    if (resuming) {
+        replayRecorder.start(suspended.getHeader());
+        if (!suspended.continueInto(simulation, replayRecorder)) {
+            TraceLog(LOG_WARNING, "SUSPEND: The run didn't play back the same, carrying on from where it ended up");
+        }
+        applyRules();
+        TraceLog(LOG_INFO, "SUSPEND: Resumed at %s", formatRunTime(game.tick).c_str());
+    } else if (!replay.isPlaying() && !options.golden && !tutorial.isActive() && !weekly && !versus && !practice) {
+        startReplayRecording(options.levelPath);
+    }
+    // every mutator combination has a board of its own, and each week's
+    // challenge one of its own for the whole gauntlet (each day's too)
+    auto leaderboardKey = [&]() -> LeaderboardKey {
+        if (weekly) {
+            return {challenge.seed, "weekly", runMutators};
+        }
+        if (daily) {
+            return {today.seed, "daily", runMutators};
+        }
+        return {levelHash(levelBricks), gameModeInfo(runMode).id, runMutators};
+    };
+    Leaderboard leaderboard;
+    leaderboard.load(leaderboardKey());
+    Leaderboard globalBoard;
+    globalBoard.load(globalLeaderboardKey());
+    LeaderboardScreen leaderboardScreen;
+    StatsScreen statsScreen;
+    ShopScreen shopScreen;
+    LevelSelectScreen levelSelect;
+    LevelChoice packLevel; // the level pack's level last picked, to count its clear towards the pack
+    // only the top menu is open; the ones under it come back as it closes
+    auto showMenu = [](auto& menu, bool open) {
+        if (menu.isOpen != open) {
+            menu.toggle();
+        }
+    };
+    flow.onOverlay(Overlay::Settings, [&]() { showMenu(settingsMenu, true); }, [&]() { showMenu(settingsMenu, false); });
+    flow.onOverlay(Overlay::Controls, [&]() { showMenu(controlsMenu, true); }, [&]() { showMenu(controlsMenu, false); });
+    flow.onOverlay(Overlay::Devices, [&]() { showMenu(devices, true); }, [&]() { showMenu(devices, false); });
+    flow.onOverlay(Overlay::Leaderboard, [&]() { showMenu(leaderboardScreen, true); }, [&]() { showMenu(leaderboardScreen, false); });
+    flow.onOverlay(Overlay::Stats, [&]() { showMenu(statsScreen, true); }, [&]() { showMenu(statsScreen, false); });
+    flow.onOverlay(Overlay::Shop, [&]() { showMenu(shopScreen, true); }, [&]() { showMenu(shopScreen, false); });
+    flow.onOverlay(Overlay::Levels, [&]() { showMenu(levelSelect, true); }, [&]() { showMenu(levelSelect, false); });
+    // a drag doesn't carry on through a menu opened over it
+    auto showPlacement = [&](bool open) {
+        placingBall = open;
+        draggingBall = draggingAim = false;
+    };
+    flow.onOverlay(Overlay::PlaceBall, [&]() { showPlacement(true); }, [&]() { showPlacement(false); });
+    // a speedrun route is the whole tutorial (played from its first level), a
+    // challenge's gauntlet or one level; replays aren't timed
+    SpeedrunTimer speedrun;
+    auto prepareSpeedrun = [&]() {
+        if (!settings.speedrunTimer || replay.isPlaying()) {
+            return;
+        }
+        if (weekly) {
+            std::vector<std::string> names;
+            for (const std::string& path : challenge.levels) {
+                names.push_back(path.empty() ? "classic" : GetFileNameWithoutExt(path.c_str()));
+            }
+            speedrun.prepare(challenge.id, names);
+        } else if (!tutorial.isActive()) {
+            speedrun.prepare(levelName, {levelName});
+        } else if (tutorial.getLevelIndex() == 0) {
+            std::vector<std::string> names;
+            for (const std::string& path : Tutorial::levelPaths()) {
+                names.push_back(GetFileNameWithoutExt(path.c_str()));
+            }
+            speedrun.prepare("Tutorial", names);
+        }
+    };
+    uint8_t carriedPresses = 0; // pressed during frames that ran no tick
+    uint8_t carriedPartnerPresses = 0;
+    int loggedIntensity = -1;
+    TouchControls touch;
+    touch.enabled = settings.touchControls;
+    settingsMenu.touchMode = settings.touchControls;
+
+    Telemetry telemetry;
+    auto applyTelemetry = [&]() {
+        bool wanted = settings.telemetry || options.telemetry;
+        if (wanted && !telemetry.isActive()) {
+            telemetry.start(levelName, seed);
+        } else if (!wanted && telemetry.isActive()) {
+            telemetry.stop(game);
+        }
+    };
+    applyTelemetry();
+
+    // Level theme first, then the profile's choice, then the default
+    auto applyTheme = [&]() {
+        std::string id = themes.has(levelTheme) ? levelTheme : profile.theme;
+        theme = &themes.get(id);
+
+        themeAtlas.unload();
+        themeAtlas.buildFromDirectory(theme->directory + "/sprites");
+        if (!currentLevel.background.empty()) {
+            background.load(GetDirectoryPath(currentLevel.path.c_str()), {{currentLevel.background}});
+        } else {
+            background.load(theme->directory, theme->background);
+        }
+        for (int i = 0; i < 4; i++) {
+            BrickMaterial material = (BrickMaterial) i;
+            auto style = theme->particles.find(material);
+            particles.setMaterialStyle(material, style != theme->particles.end() ? style->second : defaultParticleStyle(material));
+        }
+        text.unload();
+        if (!theme->font.empty()) {
+            text.addFont(theme->font);
+        }
+        text.loadDefaultChain();
+        text.prepare(languageGlyphs());
+        postProcessor.setThemeBloom(theme->bloom);
+        popups.setColor(theme->palette.text);
+        brickField.markDirty();
+        // the boss's track, the level's own or else the theme's
+        std::string track = !musicTrackPath(levelMusic).empty() ? levelMusic : theme->music;
+        if (!currentLevel.boss.music.empty() && !musicTrackPath(currentLevel.boss.music).empty()) {
+            track = currentLevel.boss.music;
+        }
+        audio.setGameplayTrack(track);
+        telemetry.decision("music", "track " + track);
+    };
+    applyTheme();
+
+    // only reports anything in BREAKOUT_HOT_RELOAD builds
+    AssetWatcher assetWatcher;
+    std::string appDirectory = GetApplicationDirectory();
+    assetWatcher.watchDirectory(appDirectory + "assets/sprites", ".png", AssetKind::Texture);
+    assetWatcher.watchDirectory(appDirectory + "assets/sounds", ".wav;.ogg;.mp3;.flac;.qoa;.manifest", AssetKind::Sound);
+    assetWatcher.watchDirectory(appDirectory + "assets/shaders", ".fs", AssetKind::Shader);
+    assetWatcher.watchDirectory(appDirectory + "themes", ".png", AssetKind::Texture);
+    assetWatcher.watchDirectory(appDirectory + "themes", ".xml", AssetKind::Theme);
+    assetWatcher.watchDirectory(appDirectory + "mods", ".png", AssetKind::Texture);
+    assetWatcher.watchDirectory(appDirectory + "mods", ".xml", AssetKind::Theme);
+    assetWatcher.watchDirectory(appDirectory + "levels", ".blvl", AssetKind::Level);
+    assetWatcher.watchDirectory(appDirectory + "mods", ".blvl", AssetKind::Level);
+    assetWatcher.watchDirectory(appDirectory + "levelpacks", ".blvl", AssetKind::Level);
+
+    // The HUD is laid out in window pixels so it hugs the screen edges at any
+    // aspect ratio instead of the letterboxed playfield
+    UiLayout hud;
+    hud.root.add({"fps", Anchor::TopLeft, {25, 25}, {200, 20}});
+    hud.root.add({"debug", Anchor::TopLeft, {25, 50}, {400, 75}});
+    hud.root.add({"score", Anchor::Top, {0, 25}, {200, 20}});
+    hud.root.add({"cheats", Anchor::Top, {0, 50}, {400, 20}});
+    hud.root.add({"tutorial", Anchor::Top, {0, 80}, {720, 56}});
+    hud.root.add({"boss", Anchor::Top, {0, 145}, {480, 40}});
+    hud.root.add({"toast", Anchor::Top, {0, 195}, {480, 56}});
+    hud.root.add({"lives", Anchor::TopRight, {25, 25}, {200, 20}});
+    hud.root.add({"coins", Anchor::TopRight, {235, 25}, {140, 20}});
+    hud.root.add({"mode", Anchor::TopLeft, {25, 50}, {300, 20}});
+    hud.root.add({"combo", Anchor::TopLeft, {25, 75}, {300, 20}});
+    hud.root.add({"score_p1", Anchor::BottomLeft, {25, 10}, {200, 20}});
+    hud.root.add({"score_p2", Anchor::BottomRight, {25, 10}, {200, 20}});
+    hud.root.add({"message", Anchor::Center, {0, 0}, {400, 40}});
+    hud.root.add({"captions", Anchor::Bottom, {0, 40}, {600, 0}});
+    hud.root.add({"replay", Anchor::Bottom, {0, 10}, {400, 20}});
+    hud.root.add({"profiler", Anchor::BottomLeft, {25, 25}, {360, 140}});
+    hud.root.add({"console", Anchor::BottomRight, {25, 25}, {520, 170}});
+    // tablets have no F1; 48 px is the smallest comfortable finger target
+    hud.root.add({"menu_button", Anchor::TopRight, {25, 55}, {96, 48}});
+    hud.root.add({"speedrun", Anchor::TopRight, {25, 110}, {280, 200}});
+    hud.root.add({"powers", Anchor::Left, {25, 0}, {220, 100}});
+
+    std::vector<std::string> languages = languageCodes();
+    auto menuFields = [&]() {
+        std::vector<SettingField> fields = settingFields(settings);
+        for (SettingField& field : fields) {
+            if (field.value == &settings.language) {
+                field.choices = languages;
+            }
+        }
+        for (SettingField& field : profileFields(profile)) {
+            if (field.value == &profile.theme) {
+                field.choices = themes.ids();
+            } else if (field.value == &profile.skin || field.value == &profile.paddle || field.value == &profile.trail) {
+                CosmeticSlot slot = field.value == &profile.skin ? CosmeticSlot::Skin : field.value == &profile.paddle ? CosmeticSlot::Paddle : CosmeticSlot::Trail;
+                field.choices = unlockedCosmetics(profile.unlocked, slot);
+            }
+            fields.push_back(field);
+        }
+        return fields;
+    };
+    EventBus events;
+    Juice juice;
+    events.subscribe([&juice](const GameEvent& event) { juice.onEvent(event); });
+    // Reduced motion has to reach every system that moves or flashes
+    auto applyMotionSettings = [&]() {
+        juice.apply(settings);
+        lighting.setReducedMotion(settings.reducedMotion);
+        particles.setReducedMotion(settings.reducedMotion);
+        popups.setReducedMotion(settings.reducedMotion);
+        background.setReducedMotion(settings.reducedMotion);
+    };
+    applyMotionSettings();
+    events.subscribe([&audio](const GameEvent& event) { audio.onEvent(event); });
+    // the rival's field is heard, but the shakes and cues stay player one's
+    EventBus rivalEvents;
+    rivalEvents.subscribe([&audio](const GameEvent& event) { audio.onEvent(event); });
+    VisualSoundCues soundCues;
+    soundCues.enabled = settings.visualSoundCues;
+    events.subscribe([&soundCues](const GameEvent& event) { soundCues.onEvent(event); });
+    events.subscribe([&popups](const GameEvent& event) { popups.onEvent(event); });
+    events.subscribe([&telemetry](const GameEvent& event) { telemetry.onEvent(event); });
+    simulation.onBrickBroken = [&](Brick& brick) {
+        ThemePalette palette = accessiblePalette(theme->palette, (ColorBlindMode) settings.colorBlindMode);
+        particles.emitBrickShatter(brick.getRectangle(), brick.material, palette.bricks[(int) brick.material]);
+        brickField.markDirty();
+    };
+    rival.onBrickBroken = [&](Brick&) { rivalBrickField.markDirty(); };
+    // a junk row moves every brick down
+    events.subscribe([&brickField](const GameEvent& event) {
+        if (event.type == EventType::GarbageReceived) {
+            brickField.markDirty();
+        }
+    });
+    rivalEvents.subscribe([&rivalBrickField](const GameEvent& event) {
+        if (event.type == EventType::GarbageReceived) {
+            rivalBrickField.markDirty();
+        }
+    });
+    events.subscribe([&lighting](const GameEvent& event) {
+        if (event.type == EventType::BrickDestroyed) {
+            lighting.addFlash(event.position, 90, ORANGE, 0.25f);
+        }
+    });
+
+    events.subscribe([&tutorial](const GameEvent& event) { tutorial.onEvent(event); });
+    // the profile's own play; watching a replay or doing the tutorial isn't
+    events.subscribe([&](const GameEvent& event) {
+        if (!replay.isPlaying() && !tutorial.isActive()) {
+            playerStats.onEvent(event);
+        }
+    });
+    // the level being played, for its breakdown at the end
+    LevelRecorder levelRecorder;
+    events.subscribe([&levelRecorder](const GameEvent& event) { levelRecorder.onEvent(event); });
+    // the same for achievements, which aren't earned by cheating either
+    AchievementTracker achievements;
+    achievements.load(loadAchievements(), profile.achievements);
+    events.subscribe([&](const GameEvent& event) {
+        if (!replay.isPlaying() && !tutorial.isActive() && !game.cheated) {
+            achievements.onEvent(event);
+        }
+    });
+    // one toast at a time, for TOAST_SECONDS each: its title and the line
+    // under it, already translated
+    const double TOAST_SECONDS = 3;
+    std::vector<std::pair<std::string, std::string>> toasts;
+    double toastStart = -1;
+    // XP goes to the profile as it's earned, and a level gained toasts
+    auto gainXp = [&](int xp) {
+        int before = playerLevel(profile.xp);
+        profile.xp += xp;
+        int after = playerLevel(profile.xp);
+        if (after > before) {
+            TraceLog(LOG_INFO, "PROFILE: Reached player level %d", after);
+            toasts.push_back({TextFormat(tr("Player level %d").c_str(), after),
+                TextFormat(tr("%d XP to the next").c_str(), xpForPlayerLevel(after + 1) - profile.xp)});
+        }
+    };
+    // what this run has done towards unlocks, besides its score
+    RunRecord runRecord;
+    events.subscribe([&runRecord](const GameEvent& event) {
+        if (event.type == EventType::ComboIncreased && event.value > runRecord.bestCombo) {
+            runRecord.bestCombo = event.value;
+        } else if (event.type == EventType::PerfectCatch) {
+            runRecord.perfectCatches++;
+        }
+    });
+
+    // F6; timers through the loop below feed it
+    Profiler profiler;
+    // only does anything in BREAKOUT_DEV_TOOLS builds
+    DevTools devTools;
+
+    // Rendering is a fixed list of passes; each one only states what it draws
+    // into and what it needs, the graph takes care of binding targets
+    std::vector<SettingField> fields;
+    RenderGraph graph;
+    graph.addTarget({"scene",
+        [&]() { BeginTextureMode(postProcessor.sceneTarget(settings, viewport.getCanvas())); },
+        []() { EndTextureMode(); }});
+    graph.addTarget({"canvas",
+        [&]() { BeginTextureMode(viewport.getCanvas()); },
+        []() { EndTextureMode(); }});
+    graph.addTarget({"window",
+        [&]() { viewport.beginOverlay(); },
+        [&]() { viewport.endFrame(); }});
+
+    graph.addPass({"lightmap", "", {}, {"lightmap"}, [&]() { lighting.render(); }});
+    // One field as the world pass draws it; versus draws the rival's too
+    auto drawField = [&](GameState& game, BrickField& brickField, Camera2D camera) {
+        TextureAtlas& activeAtlas = themeAtlas.isReady() ? themeAtlas : atlas;
+        ThemePalette palette = accessiblePalette(theme->palette, (ColorBlindMode) settings.colorBlindMode);
+        BeginMode2D(camera);
+        drawWalls(activeAtlas, *theme, screenWidth, screenHeight);
+        drawBricks(batch, brickField, activeAtlas, *theme, palette, settings.brickPatterns, game.bricks);
+        if (game.boss.alive()) {
+            drawBoss(game.boss, palette, settings.reducedMotion);
+        }
+        if (simulation.rules.mode == (int) GameMode::Endless || versus) {
+            // the line the bricks mustn't reach, redder the closer they get
+            float line = game.player.getRectangle().y;
+            float danger = fminf(fieldBottom(game.bricks) / line, 1);
+            DrawLineEx({0, line}, {(float) simulation.rules.width, line}, 2, Fade(RED, 0.15f + 0.6f * danger * danger));
+        }
+        if (&game == &simulation.state) {
+            lighting.illuminateBricks(game.bricks);
+        }
+        if (game.lives > 0) {
+            CosmeticLoadout loadout = cosmetics();
+            Color ballColor = loadout.ballColor(theme->palette.ball);
+            Color paddleColor = loadout.paddleColor(theme->palette.paddle);
+            for (const Pickup& drop : game.drops) {
+                if (drop.kind == PickupKind::Coin) {
+                    DrawCircleV(drop.position, Pickup::COIN_SIZE / 2, GOLD);
+                    DrawCircleLines(drop.position.x, drop.position.y, Pickup::COIN_SIZE / 2, ColorBrightness(GOLD, -0.4f));
+                    continue;
+                }
+                if (drop.kind == PickupKind::Time) {
+                    // a little clock face
+                    DrawCircleV(drop.position, Pickup::COIN_SIZE / 2, SKYBLUE);
+                    DrawLineV(drop.position, {drop.position.x, drop.position.y - Pickup::COIN_SIZE / 2 + 2}, DARKBLUE);
+                    DrawLineV(drop.position, {drop.position.x + Pickup::COIN_SIZE / 3, drop.position.y}, DARKBLUE);
+                    continue;
+                }
+                const PowerUp& powerUp = powerUpInfo(drop.powerUp);
+                Rectangle capsule = drop.getRectangle();
+                if (powerUp.harmful) {
+                    // dark, crossed out, so it reads as one to dodge at a glance
+                    DrawRectangleRounded(capsule, 1, 8, ColorBrightness(powerUp.color, -0.6f));
+                    DrawRectangleRoundedLines(capsule, 1, 8, 2, powerUp.color);
+                    Vector2 center = drop.position;
+                    float arm = Pickup::HEIGHT / 3;
+                    DrawLineEx({center.x - arm, center.y - arm}, {center.x + arm, center.y + arm}, 2, powerUp.color);
+                    DrawLineEx({center.x - arm, center.y + arm}, {center.x + arm, center.y - arm}, 2, powerUp.color);
+                    continue;
+                }
+                DrawRectangleRounded(capsule, 1, 8, powerUp.color);
+                DrawRectangleRoundedLines(capsule, 1, 8, 2, ColorBrightness(powerUp.color, 0.5f));
+            }
+            for (const Debris& debris : game.debris) {
+                // tumbling, so it doesn't read as a pickup
+                Rectangle rect = debris.getRectangle();
+                float spin = settings.reducedMotion ? 0 : (float) GetTime() * 240;
+                DrawRectanglePro({debris.position.x, debris.position.y, rect.width, rect.height}, {rect.width / 2, rect.height / 2}, spin, BROWN);
+            }
+            if (game.barrier.up) {
+                Rectangle barrier = game.barrier.getRectangle(simulation.rules.width, simulation.rules.height);
+                Color shieldColor = powerUpInfo(PowerUpType::Shield).color;
+                float pulse = settings.reducedMotion ? 0 : 0.15f * sinf(GetTime() * 4);
+                DrawRectangleRec(barrier, Fade(shieldColor, 0.35f + pulse));
+                DrawRectangleRec({barrier.x, barrier.y, barrier.width, 1}, shieldColor);
+            }
+            Color laserColor = powerUpInfo(PowerUpType::Laser).color;
+            for (Projectile& projectile : game.projectiles) {
+                DrawRectangleRec(projectile.getRectangle(), laserColor);
+            }
+            // co-op's second paddle is drawn, and shows its power-ups, like the first
+            std::vector<Player*> paddles = {&game.player};
+            if (simulation.rules.mode == (int) GameMode::Coop) {
+                paddles.push_back(&game.partner);
+                DrawLineEx({simulation.rules.width / 2.0f, game.player.getRectangle().y - 40}, {simulation.rules.width / 2.0f, (float) simulation.rules.height}, 1,
+                    Fade(theme->palette.text, 0.2f));
+            }
+            if (game.effects.has(PowerUpType::Laser)) {
+                // the cannons the bolts leave from
+                for (Player* player : paddles) {
+                    Rectangle paddle = player->getRectangle();
+                    for (float x : {paddle.x + LASER_INSET, paddle.x + paddle.width - LASER_INSET}) {
+                        DrawRectangleRec({x - 3, paddle.y - 6, 6, 6}, laserColor);
+                    }
+                }
+            }
+            if (game.effects.has(PowerUpType::Magnet)) {
+                // the pull on each ball it has hold of, which also gives away
+                // one flicker is hiding
+                for (Ball& ball : game.balls) {
+                    if (ball.isFree() && ball.velocity.y > 0) {
+                        Vector2 paddleCenter = paddles[std::min(ball.owner, (int) paddles.size() - 1)]->getCenter();
+                        DrawLineEx(paddleCenter, ball.getCenter(), 1, Fade(powerUpInfo(PowerUpType::Magnet).color, 0.25f));
+                    }
+                }
+            }
+            if (game.effects.has(PowerUpType::Catch)) {
+                for (Player* player : paddles) {
+                    Rectangle paddle = player->getRectangle();
+                    DrawRectangleRec({paddle.x, paddle.y - 3, paddle.width, 3}, powerUpInfo(PowerUpType::Catch).color);
+                }
+            }
+            for (Ball& ball : game.balls) {
+                if (ball.state != BallState::Caught) {
+                    continue;
+                }
+                // which way it will go
+                Vector2 center = ball.getCenter();
+                Vector2 launch = catchLaunchVelocity(ball.paddleOffset, game.player.rectangle.width / 2, simulation.ballSpeed());
+                float length = 60 / sqrtf(launch.x * launch.x + launch.y * launch.y);
+                DrawLineEx(center, {center.x + launch.x * length, center.y + launch.y * length}, 2, Fade(ballColor, 0.5f));
+            }
+            // a trail would give a flickering ball away
+            if (!game.effects.has(PowerUpType::Flicker) && &game == &simulation.state) {
+                ballTrails.draw(game.balls, ballColor, loadout.trailStyle());
+            }
+            float visibility = ballVisibility(game, settings.reducedMotion);
+            for (Ball& ball : game.balls) {
+                // one on the paddle always shows
+                float alpha = ball.isFree() ? visibility : 1;
+                if (alpha > 0) {
+                    drawSprite(activeAtlas, theme->sprite("ball"), ball.getRectangle(), Fade(ball.piercing ? ORANGE : ballColor, alpha));
+                }
+            }
+            for (size_t i = 0; i < paddles.size(); i++) {
+                Rectangle paddle = paddles[i]->getRectangle();
+                // player two's a shade darker, so the two can't be mixed up
+                Color color = i == 0 ? paddleColor : ColorBrightness(paddleColor, -0.35f);
+                if (game.effects.has(PowerUpType::ReverseControls)) {
+                    DrawRectangleLinesEx(paddle, 2, powerUpInfo(PowerUpType::ReverseControls).color);
+                }
+                if (loadout.paddleRoundness() > 0) {
+                    DrawRectangleRounded(paddle, loadout.paddleRoundness(), 8, color);
+                } else {
+                    drawSprite(activeAtlas, theme->sprite("paddle"), paddle, color);
+                }
+            }
+        }
+        EndMode2D();
+    };
+    // versus halves the view, player one's field on the left
+    auto fieldCamera = [&](int side) {
+        Camera2D camera = devTools.camera(juice.getCamera());
+        if (versus) {
+            camera.zoom *= 0.5f;
+            camera.offset = {camera.offset.x * 0.5f + side * screenWidth / 2.0f, camera.offset.y * 0.5f + screenHeight / 4.0f};
+        }
+        return camera;
+    };
+    graph.addPass({"world", "scene", {}, {"scene"}, [&]() {
+        ThemePalette palette = accessiblePalette(theme->palette, (ColorBlindMode) settings.colorBlindMode);
+        ClearBackground(palette.background);
+        background.draw(screenWidth, screenHeight);
+        drawField(game, brickField, fieldCamera(0));
+        if (versus) {
+            drawField(rival.state, rivalBrickField, fieldCamera(1));
+        }
+    }});
+    graph.addPass({"particles", "scene", {"scene"}, {"scene"}, [&]() {
+        BeginMode2D(fieldCamera(0));
+        particles.draw();
+        popups.draw(text);
+        EndMode2D();
+    }});
+    graph.addPass({"lighting", "scene", {"scene", "lightmap"}, {"scene"}, [&]() {
+        BeginMode2D(fieldCamera(0));
+        lighting.composite();
+        EndMode2D();
+    }});
+    graph.addPass({"post", "", {"scene"}, {"post"}, [&]() { postProcessor.process(settings); }});
+    graph.addPass({"present", "canvas", {"post"}, {"canvas"}, [&]() { postProcessor.drawResult(); }});
+    // drawn after post-processing so the CRT filter never blurs them
+    graph.addPass({"cues", "canvas", {"canvas"}, {"canvas"}, [&]() {
+        BeginMode2D(fieldCamera(0));
+        soundCues.drawIndicators(screenWidth, screenHeight);
+        EndMode2D();
+    }});
+    // practice's handles: a ring to drag the ball by and an arrow to aim it
+    graph.addPass({"sandbox", "canvas", {"canvas"}, {"canvas"}, [&]() {
+        if (!placingBall) {
+            return;
+        }
+        BeginMode2D(fieldCamera(0));
+        Color handleColor = theme->palette.text;
+        Vector2 center = sandbox.ball().getCenter();
+        Vector2 tip = sandbox.aimHandle();
+        DrawCircleLines(center.x, center.y, sandbox.ball().rectangle.width * 1.5f, handleColor);
+        DrawLineEx(center, tip, 2, Fade(handleColor, 0.8f));
+        DrawCircleV(tip, 6, handleColor);
+        std::string reading = TextFormat(tr("Angle %.0f, speed %.1f").c_str(), sandbox.angle(), sandbox.speed());
+        text.draw(reading, {tip.x + 10, tip.y - 10}, 16, handleColor);
+        EndMode2D();
+    }});
+    graph.addPass({"capture", "", {"canvas"}, {}, [&]() { capture.record(viewport.getCanvas(), pacer.getFrameTime()); }});
+    graph.addPass({"ui", "window", {"canvas"}, {"window"}, [&]() {
+        hud.update({0, 0, (float) GetScreenWidth(), (float) GetScreenHeight()}, settings.uiSafeArea, settings.uiScale);
+        float uiScale = hud.getScale();
+        devTools.draw(hud.rect("cheats"), uiScale);
+        if (flow.is(GameScreen::GameOver)) {
+            bool timeAttack = simulation.rules.mode == (int) GameMode::TimeAttack;
+            bool puzzle = simulation.launchBudget > 0;
+            std::string game_over = tr(game.lives > 0 ? "Level Cleared" : timeAttack ? "Time's up" : puzzle ? "Out of balls" : "Game Over");
+            if (versus) {
+                VersusResult result = versusMatch.result(simulation, rival);
+                game_over = tr(result == VersusResult::FirstWins ? "Player 1 wins" : result == VersusResult::SecondWins ? "Player 2 wins" : "Draw");
+            }
+            Vector2 corner = hud.place("message", text.measure(game_over, 40 * uiScale));
+            text.draw(game_over, corner, 40 * uiScale, theme->palette.text);
+            if (timeAttack) {
+                std::string results = TextFormat(tr("%d bricks, %d s extra time caught").c_str(), brickTotal - game.bricksLeft, game.timeCollected);
+                Rectangle message = hud.rect("message");
+                Vector2 resultsSize = text.measure(results, 20 * uiScale);
+                text.draw(results, {message.x + (message.width - resultsSize.x) / 2, corner.y + 50 * uiScale}, 20 * uiScale, theme->palette.text);
+            } else if (puzzle && !versus) {
+                std::string results = game.lives > 0 ? TextFormat(trn("%d ball to spare, +%d", "%d balls to spare, +%d", game.launchesLeft).c_str(), game.launchesLeft, game.spareBonus)
+                                      : hardcore ? tr("No second tries in hardcore")
+                                                 : TextFormat(tr("Press %s to try again").c_str(), describeInput(profile.bindings.get(Action::Restart, 0)).c_str());
+                Rectangle message = hud.rect("message");
+                Vector2 resultsSize = text.measure(results, 20 * uiScale);
+                text.draw(results, {message.x + (message.width - resultsSize.x) / 2, corner.y + 50 * uiScale}, 20 * uiScale, theme->palette.text);
+            }
+        } else if (flow.is(GameScreen::Paused) && !lostGamepad.empty() && !pauseMenu.isCountingDown()) {
+            std::string prompt = tr("Controller disconnected");
+            std::string hint = tr("Reconnect it, or press pause to carry on");
+            Vector2 corner = hud.place("message", text.measure(prompt, 40 * uiScale));
+            text.draw(prompt, corner, 40 * uiScale, theme->palette.text);
+            Rectangle message = hud.rect("message");
+            Vector2 hintSize = text.measure(hint, 20 * uiScale);
+            text.draw(hint, {message.x + (message.width - hintSize.x) / 2, corner.y + 50 * uiScale}, 20 * uiScale, theme->palette.text);
+        } else if (placingBall) {
+            std::string hint = tr("Drag the ball to move it and the arrow to aim it; enter plays on");
+            text.draw(hint, hud.place("message", text.measure(hint, 20 * uiScale)), 20 * uiScale, theme->palette.text);
+        } else if (flow.is(GameScreen::Paused) && !flow.hasOverlay()) {
+            pauseMenu.draw(text, pauseReview(), hud.root.rect, uiScale, theme->palette.text, GetTime());
+        }
+        text.draw(actualFPS, hud.place("fps", text.measure(actualFPS, 20 * uiScale)), 20 * uiScale, theme->palette.text);
+        if (versus) {
+            // each under their own field, with how close the next junk row is
+            GameState* fields[2] = {&game, &rival.state};
+            for (int i = 0; i < 2; i++) {
+                std::string side = TextFormat(tr("P%d: %d, %d lives, junk %d/%d").c_str(), i + 1, fields[i]->score, std::max(fields[i]->lives, 0),
+                    versusMatch.progress(i), VersusRules::GARBAGE_BRICKS);
+                text.draw(side, hud.place(i == 0 ? "score_p1" : "score_p2", text.measure(side, 20 * uiScale)), 20 * uiScale, theme->palette.text);
+            }
+        } else {
+            if (simulation.launchBudget > 0) {
+                // a puzzle counts serves, not lives
+                std::string serves = TextFormat(tr("Balls %d").c_str(), game.launchesLeft);
+                text.draw(serves, hud.place("lives", text.measure(serves, 20 * uiScale)), 20 * uiScale, theme->palette.text);
+            } else if (simulation.rules.mode != (int) GameMode::TimeAttack) {
+                // against the clock, lives don't come into it
+                text.draw(livesLeft, hud.place("lives", text.measure(livesLeft, 20 * uiScale)), 20 * uiScale, theme->palette.text);
+            }
+            text.draw(scorePrintable, hud.place("score", text.measure(scorePrintable, 20 * uiScale)), 20 * uiScale, theme->palette.text);
+        }
+        if (simulation.rules.mode == (int) GameMode::Endless) {
+            int seconds = (game.shiftTicks + FramePacer::TICK_RATE - 1) / FramePacer::TICK_RATE;
+            std::string rows = TextFormat(tr("Rows %d, next in %d s").c_str(), game.rowsAdded, seconds);
+            text.draw(rows, hud.place("mode", text.measure(rows, 20 * uiScale)), 20 * uiScale, theme->palette.text);
+        } else if (simulation.rules.mode == (int) GameMode::TimeAttack) {
+            int seconds = (game.timeTicks + FramePacer::TICK_RATE - 1) / FramePacer::TICK_RATE;
+            std::string clock = TextFormat(tr("Time %d:%02d").c_str(), seconds / 60, seconds % 60);
+            // the last ten seconds in red
+            text.draw(clock, hud.place("mode", text.measure(clock, 20 * uiScale)), 20 * uiScale, seconds <= 10 ? RED : theme->palette.text);
+        } else if (simulation.rules.mode == (int) GameMode::Survival) {
+            int seconds = game.survivedTicks / FramePacer::TICK_RATE;
+            std::string survived = TextFormat(tr("Survived %d:%02d, stage %d").c_str(), seconds / 60, seconds % 60, game.survivalStage + 1);
+            text.draw(survived, hud.place("mode", text.measure(survived, 20 * uiScale)), 20 * uiScale, theme->palette.text);
+        } else if (practice) {
+            std::string note = tr("Practice: nothing is kept");
+            text.draw(note, hud.place("mode", text.measure(note, 20 * uiScale)), 20 * uiScale, Fade(theme->palette.text, 0.7f));
+        } else if (hardcore) {
+            std::string streak = TextFormat(tr("Hardcore, %d cleared").c_str(), profile.hardcoreLevels);
+            text.draw(streak, hud.place("mode", text.measure(streak, 20 * uiScale)), 20 * uiScale, RED);
+        }
+        if (simulation.rules.mode == (int) GameMode::Coop) {
+            // each under their own half
+            for (int i = 0; i < 2; i++) {
+                std::string share = TextFormat(tr("P%d: %d").c_str(), i + 1, game.playerScores[i]);
+                text.draw(share, hud.place(i == 0 ? "score_p1" : "score_p2", text.measure(share, 20 * uiScale)), 20 * uiScale, theme->palette.text);
+            }
+        }
+        // fading as it gets close to losing a step
+        if (game.combo > 1 && !versus) {
+            std::string combo = TextFormat(tr("Combo %d, x%d").c_str(), game.combo, comboMultiplier(game.combo));
+            float decay = (float) game.comboTicks / (ComboRules::DECAY_SECONDS * FramePacer::TICK_RATE);
+            text.draw(combo, hud.place("combo", text.measure(combo, 20 * uiScale)), 20 * uiScale, Fade(GOLD, 1 - 0.6f * decay));
+        }
+        if (game.coins > 0) {
+            std::string coins = TextFormat(tr("Coins %d").c_str(), game.coins);
+            text.draw(coins, hud.place("coins", text.measure(coins, 20 * uiScale)), 20 * uiScale, GOLD);
+        }
+        if (!toasts.empty() && toastStart >= 0) {
+            // fading out over its last half second
+            float alpha = fminf(1, (float) (TOAST_SECONDS - (GetTime() - toastStart)) * 2);
+            Rectangle box = hud.rect("toast");
+            DrawRectangleRec(box, Fade(DARKGRAY, 0.9f * alpha));
+            DrawRectangleLinesEx(box, 2 * uiScale, Fade(GOLD, alpha));
+            text.draw(toasts.front().first, {box.x + 12 * uiScale, box.y + 6 * uiScale}, 20 * uiScale, Fade(GOLD, alpha));
+            text.draw(toasts.front().second, {box.x + 12 * uiScale, box.y + 32 * uiScale}, 16 * uiScale, Fade(theme->palette.text, alpha));
+        }
+        if (settings.touchControls) {
+            Rectangle button = hud.rect("menu_button");
+            DrawRectangleLinesEx(button, 2 * uiScale, Fade(theme->palette.text, 0.6f));
+            std::string menuText = tr("Menu");
+            text.draw(menuText, hud.place("menu_button", text.measure(menuText, 20 * uiScale)), 20 * uiScale, theme->palette.text);
+        }
+        if (replay.isPlaying()) {
+            auto clock = [](uint64_t tick) {
+                int seconds = (int) (tick / FramePacer::TICK_RATE);
+                return std::string(TextFormat("%d:%02d", seconds / 60, seconds % 60));
+            };
+            std::string status = TextFormat(tr("Replay %s / %s").c_str(), clock(game.tick).c_str(), clock(replay.getEndTick()).c_str());
+            if (replay.hasDiverged()) {
+                status += "  " + std::string(TextFormat(tr("(out of sync since %s)").c_str(), clock(replay.getDivergedTick()).c_str()));
+            }
+            text.draw(status, hud.place("replay", text.measure(status, 20 * uiScale)), 20 * uiScale, theme->palette.text);
+        }
+        tutorial.draw(text, profile.bindings, hud.rect("tutorial"), uiScale, theme->palette.text);
+        if (game.boss.alive()) {
+            // its name over its health, a notch between weak points
+            Rectangle area = hud.rect("boss");
+            Rectangle bar = {area.x, area.y + 22 * uiScale, area.width, area.height - 22 * uiScale};
+            text.draw(game.boss.name, {area.x, area.y}, 18 * uiScale, theme->palette.text);
+            DrawRectangleRec(bar, Fade(BLACK, 0.6f));
+            DrawRectangleRec({bar.x, bar.y, bar.width * game.boss.health / game.boss.maxHealth, bar.height}, RED);
+            // the last weak point empties the bar from the left, so the
+            // notches count up from there in reverse order
+            std::vector<const BossSegment*> weak;
+            for (const BossSegment& segment : game.boss.segments) {
+                if (segment.order > 0) {
+                    weak.push_back(&segment);
+                }
+            }
+            std::sort(weak.begin(), weak.end(), [](const BossSegment* a, const BossSegment* b) { return a->order > b->order; });
+            int upTo = 0;
+            for (size_t i = 0; i + 1 < weak.size(); i++) {
+                upTo += weak[i]->maxHitPoints;
+                float x = bar.x + bar.width * upTo / game.boss.maxHealth;
+                DrawLineEx({x, bar.y}, {x, bar.y + bar.height}, 2 * uiScale, theme->palette.text);
+            }
+            DrawRectangleLinesEx(bar, 1, theme->palette.text);
+        }
+        // an icon per effect in play, in the order they were caught, each
+        // ringed with the time it has left
+        Rectangle powers = hud.rect("powers");
+        float iconSize = 44 * uiScale;
+        int perRow = std::max(1, (int) (powers.width / iconSize));
+        int icon = 0;
+        auto nextIcon = [&]() {
+            Vector2 center = {powers.x + (icon % perRow + 0.5f) * iconSize, powers.y + (icon / perRow + 0.5f) * iconSize};
+            icon++;
+            return center;
+        };
+        if (game.barrier.up) {
+            // one-shot, so its ring stays full
+            drawEffectIcon(text, powerUpInfo(PowerUpType::Shield), nextIcon(), 18 * uiScale, 1, 1);
+        }
+        for (const ActiveEffect& effect : game.effects.active()) {
+            // an extended one shows full until it is back under its own time
+            float left = fminf((float) effect.ticks / effect.fullTicks, 1);
+            drawEffectIcon(text, powerUpInfo(effect.type), nextIcon(), 18 * uiScale, left, effect.stacks);
+        }
+        if (settings.speedrunTimer) {
+            speedrun.draw(text, hud.rect("speedrun"), uiScale, theme->palette.text, game.tick, GetTime());
+        }
+        Rectangle captions = hud.rect("captions");
+        soundCues.drawCaptions(text, {captions.x + captions.width / 2, captions.y}, 20 * uiScale, RAYWHITE);
+    }});
+    graph.addPass({"debug", "window", {"window"}, {"window"}, [&]() {
+        float uiScale = hud.getScale();
+        BatchStats stats = batch.getStats();
+        Rectangle debug = hud.rect("debug");
+        DrawText(TextFormat("Sprites: %d  Batches: %d  Particles: %d", stats.sprites, stats.batches, particles.getActiveCount()), debug.x, debug.y, 10 * uiScale, LIGHTGRAY);
+        pacer.drawGraph(debug.x, debug.y + 15 * uiScale, 240 * uiScale, 60 * uiScale);
+        if (settings.logConsole) {
+            logConsole.draw(hud.rect("console"), uiScale);
+        }
+    }, false});
+    graph.addPass({"profiler", "window", {"window"}, {"window"}, [&]() { profiler.draw(hud.rect("profiler"), hud.getScale()); }, false});
+    graph.addPass({"menu", "window", {"window"}, {"window"}, [&]() {
+        settingsMenu.draw(text, fields, hud.root.rect, hud.getScale());
+        controlsMenu.draw(text, profile.bindings, hud.root.rect, hud.getScale());
+        devices.draw(text, hud.root.rect, hud.getScale());
+        std::string boardTitle = weekly ? challenge.week + ", " + tr(challenge.name) : daily ? TextFormat(tr("Daily challenge, %s").c_str(), today.date.c_str()) : levelName;
+        leaderboardScreen.draw(text, leaderboard, globalBoard, boardTitle, hud.root.rect, hud.getScale());
+        statsScreen.draw(text, playerStats, profile, hud.root.rect, hud.getScale());
+        shopScreen.draw(text, profile, hud.root.rect, hud.getScale());
+        levelSelect.draw(text, playerStats, profile.packProgress, hud.root.rect, hud.getScale());
+    }});
     
-    // Borders
-    
-    Rectangle top = {0, 0, screenWidth, 1};
-    Rectangle bottom = {0, screenHeight - 1, screenWidth, 1};
-    Rectangle left = {0, 0, 1, screenHeight };
-    Rectangle right = {screenWidth - 1, 0, 1, screenHeight};
-    
+    crash.setStateSummary([&]() {
+        return std::string(TextFormat("  level: %s (%s)\n  seed: %llu\n  tick: %llu\n  score: %d, lives: %d, bricks left: %d\n",
+            levelName.c_str(), options.levelPath.empty() ? "built-in" : options.levelPath.c_str(), (unsigned long long) seed,
+            (unsigned long long) game.tick, game.score, game.lives, (int) game.bricks.size()))
+            + TextFormat("  ball: %.1f, %.1f moving %.2f, %.2f%s, %d in play\n  paddle: %.1f\n", game.balls[0].position.x, game.balls[0].position.y,
+            game.balls[0].velocity.x, game.balls[0].velocity.y, game.ballHeld ? " (held)" : "", (int) game.balls.size(), game.player.position.x)
+            + TextFormat("  screen: %s, menus open: %d, replay: %s\n  theme: %s, profile: %s\n", gameScreenName(flow.getScreen()),
+            flow.getOverlayCount(), replay.isPlaying() ? "yes" : "no", theme->id.c_str(), profile.name.c_str());
+    });
+
+    // --golden renders each scene once instead of running the game; a scene
+    // sets the state up from scratch so it doesn't depend on the ones before
+    if (options.golden) {
+        GoldenImages golden(options.updateGolden);
+        Image frame = {};
+        graph.addPass({"golden", "window", {"window"}, {"window"}, [&]() { frame = LoadImageFromScreen(); }, false});
+        std::vector<Brick> builtIn;
+        createBricks(builtIn);
+        auto resetScene = [&](const std::string& themeId) {
+            SetRandomSeed(1);
+            levelTheme = themeId;
+            levelMusic.clear();
+            applyTheme();
+            simulation.reset(builtIn, 1);
+            particles.clear();
+            popups.clear();
+            ballTrails.clear();
+            flow.change(GameScreen::Playing);
+        };
+        struct GoldenScene {
+            std::string name;
+            std::function<void()> setup;
+        };
+        std::vector<GoldenScene> scenes = {
+            {"classic", [&]() { resetScene("default"); }},
+            {"classic_neon", [&]() { resetScene("neon"); }},
+            {"ball_in_play", [&]() {
+                resetScene("default");
+                InputFrame input;
+                input.setPressed(Action::Launch, true);
+                for (int tick = 0; tick < 90; tick++) {
+                    simulation.step(input, nullptr);
+                    input.pressed = 0;
+                    ballTrails.record(game.balls);
+                }
+            }},
+            {"brick_shatter", [&]() {
+                resetScene("default");
+                for (int i = 0; i < 4; i++) {
+                    particles.emitBrickShatter({(float) 200 + i * 240, 420, 80, 30}, (BrickMaterial) i, theme->palette.bricks[i]);
+                }
+                particles.update(0.15f);
+            }},
+            {"paused", [&]() {
+                resetScene("default");
+                flow.change(GameScreen::Paused);
+                pauseMenu.open(true, true);
+            }},
+            {"game_over", [&]() {
+                resetScene("default");
+                game.lives = 0;
+                flow.change(GameScreen::GameOver);
+            }},
+            {"settings_menu", [&]() {
+                resetScene("default");
+                fields = menuFields();
+                flow.push(Overlay::Settings);
+            }},
+            {"controls_menu", [&]() {
+                resetScene("default");
+                flow.push(Overlay::Controls);
+            }},
+        };
+        for (GoldenScene& scene : scenes) {
+            scene.setup();
+            actualFPS = "FPS: 60";
+            livesLeft = TextFormat(tr("Lives: %d").c_str(), game.lives > 0 ? game.lives : 0);
+            scorePrintable = TextFormat(tr("Score: %d").c_str(), game.score);
+            viewport.update();
+            // the first frame fills the lightmap and the brick cache
+            graph.execute();
+            graph.setEnabled("golden", true);
+            graph.execute();
+            graph.setEnabled("golden", false);
+            golden.check(scene.name, frame);
+            UnloadImage(frame);
+            frame = {};
+            flow.close(Overlay::Settings);
+            flow.close(Overlay::Controls);
+        }
+        audio.unload();
+        CloseWindow();
+        return golden.finish();
+    }
+
+    int levelStartLives = game.lives; // for the medal; a challenge carries lives over
+    int lostStreak = 0; // the hardcore levels in a row a lost run ended
+    // Anything after the first level (tutorial steps, and the game after
+    // them) starts here
+    auto playLevel = [&](const std::string& path) {
+        readLevel(path);
+        simulation.dropTable = currentLevel.drops;
+    simulation.boss = currentLevel.boss;
+        simulation.launchBudget = currentLevel.launches;
+        simulation.physics = currentLevel.physics;
+        brickTotal = clearTargets();
+        simulation.reset(levelBricks, seed);
+        resetRival();
+        particles.clear();
+        popups.clear();
+        ballTrails.clear();
+        brickField.markDirty();
+        applyTheme();
+        leaderboard.load(leaderboardKey());
+        runRecord = RunRecord();
+        achievements.startRun();
+        levelRecorder.start();
+        levelStartLives = game.lives;
+        if (tutorial.isActive()) {
+            tutorial.beginLevel(currentLevel, game);
+        } else if (!replay.isPlaying() && !weekly && !versus && !practice) {
+            startReplayRecording(path);
+        }
+        flow.change(GameScreen::Playing);
+    };
+    if (tutorial.isActive()) {
+        tutorial.beginLevel(currentLevel, game);
+    }
+    bool runSaved = false; // by "Save and quit"
+    // the score and time of a challenge's levels before this one
+    int carriedScore = 0;
+    uint64_t carriedTicks = 0;
+
+    // Restart starts the level over; a challenge starts over from its first
+    // level and the tutorial retries the level it is on. Hardcore has no
+    // second tries: only a run that was won starts again.
+    auto restartRun = [&]() {
+        if (hardcore && !(flow.is(GameScreen::GameOver) && game.bricksLeft <= 0)) {
+            return;
+        }
+        if (tutorial.isActive()) {
+            speedrun.restartLevel(game.tick);
+            playLevel(tutorial.getLevelPath());
+            return;
+        }
+        speedrun.reset();
+        if (weekly) {
+            challengeLevel = 0;
+            carriedScore = 0;
+            carriedTicks = 0;
+            playLevel(challenge.levels.front());
+        } else {
+            std::string path = currentLevel.path;
+            playLevel(path);
+        }
+        prepareSpeedrun();
+    };
+    // only a live run in progress can be suspended, and only while it is
+    // being recorded, since the recording is what resumes it
+    auto canSuspend = [&]() {
+        return flow.getPrevious() == GameScreen::Playing && !tutorial.isActive() && !weekly && !game.cheated && replayRecorder.isRecording();
+    };
+    // free play only: challenges, the tutorial and matches have their levels
+    // picked for them, and hardcore can't walk out of a run it hasn't won
+    auto canChooseLevel = [&]() {
+        bool over = flow.is(GameScreen::GameOver);
+        return !weekly && !daily && !tutorial.isActive() && !versus && !replay.isPlaying() && (over || !hardcore);
+    };
+    flow.onEnter(GameScreen::Paused, [&]() {
+        bool live = flow.getPrevious() == GameScreen::Playing;
+        pauseMenu.open(live && !hardcore, canSuspend(), live && practice, live && canChooseLevel());
+    });
+    flow.onExit(GameScreen::Paused, [&]() {
+        lostGamepad.clear();
+        flow.close(Overlay::PlaceBall);
+        pauseMenu.cancelCountdown();
+    });
+    // a run goes on the board once, when it ends (after its last events were
+    // counted); tutorial and cheated runs never do
+    flow.onEnter(GameScreen::GameOver, [&]() {
+        // a versus match has a winner instead of a score to keep, and
+        // practice nothing worth keeping
+        if (tutorial.isActive() || versus || practice) {
+            return;
+        }
+        bool cleared = game.bricksLeft <= 0;
+        if (cleared) {
+            speedrun.split(game.tick, GetTime());
+        } else {
+            speedrun.reset();
+        }
+        int xp = 0;
+        if (cleared && !game.cheated && !replay.isPlaying()) {
+            achievements.record("level_cleared", levelStartLives - game.lives);
+            xp = levelClearXp(game.score, levelStartLives - game.lives);
+            gainXp(xp);
+            saveProfile(profile);
+        }
+        // every level of a challenge earns its own medal and stars
+        Medal medal = Medal::None;
+        int stars = starRating(currentLevel, brickTotal, cleared, game.score, levelStartLives - game.lives, game.tick);
+        if (!game.cheated) {
+            medal = playerStats.finishRun(levelName, levelHash(levelBricks), game.score, cleared, levelStartLives - game.lives, game.tick,
+                parSeconds(currentLevel, brickTotal), stars);
+            playerStats.save();
+        }
+        // coins are banked level by level, so a challenge lost halfway keeps
+        // what it caught; replays only show coins that were already banked
+        if (!game.cheated && !replay.isPlaying() && game.coins > 0) {
+            profile.coins += game.coins;
+            saveProfile(profile);
+        }
+        // every level a hardcore run clears counts, a challenge's too, until one is lost
+        if (hardcore && !game.cheated && !replay.isPlaying()) {
+            lostStreak = profile.hardcoreLevels;
+            profile.hardcoreLevels = cleared ? profile.hardcoreLevels + 1 : 0;
+            profile.hardcoreBest = std::max(profile.hardcoreBest, profile.hardcoreLevels);
+            saveProfile(profile);
+        }
+        // a challenge only ends on its last level, or the first one lost
+        if (weekly && cleared && challengeLevel + 1 < (int) challenge.levels.size()) {
+            return;
+        }
+        LeaderboardEntry entry;
+        entry.score = carriedScore + game.score;
+        entry.ticks = carriedTicks + game.tick;
+        entry.cleared = cleared;
+        entry.profile = profile.name;
+        entry.name = profile.leaderboardName.empty() ? profile.name : profile.leaderboardName;
+        entry.level = weekly ? tr(challenge.name) : daily ? TextFormat(tr("Daily challenge, %s").c_str(), today.date.c_str()) : levelName;
+        entry.mode = leaderboard.getKey().mode;
+        entry.mutators = runMutators;
+        if (game.cheated) {
+            TraceLog(LOG_INFO, "LEADERBOARD: Cheats were on, this run isn't going on the board");
+            return;
+        }
+        // --record-replay already names the replay; otherwise only top runs keep one
+        std::string replayPath = !options.recordReplay.empty() ? options.recordReplay : leaderboard.qualifies(entry) ? leaderboardReplayPath(levelName) : "";
+        if (!replayPath.empty() && replayRecorder.save(replayPath, simulation)) {
+            entry.replay = replayPath;
+        }
+        // the level's board keeps the replay; two boards forgetting it would
+        // delete it out from under each other
+        LeaderboardEntry globalEntry = entry;
+        globalEntry.replay.clear();
+        flow.push(Overlay::Leaderboard);
+        int place = leaderboard.submit(entry);
+        leaderboardScreen.showRun(entry, place, globalBoard.submit(globalEntry));
+        leaderboard.save();
+        globalBoard.save();
+        levelRecorder.finish(game.tick);
+        const LevelSummary& summary = levelRecorder.getSummary();
+        leaderboardScreen.setBreakdown({
+            {tr("Time"), formatRunTime(summary.ticks)},
+            {tr("Longest combo"), std::to_string(summary.longestCombo)},
+            {tr("Power-ups"), std::to_string(summary.powerUps)},
+            {tr("Bricks a minute"), TextFormat("%.1f", summary.bricksPerMinute())},
+            {tr("Accuracy"), TextFormat(tr("%d%%, %d perfect").c_str(), summary.accuracy(), summary.perfectCatches)},
+        });
+        if (medal != Medal::None) {
+            leaderboardScreen.addNote(TextFormat(tr("%s medal").c_str(), tr(medalName(medal)).c_str()));
+            leaderboardScreen.addNote(TextFormat(trn("%d of 3 stars, %d star in all", "%d of 3 stars, %d stars in all", playerStats.getStarTotal()).c_str(),
+                stars, playerStats.getStarTotal()));
+        }
+        if (simulation.rules.mode == (int) GameMode::Survival) {
+            int seconds = game.survivedTicks / FramePacer::TICK_RATE;
+            bool best = seconds > profile.survivalBest;
+            profile.survivalBest = std::max(profile.survivalBest, seconds);
+            leaderboardScreen.addNote(TextFormat(tr(best ? "Lasted %d:%02d, a new best" : "Lasted %d:%02d").c_str(), seconds / 60, seconds % 60));
+        }
+        if (hardcore && !replay.isPlaying()) {
+            leaderboardScreen.addNote(cleared ? TextFormat(trn("Hardcore: %d level in a row (best %d)", "Hardcore: %d levels in a row (best %d)", profile.hardcoreLevels).c_str(), profile.hardcoreLevels, profile.hardcoreBest)
+                                              : TextFormat(trn("Hardcore run over after %d level", "Hardcore run over after %d levels", lostStreak).c_str(), lostStreak));
+        }
+        if (xp > 0) {
+            int level = playerLevel(profile.xp);
+            leaderboardScreen.addNote(TextFormat(tr("+%d XP, player level %d (%d / %d)").c_str(), xp, level, profile.xp, xpForPlayerLevel(level + 1)));
+        }
+        if (daily) {
+            int before = dailyBest(profile.dailyScores, today.date);
+            recordDailyScore(profile.dailyScores, today.date, entry.score);
+            bool beaten = before > 0 && entry.score > before;
+            leaderboardScreen.addNote(TextFormat(tr(beaten ? "A new best for today: %d" : "Today's best: %d").c_str(), std::max(before, entry.score)));
+        }
+
+        std::string hash = TextFormat("%016llx", (unsigned long long) levelHash(levelBricks));
+        if (cleared && ("," + profile.clearedLevels + ",").find("," + hash + ",") == std::string::npos) {
+            profile.clearedLevels += (profile.clearedLevels.empty() ? "" : ",") + hash;
+        }
+        if (cleared && !packLevel.pack.empty() && packLevel.path == currentLevel.path && recordPackClear(profile.packProgress, packLevel.pack, packLevel.packIndex)) {
+            bool done = packLevel.packIndex + 1 >= packLevel.packSize;
+            leaderboardScreen.addNote(done ? tr("Every level of the pack cleared") : tr("The pack's next level is unlocked"));
+        }
+        if (weekly && cleared) {
+            finishChallenge(profile.weekly, challenge, time(nullptr));
+            int weeks = profile.weekly.current;
+            leaderboardScreen.addNote(TextFormat(trn("Weekly streak: %d week (best %d)", "Weekly streak: %d weeks (best %d)", weeks).c_str(), weeks, profile.weekly.best));
+        }
+        runRecord.score = game.score;
+        runRecord.cleared = cleared;
+        runRecord.livesLost = simulation.rules.startLives - game.lives;
+        runRecord.levelsCleared = profile.clearedLevels.empty() ? 0 : (int) std::count(profile.clearedLevels.begin(), profile.clearedLevels.end(), ',') + 1;
+        runRecord.playerLevel = playerLevel(profile.xp);
+        for (const Cosmetic* unlock : earnUnlocks(runRecord, profile.unlocked)) {
+            TraceLog(LOG_INFO, "UNLOCK: %s (%s)", unlock->name.c_str(), unlock->requirement.c_str());
+            leaderboardScreen.addNote(TextFormat(tr("Unlocked %s: %s").c_str(), tr(unlock->name).c_str(), tr(unlock->requirement).c_str()));
+        }
+        if (canChooseLevel()) {
+            leaderboardScreen.addNote(TextFormat(tr("%s for another level").c_str(), describeInput(profile.bindings.get(Action::Launch, 0)).c_str()));
+        }
+        profile.achievements = achievements.format();
+        saveProfile(profile);
+    });
+    prepareSpeedrun();
+    flow.change(replay.isPlaying() ? GameScreen::Replay : GameScreen::Playing);
+    if ((runMode == GameMode::Coop || versus) && !replay.isPlaying() && devices.getJoinedCount() < 2) {
+        // co-op and versus need each player on a device of their own first
+        flow.push(Overlay::Devices);
+    }
+
     // Main game loop
     while (!WindowShouldClose()) {   // Detect window close button or ESC key
+        profiler.beginFrame();
+        ScopedTimer updateTimer(profiler, ProfileSection::Update);
     
         // Update
         //----------------------------------------------------------------------------------
         // TODO: Update your variables here
         //----------------------------------------------------------------------------------
-        player.checkInput();
-        player.update();
-        ball.update();
-
-        if (CheckCollisionRecs(ball.getRectangle(), bottom)) {
-            lifes -= 1;
-            ball = initBall(screenWidth, screenHeight);
-        } else if (CheckCollisionRecs(ball.getRectangle(), top)) {
-            ballBounce(&ball, 't');
-        } else if (CheckCollisionRecs(ball.getRectangle(), left)) {
-            ballBounce(&ball, 'l');
-        } else if (CheckCollisionRecs(ball.getRectangle(), right)) {
-            ballBounce(&ball, 'r');
-        } else if (CheckCollisionRecs(ball.getRectangle(), player.getRectangle())) {
-            ballBounce(&ball, 'u');
-        }
-
-        if (CheckCollisionRecs(player.getRectangle(), left)) {
-            player.preventLeft();
-        } else if (player.checkCollision(right)) {
-            player.preventRight();
-        }
-        // Check collision between bricks
-        for (int i = 0; i < bricks.size(); i++) {
-            if (CheckCollisionRecs(ball.getRectangle(), bricks[i].getRectangle())) {
-                // check if below
-                if (ball.position.y > bricks[i].position.y + bricks[i].rectangle.height / 2) {
-                    ballBounce(&ball, 't');
+        viewport.update();
+        lifecycle.update();
+        gamepads.update();
+        float paddleHalf = game.player.rectangle.width / 2;
+        touch.update(GetFrameTime(), viewport.getScale(), game.player.getCenter().x, paddleHalf, screenWidth - paddleHalf);
+        Vector2 tapPoint;
+        bool tapped = touch.tapped(&tapPoint);
+        bool menuButtonTapped = tapped && settings.touchControls && CheckCollisionPointRec(tapPoint, hud.rect("menu_button"));
+        bool menuOpen = flow.hasOverlay();
+        devices.update();
+        // player one's paddle only listens to the device that joined as player
+        // one, and co-op's (or versus's) second the one that joined as player two
+        InputBindings playerBindings = devices.bindingsFor(0, profile.bindings);
+        InputBindings partnerBindings = devices.bindingsFor(1, profile.bindings);
+        bool twoPlayer = simulation.rules.mode == (int) GameMode::Coop || versus;
+        int playerGamepad = playerBindings.activeGamepad();
+        if (playerGamepad >= 0 && (GetGamepadButtonPressed() != GAMEPAD_BUTTON_UNKNOWN || playerBindings.analogMove(settings.gamepadDeadzone) != 0)) {
+            activeGamepad = playerGamepad;
+        }
+        // gameplay actions are ignored while a menu has the keyboard
+        if (!menuOpen && profile.bindings.isPressed(Action::Debug)) {
+            showDebugOverlay = !showDebugOverlay;
+        }
+        if (!menuOpen && (playerBindings.isPressed(Action::Pause) || (twoPlayer && partnerBindings.isPressed(Action::Pause)) || touch.twoFingerTapped())) {
+            if (!flow.is(GameScreen::Paused)) {
+                pauseGame();
+            } else if (pauseMenu.isCountingDown()) {
+                pauseMenu.cancelCountdown();
+            } else {
+                resumeGame();
+            }
+        }
+        // straight back to the start, for a puzzle gone wrong; a replay is
+        // watched, not played
+        if (!menuOpen && !replay.isPlaying() && (flow.is(GameScreen::Playing) || flow.is(GameScreen::GameOver)) && playerBindings.isPressed(Action::Restart)) {
+            restartRun();
+        }
+        bool playing = !menuOpen && flow.is(GameScreen::Playing);
+        bool launch = playing && (playerBindings.isPressed(Action::Launch) || (tapped && !menuButtonTapped));
+        // once a run is over, launch picks what to play next
+        if (!menuOpen && flow.is(GameScreen::GameOver) && canChooseLevel() && playerBindings.isPressed(Action::Launch)) {
+            flow.push(Overlay::Levels);
+        }
+        // not F12, raylib's own screenshot key when SUPPORT_SCREEN_CAPTURE is on
+        if (IsKeyPressed(KEY_F9)) {
+            capture.requestScreenshot(levelName);
+        }
+        if (IsKeyPressed(KEY_F10)) {
+            capture.requestClip(levelName);
+        }
+        if (IsKeyPressed(KEY_F6)) {
+            profiler.visible = !profiler.visible;
+        }
+        if (!menuOpen) {
+            devTools.update(viewport.getScale(), screenWidth, screenHeight);
+        }
+        // a menu opens over the one that is up and closes back to it
+        if (IsKeyPressed(KEY_F1) || menuButtonTapped) {
+            flow.toggle(Overlay::Settings);
+        } else if (IsKeyPressed(KEY_F2)) {
+            flow.toggle(Overlay::Controls);
+        } else if (IsKeyPressed(KEY_F4)) {
+            flow.toggle(Overlay::Devices);
+        } else if (IsKeyPressed(KEY_F11) && (IsKeyDown(KEY_LEFT_SHIFT) || IsKeyDown(KEY_RIGHT_SHIFT))) {
+            flow.toggle(Overlay::Stats);
+        } else if (IsKeyPressed(KEY_F11)) {
+            flow.toggle(Overlay::Leaderboard);
+        } else if (devices.hasDisconnected() && !flow.isOpen(Overlay::Devices)) {
+            // an unplugged controller brings the join screen up so someone can take over
+            flow.push(Overlay::Devices);
+        }
+        if (menuOpen != flow.hasOverlay()) {
+            events.publish(menuOpen ? EventType::MenuClosed : EventType::MenuOpened, {0, 0});
+        }
+        if (controlsMenu.update(profile.bindings)) {
+            profile.layout = appliedLayout = "custom";
+            events.publish(EventType::SettingChanged, {0, 0});
+            saveProfile(profile);
+        }
+        if (shopScreen.update(profile)) {
+            saveProfile(profile);
+        }
+        // the name a top run was typed in with goes on both boards
+        std::string typedName;
+        if (leaderboardScreen.update(typedName)) {
+            profile.leaderboardName = typedName;
+            leaderboard.nameLatest(typedName);
+            leaderboard.save();
+            globalBoard.nameLatest(typedName);
+            globalBoard.save();
+            saveProfile(profile);
+        }
+        LevelChoice pickedLevel;
+        if (levelSelect.update(playerStats.getStarTotal(), profile.packProgress, pickedLevel)) {
+            flow.close(Overlay::Levels);
+            options.levelPath = pickedLevel.path;
+            packLevel = pickedLevel;
+            speedrun.reset();
+            playLevel(pickedLevel.path);
+            prepareSpeedrun();
+        }
+        fields = menuFields();
+        bool menuTapChanged = tapped && !menuButtonTapped && settingsMenu.tap(fields, tapPoint);
+        if (settingsMenu.update(fields) || menuTapChanged) {
+            brickField.markDirty();
+            applyWindowMode(settings.fullscreen);
+            capture.setClipLength(settings.clipSeconds);
+            applyMotionSettings();
+            audio.apply(settings);
+            mouse.apply(settings);
+            applyRules();
+            touch.enabled = settings.touchControls;
+            if (settings.language != currentLanguage()) {
+                setLanguage(settings.language);
+                text.prepare(languageGlyphs());
+            }
+            settingsMenu.touchMode = settings.touchControls;
+            events.publish(EventType::SettingChanged, {0, 0});
+            soundCues.enabled = settings.visualSoundCues;
+            applyTelemetry();
+            pacer.apply(settings);
+            lighting.setQuality((LightingQuality) settings.lightingQuality);
+            if (theme->id != profile.theme && !themes.has(levelTheme)) {
+                applyTheme();
+            }
+            if (profile.layout != appliedLayout) {
+                applyLayout(profile.bindings, profile.layout);
+                appliedLayout = profile.layout;
+            }
+            saveProfile(profile);
+        }
+        // practice: a press on the arrow's end aims, anywhere else moves the
+        // ball there; the arrow keys turn it and speed it up or down. Before
+        // the pause menu, so the enter that picks Place ball doesn't end it.
+        if (placingBall) {
+            Vector2 pointer = GetScreenToWorld2D(GetMousePosition(), fieldCamera(0));
+            if (IsMouseButtonPressed(MOUSE_BUTTON_LEFT)) {
+                Vector2 tip = sandbox.aimHandle();
+                draggingAim = hypotf(pointer.x - tip.x, pointer.y - tip.y) < 16;
+                draggingBall = !draggingAim;
+            }
+            if (!IsMouseButtonDown(MOUSE_BUTTON_LEFT)) {
+                draggingAim = draggingBall = false;
+            }
+            if (draggingAim) {
+                sandbox.dragAimHandle(pointer);
+            } else if (draggingBall) {
+                sandbox.placeBall(pointer);
+                ballTrails.clear();
+            }
+            float turn = (IsKeyDown(KEY_RIGHT) - IsKeyDown(KEY_LEFT)) * 90 * GetFrameTime();
+            float faster = (IsKeyDown(KEY_UP) - IsKeyDown(KEY_DOWN)) * 4 * GetFrameTime();
+            if (turn != 0 || faster != 0) {
+                sandbox.aim(sandbox.angle() + turn, sandbox.speed() + faster);
+            }
+            if (IsKeyPressed(KEY_ENTER) || IsKeyPressed(KEY_KP_ENTER)) {
+                flow.close(Overlay::PlaceBall);
+                resumeGame();
+            }
+        }
+        // after the settings menu had its keys, so the enter that opens it isn't its too
+        PauseItem picked;
+        bool pauseMenuUp = flow.is(GameScreen::Paused) && !flow.hasOverlay() && lostGamepad.empty();
+        if (pauseMenuUp && (pauseMenu.update(picked) || (tapped && !menuButtonTapped && pauseMenu.tap(tapPoint, picked)))) {
+            if (picked == PauseItem::Resume) {
+                resumeGame();
+            } else if (picked == PauseItem::Restart) {
+                restartRun();
+            } else if (picked == PauseItem::Settings) {
+                flow.push(Overlay::Settings);
+            } else if (picked == PauseItem::Stats) {
+                flow.push(Overlay::Stats);
+            } else if (picked == PauseItem::Shop) {
+                flow.push(Overlay::Shop);
+            } else if (picked == PauseItem::Levels) {
+                flow.push(Overlay::Levels);
+            } else if (picked == PauseItem::PlaceBall) {
+                flow.push(Overlay::PlaceBall);
+            } else if (picked == PauseItem::Quit) {
+                if (canSuspend() && replayRecorder.save(suspendPath, simulation)) {
+                    TraceLog(LOG_INFO, "SUSPEND: Saved the run at %s", formatRunTime(game.tick).c_str());
+                    runSaved = true;
                 }
-                // check if above
-                if (ball.position.y < bricks[i].position.y - bricks[i].rectangle.height / 2) {
-                    ballBounce(&ball, 'u');
+                break;
+            }
+        }
+        if (flow.is(GameScreen::Paused) && pauseMenu.countdownFinished(GetTime())) {
+            flow.change(flow.getPrevious());
+        }
+        AssetChanges changed = assetWatcher.poll(GetFrameTime());
+        if (changed.textures && !atlas.buildFromDirectory(appDirectory + "assets/sprites")) {
+            // packed straight from the sprites, a prebuilt atlas would hide the edit
+            loadGameAtlas(atlas);
+        }
+        if (changed.themes) {
+            themes.scan();
+        }
+        if (changed.textures || changed.themes) {
+            // rebuilds the theme atlas and background and re-bakes the brick field
+            applyTheme();
+        }
+        if (changed.sounds) {
+            audio.reloadSounds();
+        }
+        if (changed.shaders) {
+            postProcessor.reloadShaders();
+        }
+        // the level being played, edited: in place, mid-run (not a recording's)
+        if (changed.levels && !replay.isPlaying() && !currentLevel.path.empty() && FileExists(currentLevel.path.c_str())
+            && GetFileModTime(currentLevel.path.c_str()) != levelFileTime) {
+            levelFileTime = GetFileModTime(currentLevel.path.c_str());
+            Level edited;
+            std::string error;
+            // endless and versus shift the field and add rows of their own,
+            // which the file's bricks can no longer be matched against
+            if (simulation.rules.mode == (int) GameMode::Endless || versus) {
+                TraceLog(LOG_INFO, "HOTRELOAD: Keeping the level as it is, %s fields can't be reloaded in place", gameModeInfo(runMode).id);
+            } else if (!loadLevel(currentLevel.path, edited, error)) {
+                TraceLog(LOG_WARNING, "HOTRELOAD: Keeping the level as it was: %s", error.c_str());
+            } else {
+                currentLevel.bricks = edited.bricks;
+                currentLevel.drops = edited.drops;
+                currentLevel.physics = edited.physics;
+                levelBricks = edited.bricks;
+                simulation.dropTable = edited.drops;
+                simulation.physics = edited.physics;
+                simulation.reloadBricks(levelBricks);
+                brickTotal = clearTargets();
+                brickField.markDirty();
+                TraceLog(LOG_INFO, "HOTRELOAD: Reloaded %s, %d bricks", currentLevel.path.c_str(), (int) game.bricks.size());
+            }
+        }
+        // watching a replay: left/right skip five seconds
+        if (replay.isPlaying() && !menuOpen && (IsKeyPressed(KEY_LEFT) || IsKeyPressed(KEY_RIGHT))) {
+            uint64_t skip = 5 * FramePacer::TICK_RATE;
+            if (IsKeyPressed(KEY_RIGHT)) {
+                replay.seek(simulation, game.tick + skip);
+            } else {
+                replay.seek(simulation, game.tick > skip ? game.tick - skip : 0);
+            }
+            // nothing cosmetic from before the jump belongs to the new moment
+            particles.clear();
+            popups.clear();
+            ballTrails.clear();
+            brickField.markDirty();
+        }
+        pacer.beginFrame();
+        juice.update(pacer.getFrameTime());
+        // the pointer is only held while actually playing
+        mouse.setCaptured(!flow.hasOverlay() && flow.is(GameScreen::Playing) && IsWindowFocused());
+        mouse.update(pacer.getFrameTime(), viewport.getScale(), game.player.getCenter().x, paddleHalf, screenWidth - paddleHalf);
+
+        // devices are read once per frame; the ticks only ever see this frame
+        // (or a recorded one), which is what makes recordings replayable
+        InputFrame liveInput;
+        for (Action action : {Action::MoveLeft, Action::MoveRight, Action::Launch, Action::ActivatePower}) {
+            liveInput.setHeld(action, playerBindings.isDown(action));
+            liveInput.setPressed(action, playing && playerBindings.isPressed(action));
+        }
+        liveInput.setPressed(Action::Launch, launch);
+        liveInput.setStick(playerBindings.analogMove(settings.gamepadDeadzone));
+        liveInput.setFine(playerBindings.fineControl());
+        if (touch.isDragging()) {
+            liveInput.setSteer(touch.getTarget());
+        } else if (mouse.isActive()) {
+            liveInput.setSteer(mouse.getTarget());
+        }
+        // the mouse and touch stay player one's
+        InputFrame partnerInput;
+        if (twoPlayer) {
+            for (Action action : {Action::MoveLeft, Action::MoveRight, Action::Launch, Action::ActivatePower}) {
+                partnerInput.setHeld(action, partnerBindings.isDown(action));
+                partnerInput.setPressed(action, playing && partnerBindings.isPressed(action));
+            }
+            partnerInput.setStick(partnerBindings.analogMove(settings.gamepadDeadzone));
+            partnerInput.setFine(partnerBindings.fineControl());
+        }
+
+        int ticks = pacer.consumeTicks();
+        // a finished replay holds its last frame, a finished run (a cleared
+        // level too) its last brick
+        bool stopped = flow.hasOverlay() || flow.is(GameScreen::Paused) || flow.is(GameScreen::GameOver);
+        if (stopped || juice.isFrozen() || (flow.is(GameScreen::Replay) && replay.isFinished(simulation))) {
+            ticks = 0;
+            pacer.resetAccumulator();
+        }
+        // dev builds step a paused game one tick at a time
+        if (devTools.takeStep() && flow.is(GameScreen::Paused) && flow.getPrevious() == GameScreen::Playing && !flow.hasOverlay()) {
+            ticks = 1;
+        }
+        devTools.apply(simulation, replay.isPlaying());
+        if (!stopped && flow.is(GameScreen::Playing) && !tutorial.isActive()) {
+            playerStats.addPlayTime(pacer.getFrameTime(), ticks);
+        }
+        // at high frame rates most frames run no tick at all; a press in one of
+        // them (or during hit-stop) is held for the next tick instead of lost
+        liveInput.pressed |= carriedPresses;
+        carriedPresses = ticks == 0 && !stopped ? liveInput.pressed : 0;
+        partnerInput.pressed |= carriedPartnerPresses;
+        carriedPartnerPresses = ticks == 0 && !stopped ? partnerInput.pressed : 0;
+        updateTimer.stop();
+        {
+            ScopedTimer physicsTimer(profiler, ProfileSection::Physics);
+            double frameStart = GetTime();
+            for (int tick = 0; tick < ticks; tick++) {
+                // the ticks of one frame stand for moments spread over the past
+                // frame; stamping events that way keeps their sounds spaced out
+                events.setTime(frameStart - (ticks - 1 - tick) * FramePacer::TICK_SECONDS);
+                if (replay.isPlaying()) {
+                    replay.step(simulation, &events);
+                } else {
+                    InputFrame input = liveInput;
+                    if (playback.isPlaying()) {
+                        input = playback.frameAt(game.tick);
+                        if (playback.isFinished(game.tick)) {
+                            TraceLog(LOG_INFO, "INPUT: Playback finished at tick %llu", (unsigned long long) game.tick);
+                            playback.stop();
+                        }
+                    }
+                    recorder.record(game.tick, input);
+                    crash.recordInput(game.tick, input);
+                    if (versus) {
+                        // the second player's paddle is on the rival field
+                        simulation.step(input, &events);
+                        rivalEvents.setTime(frameStart - (ticks - 1 - tick) * FramePacer::TICK_SECONDS);
+                        rival.step(partnerInput, &rivalEvents);
+                        versusMatch.exchange(simulation, rival, &events, &rivalEvents);
+                    } else {
+                        replayRecorder.record(input, simulation, partnerInput);
+                        simulation.step(input, &events, partnerInput);
+                    }
                 }
-                // check if left
-                if (ball.position.x < bricks[i].position.x - bricks[i].rectangle.width / 2) {
-                    ballBounce(&ball, 'l');
+                liveInput.pressed = 0;
+                partnerInput.pressed = 0;
+                ballTrails.record(game.balls);
+            }
+        }
+        events.setTime(-1);
+        rivalEvents.setTime(-1);
+        {
+            // the events mostly end up as sounds, so their fan-out counts as audio
+            ScopedTimer audioTimer(profiler, ProfileSection::Audio);
+            // the paddle is the listener: bounces far up the field sound distant
+            audio.setListener(game.player.getCenter(), screenWidth, hypotf(screenWidth, screenHeight));
+            events.dispatch();
+            rivalEvents.dispatch();
+        }
+        std::vector<const Achievement*> unlocked = achievements.takeUnlocked();
+        for (const Achievement* achievement : unlocked) {
+            TraceLog(LOG_INFO, "ACHIEVEMENTS: Unlocked %s", achievement->id.c_str());
+            toasts.push_back({TextFormat(tr("Achievement: %s").c_str(), tr(achievement->name).c_str()), tr(achievement->description)});
+            gainXp(achievement->xp);
+        }
+        if (!unlocked.empty()) {
+            profile.achievements = achievements.format();
+            saveProfile(profile);
+        }
+        if (!toasts.empty() && toastStart >= 0 && GetTime() - toastStart >= TOAST_SECONDS) {
+            toasts.erase(toasts.begin());
+            toastStart = -1;
+        }
+        if (!toasts.empty() && toastStart < 0) {
+            toastStart = GetTime();
+        }
+        if (game.cheated) {
+            speedrun.invalidate();
+        }
+        if (flow.is(GameScreen::Playing)) {
+            speedrun.update(game.tick, !game.ballHeld, (SpeedrunStart) settings.speedrunStart, GetTime());
+        }
+        bool cleared = game.bricksLeft <= 0;
+        bool decided = versus ? versusMatch.result(simulation, rival) != VersusResult::Playing : game.lives <= 0 || cleared;
+        if (flow.is(GameScreen::Playing) && decided) {
+            flow.change(GameScreen::GameOver);
+        }
+        // a challenge goes straight on to its next level, lives and score in tow
+        if (weekly && flow.is(GameScreen::GameOver) && cleared && challengeLevel + 1 < (int) challenge.levels.size()) {
+            carriedScore += game.score;
+            carriedTicks += game.tick;
+            int lives = game.lives;
+            challengeLevel++;
+            playLevel(challenge.levels[challengeLevel]);
+            game.lives = levelStartLives = lives;
+        }
+        // the tutorial moves on by itself and retries a level that ran out of lives
+        if (tutorial.isActive()) {
+            tutorial.update(game, flow.is(GameScreen::Paused), cleared);
+            if (tutorial.isLevelDone() || profile.tutorialDone) {
+                // a tutorial level is done when its prompts are, cleared or not
+                if (profile.tutorialDone) {
+                    speedrun.reset();
+                } else {
+                    speedrun.split(game.tick, GetTime());
                 }
-                // chick if right
-                if (ball.position.x > bricks[i].position.x + bricks[i].rectangle.width / 2) {
-                    ballBounce(&ball, 'r');
+                bool more = !profile.tutorialDone && tutorial.nextLevel();
+                profile.tutorialDone = !more;
+                profile.tutorialLevel = more ? tutorial.getLevelIndex() : 0;
+                saveProfile(profile);
+                if (!more) {
+                    tutorial.stop();
+                    TraceLog(LOG_INFO, "TUTORIAL: Finished, on to the game");
                 }
-                // delete brick
-                if (bricks.size() > 1) {
-                    // bricks.erase(bricks[i]);
-                    bricks.erase(bricks.begin() + i);
+                playLevel(more ? tutorial.getLevelPath() : options.levelPath);
+                if (!more) {
+                    prepareSpeedrun();
                 }
-                score++;
+            } else if (game.lives <= 0) {
+                speedrun.restartLevel(game.tick);
+                playLevel(tutorial.getLevelPath());
+            }
+        }
 
-                break;
+        {
+            ScopedTimer effectsTimer(profiler, ProfileSection::Update);
+            // slow motion reaches the effects too, from the same scale the ball moves by
+            particles.update(pacer.getFrameTime() * game.timeScale);
+            popups.update(pacer.getFrameTime() * game.timeScale);
+            soundCues.update(pacer.getFrameTime());
+            background.update(pacer.getFrameTime(), game.balls[0].getCenter());
+            for (Ball& ball : game.balls) {
+                if (ball.piercing) {
+                    lighting.addLight(ball.getCenter(), 80, ORANGE, 0.8f);
+                }
             }
+            // falling capsules glow in their own color
+            for (const Pickup& drop : game.drops) {
+                if (drop.kind == PickupKind::PowerUp) {
+                    lighting.addLight(drop.position, 50, powerUpInfo(drop.powerUp).color, 0.6f);
+                }
+            }
+            float intensity = musicIntensity(game.combo, game.bricksLeft, brickTotal, game.boss.phase());
+            audio.setIntensity(intensity);
+            audio.setTimeScale(flow.is(GameScreen::Playing) ? game.timeScale : 1);
+            // logged in quarter steps, not every frame it moves
+            int intensityStep = (int) (intensity * 4);
+            if (intensityStep != loggedIntensity) {
+                telemetry.decision("music", "intensity", intensity);
+                loggedIntensity = intensityStep;
+            }
+            telemetry.sampleFrame(pacer.getFrameTime(), ticks);
+        }
+        {
+            ScopedTimer audioTimer(profiler, ProfileSection::Audio);
+            audio.update(pacer.getFrameTime());
         }
 
         actualFPS = "FPS: " + std::to_string(GetFPS());
-        livesLeft = "Lives: " + std::to_string(lifes);
+        livesLeft = TextFormat(tr("Lives: %d").c_str(), game.lives > 0 ? game.lives : 0);
+        scorePrintable = TextFormat(tr("Score: %d").c_str(), carriedScore + game.score);
         // Draw
         //----------------------------------------------------------------------------------
-        BeginDrawing();
-
-        // DrawRectangleRec(top, RAYWHITE);
-        // DrawRectangleRec(bottom, RAYWHITE);
-        // DrawRectangleRec(left, RAYWHITE);
-        // DrawRectangleRec(right, RAYWHITE);
-        ClearBackground(BLACK);
-        drawBricks(bricks);
-
-        if (lifes > 0) {
-            ball.draw();
-            player.draw();
-        } else {
-            std::string game_over = "Game Over";
-            livesLeft = "Lives: 0";
-            DrawText(game_over.c_str(), screenWidth / 2 - 25, screenHeight / 2, 40, LIGHTGRAY);
+        graph.setEnabled("debug", showDebugOverlay);
+        graph.setEnabled("profiler", profiler.visible);
+        {
+            ScopedTimer renderTimer(profiler, ProfileSection::Render);
+            graph.execute();
+            lighting.update(pacer.getFrameTime());
         }
-        scorePrintable = "Score: " + std::to_string(score);
-
-        DrawText(actualFPS.c_str(), 25, 25, 20, LIGHTGRAY);
-        DrawText(livesLeft.c_str(), screenWidth - 100, 25, 20, LIGHTGRAY);
-        DrawText(scorePrintable.c_str(), screenWidth / 2, 25, 20, LIGHTGRAY);
-        EndDrawing();
         //----------------------------------------------------------------------------------
     }
 
     // De-Initialization
     //--------------------------------------------------------------------------------------
+    // closing the window doesn't get a hardcore run out of a lost ball: it is
+    // saved to carry on with next launch (and that save is gone again once
+    // resumed), or where it can't be, it counts as lost
+    bool runGoing = (flow.is(GameScreen::Playing) || (flow.is(GameScreen::Paused) && flow.getPrevious() == GameScreen::Playing)) && game.lives > 0 && game.bricksLeft > 0;
+    if (hardcore && runGoing && !runSaved) {
+        if (!weekly && !game.cheated && replayRecorder.isRecording() && replayRecorder.save(suspendPath, simulation)) {
+            TraceLog(LOG_INFO, "SUSPEND: Saved the hardcore run at %s", formatRunTime(game.tick).c_str());
+        } else if (!game.cheated) {
+            TraceLog(LOG_INFO, "HARDCORE: Quit mid-run, the streak of %d is over", profile.hardcoreLevels);
+            profile.hardcoreLevels = 0;
+        }
+    }
+    if (recorder.isRecording()) {
+        recorder.save(options.recordInput, game.tick);
+    }
+    if (replayRecorder.isRecording() && !options.recordReplay.empty()) {
+        replayRecorder.save(options.recordReplay, simulation);
+    }
+    // a run still going keeps the best segments it got
+    speedrun.reset();
+    playerStats.save();
+    telemetry.stop(game);
+    saveSettings(settingsPath(), settings);
+    profile.achievements = achievements.format();
+    saveProfile(profile);
+    postProcessor.unload();
+    lighting.unload();
+    brickField.unload();
+    capture.unload();
+    audio.unload();
+    viewport.unload();
+    atlas.unload();
+    themeAtlas.unload();
+    background.unload();
+    text.unload();
     CloseWindow();        // Close window and OpenGL context
     //--------------------------------------------------------------------------------------
 
     return 0;
 }
 
-Entity initBall(int screenWidth, int screenHeight) {
-    Vector2 startPosition;
-    startPosition.x = screenWidth / 2;
-    startPosition.y = screenHeight / 2;
-    Entity entity(startPosition, 10, 10);
-
-    float randx = rand() * -2;
-    float randy = rand();
-    Vector2 startVelocity = {2, 2};
-    entity.velocity = startVelocity;
-    return entity; 
-}
+void drawBricks(SpriteBatch &batch, BrickField &field, TextureAtlas &atlas, const Theme &theme, const ThemePalette &palette, bool patterns, std::vector<Brick> &bricks) {
+    static const char* materialSprites[] = {"brick", "brick_glass", "brick_stone", "brick_metal"};
+    // under the bricks, how far each gravity well reaches: a glow that
+    // brightens towards one that pulls, a rim that brightens outwards on
+    // one that pushes
+    for (Brick &brick : bricks) {
+        if (!brick.well.active() || brick.phased || brick.hidden) {
+            continue;
+        }
+        Vector2 center = brick.getCenter();
+        Color color = palette.bricks[(int) brick.material];
+        if (brick.well.strength > 0) {
+            DrawCircleGradient(center.x, center.y, brick.well.radius, Fade(color, 0.2f), Fade(color, 0));
+        } else {
+            DrawRing(center, brick.well.radius * 0.8f, brick.well.radius, 0, 360, 48, Fade(color, 0.15f));
+        }
+        DrawCircleLines(center.x, center.y, brick.well.radius, Fade(color, 0.3f));
+    }
+    if (field.isReady() && bricks.size() >= BrickField::INSTANCING_THRESHOLD) {
+        field.draw(atlas, theme, palette, bricks);
+    } else {
+        batch.begin();
+        for (int i = 0; i < bricks.size(); i++) {
+            if (bricks[i].hidden) {
+                continue;
+            }
+            int material = (int) bricks[i].material;
+            Color color = palette.bricks[material];
+            if (bricks[i].phased) {
+                color = Fade(color, Brick::PHASED_ALPHA);
+            }
+            AnimationState& animation = bricks[i].animation;
+            if (animation.playing && atlas.isReady() && atlas.has(animation.clip.sheet)) {
+                batch.add(atlas.getTexture(), animation.frameRegion(atlas), bricks[i].getRectangle(), color);
+                continue;
+            }
+            batch.addRegion(atlas, theme.sprite(materialSprites[material]), bricks[i].getRectangle(), color);
+        }
+        batch.end();
+    }
 
-Player initPlayer(int screenWidth, int screenHeight) {
-    Vector2 startPosition; 
-    startPosition.x = screenWidth / 2.0;
-    startPosition.y = (screenHeight - 50 ); 
+    if (patterns) {
+        for (Brick &brick : bricks) {
+            if (brick.hidden) {
+                continue;
+            }
+            drawBrickPattern(brick.getRectangle(), brick.material, palette.bricks[(int) brick.material]);
+        }
+    }
 
-    Player player(startPosition, 100, 20);
-    player.init();
-    return player;
+    // Cracks over the bricks that have taken hits, one more per hit: the
+    // theme's brick_crack_1..3 sprites, or lines across the brick without them
+    static const Vector2 cracks[][3] = {
+        {{0.2f, 0}, {0.35f, 0.55f}, {0.28f, 1}},
+        {{0.7f, 1}, {0.58f, 0.4f}, {0.78f, 0}},
+        {{0, 0.35f}, {0.5f, 0.6f}, {1, 0.45f}},
+    };
+    // switches wear a ring, so they read as something to hit
+    for (Brick &brick : bricks) {
+        if (!brick.switchGroup.empty() && !brick.hidden) {
+            Rectangle rect = brick.getRectangle();
+            float radius = rect.height * 0.3f;
+            DrawRing({rect.x + rect.width / 2, rect.y + rect.height / 2}, radius * 0.5f, radius, 0, 360, 16, Fade(WHITE, brick.phased ? Brick::PHASED_ALPHA : 0.9f));
+        }
+    }
+    for (Brick &brick : bricks) {
+        int damage = std::min(brick.damage(), 3);
+        if (damage <= 0) {
+            continue;
+        }
+        Rectangle rect = brick.getRectangle();
+        std::string region = theme.sprite(TextFormat("brick_crack_%d", damage));
+        if (atlas.isReady() && atlas.has(region)) {
+            DrawTexturePro(atlas.getTexture(), atlas.region(region), rect, {0, 0}, 0, WHITE);
+            continue;
+        }
+        for (int c = 0; c < damage; c++) {
+            for (int p = 0; p < 2; p++) {
+                Vector2 from = {rect.x + cracks[c][p].x * rect.width, rect.y + cracks[c][p].y * rect.height};
+                Vector2 to = {rect.x + cracks[c][p + 1].x * rect.width, rect.y + cracks[c][p + 1].y * rect.height};
+                DrawLineEx(from, to, 1.5f, Fade(BLACK, 0.6f));
+            }
+        }
+    }
 }
 
-void ballBounce(Entity* entity, char direction) {
-    switch (direction) {
-    case 't':
-        entity->velocity.y *= -1; 
-        break;
-    
-    case 'l':
-        entity->velocity.x *= -1;
-        break;
-
-    case 'r':
-        entity->velocity.x *= -1;
-        break;
-    default:
-        entity->velocity.y = -1 * abs(entity->velocity.y);
+void drawSprite(TextureAtlas &atlas, const std::string &region, Rectangle dest, Color tint) {
+    if (atlas.isReady() && atlas.has(region)) {
+        DrawTexturePro(atlas.getTexture(), atlas.region(region), dest, {0, 0}, 0, tint);
+    } else {
+        DrawRectangleRec(dest, tint);
     }
 }
 
-void createBricks(std::vector<Entity> &bricks) {
-    int brickSizeX = 48;
-    int brickSizeY = 10;
-
-    int bricksPerRow = 20;
-    int brickSpacing = 5; 
-    int borderSpacing = 25;
-    int sizeX = 1280 - (borderSpacing * 2);
-
-    for (int i = 0; i < bricksPerRow; i++) {
-        bricks.push_back({(50 + (50 * i)), 50, 48, 10});
+// Purely cosmetic, the ball still bounces off the 1px borders
+void drawWalls(TextureAtlas &atlas, const Theme &theme, int screenWidth, int screenHeight) {
+    const int thickness = 6;
+    std::string region = theme.sprite("wall");
+    bool tiled = atlas.isReady() && atlas.has(region);
+    if (!tiled && theme.palette.wall.a == 0) {
+        return;
     }
-    for (int i = 0; i < bricksPerRow; i++) {
-        bricks.push_back({(50 + (50 * i)), 65, 48, 10});
+    Color tint = tiled ? WHITE : theme.palette.wall;
+    if (!tiled) {
+        DrawRectangle(0, 0, screenWidth, thickness, tint);
+        DrawRectangle(0, 0, thickness, screenHeight, tint);
+        DrawRectangle(screenWidth - thickness, 0, thickness, screenHeight, tint);
+        return;
     }
-    for (int i = 0; i < bricksPerRow; i++) {
-        bricks.push_back({(50 + (50 * i)), 80, 48, 10});
+    Rectangle source = atlas.region(region);
+    float tile = thickness * source.width / source.height;
+    for (float x = 0; x < screenWidth; x += tile) {
+        DrawTexturePro(atlas.getTexture(), source, {x, 0, tile, (float) thickness}, {0, 0}, 0, tint);
     }
-    for (int i = 0; i < bricksPerRow; i++) {
-        bricks.push_back({(50 + (50 * i)), 95, 48, 10});
+    for (float y = thickness; y < screenHeight; y += tile) {
+        DrawTexturePro(atlas.getTexture(), source, {0, y, (float) thickness, tile}, {0, 0}, 0, tint);
+        DrawTexturePro(atlas.getTexture(), source, {(float) screenWidth - thickness, y, (float) thickness, tile}, {0, 0}, 0, tint);
     }
 }
 
-void drawBricks(std::vector<Entity> &bricks) {
-    for (int i = 0; i < bricks.size(); i++) {
-        bricks[i].draw();
+// Debuffs are dark with a colored ring, the rest the other way round, so the
+// two tell apart without reading the badge.
+void drawEffectIcon(TextRenderer &text, const PowerUp &powerUp, Vector2 center, float radius, float left, int stacks) {
+    float ring = radius / 5;
+    DrawCircleV(center, radius - ring, powerUp.harmful ? ColorBrightness(powerUp.color, -0.6f) : Fade(powerUp.color, 0.6f));
+    DrawRing(center, radius - ring, radius, 0, 360, 32, Fade(BLACK, 0.5f));
+    DrawRing(center, radius - ring, radius, -90, -90 + 360 * left, 32, powerUp.color);
+    float fontSize = radius * 0.8f;
+    Vector2 size = text.measure(powerUp.badge, fontSize);
+    text.draw(powerUp.badge, {center.x - size.x / 2, center.y - size.y / 2}, fontSize, powerUp.harmful ? powerUp.color : RAYWHITE);
+    if (stacks > 1) {
+        std::string count = TextFormat("x%d", stacks);
+        text.draw(count, {center.x + radius * 0.6f, center.y - radius * 1.1f}, fontSize * 0.8f, RAYWHITE);
     }
-}
\ No newline at end of file
+}
+
+// Armor in the metal color, the weak point that can be hit now pulsing and
+// the ones still to come outlined; broken ones go dark.
+void drawBoss(const Boss &boss, const ThemePalette &palette, bool reducedMotion) {
+    Color armor = palette.bricks[(int) BrickMaterial::Metal];
+    int current = boss.weakPoint();
+    float pulse = reducedMotion ? 1 : 0.75f + 0.25f * sinf(GetTime() * 6);
+    for (size_t i = 0; i < boss.segments.size(); i++) {
+        const BossSegment &segment = boss.segments[i];
+        Rectangle rect = boss.segmentRect(segment);
+        if (segment.order == 0) {
+            DrawRectangleRec(rect, armor);
+        } else if ((int) i == current) {
+            DrawRectangleRec(rect, Fade(RED, pulse));
+        } else if (segment.hitPoints > 0) {
+            DrawRectangleRec(rect, ColorBrightness(armor, -0.3f));
+            DrawRectangleLinesEx(rect, 2, RED);
+        } else {
+            DrawRectangleRec(rect, ColorBrightness(armor, -0.6f));
+        }
+        DrawRectangleLinesEx(rect, 1, ColorBrightness(armor, -0.5f));
+    }
+}
@@ -0,0 +1,9 @@
+#!/bin/sh
+# Registers the .blvl file type and breakout:// links for the current user.
+set -e
+DIR=$(dirname "$0")
+xdg-mime install --novendor "$DIR/breakout-level.xml"
+desktop-file-install --dir="$HOME/.local/share/applications" "$DIR/breakout.desktop"
+xdg-mime default breakout.desktop application/x-breakout-level
+xdg-mime default breakout.desktop x-scheme-handler/breakout
+update-desktop-database "$HOME/.local/share/applications"